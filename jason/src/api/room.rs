@@ -153,6 +153,11 @@ enum RoomError {
     /// [`WebSocketSession`] returned [`SessionError`].
     #[display(fmt = "WebSocketSession error occurred: {}", _0)]
     SessionError(#[js(cause)] SessionError),
+
+    /// Returned if payload of the received [`RpcEvent::Custom`] can't be
+    /// converted into [`JsValue`].
+    #[display(fmt = "Invalid payload of custom event: {}", _0)]
+    InvalidCustomEventPayload(String),
 }
 
 impl From<RpcClientError> for RoomError {
@@ -331,6 +336,13 @@ impl RoomHandle {
             .map(|inner| inner.on_failed_local_media.set_func(f))
     }
 
+    /// Sets `on_custom_event` callback, which will be invoked with a payload
+    /// of every custom event sent to this [`Room`] by media server.
+    pub fn on_custom_event(&self, f: js_sys::Function) -> Result<(), JsValue> {
+        upgrade_or_detached!(self.0)
+            .map(|inner| inner.on_custom_event.set_func(f))
+    }
+
     /// Sets `on_connection_loss` callback, which will be invoked on
     /// [`WebSocketRpcClient`] connection loss.
     pub fn on_connection_loss(
@@ -714,6 +726,9 @@ struct InnerRoom {
     /// Callback to be invoked when [`RpcSession`] loses connection.
     on_connection_loss: Callback1<ReconnectHandle>,
 
+    /// Callback to be invoked with payloads of [`RpcEvent::Custom`]s.
+    on_custom_event: Callback1<JsValue>,
+
     /// JS callback which will be called when this [`Room`] will be closed.
     on_close: Rc<Callback1<RoomCloseReason>>,
 
@@ -742,6 +757,7 @@ impl InnerRoom {
             peer_event_sender,
             connections: Connections::default(),
             on_connection_loss: Callback1::default(),
+            on_custom_event: Callback1::default(),
             on_failed_local_media: Rc::new(Callback1::default()),
             on_local_track: Callback1::default(),
             on_close: Rc::new(Callback1::default()),
@@ -1176,6 +1192,10 @@ impl EventHandler for InnerRoom {
                 TrackUpdate::IceRestart => {
                     peer.restart_ice();
                 }
+                TrackUpdate::ForceRelay(is_force_relayed) => {
+                    peer.set_force_relay(is_force_relayed)
+                        .map_err(tracerr::map_from_and_wrap!())?;
+                }
             }
         }
         let kinds = peer
@@ -1209,6 +1229,16 @@ impl EventHandler for InnerRoom {
         Ok(())
     }
 
+    /// Passes payload of the received [`RpcEvent::Custom`] to the
+    /// `on_custom_event` JS callback.
+    async fn on_custom(&self, payload: serde_json::Value) -> Self::Output {
+        let payload = JsValue::from_serde(&payload).map_err(|e| {
+            tracerr::new!(RoomError::InvalidCustomEventPayload(e.to_string()))
+        })?;
+        self.on_custom_event.call(payload);
+        Ok(())
+    }
+
     #[inline]
     async fn on_room_joined(&self, _: MemberId) -> Self::Output {
         unreachable!("Room can't receive Event::RoomJoined")
@@ -1218,6 +1248,7 @@ impl EventHandler for InnerRoom {
     async fn on_room_left(
         &self,
         _: medea_client_api_proto::CloseReason,
+        _: Option<String>,
     ) -> Self::Output {
         unreachable!("Room can't receive Event::RoomLeft")
     }
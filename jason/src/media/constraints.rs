@@ -663,6 +663,19 @@ impl TrackConstraints {
         }
     }
 
+    /// Sets importance of this [`TrackConstraints`].
+    pub fn set_required(&mut self, required: bool) {
+        match self {
+            TrackConstraints::Video(VideoSource::Device(device)) => {
+                device.required = required;
+            }
+            TrackConstraints::Video(VideoSource::Display(display)) => {
+                display.required = required;
+            }
+            TrackConstraints::Audio(audio) => audio.required = required,
+        }
+    }
+
     /// Returns this [`TrackConstraints`] media source kind.
     pub fn media_source_kind(&self) -> MediaSourceKind {
         match &self {
@@ -132,6 +132,12 @@ pub enum RTCPeerConnectionError {
     #[display(fmt = "Failed to set remote SDP description: {}", _0)]
     #[from(ignore)]
     SetRemoteDescriptionFailed(JsError),
+
+    /// Occurs if the configuration of the [`RtcPeerConnection`] cannot be
+    /// changed.
+    #[display(fmt = "Failed to set RTCPeerConnection configuration: {}", _0)]
+    #[from(ignore)]
+    SetConfigurationFailed(JsError),
 }
 
 type Result<T> = std::result::Result<T, Traced<RTCPeerConnectionError>>;
@@ -208,13 +214,8 @@ impl RtcPeerConnection {
         I: IntoIterator<Item = IceServer>,
     {
         let mut peer_conf = RtcConfiguration::new();
-        let policy = if is_force_relayed {
-            RtcIceTransportPolicy::Relay
-        } else {
-            RtcIceTransportPolicy::All
-        };
         peer_conf.bundle_policy(RtcBundlePolicy::MaxBundle);
-        peer_conf.ice_transport_policy(policy);
+        peer_conf.ice_transport_policy(ice_transport_policy(is_force_relayed));
         peer_conf.ice_servers(&RtcIceServers::from(ice_servers));
         let peer = SysRtcPeerConnection::new_with_configuration(&peer_conf)
             .map_err(Into::into)
@@ -482,6 +483,30 @@ impl RtcPeerConnection {
         self.ice_restart.set(true);
     }
 
+    /// Updates [ICE transport policy][1] of this [`RtcPeerConnection`], so all
+    /// media is forcibly relayed through a TURN server if `is_force_relayed`
+    /// is `true`.
+    ///
+    /// Updated policy is used for ICE candidates gathered after the next ICE
+    /// restart.
+    ///
+    /// # Errors
+    ///
+    /// With [`RTCPeerConnectionError::SetConfigurationFailed`] if
+    /// [RtcPeerConnection.setConfiguration()][2] fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcconfiguration-icetransportpolicy
+    /// [2]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection-setconfiguration
+    pub fn set_force_relay(&self, is_force_relayed: bool) -> Result<()> {
+        let mut peer_conf = self.peer.get_configuration();
+        peer_conf.ice_transport_policy(ice_transport_policy(is_force_relayed));
+        self.peer
+            .set_configuration(&peer_conf)
+            .map_err(Into::into)
+            .map_err(RTCPeerConnectionError::SetConfigurationFailed)
+            .map_err(tracerr::wrap!())
+    }
+
     /// Obtains [SDP answer][`SdpType::Answer`] from the underlying
     /// [RTCPeerConnection][`SysRtcPeerConnection`] and sets it as local
     /// description.
@@ -669,3 +694,13 @@ impl Drop for RtcPeerConnection {
 fn get_peer_connection_state(peer: &SysRtcPeerConnection) -> Option<String> {
     get_property_by_name(peer, "connectionState", |v| v.as_string())
 }
+
+/// Returns [`RtcIceTransportPolicy`] relaying all media through a TURN server
+/// if `is_force_relayed` is `true`.
+fn ice_transport_policy(is_force_relayed: bool) -> RtcIceTransportPolicy {
+    if is_force_relayed {
+        RtcIceTransportPolicy::Relay
+    } else {
+        RtcIceTransportPolicy::All
+    }
+}
@@ -497,7 +497,6 @@ impl MediaConnections {
                         caps: track.media_type.into(),
                         mid,
                         media_exchange_state,
-                        required,
                         send_constraints: send_constraints.clone(),
                     }
                     .build()
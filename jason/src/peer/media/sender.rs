@@ -1,6 +1,9 @@
 //! Implementation of the `MediaTrack` with a `Send` direction.
 
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, Ref, RefCell},
+    rc::Rc,
+};
 
 use medea_client_api_proto::{MediaSourceKind, TrackId, TrackPatchEvent};
 
@@ -27,7 +30,6 @@ pub struct SenderBuilder<'a> {
     pub caps: TrackConstraints,
     pub mid: Option<String>,
     pub media_exchange_state: media_exchange_state::Stable,
-    pub required: bool,
     pub send_constraints: LocalTracksConstraints,
 }
 
@@ -64,11 +66,10 @@ impl<'a> SenderBuilder<'a> {
             media_exchange_state::Controller::new(self.media_exchange_state);
         let this = Rc::new(Sender {
             track_id: self.track_id,
-            caps: self.caps,
+            caps: RefCell::new(self.caps),
             general_media_exchange_state: Cell::new(self.media_exchange_state),
             transceiver,
             media_exchange_state: media_exchange_state_controller,
-            required: self.required,
             send_constraints: self.send_constraints,
         });
 
@@ -80,19 +81,18 @@ impl<'a> SenderBuilder<'a> {
 /// remote peer.
 pub struct Sender {
     track_id: TrackId,
-    caps: TrackConstraints,
+    caps: RefCell<TrackConstraints>,
     transceiver: Transceiver,
     media_exchange_state: Rc<media_exchange_state::Controller>,
     general_media_exchange_state: Cell<media_exchange_state::Stable>,
-    required: bool,
     send_constraints: LocalTracksConstraints,
 }
 
 impl Sender {
     /// Returns [`TrackConstraints`] of this [`Sender`].
     #[inline]
-    pub fn caps(&self) -> &TrackConstraints {
-        &self.caps
+    pub fn caps(&self) -> Ref<'_, TrackConstraints> {
+        self.caps.borrow()
     }
 
     /// Returns `true` if this [`Sender`] is publishing media traffic.
@@ -157,10 +157,9 @@ impl Sender {
     /// Indicates whether this [`Sender`] is enabled in
     /// [`LocalStreamConstraints`].
     fn enabled_in_cons(&self) -> bool {
-        self.send_constraints.is_track_enabled(
-            self.caps.media_kind(),
-            self.caps.media_source_kind(),
-        )
+        let caps = self.caps.borrow();
+        self.send_constraints
+            .is_track_enabled(caps.media_kind(), caps.media_source_kind())
     }
 
     /// Updates this [`Sender`]s tracks based on the provided
@@ -195,6 +194,9 @@ impl Sender {
         if let Some(enabled) = track.enabled_general {
             self.update_general_media_exchange_state(enabled.into());
         }
+        if let Some(required) = track.required {
+            self.caps.borrow_mut().set_required(required);
+        }
 
         requires_media_update
     }
@@ -245,11 +247,11 @@ impl TransceiverSide for Sender {
     }
 
     fn kind(&self) -> MediaKind {
-        MediaKind::from(&self.caps)
+        MediaKind::from(&*self.caps.borrow())
     }
 
     fn source_kind(&self) -> MediaSourceKind {
-        self.caps.media_source_kind()
+        self.caps.borrow().media_source_kind()
     }
 
     fn mid(&self) -> Option<String> {
@@ -257,7 +259,7 @@ impl TransceiverSide for Sender {
     }
 
     fn is_transitable(&self) -> bool {
-        match &self.caps {
+        match &*self.caps.borrow() {
             TrackConstraints::Video(VideoSource::Device(_)) => {
                 self.send_constraints.inner().get_device_video().is_some()
             }
@@ -288,7 +290,7 @@ impl Disableable for Sender {
         &self,
         desired_state: media_exchange_state::Stable,
     ) -> Result<()> {
-        if self.required {
+        if self.caps.borrow().required() {
             Err(tracerr::new!(
                 MediaConnectionsError::CannotDisableRequiredSender
             ))
@@ -510,6 +510,20 @@ impl PeerConnection {
         self.peer.restart_ice();
     }
 
+    /// Updates ICE transport policy of this [`PeerConnection`], so all media
+    /// is forcibly relayed through a TURN server if `is_force_relayed` is
+    /// `true`.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`PeerError::RtcPeerConnection`] if failed to update
+    /// [`RtcPeerConnection`] configuration.
+    pub fn set_force_relay(&self, is_force_relayed: bool) -> Result<()> {
+        self.peer
+            .set_force_relay(is_force_relayed)
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
     /// Returns `true` if all [`Receiver`]s audio tracks are enabled.
     pub fn is_recv_audio_enabled(&self) -> bool {
         self.media_connections.is_recv_audio_enabled()
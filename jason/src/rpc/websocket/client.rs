@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc, time::Duration};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
 
 use derive_more::Display;
 use futures::{
@@ -7,8 +7,8 @@ use futures::{
     stream::{LocalBoxStream, StreamExt as _},
 };
 use medea_client_api_proto::{
-    ClientMsg, CloseReason as CloseByServerReason, Command, Credential, Event,
-    MemberId, RoomId, RpcSettings, ServerMsg,
+    Capability, ClientMsg, CloseReason as CloseByServerReason, Command,
+    Credential, Event, Handshake, MemberId, RoomId, RpcSettings, ServerMsg,
 };
 use medea_macro::dispatchable;
 use medea_reactive::ObservableCell;
@@ -132,6 +132,13 @@ struct Inner {
 
     /// Current [`State`] of this [`WebSocketRpcClient`].
     state: ObservableCell<ClientState>,
+
+    /// Sequence numbers of the last [`ServerMsg::Event`]s received for each
+    /// joined [`Room`], which are acknowledged to Media Server on
+    /// reconnection.
+    ///
+    /// [`Room`]: crate::api::Room
+    last_seqs: HashMap<RoomId, u32>,
 }
 
 /// Factory closure which creates [`RpcTransport`] for
@@ -160,6 +167,7 @@ impl Inner {
             state: ObservableCell::new(ClientState::Closed(
                 ClosedStateReason::NeverConnected,
             )),
+            last_seqs: HashMap::new(),
         })
     }
 }
@@ -224,18 +232,28 @@ impl WebSocketRpcClient {
         Self(Inner::new(rpc_transport_factory))
     }
 
-    /// Authorizes [`WebSocketRpcClient`] on the Media Server.
+    /// Authorizes [`WebSocketRpcClient`] on the Media Server, declaring
+    /// protocol version and all the [`Capability`]s supported by Jason.
+    ///
+    /// If some [`ServerMsg::Event`]s have been received for the `Room` with
+    /// the provided [`RoomId`] already, then acknowledges the last of them, so
+    /// Media Server replays only the missed ones.
     pub fn authorize(
         &self,
         room_id: RoomId,
         member_id: MemberId,
         credential: Credential,
     ) {
+        let last_seq = self.0.borrow().last_seqs.get(&room_id).copied();
         self.send_command(
             room_id,
             Command::JoinRoom {
                 member_id,
                 credential,
+                last_seq,
+                handshake: Some(Handshake::new(
+                    Capability::ALL.iter().copied(),
+                )),
             },
         );
     }
@@ -243,6 +261,7 @@ impl WebSocketRpcClient {
     /// Leaves `Room` with a provided [`RoomId`].
     #[inline]
     pub fn leave_room(&self, room_id: RoomId, member_id: MemberId) {
+        self.0.borrow_mut().last_seqs.remove(&room_id);
         self.send_command(room_id, Command::LeaveRoom { member_id });
     }
 
@@ -298,15 +317,30 @@ impl WebSocketRpcClient {
     /// Handles [`ServerMsg`]s from a remote server.
     fn on_transport_message(&self, msg: ServerMsg) {
         let msg = match msg {
-            ServerMsg::Event { room_id, event } => match event {
+            ServerMsg::Event {
+                room_id,
+                seq,
+                event,
+            } => match event {
                 Event::RoomJoined { member_id } => {
                     Some(RpcEvent::JoinedRoom { room_id, member_id })
                 }
-                Event::RoomLeft { close_reason } => Some(RpcEvent::LeftRoom {
-                    room_id,
-                    close_reason: CloseReason::ByServer(close_reason),
-                }),
-                _ => Some(RpcEvent::Event { room_id, event }),
+                Event::RoomLeft { close_reason, .. } => {
+                    self.0.borrow_mut().last_seqs.remove(&room_id);
+                    Some(RpcEvent::LeftRoom {
+                        room_id,
+                        close_reason: CloseReason::ByServer(close_reason),
+                    })
+                }
+                _ => {
+                    if let Some(seq) = seq {
+                        self.0
+                            .borrow_mut()
+                            .last_seqs
+                            .insert(room_id.clone(), seq);
+                    }
+                    Some(RpcEvent::Event { room_id, event })
+                }
             },
             ServerMsg::RpcSettings(settings) => {
                 self.update_settings(
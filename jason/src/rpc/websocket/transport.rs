@@ -2,20 +2,27 @@
 //!
 //! [WebSocket]: https://developer.mozilla.org/ru/docs/WebSockets
 
-use std::{cell::RefCell, convert::TryFrom, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use derive_more::{Display, From, Into};
 use futures::{channel::mpsc, stream::LocalBoxStream, StreamExt};
-use medea_client_api_proto::{ClientMsg, ServerMsg};
+use js_sys::{Array, ArrayBuffer, Uint8Array};
+use medea_client_api_proto::{encoding::Encoding, ClientMsg, ServerMsg};
 use medea_reactive::ObservableCell;
 use tracerr::Traced;
-use web_sys::{CloseEvent, Event, MessageEvent, WebSocket as SysWebSocket};
+use wasm_bindgen::{JsCast as _, JsValue};
+use web_sys::{
+    BinaryType, CloseEvent, Event, MessageEvent, WebSocket as SysWebSocket,
+};
 
 use crate::{
     rpc::{websocket::client::ClientDisconnect, ApiUrl, CloseMsg},
     utils::{
-        EventListener, EventListenerBindError, JasonError, JsCaused, JsError,
-        JsonParseError,
+        CodecError, EventListener, EventListenerBindError, JasonError,
+        JsCaused, JsError,
     },
 };
 
@@ -54,17 +61,21 @@ pub enum TransportError {
     #[display(fmt = "Failed to init WebSocket")]
     InitSocket,
 
-    /// Occurs when [`ClientMsg`] cannot be parsed.
-    #[display(fmt = "Failed to parse client message: {}", _0)]
-    ParseClientMessage(JsonParseError),
+    /// Occurs when [`ClientMsg`] cannot be encoded.
+    #[display(fmt = "Failed to encode client message: {}", _0)]
+    ParseClientMessage(CodecError),
 
-    /// Occurs when [`ServerMessage`] cannot be parsed.
+    /// Occurs when [`ServerMessage`] cannot be decoded.
     #[display(fmt = "Failed to parse server message: {}", _0)]
-    ParseServerMessage(JsonParseError),
+    ParseServerMessage(CodecError),
 
-    /// Occurs if the parsed message is not string.
-    #[display(fmt = "Message is not a string")]
-    MessageNotString,
+    /// Occurs if the received message is not a string while JSON [`Encoding`]
+    /// is used, or is not an [ArrayBuffer][1] while binary [`Encoding`] is
+    /// used.
+    ///
+    /// [1]: https://developer.mozilla.org/docs/Web/API/ArrayBuffer
+    #[display(fmt = "Message doesn't match the negotiated encoding")]
+    UnexpectedMessageType,
 
     /// Occurs when a message cannot be send to server.
     #[display(fmt = "Failed to send message: {}", _0)]
@@ -94,15 +105,37 @@ impl From<EventListenerBindError> for TransportError {
 #[derive(Clone, From, Into)]
 pub struct ServerMessage(ServerMsg);
 
-impl TryFrom<&MessageEvent> for ServerMessage {
-    type Error = TransportError;
-
-    fn try_from(msg: &MessageEvent) -> std::result::Result<Self, Self::Error> {
-        use TransportError::{MessageNotString, ParseServerMessage};
-
-        let payload = msg.data().as_string().ok_or(MessageNotString)?;
+impl ServerMessage {
+    /// Decodes [`ServerMessage`] from the provided [MessageEvent][1] with the
+    /// provided [`Encoding`].
+    ///
+    /// # Errors
+    ///
+    /// With [`TransportError::UnexpectedMessageType`] if type of the received
+    /// data doesn't match the provided [`Encoding`].
+    ///
+    /// With [`TransportError::ParseServerMessage`] if the received data cannot
+    /// be decoded into [`ServerMsg`].
+    ///
+    /// [1]: https://developer.mozilla.org/en-US/docs/Web/API/MessageEvent
+    fn decode(
+        msg: &MessageEvent,
+        encoding: Encoding,
+    ) -> std::result::Result<Self, TransportError> {
+        use TransportError::{ParseServerMessage, UnexpectedMessageType};
+
+        let data = msg.data();
+        let payload = if encoding.is_binary() {
+            let buffer = data
+                .dyn_into::<ArrayBuffer>()
+                .map_err(|_| UnexpectedMessageType)?;
+            Uint8Array::new(&buffer).to_vec()
+        } else {
+            data.as_string().ok_or(UnexpectedMessageType)?.into_bytes()
+        };
 
-        serde_json::from_str::<ServerMsg>(&payload)
+        encoding
+            .decode::<ServerMsg>(&payload)
             .map_err(|e| ParseServerMessage(e.into()))
             .map(Self::from)
     }
@@ -161,6 +194,11 @@ struct InnerSocket {
     /// State of [`WebSocketTransport`] connection.
     socket_state: ObservableCell<TransportState>,
 
+    /// [`Encoding`] of messages negotiated with a server.
+    ///
+    /// Determined once the connection is open.
+    encoding: Cell<Encoding>,
+
     /// Listener for [WebSocket] [open event][1].
     ///
     /// [WebSocket]: https://developer.mozilla.org/docs/Web/API/WebSocket
@@ -190,13 +228,23 @@ struct InnerSocket {
 }
 
 impl InnerSocket {
+    /// Creates new [WebSocket] offering all the supported [`Encoding`]s as
+    /// its subprotocols.
+    ///
+    /// [WebSocket]: https://developer.mozilla.org/docs/Web/API/WebSocket
     fn new(url: &str) -> Result<Self> {
-        let socket = SysWebSocket::new(&url)
+        let subprotocols: Array = Encoding::ALL
+            .iter()
+            .map(|encoding| JsValue::from_str(encoding.subprotocol()))
+            .collect();
+        let socket = SysWebSocket::new_with_str_sequence(&url, &subprotocols)
             .map_err(Into::into)
             .map_err(TransportError::CreateSocket)
             .map_err(tracerr::wrap!())?;
+        socket.set_binary_type(BinaryType::Arraybuffer);
         Ok(Self {
             socket_state: ObservableCell::new(TransportState::Connecting),
+            encoding: Cell::new(Encoding::default()),
             socket: Rc::new(socket),
             on_open_listener: None,
             on_message_listener: None,
@@ -272,7 +320,14 @@ impl WebSocketRpcTransport {
                     Rc::clone(&socket_mut.socket),
                     "open",
                     move |_| {
-                        inner.borrow().socket_state.set(TransportState::Open);
+                        let inner = inner.borrow();
+                        inner.encoding.set(
+                            Encoding::from_subprotocol(
+                                &inner.socket.protocol(),
+                            )
+                            .unwrap_or_default(),
+                        );
+                        inner.socket_state.set(TransportState::Open);
                     },
                 )
                 .map_err(tracerr::map_from_and_wrap!(=> TransportError))?,
@@ -326,16 +381,18 @@ impl WebSocketRpcTransport {
             Rc::clone(&self.0.borrow().socket),
             "message",
             move |msg| {
-                let msg =
-                    match ServerMessage::try_from(&msg).map(ServerMsg::from) {
-                        Ok(parsed) => parsed,
-                        Err(e) => {
-                            // TODO: protocol versions mismatch? should drop
-                            //       connection if so
-                            JasonError::from(tracerr::new!(e)).print();
-                            return;
-                        }
-                    };
+                let encoding = this.borrow().encoding.get();
+                let msg = match ServerMessage::decode(&msg, encoding)
+                    .map(ServerMsg::from)
+                {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        // TODO: protocol versions mismatch? should drop
+                        //       connection if so
+                        JasonError::from(tracerr::new!(e)).print();
+                        return;
+                    }
+                };
 
                 let mut this_mut = this.borrow_mut();
                 this_mut.on_message_subs.retain(|on_message| {
@@ -365,18 +422,27 @@ impl RpcTransport for WebSocketRpcTransport {
 
     fn send(&self, msg: &ClientMsg) -> Result<()> {
         let inner = self.0.borrow();
-        let message = serde_json::to_string(msg)
+        let encoding = inner.encoding.get();
+        let mut message = encoding
+            .encode(msg)
             .map_err(|e| TransportError::ParseClientMessage(e.into()))
             .map_err(tracerr::wrap!())?;
 
         let state = &*inner.socket_state.borrow();
         match state {
-            TransportState::Open => inner
-                .socket
-                .send_with_str(&message)
-                .map_err(Into::into)
-                .map_err(TransportError::SendMessage)
-                .map_err(tracerr::wrap!()),
+            TransportState::Open => {
+                let sent = if encoding.is_binary() {
+                    inner.socket.send_with_u8_array(&mut message)
+                } else {
+                    inner.socket.send_with_str(
+                        &String::from_utf8(message)
+                            .expect("JSON is always valid UTF-8"),
+                    )
+                };
+                sent.map_err(Into::into)
+                    .map_err(TransportError::SendMessage)
+                    .map_err(tracerr::wrap!())
+            }
             _ => Err(tracerr::new!(TransportError::ClosedSocket)),
         }
     }
@@ -4,7 +4,8 @@ use std::{
     rc::Rc,
 };
 
-use derive_more::{Display, From};
+use derive_more::Display;
+use medea_client_api_proto::encoding::{DecodeError, EncodeError};
 use tracerr::{Trace, Traced};
 use wasm_bindgen::{prelude::*, JsCast};
 
@@ -149,16 +150,37 @@ where
 #[display(fmt = "Handler is in detached state.")]
 pub struct HandlerDetachedError;
 
-/// Wrapper for [`serde_json::error::Error`] that provides [`Clone`], [`Debug`],
-/// [`Display`] implementations.
-#[derive(Clone, Debug, Display, From)]
-#[from(forward)]
-pub struct JsonParseError(Rc<serde_json::error::Error>);
+/// Wrapper for errors of encoding and decoding Client API messages that
+/// provides [`Clone`], [`Debug`], [`Display`] implementations.
+#[derive(Clone, Debug, Display)]
+pub enum CodecError {
+    /// Failed to encode [`ClientMsg`].
+    ///
+    /// [`ClientMsg`]: medea_client_api_proto::ClientMsg
+    Encode(Rc<EncodeError>),
+
+    /// Failed to decode [`ServerMsg`].
+    ///
+    /// [`ServerMsg`]: medea_client_api_proto::ServerMsg
+    Decode(Rc<DecodeError>),
+}
+
+impl From<EncodeError> for CodecError {
+    #[inline]
+    fn from(err: EncodeError) -> Self {
+        Self::Encode(Rc::new(err))
+    }
+}
+
+impl From<DecodeError> for CodecError {
+    #[inline]
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(Rc::new(err))
+    }
+}
 
-impl PartialEq for JsonParseError {
+impl PartialEq for CodecError {
     fn eq(&self, other: &Self) -> bool {
-        self.0.line() == other.0.line()
-            && self.0.column() == other.0.column()
-            && self.0.classify() == other.0.classify()
+        self.to_string() == other.to_string()
     }
 }
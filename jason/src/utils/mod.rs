@@ -22,7 +22,7 @@ pub use self::errors::console_error;
 pub use self::{
     callback::{Callback0, Callback1, Callback2},
     errors::{
-        HandlerDetachedError, JasonError, JsCaused, JsError, JsonParseError,
+        CodecError, HandlerDetachedError, JasonError, JsCaused, JsError,
     },
     event_listener::{EventListener, EventListenerBindError},
     resettable_delay::{resettable_delay_for, ResettableDelayHandle},
@@ -38,6 +38,7 @@ async fn only_one_strong_rpc_rc_exists() {
                         RPC_SETTINGS,
                         ServerMsg::Event {
                             room_id: "room_id".into(),
+                            seq: None,
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
@@ -82,6 +83,7 @@ async fn rpc_dropped_on_jason_dispose() {
                         RPC_SETTINGS,
                         ServerMsg::Event {
                             room_id: "room_id".into(),
+                            seq: None,
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
@@ -171,6 +173,7 @@ async fn room_dispose_works() {
             client_msg_txs.borrow().iter().for_each(|tx| {
                 tx.unbounded_send(ServerMsg::Event {
                     room_id: "room_id".into(),
+                    seq: None,
                     event: Event::RoomJoined {
                         member_id: "member_id".into(),
                     },
@@ -197,6 +200,7 @@ async fn room_dispose_works() {
             client_msg_txs.borrow().iter().for_each(|tx| {
                 tx.unbounded_send(ServerMsg::Event {
                     room_id: "another_room_id".into(),
+                    seq: None,
                     event: Event::RoomJoined {
                         member_id: "member_id".into(),
                     },
@@ -219,7 +223,9 @@ async fn room_dispose_works() {
             room_id: _,
             command: Command::JoinRoom {
                 member_id: _,
-                credential: _
+                credential: _,
+                last_seq: _,
+                handshake: _
             }
         }
     ));
@@ -229,7 +235,9 @@ async fn room_dispose_works() {
             room_id: _,
             command: Command::JoinRoom {
                 member_id: _,
-                credential: _
+                credential: _,
+                last_seq: _,
+                handshake: _
             }
         }
     ));
@@ -279,6 +287,7 @@ async fn room_closes_on_rpc_transport_close() {
                             RPC_SETTINGS,
                             ServerMsg::Event {
                                 room_id: "room_id".into(),
+                                seq: None,
                                 event: Event::RoomJoined {
                                     member_id: "member_id".into(),
                                 },
@@ -1035,6 +1035,48 @@ mod on_close_callback {
     }
 }
 
+/// Tests for `RoomHandle.on_custom_event` JS side callback.
+mod on_custom_event_callback {
+    use wasm_bindgen::{prelude::*, JsValue};
+
+    use super::*;
+
+    #[wasm_bindgen(inline_js = "export function get_text(payload) { return \
+                                payload.text; }")]
+    extern "C" {
+        fn get_text(payload: &JsValue) -> String;
+    }
+
+    /// Tests that JS side [`RoomHandle::on_custom_event`] receives payloads
+    /// of [`Event::Custom`]s.
+    ///
+    /// # Algorithm
+    ///
+    /// 1. Subscribe to [`RoomHandle::on_custom_event`].
+    ///
+    /// 2. Send [`Event::Custom`] to [`Room`].
+    ///
+    /// 3. Check that JS callback was called with payload of this event.
+    #[wasm_bindgen_test]
+    async fn passes_payload_to_callback() {
+        let (event_tx, event_rx) = mpsc::unbounded();
+        let (room, _) = get_test_room(Box::pin(event_rx));
+        let room_handle = room.new_handle();
+
+        let (cb, test_result) = js_callback!(|payload: JsValue| {
+            cb_assert_eq!(get_text(&payload), "recording started");
+        });
+        room_handle.on_custom_event(cb.into()).unwrap();
+
+        event_tx
+            .unbounded_send(Event::Custom {
+                payload: serde_json::json!({"text": "recording started"}),
+            })
+            .unwrap();
+        wait_and_check_test_result(test_result, || {}).await;
+    }
+}
+
 mod rpc_close_reason_on_room_drop {
     //! Tests which checks that when [`Room`] is dropped, the right close reason
     //! is provided to [`RpcClient`].
@@ -1557,6 +1599,7 @@ async fn disable_by_server() {
                 id: audio_track_id,
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             })],
         })
         .unwrap();
@@ -1587,6 +1630,7 @@ async fn enable_by_server() {
                 id: audio_track_id,
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             })],
         })
         .unwrap();
@@ -1603,6 +1647,7 @@ async fn enable_by_server() {
                 id: audio_track_id,
                 enabled_general: Some(true),
                 enabled_individual: Some(true),
+                required: None,
             })],
         })
         .unwrap();
@@ -1638,6 +1683,7 @@ async fn only_one_gum_performed_on_enable() {
                 id: audio_track_id,
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             })],
         })
         .unwrap();
@@ -1655,6 +1701,7 @@ async fn only_one_gum_performed_on_enable() {
                 id: audio_track_id,
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             })],
         })
         .unwrap();
@@ -1689,6 +1736,7 @@ async fn only_one_gum_performed_on_enable_by_server() {
                 id: audio_track_id,
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             })],
         })
         .unwrap();
@@ -1706,6 +1754,7 @@ async fn only_one_gum_performed_on_enable_by_server() {
                 id: audio_track_id,
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             })],
         })
         .unwrap();
@@ -146,6 +146,7 @@ async fn disable_and_enable_all_tracks_in_media_manager() {
             id: audio_track_id,
             enabled_general: Some(false),
             enabled_individual: Some(false),
+            required: None,
         }])
         .await
         .unwrap();
@@ -160,6 +161,7 @@ async fn disable_and_enable_all_tracks_in_media_manager() {
             id: video_track_id,
             enabled_general: Some(false),
             enabled_individual: Some(false),
+            required: None,
         }])
         .await
         .unwrap();
@@ -174,6 +176,7 @@ async fn disable_and_enable_all_tracks_in_media_manager() {
             id: audio_track_id,
             enabled_individual: Some(true),
             enabled_general: Some(true),
+            required: None,
         }])
         .await
         .unwrap();
@@ -188,6 +191,7 @@ async fn disable_and_enable_all_tracks_in_media_manager() {
             id: video_track_id,
             enabled_individual: Some(true),
             enabled_general: Some(true),
+            required: None,
         }])
         .await
         .unwrap();
@@ -249,6 +253,7 @@ mod sender_patch {
                 id: TrackId(track_id.0 + 100),
                 enabled_individual: Some(false),
                 enabled_general: Some(false),
+                required: None,
             })
             .await;
 
@@ -263,6 +268,7 @@ mod sender_patch {
                 id: track_id,
                 enabled_individual: Some(false),
                 enabled_general: Some(false),
+                required: None,
             })
             .await;
 
@@ -277,6 +283,7 @@ mod sender_patch {
                 id: track_id,
                 enabled_individual: Some(true),
                 enabled_general: Some(true),
+                required: None,
             })
             .await;
 
@@ -291,6 +298,7 @@ mod sender_patch {
                 id: track_id,
                 enabled_individual: Some(false),
                 enabled_general: Some(false),
+                required: None,
             })
             .await;
         assert!(sender.is_general_disabled());
@@ -300,6 +308,7 @@ mod sender_patch {
                 id: track_id,
                 enabled_individual: Some(false),
                 enabled_general: Some(false),
+                required: None,
             })
             .await;
 
@@ -314,6 +323,7 @@ mod sender_patch {
                 id: track_id,
                 enabled_individual: None,
                 enabled_general: None,
+                required: None,
             })
             .await;
 
@@ -359,6 +369,7 @@ mod receiver_patch {
             id: TrackId(TRACK_ID.0 + 100),
             enabled_individual: Some(false),
             enabled_general: Some(false),
+            required: None,
         });
 
         assert!(!receiver.is_general_disabled());
@@ -371,6 +382,7 @@ mod receiver_patch {
             id: TRACK_ID,
             enabled_individual: Some(false),
             enabled_general: Some(false),
+            required: None,
         });
 
         assert!(receiver.is_general_disabled());
@@ -383,6 +395,7 @@ mod receiver_patch {
             id: TRACK_ID,
             enabled_individual: Some(true),
             enabled_general: Some(true),
+            required: None,
         });
 
         assert!(!receiver.is_general_disabled());
@@ -395,6 +408,7 @@ mod receiver_patch {
             id: TRACK_ID,
             enabled_individual: Some(false),
             enabled_general: Some(false),
+            required: None,
         });
         assert!(receiver.is_general_disabled());
 
@@ -402,6 +416,7 @@ mod receiver_patch {
             id: TRACK_ID,
             enabled_individual: Some(false),
             enabled_general: Some(false),
+            required: None,
         });
 
         assert!(receiver.is_general_disabled());
@@ -414,6 +429,7 @@ mod receiver_patch {
             id: TRACK_ID,
             enabled_individual: None,
             enabled_general: None,
+            required: None,
         });
 
         assert!(!receiver.is_general_disabled());
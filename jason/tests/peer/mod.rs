@@ -44,6 +44,7 @@ fn toggle_disable_tracks_updates(
             id: TrackId(*track_id),
             enabled_individual: Some(enabled),
             enabled_general: Some(enabled),
+            required: None,
         })
         .collect()
 }
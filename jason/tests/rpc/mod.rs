@@ -82,6 +82,7 @@ async fn message_received_from_transport_is_transmitted_to_sub() {
                 }),
                 ServerMsg::Event {
                     room_id: "".into(),
+                    seq: None,
                     event: SRV_EVENT,
                 },
             ])
@@ -9,7 +9,7 @@ use std::{
 
 use futures::{future, stream, FutureExt as _, StreamExt as _};
 use medea_client_api_proto::{
-    ClientMsg, CloseReason, Command, Event, ServerMsg,
+    Capability, ClientMsg, CloseReason, Command, Event, Handshake, ServerMsg,
 };
 use medea_jason::rpc::{
     websocket::{MockRpcTransport, TransportState},
@@ -35,8 +35,10 @@ async fn could_not_auth_err() {
                         RPC_SETTINGS,
                         ServerMsg::Event {
                             room_id: "room_id".into(),
+                            seq: None,
                             event: Event::RoomLeft {
                                 close_reason: CloseReason::InternalError,
+                                description: None,
                             },
                         },
                     ]))
@@ -91,6 +93,7 @@ async fn concurrent_connect_requests() {
                         RPC_SETTINGS,
                         ServerMsg::Event {
                             room_id: "room_id".into(),
+                            seq: None,
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
@@ -190,6 +193,7 @@ async fn reconnect_after_transport_abnormal_close() {
                         RPC_SETTINGS,
                         ServerMsg::Event {
                             room_id: "room_id".into(),
+                            seq: None,
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
@@ -247,6 +251,10 @@ async fn reconnect_after_transport_abnormal_close() {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: Some(Handshake::new(
+                        Capability::ALL.iter().copied(),
+                    )),
                 }
             },
             // reconnect
@@ -255,6 +263,10 @@ async fn reconnect_after_transport_abnormal_close() {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: Some(Handshake::new(
+                        Capability::ALL.iter().copied(),
+                    )),
                 }
             }
         ]
@@ -1,5 +1,7 @@
 //! `Endpoint` related methods and entities.
 
+use std::collections::HashMap;
+
 use medea_control_api_proto::grpc::api as proto;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
@@ -178,6 +180,9 @@ impl WebRtcPublishEndpoint {
             on_stop: String::new(),
             audio_settings: Some(self.audio_settings.into()),
             video_settings: Some(self.video_settings.into()),
+            version: 0,
+            labels: HashMap::new(),
+            metadata: String::new(),
         }
     }
 }
@@ -231,6 +236,9 @@ impl WebRtcPlayEndpoint {
             force_relay: self.force_relay,
             on_start: String::new(),
             on_stop: String::new(),
+            version: 0,
+            labels: HashMap::new(),
+            metadata: String::new(),
         }
     }
 }
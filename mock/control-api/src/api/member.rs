@@ -7,6 +7,40 @@ use serde::{Deserialize, Serialize};
 
 use super::endpoint::Endpoint;
 
+/// Credentials of [Control API] `Member`.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(untagged)]
+pub enum Credentials {
+    /// Plain credentials, which are hashed on Medea side.
+    Plain(String),
+
+    /// [Argon2] hash of credentials encoded as [PHC string].
+    ///
+    /// [Argon2]: https://en.wikipedia.org/wiki/Argon2
+    /// [PHC string]: https://tinyurl.com/y5rsqkgn
+    Hash { hash: String },
+}
+
+impl Into<proto::member::Credentials> for Credentials {
+    fn into(self) -> proto::member::Credentials {
+        match self {
+            Self::Plain(plain) => proto::member::Credentials::Plain(plain),
+            Self::Hash { hash } => proto::member::Credentials::Hash(hash),
+        }
+    }
+}
+
+impl From<proto::member::Credentials> for Credentials {
+    fn from(proto: proto::member::Credentials) -> Self {
+        match proto {
+            proto::member::Credentials::Plain(plain) => Self::Plain(plain),
+            proto::member::Credentials::Hash(hash) => Self::Hash { hash },
+        }
+    }
+}
+
 /// Entity that represents [Control API] `Member`.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
@@ -24,7 +58,7 @@ pub struct Member {
     /// Optional `Member` credentials.
     ///
     /// If `None` then random credentials will be generated on Medea side.
-    credentials: Option<String>,
+    credentials: Option<Credentials>,
 
     /// URL to which `OnJoin` Control API callback will be sent.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -62,12 +96,15 @@ impl Member {
         proto::Member {
             pipeline: member_elements,
             id,
-            credentials: self.credentials.unwrap_or_default(),
+            credentials: self.credentials.map(Into::into),
             on_join: self.on_join.unwrap_or_default(),
             on_leave: self.on_leave.unwrap_or_default(),
             idle_timeout: self.idle_timeout.map(Into::into),
             reconnect_timeout: self.reconnect_timeout.map(Into::into),
             ping_interval: self.ping_interval.map(Into::into),
+            version: 0,
+            labels: HashMap::new(),
+            metadata: String::new(),
         }
     }
 
@@ -91,7 +128,7 @@ impl From<proto::Member> for Member {
         Self {
             id: proto.id,
             pipeline: member_pipeline,
-            credentials: Some(proto.credentials),
+            credentials: proto.credentials.map(Into::into),
             on_join: Some(proto.on_join).filter(|s| !s.is_empty()),
             on_leave: Some(proto.on_leave).filter(|s| !s.is_empty()),
             idle_timeout: proto.idle_timeout.map(|dur| dur.try_into().unwrap()),
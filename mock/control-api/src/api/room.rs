@@ -31,6 +31,11 @@ impl Room {
                 .into_iter()
                 .map(|(id, member)| (id.clone(), member.into_proto(id)))
                 .collect(),
+            auto_remove: None,
+            on_remove: String::new(),
+            version: 0,
+            labels: HashMap::new(),
+            metadata: String::new(),
         }
     }
 }
@@ -51,7 +51,11 @@ impl From<(String, String, String)> for Fid {
 
 /// Returns new [`proto::IdRequest`] with provided FIDs.
 fn id_request(ids: Vec<String>) -> proto::IdRequest {
-    proto::IdRequest { fid: ids }
+    proto::IdRequest {
+        fid: ids,
+        expected_versions: HashMap::new(),
+        label_selector: HashMap::new(),
+    }
 }
 
 /// Client for [Medea]'s [Control API].
@@ -127,6 +131,7 @@ impl ControlClient {
         let req = proto::CreateRequest {
             parent_fid: fid.into(),
             el: Some(el),
+            expected_version: 0,
         };
 
         let response = self.get_client().create(tonic::Request::new(req)).await;
@@ -0,0 +1,167 @@
+//! Wire encodings of Client API messages.
+//!
+//! Encoding is negotiated during WebSocket handshake via
+//! [`Sec-WebSocket-Protocol`][1] header: `Client` offers the subprotocols it
+//! supports, and `Media Server` picks the first one it supports too. If no
+//! subprotocol is offered, [`Encoding::Json`] is used.
+//!
+//! [1]: https://tools.ietf.org/html/rfc6455#section-11.3.4
+
+use derive_more::{Display, From};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Encoding of Client API messages sent over the wire.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum Encoding {
+    /// Messages are encoded as JSON and sent in text frames.
+    #[display(fmt = "JSON")]
+    Json,
+
+    /// Messages are encoded as [MessagePack][1] and sent in binary frames.
+    ///
+    /// [1]: https://msgpack.org
+    #[display(fmt = "MessagePack")]
+    MessagePack,
+}
+
+impl Default for Encoding {
+    #[inline]
+    fn default() -> Self {
+        Self::Json
+    }
+}
+
+impl Encoding {
+    /// All the supported [`Encoding`]s in the order of preference.
+    pub const ALL: [Self; 2] = [Self::MessagePack, Self::Json];
+
+    /// Returns name of WebSocket subprotocol identifying this [`Encoding`].
+    #[inline]
+    #[must_use]
+    pub fn subprotocol(self) -> &'static str {
+        match self {
+            Self::Json => "medea-json",
+            Self::MessagePack => "medea-msgpack",
+        }
+    }
+
+    /// Returns [`Encoding`] identified by the provided WebSocket subprotocol
+    /// name, if any.
+    #[must_use]
+    pub fn from_subprotocol(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|encoding| encoding.subprotocol() == name)
+    }
+
+    /// Picks [`Encoding`] from the provided value of
+    /// `Sec-WebSocket-Protocol` header, respecting the order of subprotocols
+    /// offered by `Client`.
+    ///
+    /// Returns [`None`] if none of the offered subprotocols is supported.
+    #[must_use]
+    pub fn negotiate(offered: &str) -> Option<Self> {
+        offered
+            .split(',')
+            .map(str::trim)
+            .find_map(Self::from_subprotocol)
+    }
+
+    /// Indicates whether messages of this [`Encoding`] are sent in binary
+    /// frames rather than text ones.
+    #[inline]
+    #[must_use]
+    pub fn is_binary(self) -> bool {
+        match self {
+            Self::Json => false,
+            Self::MessagePack => true,
+        }
+    }
+
+    /// Encodes the provided message with this [`Encoding`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided message cannot be serialized.
+    pub fn encode<T: Serialize>(self, msg: &T) -> Result<Vec<u8>, EncodeError> {
+        Ok(match self {
+            Self::Json => serde_json::to_vec(msg)?,
+            Self::MessagePack => rmp_serde::to_vec_named(msg)?,
+        })
+    }
+
+    /// Decodes message of this [`Encoding`] from the provided bytes.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided bytes don't represent a valid message of this
+    /// [`Encoding`].
+    pub fn decode<T: DeserializeOwned>(
+        self,
+        bytes: &[u8],
+    ) -> Result<T, DecodeError> {
+        Ok(match self {
+            Self::Json => serde_json::from_slice(bytes)?,
+            Self::MessagePack => rmp_serde::from_slice(bytes)?,
+        })
+    }
+}
+
+/// Error of encoding Client API message.
+#[derive(Debug, Display, From)]
+pub enum EncodeError {
+    /// Failed to encode message as JSON.
+    #[display(fmt = "Failed to encode JSON: {}", _0)]
+    Json(serde_json::Error),
+
+    /// Failed to encode message as MessagePack.
+    #[display(fmt = "Failed to encode MessagePack: {}", _0)]
+    MessagePack(rmp_serde::encode::Error),
+}
+
+/// Error of decoding Client API message.
+#[derive(Debug, Display, From)]
+pub enum DecodeError {
+    /// Failed to decode message from JSON.
+    #[display(fmt = "Failed to decode JSON: {}", _0)]
+    Json(serde_json::Error),
+
+    /// Failed to decode message from MessagePack.
+    #[display(fmt = "Failed to decode MessagePack: {}", _0)]
+    MessagePack(rmp_serde::decode::Error),
+}
+
+#[cfg(all(test, feature = "medea", feature = "jason"))]
+mod spec {
+    use crate::{Event, PeerId, RoomId, ServerMsg};
+
+    use super::*;
+
+    #[test]
+    fn negotiates_first_supported_subprotocol() {
+        assert_eq!(Encoding::negotiate("medea-json"), Some(Encoding::Json));
+        assert_eq!(
+            Encoding::negotiate("unknown, medea-msgpack , medea-json"),
+            Some(Encoding::MessagePack),
+        );
+        assert_eq!(Encoding::negotiate("unknown"), None);
+        assert_eq!(Encoding::negotiate(""), None);
+    }
+
+    #[test]
+    fn round_trips_messages() {
+        let msg = ServerMsg::Event {
+            room_id: RoomId("room".into()),
+            seq: Some(3),
+            event: Event::PeersRemoved {
+                peer_ids: vec![PeerId(1), PeerId(2)],
+            },
+        };
+        for encoding in &Encoding::ALL {
+            let bytes = encoding.encode(&msg).unwrap();
+            let decoded: ServerMsg = encoding.decode(&bytes).unwrap();
+            assert_eq!(decoded, msg, "{}", encoding);
+        }
+    }
+}
@@ -23,16 +23,23 @@
 //! [2]: https://tinyurl.com/y8bacb93
 //! [3]: https://tinyurl.com/y4j3b4cs
 
+pub mod encoding;
 pub mod stats;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use derive_more::{Constructor, Display, From};
+use derive_more::{Display, From};
 use medea_macro::dispatchable;
 use serde::{Deserialize, Serialize};
 
 use self::stats::RtcStat;
 
+/// Version of Client API protocol implemented by this crate.
+///
+/// Should be incremented whenever [`ClientMsg`] or [`ServerMsg`] change in a
+/// way that cannot be covered by a [`Capability`].
+pub const PROTOCOL_VERSION: u32 = 1;
+
 /// ID of `Room`.
 #[derive(
     Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, From, Display,
@@ -117,6 +124,14 @@ pub enum ServerMsg {
         /// ID of `Room` that this [`Event`] is associated with.
         room_id: RoomId,
 
+        /// Sequence number of this [`Event`] among all the [`Event`]s sent to
+        /// `Client`'s `Member` in this `Room`.
+        ///
+        /// `None` if this [`Event`] is not replayed on reconnection (like
+        /// [`Event::RoomJoined`] and [`Event::RoomLeft`]).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seq: Option<u32>,
+
         /// Actual [`Event`] sent to `Client`.
         event: Event,
     },
@@ -174,6 +189,22 @@ pub enum Command {
 
         /// [`Credential`] of `Client`'s `Member`.
         credential: Credential,
+
+        /// Sequence number of the last [`ServerMsg::Event`] received by
+        /// `Client` in this `Room`, if it reconnects.
+        ///
+        /// `Media Server` replays all the [`Event`]s sent after it, or
+        /// resynchronizes `Client` with a full state snapshot if they are not
+        /// available anymore.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_seq: Option<u32>,
+
+        /// Protocol version and capabilities of `Client`.
+        ///
+        /// `None` if `Client` predates protocol versioning, so it's served
+        /// with [`PROTOCOL_VERSION`] `0` and [`Capability::BASELINE`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        handshake: Option<Handshake>,
     },
 
     /// Request of `Client` to leave `Room`.
@@ -352,8 +383,59 @@ impl From<IceConnectionState> for PeerConnectionState {
     }
 }
 
+/// Protocol version and capabilities declared by `Client` when joining `Room`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Handshake {
+    /// Version of Client API protocol spoken by `Client`.
+    pub protocol_version: u32,
+
+    /// Optional protocol features supported by `Client`.
+    #[serde(default)]
+    pub capabilities: HashSet<Capability>,
+}
+
+impl Handshake {
+    /// Creates new [`Handshake`] declaring [`PROTOCOL_VERSION`] and the
+    /// provided [`Capability`]s.
+    #[must_use]
+    pub fn new<I: IntoIterator<Item = Capability>>(capabilities: I) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: capabilities.into_iter().collect(),
+        }
+    }
+}
+
+/// Optional feature of Client API protocol, which `Client` may declare support
+/// of in its [`Handshake`].
+///
+/// `Media Server` doesn't send [`Event`]s requiring a [`Capability`] that
+/// `Client` hasn't declared.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Capability {
+    /// `Client` understands [`Event::ConnectionQualityUpdated`].
+    ConnectionQuality,
+
+    /// `Client` understands [`Event::Custom`].
+    CustomEvents,
+
+    /// [`Capability`] unknown to this side, declared by a newer `Client`.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Capability {
+    /// All the [`Capability`]s known to this version of protocol.
+    pub const ALL: [Self; 2] = [Self::ConnectionQuality, Self::CustomEvents];
+    /// [`Capability`]s supported by any `Client`, including the ones not
+    /// sending [`Handshake`] at all.
+    pub const BASELINE: [Self; 1] = [Self::ConnectionQuality];
+}
+
 /// Reason of disconnecting Web Client from Media Server.
-#[derive(Copy, Clone, Debug, Deserialize, Display, Serialize, Eq, PartialEq)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, Display, Serialize, Eq, PartialEq,
+)]
 pub enum CloseReason {
     /// Client session was finished on a server side.
     Finished,
@@ -377,16 +459,50 @@ pub enum CloseReason {
 
     /// Client was evicted on the server side.
     Evicted,
+
+    /// Client speaks a version of protocol that is not supported by server.
+    IncompatibleProtocol,
+
+    /// Client has been sending commands faster than allowed by server for too
+    /// long.
+    RateLimited,
 }
 
 /// Description which is sent in [Close] WebSocket frame from Media Server
 /// to Web Client.
 ///
 /// [Close]: https://tools.ietf.org/html/rfc6455#section-5.5.1
-#[derive(Constructor, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct CloseDescription {
     /// Reason of why WebSocket connection has been closed.
     pub reason: CloseReason,
+
+    /// Human-readable description of why WebSocket connection has been
+    /// closed, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl CloseDescription {
+    /// Creates new [`CloseDescription`] with the provided [`CloseReason`] and
+    /// without any human-readable description.
+    #[must_use]
+    pub fn new(reason: CloseReason) -> Self {
+        Self {
+            reason,
+            description: None,
+        }
+    }
+
+    /// Creates new [`CloseDescription`] with the provided [`CloseReason`] and
+    /// human-readable description.
+    #[must_use]
+    pub fn with_description(reason: CloseReason, description: String) -> Self {
+        Self {
+            reason,
+            description: Some(description),
+        }
+    }
 }
 
 /// WebSocket message from Medea to Jason.
@@ -405,6 +521,10 @@ pub enum Event {
     RoomLeft {
         /// [`CloseReason`] with which `Client` was left.
         close_reason: CloseReason,
+
+        /// Human-readable description of why `Client` was left, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
     },
 
     /// Media Server notifies Web Client about necessity of RTCPeerConnection
@@ -456,6 +576,34 @@ pub enum Event {
         /// Estimated connection quality.
         quality_score: ConnectionQualityScore,
     },
+
+    /// Media Server passes custom application-level event sent via Control
+    /// API.
+    Custom {
+        /// Opaque JSON payload of this event, passed as is.
+        payload: serde_json::Value,
+    },
+}
+
+impl Event {
+    /// Returns [`Capability`] that `Client` should declare to receive this
+    /// [`Event`], if any.
+    #[must_use]
+    pub fn required_capability(&self) -> Option<Capability> {
+        match self {
+            Self::ConnectionQualityUpdated { .. } => {
+                Some(Capability::ConnectionQuality)
+            }
+            Self::Custom { .. } => Some(Capability::CustomEvents),
+            Self::RoomJoined { .. }
+            | Self::RoomLeft { .. }
+            | Self::PeerCreated { .. }
+            | Self::SdpAnswerMade { .. }
+            | Self::IceCandidateDiscovered { .. }
+            | Self::PeersRemoved { .. }
+            | Self::TracksApplied { .. } => None,
+        }
+    }
 }
 
 /// `Peer`'s negotiation role.
@@ -489,6 +637,13 @@ pub enum TrackUpdate {
 
     /// [`Peer`] should start ICE restart process on the next renegotiation.
     IceRestart,
+
+    /// [`Peer`] should forcibly relay all media through a TURN server (if
+    /// `true`) or stop doing so (if `false`).
+    ///
+    /// Takes effect once ICE is restarted, so is always accompanied with a
+    /// [`TrackUpdate::IceRestart`].
+    ForceRelay(bool),
 }
 
 /// Represents [RTCIceCandidateInit][1] object.
@@ -550,6 +705,9 @@ pub struct TrackPatchEvent {
     /// So intention of this `Member` (`enabled_individual`) can be
     /// `false`, but real media exchange state can be `true`.
     pub enabled_general: Option<bool>,
+
+    /// Indicator whether the [`Track`] is required to call starting.
+    pub required: Option<bool>,
 }
 
 impl From<TrackPatchCommand> for TrackPatchEvent {
@@ -558,6 +716,7 @@ impl From<TrackPatchCommand> for TrackPatchEvent {
             id: from.id,
             enabled_individual: from.enabled,
             enabled_general: None,
+            required: None,
         }
     }
 }
@@ -571,6 +730,7 @@ impl TrackPatchEvent {
             id,
             enabled_general: None,
             enabled_individual: None,
+            required: None,
         }
     }
 
@@ -590,6 +750,10 @@ impl TrackPatchEvent {
         if let Some(enabled_individual) = another.enabled_individual {
             self.enabled_individual = Some(enabled_individual);
         }
+
+        if let Some(required) = another.required {
+            self.required = Some(required);
+        }
     }
 }
 
@@ -714,32 +878,38 @@ mod test {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(false),
                         enabled_individual: Some(false),
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: None,
                         enabled_individual: None,
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
+                        required: None,
                     },
                 ],
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
+                    required: None,
                 },
             ),
             (
@@ -748,17 +918,20 @@ mod test {
                         id: TrackId(1),
                         enabled_general: None,
                         enabled_individual: None,
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
+                        required: None,
                     },
                 ],
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
+                    required: None,
                 },
             ),
             (
@@ -767,17 +940,20 @@ mod test {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: None,
                         enabled_individual: None,
+                        required: None,
                     },
                 ],
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
+                    required: None,
                 },
             ),
             (
@@ -786,17 +962,42 @@ mod test {
                         id: TrackId(1),
                         enabled_general: None,
                         enabled_individual: None,
+                        required: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(2),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
+                        required: None,
                     },
                 ],
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: None,
                     enabled_individual: None,
+                    required: None,
+                },
+            ),
+            (
+                vec![
+                    TrackPatchEvent {
+                        id: TrackId(1),
+                        enabled_general: Some(true),
+                        enabled_individual: None,
+                        required: Some(true),
+                    },
+                    TrackPatchEvent {
+                        id: TrackId(1),
+                        enabled_general: None,
+                        enabled_individual: None,
+                        required: Some(false),
+                    },
+                ],
+                TrackPatchEvent {
+                    id: TrackId(1),
+                    enabled_general: Some(true),
+                    enabled_individual: None,
+                    required: Some(false),
                 },
             ),
         ] {
@@ -812,4 +1013,22 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn handshake_tolerates_unknown_capabilities() {
+        let handshake: Handshake = serde_json::from_str(
+            r#"{
+                "protocol_version": 2,
+                "capabilities": ["CustomEvents", "Teleportation"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(handshake.protocol_version, 2);
+        assert_eq!(
+            handshake.capabilities,
+            vec![Capability::CustomEvents, Capability::Unknown]
+                .into_iter()
+                .collect(),
+        );
+    }
 }
@@ -4,6 +4,14 @@ pub struct CreateRequest {
     /// FID (full ID) of the Element in which the provided Element will be created.
     #[prost(string, tag="1")]
     pub parent_fid: std::string::String,
+    /// Expected version of the Element in which the provided Element will be
+    /// created.
+    ///
+    /// If not 0, then the provided Element is created only if the parent Element
+    /// has exactly this version, otherwise an Error with 1028 code is returned.
+    /// Ignored when a Room is created.
+    #[prost(uint64, tag="6")]
+    pub expected_version: u64,
     /// Spec of the created Element.
     #[prost(oneof="create_request::El", tags="2, 3, 4, 5")]
     pub el: ::std::option::Option<create_request::El>,
@@ -22,12 +30,190 @@ pub mod create_request {
         WebrtcPub(super::WebRtcPublishEndpoint),
     }
 }
+/// Request of applying a spec to an Element with a given FID (full ID).
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApplyRequest {
+    /// FID (full ID) of the Element in which the provided Element will be
+    /// applied.
+    #[prost(string, tag="1")]
+    pub parent_fid: std::string::String,
+    /// Policy to apply the provided spec with.
+    #[prost(enumeration="apply_request::Policy", tag="4")]
+    pub policy: i32,
+    /// Expected version of the applied Element.
+    ///
+    /// If not 0, then the provided spec is applied only if the applied Element
+    /// exists and has exactly this version, otherwise an Error with 1028 code is
+    /// returned.
+    #[prost(uint64, tag="5")]
+    pub expected_version: u64,
+    /// Spec of the applied Element.
+    #[prost(oneof="apply_request::El", tags="2, 3")]
+    pub el: ::std::option::Option<apply_request::El>,
+}
+pub mod apply_request {
+    /// Policy of how the Elements which are not specified in the provided
+    /// pipeline should be treated.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Policy {
+        /// Elements that exist, but are not specified in the provided pipeline
+        /// will be removed.
+        Apply = 0,
+        /// Elements that exist, but are not specified in the provided pipeline
+        /// will be kept.
+        Append = 1,
+    }
+    /// Spec of the applied Element.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum El {
+        #[prost(message, tag="2")]
+        Member(super::Member),
+        #[prost(message, tag="3")]
+        Room(super::Room),
+    }
+}
+/// Request of updating properties of an Element with a given FID (full ID)
+/// in place.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateRequest {
+    /// FID (full ID) of the updated Element.
+    #[prost(string, tag="1")]
+    pub fid: std::string::String,
+    /// Expected version of the updated Element.
+    ///
+    /// If not 0, then the Element is updated only if it has exactly this
+    /// version, otherwise an Error with 1028 code is returned.
+    #[prost(uint64, tag="4")]
+    pub expected_version: u64,
+    /// Patch to update the Element with.
+    #[prost(oneof="update_request::Patch", tags="2, 3")]
+    pub patch: ::std::option::Option<update_request::Patch>,
+}
+pub mod update_request {
+    /// Patch to update the Element with.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Patch {
+        #[prost(message, tag="2")]
+        Member(super::MemberPatch),
+        #[prost(message, tag="3")]
+        WebrtcPub(super::WebRtcPublishEndpointPatch),
+    }
+}
+/// Patch of the Member properties.
+///
+/// Properties which are not specified are kept unchanged.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemberPatch {
+    /// New timeout of receiving heartbeat messages from the Member via Client
+    /// API.
+    #[prost(message, optional, tag="1")]
+    pub idle_timeout: ::std::option::Option<::prost_types::Duration>,
+    /// New timeout of the Member reconnecting via Client API.
+    #[prost(message, optional, tag="2")]
+    pub reconnect_timeout: ::std::option::Option<::prost_types::Duration>,
+    /// New interval of sending pings from a media server to the Member via
+    /// Client API.
+    #[prost(message, optional, tag="3")]
+    pub ping_interval: ::std::option::Option<::prost_types::Duration>,
+}
+/// Patch of the WebRtcPublishEndpoint properties.
+///
+/// Properties which are not specified are kept unchanged.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebRtcPublishEndpointPatch {
+    /// New option to relay all media through a TURN server forcibly.
+    #[prost(message, optional, tag="1")]
+    pub force_relay: ::std::option::Option<bool>,
+    /// New settings for the audio media type.
+    ///
+    /// Publish policy can't be changed from or to DISABLED this way, an Error
+    /// with 1029 code is returned on such attempt.
+    #[prost(message, optional, tag="2")]
+    pub audio_settings: ::std::option::Option<web_rtc_publish_endpoint::AudioSettings>,
+    /// New settings for the video media type.
+    ///
+    /// Publish policy can't be changed from or to DISABLED this way, an Error
+    /// with 1029 code is returned on such attempt.
+    #[prost(message, optional, tag="3")]
+    pub video_settings: ::std::option::Option<web_rtc_publish_endpoint::VideoSettings>,
+}
+/// Request of kicking a Member with a given FID (full ID) out of its Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KickRequest {
+    /// FID (full ID) of the kicked Member.
+    #[prost(string, tag="1")]
+    pub fid: std::string::String,
+    /// Human-readable description of why the Member is kicked, which is passed
+    /// to its client along with the EVICTED close reason.
+    ///
+    /// Not passed if empty. Should be at most 80 bytes long, otherwise an
+    /// Error with 1032 code is returned.
+    #[prost(string, tag="2")]
+    pub description: std::string::String,
+    /// Expected version of the kicked Member.
+    ///
+    /// If not 0, then the Member is kicked only if it has exactly this version,
+    /// otherwise an Error with 1028 code is returned.
+    #[prost(uint64, tag="5")]
+    pub expected_version: u64,
+    /// New credentials of the kicked Member, replacing its current ones
+    /// atomically with closing its session.
+    ///
+    /// Credentials are kept unchanged if not specified.
+    #[prost(oneof="kick_request::Credentials", tags="3, 4")]
+    pub credentials: ::std::option::Option<kick_request::Credentials>,
+}
+pub mod kick_request {
+    /// New credentials of the kicked Member, replacing its current ones
+    /// atomically with closing its session.
+    ///
+    /// Credentials are kept unchanged if not specified.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Credentials {
+        /// Plain credentials.
+        ///
+        /// If empty, then random plain credentials are generated.
+        #[prost(string, tag="3")]
+        Plain(std::string::String),
+        /// Argon2 hash of credentials encoded as a PHC string.
+        #[prost(string, tag="4")]
+        Hash(std::string::String),
+    }
+}
+/// Request of sending custom event to a Room or a Member with a given FID
+/// (full ID).
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CustomEventRequest {
+    /// FID (full ID) of the Room or the Member receiving the event.
+    #[prost(string, tag="1")]
+    pub fid: std::string::String,
+    /// JSON payload of the event, passed to clients as is.
+    #[prost(string, tag="2")]
+    pub payload: std::string::String,
+}
 /// Request with many FIDs (full IDs) of Elements.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct IdRequest {
     /// List of Elements FIDs.
     #[prost(string, repeated, tag="1")]
     pub fid: ::std::vec::Vec<std::string::String>,
+    /// Expected versions of the Elements by their FIDs.
+    ///
+    /// If specified for some Element, then Delete removes the Elements only if
+    /// this Element exists and has exactly this version, otherwise an Error with
+    /// 1028 code is returned and nothing is removed.
+    /// Versions of FIDs not listed in the fid field are ignored.
+    ///
+    /// Ignored by Get.
+    #[prost(map="string, uint64", tag="2")]
+    pub expected_versions: ::std::collections::HashMap<std::string::String, u64>,
+    /// Label selector of the Rooms to process in addition to the listed FIDs.
+    ///
+    /// Selects all the Rooms having all the specified labels with exactly the
+    /// specified values. Ignored if empty.
+    #[prost(map="string, string", tag="3")]
+    pub label_selector: ::std::collections::HashMap<std::string::String, std::string::String>,
 }
 /// Response which doesn't return anything on successful result,
 /// but is fallible with an Error.
@@ -71,6 +257,149 @@ pub struct GetResponse {
     #[prost(message, optional, tag="2")]
     pub error: ::std::option::Option<Error>,
 }
+/// Request of listing existing Rooms.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRequest {
+    /// Cursor to continue listing from, as returned in the
+    /// ListResponse.next_cursor.
+    ///
+    /// If empty, then listing starts from the first Room.
+    #[prost(string, tag="1")]
+    pub cursor: std::string::String,
+    /// Maximum number of Rooms to return.
+    ///
+    /// If 0, then 100 Rooms are returned at most. Cannot exceed 1000.
+    #[prost(uint32, tag="2")]
+    pub limit: u32,
+    /// Indicator whether full specs of the listed Rooms should be returned
+    /// (deep mode), or only their summaries (shallow mode).
+    #[prost(bool, tag="3")]
+    pub deep: bool,
+    /// Filter of the listed Rooms.
+    #[prost(message, optional, tag="4")]
+    pub filter: ::std::option::Option<list_request::Filter>,
+}
+pub mod list_request {
+    /// Filter of the listed Rooms.
+    ///
+    /// All the specified conditions must be met for a Room to be listed.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Filter {
+        /// Minimum number of Members in a Room.
+        #[prost(message, optional, tag="1")]
+        pub min_members: ::std::option::Option<u32>,
+        /// Maximum number of Members in a Room.
+        #[prost(message, optional, tag="2")]
+        pub max_members: ::std::option::Option<u32>,
+        /// Minimum number of Members connected to a Room.
+        #[prost(message, optional, tag="3")]
+        pub min_connected_members: ::std::option::Option<u32>,
+        /// Maximum number of Members connected to a Room.
+        #[prost(message, optional, tag="4")]
+        pub max_connected_members: ::std::option::Option<u32>,
+        /// Origin of a Room.
+        #[prost(enumeration="filter::Origin", tag="5")]
+        pub origin: i32,
+        /// Labels which a Room must have with exactly the specified values.
+        #[prost(map="string, string", tag="6")]
+        pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    }
+    pub mod filter {
+        /// Origin of a Room.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+        #[repr(i32)]
+        pub enum Origin {
+            /// Rooms of any origin.
+            Any = 0,
+            /// Rooms created from static Control API specs.
+            Static = 1,
+            /// Rooms created via Control API.
+            Dynamic = 2,
+        }
+    }
+}
+/// Response of List RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResponse {
+    /// Listed Rooms in order of their IDs.
+    #[prost(message, repeated, tag="1")]
+    pub rooms: ::std::vec::Vec<list_response::RoomInfo>,
+    /// Cursor to request the next page of Rooms with.
+    ///
+    /// Empty if there are no more Rooms to list.
+    #[prost(string, tag="2")]
+    pub next_cursor: std::string::String,
+    /// Error of the ListResponse.
+    #[prost(message, optional, tag="3")]
+    pub error: ::std::option::Option<Error>,
+}
+pub mod list_response {
+    /// Summary of a listed Room.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RoomInfo {
+        /// ID of the Room.
+        #[prost(string, tag="1")]
+        pub id: std::string::String,
+        /// Number of Members in the Room.
+        #[prost(uint32, tag="2")]
+        pub members: u32,
+        /// Number of Members connected to the Room.
+        #[prost(uint32, tag="3")]
+        pub connected_members: u32,
+        /// Indicator whether the Room was created from a static Control API spec.
+        #[prost(bool, tag="4")]
+        pub is_static: bool,
+        /// Full spec of the Room.
+        ///
+        /// Returned only in deep mode.
+        #[prost(message, optional, tag="5")]
+        pub room: ::std::option::Option<super::Room>,
+        /// Labels of the Room.
+        #[prost(map="string, string", tag="6")]
+        pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    }
+}
+/// Request of watching lifecycle events of Elements.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchRequest {
+    /// FID (full ID) of the Element to watch events of this Element and all
+    /// its nested Elements.
+    ///
+    /// If empty, then events of all Elements are watched.
+    #[prost(string, tag="1")]
+    pub fid: std::string::String,
+}
+/// Lifecycle event of an Element.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ElementEvent {
+    /// FID (full ID) of the Element this event occurred with.
+    #[prost(string, tag="1")]
+    pub fid: std::string::String,
+    /// Kind of this event.
+    #[prost(enumeration="element_event::Kind", tag="2")]
+    pub kind: i32,
+    /// Time of this event occurring.
+    #[prost(string, tag="3")]
+    pub at: std::string::String,
+}
+pub mod element_event {
+    /// Kind of an Element lifecycle event.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Kind {
+        /// Element has been created.
+        Created = 0,
+        /// Element has been deleted.
+        Deleted = 1,
+        /// Member has established its RPC connection with the media server.
+        MemberConnected = 2,
+        /// Member's RPC connection with the media server has been closed.
+        MemberDisconnected = 3,
+    }
+}
 /// Error of failed request.
 ///
 /// If the Error is not returned then request is considered as successful.
@@ -124,6 +453,28 @@ pub struct Room {
     /// Pipeline of this Room.
     #[prost(map="string, message", tag="2")]
     pub pipeline: ::std::collections::HashMap<std::string::String, room::Element>,
+    /// Rule of automatic removing of this Room.
+    ///
+    /// If not specified, then this Room is kept until it's deleted explicitly.
+    #[prost(message, optional, tag="3")]
+    pub auto_remove: ::std::option::Option<room::AutoRemove>,
+    /// URL of the callback to fire when this Room is removed automatically.
+    #[prost(string, tag="4")]
+    pub on_remove: std::string::String,
+    /// Version of this Room.
+    ///
+    /// Changes every time this Room or any of its nested Elements is changed.
+    /// Ignored when this Room is created or applied.
+    #[prost(uint64, tag="5")]
+    pub version: u64,
+    /// Labels of this Room.
+    ///
+    /// Arbitrary key-value pairs which Rooms can be selected by.
+    #[prost(map="string, string", tag="6")]
+    pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    /// Opaque metadata of this Room, which is stored and returned as is.
+    #[prost(string, tag="7")]
+    pub metadata: std::string::String,
 }
 pub mod room {
     /// Elements which Room's pipeline can contain.
@@ -143,6 +494,33 @@ pub mod room {
             WebrtcPub(super::super::WebRtcPublishEndpoint),
         }
     }
+    /// Rule of automatic removing of a Room.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct AutoRemove {
+        /// Kind of this rule.
+        #[prost(enumeration="auto_remove::Kind", tag="1")]
+        pub kind: i32,
+        /// Duration which a Room should stay without connected Members for to be
+        /// removed.
+        ///
+        /// Used only with the EMPTY_FOR kind.
+        #[prost(message, optional, tag="2")]
+        pub empty_for: ::std::option::Option<::prost_types::Duration>,
+    }
+    pub mod auto_remove {
+        /// Kind of a Room automatic removing rule.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+        #[repr(i32)]
+        pub enum Kind {
+            /// Room is kept until it's deleted explicitly.
+            Never = 0,
+            /// Room is removed once all its connected Members have left it.
+            AllLeft = 1,
+            /// Room is removed once it has been without connected Members for the
+            /// specified duration.
+            EmptyFor = 2,
+        }
+    }
 }
 /// Media element which represents a client authorized to participate
 /// in a some bigger media pipeline.
@@ -159,9 +537,6 @@ pub struct Member {
     /// with a media server via Client API.
     #[prost(string, tag="3")]
     pub on_leave: std::string::String,
-    /// Credentials of the Member to authorize via Client API with.
-    #[prost(string, tag="4")]
-    pub credentials: std::string::String,
     /// Timeout of receiving heartbeat messages from the Member via Client API.
     /// Once reached, the Member is considered being idle.
     #[prost(message, optional, tag="5")]
@@ -176,6 +551,30 @@ pub struct Member {
     /// Pipeline of this Member.
     #[prost(map="string, message", tag="8")]
     pub pipeline: ::std::collections::HashMap<std::string::String, member::Element>,
+    /// Version of this Member.
+    ///
+    /// Changes every time this Member or any of its nested Elements is changed.
+    /// Ignored when this Member is created or applied.
+    #[prost(uint64, tag="10")]
+    pub version: u64,
+    /// Labels of this Member.
+    ///
+    /// Arbitrary key-value pairs which Members can be selected by.
+    #[prost(map="string, string", tag="11")]
+    pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    /// Opaque metadata of this Member, which is stored and returned as is.
+    #[prost(string, tag="12")]
+    pub metadata: std::string::String,
+    /// Credentials of the Member to authorize via Client API with.
+    ///
+    /// Plain credentials are hashed once the Member is created, so only their
+    /// hash is returned afterwards. If no credentials are provided, then random
+    /// plain ones are generated.
+    ///
+    /// If a hash is provided, then the sid returned for this Member doesn't
+    /// contain credentials, so a client should append them on its own.
+    #[prost(oneof="member::Credentials", tags="4, 9")]
+    pub credentials: ::std::option::Option<member::Credentials>,
 }
 pub mod member {
     /// Elements which Member's pipeline can contain.
@@ -193,6 +592,23 @@ pub mod member {
             WebrtcPub(super::super::WebRtcPublishEndpoint),
         }
     }
+    /// Credentials of the Member to authorize via Client API with.
+    ///
+    /// Plain credentials are hashed once the Member is created, so only their
+    /// hash is returned afterwards. If no credentials are provided, then random
+    /// plain ones are generated.
+    ///
+    /// If a hash is provided, then the sid returned for this Member doesn't
+    /// contain credentials, so a client should append them on its own.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Credentials {
+        /// Plain credentials.
+        #[prost(string, tag="4")]
+        Plain(std::string::String),
+        /// Argon2 hash of credentials encoded as a PHC string.
+        #[prost(string, tag="9")]
+        Hash(std::string::String),
+    }
 }
 /// Media element which is able to receive media data from a client via WebRTC
 /// (allows to publish media data).
@@ -219,6 +635,20 @@ pub struct WebRtcPublishEndpoint {
     /// Settings for the video media type of this element.
     #[prost(message, optional, tag="7")]
     pub video_settings: ::std::option::Option<web_rtc_publish_endpoint::VideoSettings>,
+    /// Version of this WebRtcPublishEndpoint.
+    ///
+    /// Changes every time this WebRtcPublishEndpoint is changed.
+    /// Ignored when this WebRtcPublishEndpoint is created or applied.
+    #[prost(uint64, tag="8")]
+    pub version: u64,
+    /// Labels of this WebRtcPublishEndpoint.
+    ///
+    /// Arbitrary key-value pairs which WebRtcPublishEndpoints can be selected by.
+    #[prost(map="string, string", tag="9")]
+    pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    /// Opaque metadata of this WebRtcPublishEndpoint, which is stored and returned as is.
+    #[prost(string, tag="10")]
+    pub metadata: std::string::String,
 }
 pub mod web_rtc_publish_endpoint {
     /// Audio media type settings of WebRtcPublishEndpoint.
@@ -291,5 +721,19 @@ pub struct WebRtcPlayEndpoint {
     /// Option to relay all media through a TURN server forcibly.
     #[prost(bool, tag="5")]
     pub force_relay: bool,
+    /// Version of this WebRtcPlayEndpoint.
+    ///
+    /// Changes every time this WebRtcPlayEndpoint is changed.
+    /// Ignored when this WebRtcPlayEndpoint is created or applied.
+    #[prost(uint64, tag="6")]
+    pub version: u64,
+    /// Labels of this WebRtcPlayEndpoint.
+    ///
+    /// Arbitrary key-value pairs which WebRtcPlayEndpoints can be selected by.
+    #[prost(map="string, string", tag="7")]
+    pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    /// Opaque metadata of this WebRtcPlayEndpoint, which is stored and returned as is.
+    #[prost(string, tag="8")]
+    pub metadata: std::string::String,
 }
-# [ doc = r" Generated client implementations." ] pub mod control_api_client { # ! [ allow ( unused_variables , dead_code , missing_docs ) ] use tonic :: codegen :: * ; # [ doc = " Media server's Control API service." ] pub struct ControlApiClient < T > { inner : tonic :: client :: Grpc < T > , } impl ControlApiClient < tonic :: transport :: Channel > { # [ doc = r" Attempt to create a new client by connecting to a given endpoint." ] pub async fn connect < D > ( dst : D ) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new ( dst ) ? . connect ( ) . await ? ; Ok ( Self :: new ( conn ) ) } } impl < T > ControlApiClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new ( inner : T ) -> Self { let inner = tonic :: client :: Grpc :: new ( inner ) ; Self { inner } } pub fn with_interceptor ( inner : T , interceptor : impl Into < tonic :: Interceptor > ) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor ( inner , interceptor ) ; Self { inner } } # [ doc = " Creates new Element with a given ID." ] # [ doc = "" ] # [ doc = " Not idempotent. Errors if an Element with the same ID already exists." ] pub async fn create ( & mut self , request : impl tonic :: IntoRequest < super :: CreateRequest > , ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Create" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Removes Element by its ID." ] # [ doc = " Allows referring multiple Elements on the last two levels." ] # [ doc = "" ] # [ doc = " Idempotent. If no Elements with such IDs exist, then succeeds." ] pub async fn delete ( & mut self , request : impl tonic :: IntoRequest < super :: IdRequest > , ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Delete" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Returns Element by its ID." ] # [ doc = " Allows referring multiple Elements." ] # [ doc = " If no ID specified, returns all Elements declared." ] pub async fn get ( & mut self , request : impl tonic :: IntoRequest < super :: IdRequest > , ) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Get" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } } impl < T : Clone > Clone for ControlApiClient < T > { fn clone ( & self ) -> Self { Self { inner : self . inner . clone ( ) , } } } impl < T > std :: fmt :: Debug for ControlApiClient < T > { fn fmt ( & self , f : & mut std :: fmt :: Formatter < '_ > ) -> std :: fmt :: Result { write ! ( f , "ControlApiClient {{ ... }}" ) } } }# [ doc = r" Generated server implementations." ] pub mod control_api_server { # ! [ allow ( unused_variables , dead_code , missing_docs ) ] use tonic :: codegen :: * ; # [ doc = "Generated trait containing gRPC methods that should be implemented for use with ControlApiServer." ] # [ async_trait ] pub trait ControlApi : Send + Sync + 'static { # [ doc = " Creates new Element with a given ID." ] # [ doc = "" ] # [ doc = " Not idempotent. Errors if an Element with the same ID already exists." ] async fn create ( & self , request : tonic :: Request < super :: CreateRequest > ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [ doc = " Removes Element by its ID." ] # [ doc = " Allows referring multiple Elements on the last two levels." ] # [ doc = "" ] # [ doc = " Idempotent. If no Elements with such IDs exist, then succeeds." ] async fn delete ( & self , request : tonic :: Request < super :: IdRequest > ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [ doc = " Returns Element by its ID." ] # [ doc = " Allows referring multiple Elements." ] # [ doc = " If no ID specified, returns all Elements declared." ] async fn get ( & self , request : tonic :: Request < super :: IdRequest > ) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > ; } # [ doc = " Media server's Control API service." ] # [ derive ( Debug ) ] pub struct ControlApiServer < T : ControlApi > { inner : _Inner < T > , } struct _Inner < T > ( Arc < T > , Option < tonic :: Interceptor > ) ; impl < T : ControlApi > ControlApiServer < T > { pub fn new ( inner : T ) -> Self { let inner = Arc :: new ( inner ) ; let inner = _Inner ( inner , None ) ; Self { inner } } pub fn with_interceptor ( inner : T , interceptor : impl Into < tonic :: Interceptor > ) -> Self { let inner = Arc :: new ( inner ) ; let inner = _Inner ( inner , Some ( interceptor . into ( ) ) ) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for ControlApiServer < T > where T : ControlApi , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready ( & mut self , _cx : & mut Context < '_ > ) -> Poll < Result < ( ) , Self :: Error >> { Poll :: Ready ( Ok ( ( ) ) ) } fn call ( & mut self , req : http :: Request < B > ) -> Self :: Future { let inner = self . inner . clone ( ) ; match req . uri ( ) . path ( ) { "/api.ControlApi/Create" => { # [ allow ( non_camel_case_types ) ] struct CreateSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: CreateRequest > for CreateSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: CreateRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . create ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = CreateSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Delete" => { # [ allow ( non_camel_case_types ) ] struct DeleteSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for DeleteSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: IdRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . delete ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = DeleteSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Get" => { # [ allow ( non_camel_case_types ) ] struct GetSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for GetSvc < T > { type Response = super :: GetResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: IdRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . get ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = GetSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } _ => Box :: pin ( async move { Ok ( http :: Response :: builder ( ) . status ( 200 ) . header ( "grpc-status" , "12" ) . body ( tonic :: body :: BoxBody :: empty ( ) ) . unwrap ( ) ) } ) , } } } impl < T : ControlApi > Clone for ControlApiServer < T > { fn clone ( & self ) -> Self { let inner = self . inner . clone ( ) ; Self { inner } } } impl < T : ControlApi > Clone for _Inner < T > { fn clone ( & self ) -> Self { Self ( self . 0 . clone ( ) , self . 1 . clone ( ) ) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt ( & self , f : & mut std :: fmt :: Formatter < '_ > ) -> std :: fmt :: Result { write ! ( f , "{:?}" , self . 0 ) } } impl < T : ControlApi > tonic :: transport :: NamedService for ControlApiServer < T > { const NAME : & 'static str = "api.ControlApi" ; } }
\ No newline at end of file
+# [ doc = r" Generated client implementations." ] pub mod control_api_client { # ! [ allow ( unused_variables , dead_code , missing_docs ) ] use tonic :: codegen :: * ; # [ doc = " Media server's Control API service." ] pub struct ControlApiClient < T > { inner : tonic :: client :: Grpc < T > , } impl ControlApiClient < tonic :: transport :: Channel > { # [ doc = r" Attempt to create a new client by connecting to a given endpoint." ] pub async fn connect < D > ( dst : D ) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new ( dst ) ? . connect ( ) . await ? ; Ok ( Self :: new ( conn ) ) } } impl < T > ControlApiClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new ( inner : T ) -> Self { let inner = tonic :: client :: Grpc :: new ( inner ) ; Self { inner } } pub fn with_interceptor ( inner : T , interceptor : impl Into < tonic :: Interceptor > ) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor ( inner , interceptor ) ; Self { inner } } # [ doc = " Creates new Element with a given ID." ] # [ doc = "" ] # [ doc = " Not idempotent. Errors if an Element with the same ID already exists." ] pub async fn create ( & mut self , request : impl tonic :: IntoRequest < super :: CreateRequest > , ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Create" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Applies given spec to Element by its ID." ] # [ doc = "" ] # [ doc = " Idempotent. If no Element with such ID exists, then it will be created," ] # [ doc = " otherwise it will be reconfigured. Elements that exist, but are not" ] # [ doc = " specified in the provided pipeline will be removed or kept depending on" ] # [ doc = " the provided ApplyRequest.Policy." ] pub async fn apply ( & mut self , request : impl tonic :: IntoRequest < super :: ApplyRequest > , ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Apply" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Updates properties of the live Element by its FID (full ID) in place." ] # [ doc = "" ] # [ doc = " Unlike Apply, doesn't recreate the Element, so its established" ] # [ doc = " connections are kept. Only the properties specified in the provided patch" ] # [ doc = " are changed." ] pub async fn update ( & mut self , request : impl tonic :: IntoRequest < super :: UpdateRequest > , ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Update" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Forcibly closes the Client API session of the Member by its FID (full ID)." ] # [ doc = "" ] # [ doc = " The Member itself is kept, so it may join the Room again later, unless" ] # [ doc = " its credentials are rotated by this request." ] # [ doc = "" ] # [ doc = " Idempotent. If the Member is not connected, then only its credentials" ] # [ doc = " are rotated (if provided)." ] # [ doc = "" ] # [ doc = " Returns sid of the kicked Member, which contains its new credentials if" ] # [ doc = " plain ones are provided." ] pub async fn kick ( & mut self , request : impl tonic :: IntoRequest < super :: KickRequest > , ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Kick" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Sends custom application-level event with an opaque JSON payload to the" ] # [ doc = " Member with a given FID (full ID), or to all the connected Members of the" ] # [ doc = " Room with a given FID." ] # [ doc = "" ] # [ doc = " Errors if the Member is not connected." ] pub async fn send_custom_event ( & mut self , request : impl tonic :: IntoRequest < super :: CustomEventRequest > , ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/SendCustomEvent" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Removes Element by its ID." ] # [ doc = " Allows referring multiple Elements on the last two levels, and selecting" ] # [ doc = " Rooms by their labels." ] # [ doc = "" ] # [ doc = " Idempotent. If no Elements with such IDs exist, then succeeds." ] pub async fn delete ( & mut self , request : impl tonic :: IntoRequest < super :: IdRequest > , ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Delete" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Returns Element by its ID." ] # [ doc = " Allows referring multiple Elements, and selecting Rooms by their labels." ] # [ doc = " If no ID specified, returns all Elements declared." ] pub async fn get ( & mut self , request : impl tonic :: IntoRequest < super :: IdRequest > , ) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Get" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Lists existing Rooms page by page in order of their IDs." ] # [ doc = "" ] # [ doc = " Allows filtering Rooms by their Members, origin and labels." ] pub async fn list ( & mut self , request : impl tonic :: IntoRequest < super :: ListRequest > , ) -> Result < tonic :: Response < super :: ListResponse > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/List" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } # [ doc = " Streams lifecycle events of Elements whose FID (full ID) starts with the" ] # [ doc = " provided one." ] # [ doc = "" ] # [ doc = " The stream lasts until it's closed by the client, the client falls too far" ] # [ doc = " behind the events, or the server shuts down." ] pub async fn watch ( & mut self , request : impl tonic :: IntoRequest < super :: WatchRequest > , ) -> Result < tonic :: Response < tonic :: codec :: Streaming < super :: ElementEvent >> , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/api.ControlApi/Watch" ) ; self . inner . server_streaming ( request . into_request ( ) , path , codec ) . await } } impl < T : Clone > Clone for ControlApiClient < T > { fn clone ( & self ) -> Self { Self { inner : self . inner . clone ( ) , } } } impl < T > std :: fmt :: Debug for ControlApiClient < T > { fn fmt ( & self , f : & mut std :: fmt :: Formatter < '_ > ) -> std :: fmt :: Result { write ! ( f , "ControlApiClient {{ ... }}" ) } } }# [ doc = r" Generated server implementations." ] pub mod control_api_server { # ! [ allow ( unused_variables , dead_code , missing_docs ) ] use tonic :: codegen :: * ; # [ doc = "Generated trait containing gRPC methods that should be implemented for use with ControlApiServer." ] # [ async_trait ] pub trait ControlApi : Send + Sync + 'static { # [ doc = " Creates new Element with a given ID." ] # [ doc = "" ] # [ doc = " Not idempotent. Errors if an Element with the same ID already exists." ] async fn create ( & self , request : tonic :: Request < super :: CreateRequest > ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [ doc = " Applies given spec to Element by its ID." ] # [ doc = "" ] # [ doc = " Idempotent. If no Element with such ID exists, then it will be created," ] # [ doc = " otherwise it will be reconfigured. Elements that exist, but are not" ] # [ doc = " specified in the provided pipeline will be removed or kept depending on" ] # [ doc = " the provided ApplyRequest.Policy." ] async fn apply ( & self , request : tonic :: Request < super :: ApplyRequest > ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [ doc = " Updates properties of the live Element by its FID (full ID) in place." ] # [ doc = "" ] # [ doc = " Unlike Apply, doesn't recreate the Element, so its established" ] # [ doc = " connections are kept. Only the properties specified in the provided patch" ] # [ doc = " are changed." ] async fn update ( & self , request : tonic :: Request < super :: UpdateRequest > ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [ doc = " Forcibly closes the Client API session of the Member by its FID (full ID)." ] # [ doc = "" ] # [ doc = " The Member itself is kept, so it may join the Room again later, unless" ] # [ doc = " its credentials are rotated by this request." ] # [ doc = "" ] # [ doc = " Idempotent. If the Member is not connected, then only its credentials" ] # [ doc = " are rotated (if provided)." ] # [ doc = "" ] # [ doc = " Returns sid of the kicked Member, which contains its new credentials if" ] # [ doc = " plain ones are provided." ] async fn kick ( & self , request : tonic :: Request < super :: KickRequest > ) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [ doc = " Sends custom application-level event with an opaque JSON payload to the" ] # [ doc = " Member with a given FID (full ID), or to all the connected Members of the" ] # [ doc = " Room with a given FID." ] # [ doc = "" ] # [ doc = " Errors if the Member is not connected." ] async fn send_custom_event ( & self , request : tonic :: Request < super :: CustomEventRequest > ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [ doc = " Removes Element by its ID." ] # [ doc = " Allows referring multiple Elements on the last two levels, and selecting" ] # [ doc = " Rooms by their labels." ] # [ doc = "" ] # [ doc = " Idempotent. If no Elements with such IDs exist, then succeeds." ] async fn delete ( & self , request : tonic :: Request < super :: IdRequest > ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [ doc = " Returns Element by its ID." ] # [ doc = " Allows referring multiple Elements, and selecting Rooms by their labels." ] # [ doc = " If no ID specified, returns all Elements declared." ] async fn get ( & self , request : tonic :: Request < super :: IdRequest > ) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > ; # [ doc = " Lists existing Rooms page by page in order of their IDs." ] # [ doc = "" ] # [ doc = " Allows filtering Rooms by their Members, origin and labels." ] async fn list ( & self , request : tonic :: Request < super :: ListRequest > ) -> Result < tonic :: Response < super :: ListResponse > , tonic :: Status > ; # [ doc = "Server streaming response type for the Watch method." ] type WatchStream : Stream < Item = Result < super :: ElementEvent , tonic :: Status >> + Send + Sync + 'static ; # [ doc = " Streams lifecycle events of Elements whose FID (full ID) starts with the" ] # [ doc = " provided one." ] # [ doc = "" ] # [ doc = " The stream lasts until it's closed by the client, the client falls too far" ] # [ doc = " behind the events, or the server shuts down." ] async fn watch ( & self , request : tonic :: Request < super :: WatchRequest > ) -> Result < tonic :: Response < Self :: WatchStream > , tonic :: Status > ; } # [ doc = " Media server's Control API service." ] # [ derive ( Debug ) ] pub struct ControlApiServer < T : ControlApi > { inner : _Inner < T > , } struct _Inner < T > ( Arc < T > , Option < tonic :: Interceptor > ) ; impl < T : ControlApi > ControlApiServer < T > { pub fn new ( inner : T ) -> Self { let inner = Arc :: new ( inner ) ; let inner = _Inner ( inner , None ) ; Self { inner } } pub fn with_interceptor ( inner : T , interceptor : impl Into < tonic :: Interceptor > ) -> Self { let inner = Arc :: new ( inner ) ; let inner = _Inner ( inner , Some ( interceptor . into ( ) ) ) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for ControlApiServer < T > where T : ControlApi , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready ( & mut self , _cx : & mut Context < '_ > ) -> Poll < Result < ( ) , Self :: Error >> { Poll :: Ready ( Ok ( ( ) ) ) } fn call ( & mut self , req : http :: Request < B > ) -> Self :: Future { let inner = self . inner . clone ( ) ; match req . uri ( ) . path ( ) { "/api.ControlApi/Create" => { # [ allow ( non_camel_case_types ) ] struct CreateSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: CreateRequest > for CreateSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: CreateRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . create ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = CreateSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Apply" => { # [ allow ( non_camel_case_types ) ] struct ApplySvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ApplyRequest > for ApplySvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: ApplyRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . apply ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = ApplySvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Update" => { # [ allow ( non_camel_case_types ) ] struct UpdateSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: UpdateRequest > for UpdateSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: UpdateRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . update ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = UpdateSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Kick" => { # [ allow ( non_camel_case_types ) ] struct KickSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: KickRequest > for KickSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: KickRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . kick ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = KickSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/SendCustomEvent" => { # [ allow ( non_camel_case_types ) ] struct SendCustomEventSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: CustomEventRequest > for SendCustomEventSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: CustomEventRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . send_custom_event ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = SendCustomEventSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Delete" => { # [ allow ( non_camel_case_types ) ] struct DeleteSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for DeleteSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: IdRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . delete ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = DeleteSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Get" => { # [ allow ( non_camel_case_types ) ] struct GetSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for GetSvc < T > { type Response = super :: GetResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: IdRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . get ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = GetSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/List" => { # [ allow ( non_camel_case_types ) ] struct ListSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ListRequest > for ListSvc < T > { type Response = super :: ListResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: ListRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . list ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = ListSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } "/api.ControlApi/Watch" => { # [ allow ( non_camel_case_types ) ] struct WatchSvc < T : ControlApi > ( pub Arc < T > ) ; impl < T : ControlApi > tonic :: server :: ServerStreamingService < super :: WatchRequest > for WatchSvc < T > { type Response = super :: ElementEvent ; type ResponseStream = T :: WatchStream ; type Future = BoxFuture < tonic :: Response < Self :: ResponseStream > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: WatchRequest > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . watch ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 ; let inner = inner . 0 ; let method = WatchSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . server_streaming ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } _ => Box :: pin ( async move { Ok ( http :: Response :: builder ( ) . status ( 200 ) . header ( "grpc-status" , "12" ) . body ( tonic :: body :: BoxBody :: empty ( ) ) . unwrap ( ) ) } ) , } } } impl < T : ControlApi > Clone for ControlApiServer < T > { fn clone ( & self ) -> Self { let inner = self . inner . clone ( ) ; Self { inner } } } impl < T : ControlApi > Clone for _Inner < T > { fn clone ( & self ) -> Self { Self ( self . 0 . clone ( ) , self . 1 . clone ( ) ) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt ( & self , f : & mut std :: fmt :: Formatter < '_ > ) -> std :: fmt :: Result { write ! ( f , "{:?}" , self . 0 ) } } impl < T : ControlApi > tonic :: transport :: NamedService for ControlApiServer < T > { const NAME : & 'static str = "api.ControlApi" ; } }
\ No newline at end of file
@@ -7,8 +7,20 @@ pub struct Request {
     /// Time of event occurring.
     #[prost(string, tag="2")]
     pub at: std::string::String,
+    /// Sequence number of this request among all the requests of the element.
+    ///
+    /// Starts from 1 and is increased by 1 with each emitted event of the
+    /// element, so gaps and reorderings can be detected by a receiver.
+    #[prost(uint64, tag="7")]
+    pub seq: u64,
+    /// Labels of the element that event was occurred with.
+    #[prost(map="string, string", tag="9")]
+    pub labels: ::std::collections::HashMap<std::string::String, std::string::String>,
+    /// Opaque metadata of the element that event was occurred with.
+    #[prost(string, tag="10")]
+    pub metadata: std::string::String,
     /// Occurred callback event.
-    #[prost(oneof="request::Event", tags="3, 4")]
+    #[prost(oneof="request::Event", tags="3, 4, 5, 6, 8")]
     pub event: ::std::option::Option<request::Event>,
 }
 pub mod request {
@@ -19,6 +31,12 @@ pub mod request {
         OnJoin(super::OnJoin),
         #[prost(message, tag="4")]
         OnLeave(super::OnLeave),
+        #[prost(message, tag="5")]
+        OnStart(super::OnStart),
+        #[prost(message, tag="6")]
+        OnStop(super::OnStop),
+        #[prost(message, tag="8")]
+        OnRemove(super::OnRemove),
     }
 }
 /// Empty response of the Callback service.
@@ -49,6 +67,66 @@ pub mod on_leave {
         LostConnection = 1,
         /// Medea media server is shutting down.
         ServerShutdown = 2,
+        /// Member was kicked out of the Room via Control API.
+        Kicked = 3,
     }
 }
+/// Event that fires when media traffic starts flowing in an Endpoint.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnStart {
+    /// Type of the media traffic which started flowing.
+    #[prost(enumeration="MediaType", tag="1")]
+    pub media_type: i32,
+    /// Direction of the media traffic which started flowing.
+    #[prost(enumeration="MediaDirection", tag="2")]
+    pub media_direction: i32,
+}
+/// Event that fires when media traffic stops flowing in an Endpoint.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnStop {
+    /// Type of the media traffic which stopped flowing.
+    #[prost(enumeration="MediaType", tag="1")]
+    pub media_type: i32,
+    /// Direction of the media traffic which stopped flowing.
+    #[prost(enumeration="MediaDirection", tag="2")]
+    pub media_direction: i32,
+}
+/// Event that fires when Room is removed automatically according to its
+/// auto-removing rule.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnRemove {
+    /// Reason of why Room was removed.
+    #[prost(enumeration="on_remove::Reason", tag="1")]
+    pub reason: i32,
+}
+pub mod on_remove {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Reason {
+        /// All the connected Members have left the Room.
+        AllLeft = 0,
+        /// Room has been without connected Members for the specified duration.
+        EmptyTimeout = 1,
+    }
+}
+/// Type of the media traffic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MediaType {
+    /// Audio media traffic.
+    Audio = 0,
+    /// Video media traffic.
+    Video = 1,
+    /// Audio and video media traffic.
+    Both = 2,
+}
+/// Direction of the media traffic relatively to an Endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MediaDirection {
+    /// Endpoint is a publisher (WebRtcPublishEndpoint).
+    Publish = 0,
+    /// Endpoint is a player (WebRtcPlayEndpoint).
+    Play = 1,
+}
 # [ doc = r" Generated client implementations." ] pub mod callback_client { # ! [ allow ( unused_variables , dead_code , missing_docs ) ] use tonic :: codegen :: * ; # [ doc = " Service for receiving callbacks from Medea media server." ] pub struct CallbackClient < T > { inner : tonic :: client :: Grpc < T > , } impl CallbackClient < tonic :: transport :: Channel > { # [ doc = r" Attempt to create a new client by connecting to a given endpoint." ] pub async fn connect < D > ( dst : D ) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new ( dst ) ? . connect ( ) . await ? ; Ok ( Self :: new ( conn ) ) } } impl < T > CallbackClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new ( inner : T ) -> Self { let inner = tonic :: client :: Grpc :: new ( inner ) ; Self { inner } } pub fn with_interceptor ( inner : T , interceptor : impl Into < tonic :: Interceptor > ) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor ( inner , interceptor ) ; Self { inner } } # [ doc = "/ Fires when a certain callback event happens on Medea media server." ] pub async fn on_event ( & mut self , request : impl tonic :: IntoRequest < super :: Request > , ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready ( ) . await . map_err ( | e | { tonic :: Status :: new ( tonic :: Code :: Unknown , format ! ( "Service was not ready: {}" , e . into ( ) ) ) } ) ? ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let path = http :: uri :: PathAndQuery :: from_static ( "/callback.Callback/OnEvent" ) ; self . inner . unary ( request . into_request ( ) , path , codec ) . await } } impl < T : Clone > Clone for CallbackClient < T > { fn clone ( & self ) -> Self { Self { inner : self . inner . clone ( ) , } } } impl < T > std :: fmt :: Debug for CallbackClient < T > { fn fmt ( & self , f : & mut std :: fmt :: Formatter < '_ > ) -> std :: fmt :: Result { write ! ( f , "CallbackClient {{ ... }}" ) } } }# [ doc = r" Generated server implementations." ] pub mod callback_server { # ! [ allow ( unused_variables , dead_code , missing_docs ) ] use tonic :: codegen :: * ; # [ doc = "Generated trait containing gRPC methods that should be implemented for use with CallbackServer." ] # [ async_trait ] pub trait Callback : Send + Sync + 'static { # [ doc = "/ Fires when a certain callback event happens on Medea media server." ] async fn on_event ( & self , request : tonic :: Request < super :: Request > ) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; } # [ doc = " Service for receiving callbacks from Medea media server." ] # [ derive ( Debug ) ] pub struct CallbackServer < T : Callback > { inner : _Inner < T > , } struct _Inner < T > ( Arc < T > , Option < tonic :: Interceptor > ) ; impl < T : Callback > CallbackServer < T > { pub fn new ( inner : T ) -> Self { let inner = Arc :: new ( inner ) ; let inner = _Inner ( inner , None ) ; Self { inner } } pub fn with_interceptor ( inner : T , interceptor : impl Into < tonic :: Interceptor > ) -> Self { let inner = Arc :: new ( inner ) ; let inner = _Inner ( inner , Some ( interceptor . into ( ) ) ) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for CallbackServer < T > where T : Callback , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready ( & mut self , _cx : & mut Context < '_ > ) -> Poll < Result < ( ) , Self :: Error >> { Poll :: Ready ( Ok ( ( ) ) ) } fn call ( & mut self , req : http :: Request < B > ) -> Self :: Future { let inner = self . inner . clone ( ) ; match req . uri ( ) . path ( ) { "/callback.Callback/OnEvent" => { # [ allow ( non_camel_case_types ) ] struct OnEventSvc < T : Callback > ( pub Arc < T > ) ; impl < T : Callback > tonic :: server :: UnaryService < super :: Request > for OnEventSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call ( & mut self , request : tonic :: Request < super :: Request > ) -> Self :: Future { let inner = self . 0 . clone ( ) ; let fut = async move { ( * inner ) . on_event ( request ) . await } ; Box :: pin ( fut ) } } let inner = self . inner . clone ( ) ; let fut = async move { let interceptor = inner . 1 . clone ( ) ; let inner = inner . 0 ; let method = OnEventSvc ( inner ) ; let codec = tonic :: codec :: ProstCodec :: default ( ) ; let mut grpc = if let Some ( interceptor ) = interceptor { tonic :: server :: Grpc :: with_interceptor ( codec , interceptor ) } else { tonic :: server :: Grpc :: new ( codec ) } ; let res = grpc . unary ( method , req ) . await ; Ok ( res ) } ; Box :: pin ( fut ) } _ => Box :: pin ( async move { Ok ( http :: Response :: builder ( ) . status ( 200 ) . header ( "grpc-status" , "12" ) . body ( tonic :: body :: BoxBody :: empty ( ) ) . unwrap ( ) ) } ) , } } } impl < T : Callback > Clone for CallbackServer < T > { fn clone ( & self ) -> Self { let inner = self . inner . clone ( ) ; Self { inner } } } impl < T : Callback > Clone for _Inner < T > { fn clone ( & self ) -> Self { Self ( self . 0 . clone ( ) , self . 1 . clone ( ) ) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt ( & self , f : & mut std :: fmt :: Formatter < '_ > ) -> std :: fmt :: Result { write ! ( f , "{:?}" , self . 0 ) } } impl < T : Callback > tonic :: transport :: NamedService for CallbackServer < T > { const NAME : & 'static str = "callback.Callback" ; } }
\ No newline at end of file
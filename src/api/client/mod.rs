@@ -4,7 +4,10 @@
 
 mod session;
 
+pub mod rate_limit;
 pub mod rpc_connection;
 pub mod server;
+pub mod sse;
+pub mod token;
 
-pub use self::session::RpcServerRepository;
+pub use self::session::{RpcServerRepository, TokenAuthorizer};
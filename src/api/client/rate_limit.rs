@@ -0,0 +1,300 @@
+//! Rate limiting of [`Command`]s received from a single RPC connection.
+
+use std::time::{Duration, Instant};
+
+use medea_client_api_proto::Command;
+
+use crate::conf::rpc::{CommandLimit, RateLimit};
+
+/// Verdict of [`CommandRateLimiter`] on a received [`Command`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    /// [`Command`] is within limits and should be handled.
+    Accept,
+
+    /// [`Command`] exceeds limits and should be dropped.
+    Drop,
+
+    /// [`Command`] exceeds limits, and too many [`Command`]s have been dropped
+    /// recently, so RPC connection should be closed.
+    Abuse,
+}
+
+/// [Token bucket][1] limiting rate of a single [`Command`] kind.
+///
+/// [1]: https://en.wikipedia.org/wiki/Token_bucket
+#[derive(Debug)]
+struct TokenBucket {
+    /// [`CommandLimit`] this [`TokenBucket`] enforces.
+    limit: CommandLimit,
+
+    /// Number of currently available tokens.
+    tokens: f64,
+
+    /// Time of the last tokens refill.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates new full [`TokenBucket`] enforcing the provided
+    /// [`CommandLimit`].
+    fn new(limit: CommandLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            last_refill: now,
+        }
+    }
+
+    /// Takes a token from this [`TokenBucket`] refilling it first.
+    ///
+    /// Returns `false` if there are no tokens available.
+    fn try_take(&mut self, now: Instant) -> bool {
+        if self.limit.per_second == 0 {
+            return true;
+        }
+
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.last_refill = now;
+        self.tokens = (self.tokens
+            + elapsed.as_secs_f64() * f64::from(self.limit.per_second))
+        .min(f64::from(self.limit.burst));
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Rate limiter of [`Command`]s received from a single RPC connection.
+///
+/// Each limited [`Command`] kind has its own [`TokenBucket`], so flooding
+/// with one of them doesn't affect the others.
+#[derive(Debug)]
+pub struct CommandRateLimiter {
+    /// [`TokenBucket`] of [`Command::JoinRoom`].
+    join_room: TokenBucket,
+
+    /// [`TokenBucket`] of [`Command::SetIceCandidate`].
+    set_ice_candidate: TokenBucket,
+
+    /// [`TokenBucket`] of [`Command::UpdateTracks`].
+    update_tracks: TokenBucket,
+
+    /// [`TokenBucket`] of [`Command::AddPeerConnectionMetrics`].
+    add_peer_connection_metrics: TokenBucket,
+
+    /// Maximum number of [`Command`]s allowed to be dropped within
+    /// [`CommandRateLimiter::abuse_period`].
+    max_dropped: u32,
+
+    /// Period to count dropped [`Command`]s within.
+    abuse_period: Duration,
+
+    /// Number of [`Command`]s dropped since
+    /// [`CommandRateLimiter::dropped_since`].
+    dropped: u32,
+
+    /// Start of the current period of counting dropped [`Command`]s.
+    dropped_since: Instant,
+}
+
+impl CommandRateLimiter {
+    /// Creates new [`CommandRateLimiter`] enforcing the provided
+    /// [`RateLimit`].
+    #[must_use]
+    pub fn new(conf: &RateLimit) -> Self {
+        let now = Instant::now();
+        Self {
+            join_room: TokenBucket::new(conf.join_room, now),
+            set_ice_candidate: TokenBucket::new(conf.set_ice_candidate, now),
+            update_tracks: TokenBucket::new(conf.update_tracks, now),
+            add_peer_connection_metrics: TokenBucket::new(
+                conf.add_peer_connection_metrics,
+                now,
+            ),
+            max_dropped: conf.max_dropped,
+            abuse_period: conf.abuse_period,
+            dropped: 0,
+            dropped_since: now,
+        }
+    }
+
+    /// Checks whether the provided [`Command`] received now is within limits.
+    #[inline]
+    pub fn check(&mut self, command: &Command) -> Verdict {
+        self.check_at(command, Instant::now())
+    }
+
+    /// Checks whether the provided [`Command`] received at the provided time
+    /// is within limits.
+    fn check_at(&mut self, command: &Command, now: Instant) -> Verdict {
+        let bucket = match command {
+            Command::JoinRoom { .. } => &mut self.join_room,
+            Command::SetIceCandidate { .. } => &mut self.set_ice_candidate,
+            Command::UpdateTracks { .. } => &mut self.update_tracks,
+            Command::AddPeerConnectionMetrics { .. } => {
+                &mut self.add_peer_connection_metrics
+            }
+            Command::LeaveRoom { .. }
+            | Command::MakeSdpOffer { .. }
+            | Command::MakeSdpAnswer { .. } => return Verdict::Accept,
+        };
+        if bucket.try_take(now) {
+            return Verdict::Accept;
+        }
+
+        if now.saturating_duration_since(self.dropped_since)
+            >= self.abuse_period
+        {
+            self.dropped = 0;
+            self.dropped_since = now;
+        }
+        self.dropped += 1;
+        if self.dropped > self.max_dropped {
+            Verdict::Abuse
+        } else {
+            Verdict::Drop
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::{PeerConnectionState, PeerId, PeerMetrics};
+
+    use super::*;
+
+    fn metrics() -> Command {
+        Command::AddPeerConnectionMetrics {
+            peer_id: PeerId(1),
+            metrics: PeerMetrics::PeerConnectionState(
+                PeerConnectionState::Connected,
+            ),
+        }
+    }
+
+    fn update_tracks() -> Command {
+        Command::UpdateTracks {
+            peer_id: PeerId(1),
+            tracks_patches: Vec::new(),
+        }
+    }
+
+    fn join_room() -> Command {
+        Command::JoinRoom {
+            member_id: "member".into(),
+            credential: "test".into(),
+            last_seq: None,
+            handshake: None,
+        }
+    }
+
+    fn limiter() -> CommandRateLimiter {
+        CommandRateLimiter::new(&RateLimit {
+            add_peer_connection_metrics: CommandLimit::new(2, 3),
+            max_dropped: 2,
+            abuse_period: Duration::from_secs(10),
+            ..RateLimit::default()
+        })
+    }
+
+    #[test]
+    fn drops_commands_exceeding_burst() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(&metrics(), now), Verdict::Accept);
+        }
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Drop);
+        assert_eq!(limiter.check_at(&update_tracks(), now), Verdict::Accept);
+    }
+
+    #[test]
+    fn refills_tokens_over_time() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(&metrics(), now), Verdict::Accept);
+        }
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Drop);
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(limiter.check_at(&metrics(), later), Verdict::Accept);
+        assert_eq!(limiter.check_at(&metrics(), later), Verdict::Drop);
+
+        let much_later = later + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert_eq!(
+                limiter.check_at(&metrics(), much_later),
+                Verdict::Accept
+            );
+        }
+    }
+
+    #[test]
+    fn detects_persistent_abuse() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(&metrics(), now), Verdict::Accept);
+        }
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Drop);
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Drop);
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Abuse);
+    }
+
+    #[test]
+    fn forgets_drops_after_abuse_period() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(&metrics(), now), Verdict::Accept);
+        }
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Drop);
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Drop);
+
+        let later = now + Duration::from_secs(10);
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(&metrics(), later), Verdict::Accept);
+        }
+        assert_eq!(limiter.check_at(&metrics(), later), Verdict::Drop);
+        assert_eq!(limiter.check_at(&metrics(), later), Verdict::Drop);
+        assert_eq!(limiter.check_at(&metrics(), later), Verdict::Abuse);
+    }
+
+    #[test]
+    fn limits_join_room() {
+        let mut limiter = CommandRateLimiter::new(&RateLimit {
+            join_room: CommandLimit::new(1, 2),
+            ..RateLimit::default()
+        });
+        let now = Instant::now();
+
+        for _ in 0..2 {
+            assert_eq!(limiter.check_at(&join_room(), now), Verdict::Accept);
+        }
+        assert_eq!(limiter.check_at(&join_room(), now), Verdict::Drop);
+        assert_eq!(limiter.check_at(&metrics(), now), Verdict::Accept);
+    }
+
+    #[test]
+    fn zero_rate_disables_limit() {
+        let mut limiter = CommandRateLimiter::new(&RateLimit {
+            add_peer_connection_metrics: CommandLimit::new(0, 0),
+            ..RateLimit::default()
+        });
+        let now = Instant::now();
+
+        for _ in 0..1000 {
+            assert_eq!(limiter.check_at(&metrics(), now), Verdict::Accept);
+        }
+    }
+}
@@ -38,6 +38,7 @@ impl CommandMessage {
 #[rtype(result = "()")]
 pub struct EventMessage {
     pub room_id: RoomId,
+    pub seq: u32,
     pub event: Event,
 }
 
@@ -59,17 +60,23 @@ pub trait RpcConnection: fmt::Debug + Send {
         close_description: CloseDescription,
     ) -> LocalBoxFuture<'static, ()>;
 
-    /// Sends [`Event`] to remote [`Member`].
+    /// Sends [`Event`] with the provided sequence number to remote
+    /// [`Member`].
     ///
     /// [`Member`]: crate::signalling::elements::member::Member
-    fn send_event(&self, room_id: RoomId, event: Event);
+    fn send_event(&self, room_id: RoomId, seq: u32, event: Event);
+
+    /// Updates [`RpcConnectionSettings`] of the session with the provided
+    /// [`RoomId`], notifying the client if the effective settings have
+    /// changed.
+    fn update_settings(&self, room_id: RoomId, settings: RpcConnectionSettings);
 }
 
 #[cfg(test)]
 impl_debug_by_struct_name!(MockRpcConnection);
 
 /// Settings of [`WsSession`].
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct RpcConnectionSettings {
     /// [`Duration`], after which [`WsSession`] will be considered idle if no
     /// heartbeat messages were received.
@@ -81,6 +88,27 @@ pub struct RpcConnectionSettings {
     pub ping_interval: Duration,
 }
 
+/// Way [`Member`] is authorized with when establishing [`RpcConnection`].
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Clone, Debug)]
+pub enum RpcAuthorization {
+    /// [`Credential`] to be verified against [`Member`]'s credentials.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    Credential(Credential),
+
+    /// Signed token of [`Member`] already validated by transport.
+    Token,
+}
+
+impl From<Credential> for RpcAuthorization {
+    #[inline]
+    fn from(credential: Credential) -> Self {
+        Self::Credential(credential)
+    }
+}
+
 /// Signal of new [`RpcConnection`] being established with specified [`Member`].
 /// Transport should consider dropping connection if message result is err.
 ///
@@ -93,11 +121,17 @@ pub struct RpcConnectionEstablished {
     /// [`Member`]: crate::signalling::elements::member::Member
     pub member_id: MemberId,
 
-    /// Credential of [`Member`] to authorize WebSocket connection with.
-    pub credentials: Credential,
+    /// Authorization of [`Member`] to authorize WebSocket connection with.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub authorization: RpcAuthorization,
 
     /// Established [`RpcConnection`].
     pub connection: Box<dyn RpcConnection>,
+
+    /// Sequence number of the last [`Event`] received by the client, if it
+    /// reconnects.
+    pub last_seq: Option<u32>,
 }
 /// Signal of existing [`RpcConnection`] of specified [`Member`] being closed.
 ///
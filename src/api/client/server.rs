@@ -5,15 +5,21 @@ use std::io;
 use actix::{Actor, Addr, Handler, ResponseFuture};
 use actix_web::{
     dev::Server as ActixServer,
+    http::header,
     middleware,
     web::{resource, Data, Payload, ServiceConfig},
     App, HttpRequest, HttpResponse, HttpServer,
 };
 use actix_web_actors::ws;
 use futures::FutureExt as _;
+use medea_client_api_proto::encoding::Encoding;
 
 use crate::{
-    api::client::session::WsSession,
+    api::client::{
+        session::{TokenAuthorizer, WsSession},
+        sse::{self, SseSessions},
+        token::MemberTokenAuthorizer,
+    },
     conf::{Conf, Rpc},
     log::prelude::*,
     shutdown::ShutdownGracefully,
@@ -22,17 +28,36 @@ use crate::{
 
 /// Handles all HTTP requests, performs WebSocket handshake (upgrade) and starts
 /// new [`WsSession`] for WebSocket connection.
+///
+/// [`Encoding`] of Client API messages is negotiated via
+/// `Sec-WebSocket-Protocol` header, falling back to [`Encoding::Json`] if
+/// client doesn't request any supported subprotocol.
 async fn ws_index(
     request: HttpRequest,
     state: Data<Context>,
     payload: Payload,
 ) -> actix_web::Result<HttpResponse> {
-    ws::start(
+    let encoding = request
+        .headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|offered| offered.to_str().ok())
+        .and_then(Encoding::negotiate)
+        .unwrap_or_default();
+    let subprotocols: Vec<_> =
+        Encoding::ALL.iter().map(|e| e.subprotocol()).collect();
+
+    ws::start_with_protocols(
         WsSession::new(
             Box::new(state.rooms.clone()),
+            state.token_authorizer.clone().map(|authorizer| {
+                Box::new(authorizer) as Box<dyn TokenAuthorizer>
+            }),
             state.config.idle_timeout,
             state.config.ping_interval,
+            &state.config.rate_limit,
+            encoding,
         ),
+        &subprotocols,
         &request,
         payload,
     )
@@ -45,11 +70,24 @@ pub struct Context {
     /// [`Room`]: crate::signalling::Room
     pub rooms: RoomRepository,
 
+    /// Authorizer of `Member`s presenting signed tokens.
+    ///
+    /// [`None`] if authentication with tokens is disabled.
+    pub token_authorizer: Option<MemberTokenAuthorizer>,
+
+    /// Sessions served via [SSE] fallback transport.
+    ///
+    /// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+    pub sse_sessions: SseSessions,
+
     /// Settings of application.
     pub config: Rpc,
 }
 
-/// HTTP server that handles WebSocket connections of Client API.
+/// HTTP server that handles WebSocket (and [SSE] fallback) connections of
+/// Client API.
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
 pub struct Server(ActixServer);
 
 impl Server {
@@ -58,12 +96,22 @@ impl Server {
     /// # Errors
     ///
     /// Errors if binding [`HttpServer`] to a listening address fails.
-    pub fn run(rooms: RoomRepository, config: Conf) -> io::Result<Addr<Self>> {
+    pub fn run(
+        rooms: RoomRepository,
+        token_authorizer: Option<MemberTokenAuthorizer>,
+        config: Conf,
+    ) -> io::Result<Addr<Self>> {
         let server_addr = config.server.client.http.bind_addr();
+        let sse_sessions = SseSessions::default();
 
         let server = HttpServer::new(move || {
             App::new()
-                .app_data(Self::app_data(rooms.clone(), config.rpc))
+                .app_data(Data::new(Context {
+                    rooms: rooms.clone(),
+                    token_authorizer: token_authorizer.clone(),
+                    sse_sessions: sse_sessions.clone(),
+                    config: config.rpc.clone(),
+                }))
                 .configure(Self::configure)
                 .wrap(middleware::Logger::default())
         })
@@ -76,15 +124,18 @@ impl Server {
         Ok(Self(server).start())
     }
 
-    /// Set application data.
-    fn app_data(rooms: RoomRepository, config: Rpc) -> Data<Context> {
-        Data::new(Context { rooms, config })
-    }
-
     /// Run external configuration as part of the application building
     /// process
     fn configure(cfg: &mut ServiceConfig) {
-        cfg.service(resource("/ws").route(actix_web::web::get().to(ws_index)));
+        cfg.service(resource("/ws").route(actix_web::web::get().to(ws_index)))
+            .service(
+                resource("/sse")
+                    .route(actix_web::web::get().to(sse::sse_index)),
+            )
+            .service(
+                resource("/sse/{token}")
+                    .route(actix_web::web::post().to(sse::sse_message)),
+            );
     }
 }
 
@@ -1,7 +1,7 @@
 //! WebSocket session.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::TryInto as _,
     fmt::{Debug, Display, Error, Formatter},
     sync::atomic::{AtomicU64, Ordering},
@@ -18,17 +18,24 @@ use actix_web_actors::ws::{self, CloseCode};
 use bytes::{Buf, BytesMut};
 use futures::future::{FutureExt as _, LocalBoxFuture};
 use medea_client_api_proto::{
-    ClientMsg, CloseDescription, CloseReason, Command, Credential, Event,
-    MemberId, RoomId, RpcSettings, ServerMsg,
+    encoding::Encoding, Capability, ClientMsg, CloseDescription, CloseReason,
+    Command, Credential, Event, Handshake, MemberId, RoomId, RpcSettings,
+    ServerMsg, PROTOCOL_VERSION,
 };
 
 use crate::{
     api::{
-        client::rpc_connection::{
-            ClosedReason, EventMessage, RpcConnection, RpcConnectionSettings,
+        client::{
+            rate_limit::{CommandRateLimiter, Verdict},
+            rpc_connection::{
+                ClosedReason, EventMessage, RpcAuthorization, RpcConnection,
+                RpcConnectionSettings,
+            },
+            token::TokenError,
         },
         RpcServer, RpcServerError,
     },
+    conf::rpc::RateLimit,
     log::prelude::*,
 };
 
@@ -45,6 +52,34 @@ pub trait RpcServerRepository: Debug {
 #[cfg(test)]
 impl_debug_by_struct_name!(MockRpcServerRepository);
 
+/// Authorizer of [`Member`]s presenting signed tokens instead of their
+/// credentials.
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[cfg_attr(test, mockall::automock)]
+pub trait TokenAuthorizer: Debug {
+    /// Validates provided token of [`Member`] joining [`Room`] and ensures
+    /// that this [`Member`] exists, creating it if necessary.
+    ///
+    /// Connection authorized this way is established without verifying
+    /// [`Member`]'s credentials, which are never changed by a token.
+    ///
+    /// Returns [`None`] if provided [`Credential`] is not a token, so it
+    /// should be verified as usual credentials.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    /// [`Room`]: crate::signalling::room::Room
+    fn authorize(
+        &self,
+        room_id: RoomId,
+        member_id: MemberId,
+        token: Credential,
+    ) -> Option<LocalBoxFuture<'static, Result<(), TokenError>>>;
+}
+
+#[cfg(test)]
+impl_debug_by_struct_name!(MockTokenAuthorizer);
+
 /// Used to generate [`WsSession`] IDs.
 static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -76,9 +111,30 @@ pub struct WsSession {
     /// Repository of the all [`RpcServer`]s registered on this Media Server.
     rpc_server_repo: Box<dyn RpcServerRepository>,
 
+    /// Authorizer of [`Member`]s presenting signed tokens.
+    ///
+    /// [`None`] if authentication with tokens is disabled.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    token_authorizer: Option<Box<dyn TokenAuthorizer>>,
+
     /// All sessions which this [`WsSession`] is serves.
     sessions: HashMap<RoomId, (MemberId, Box<dyn RpcServer>)>,
 
+    /// [`RpcConnectionSettings`] this [`WsSession`] was created with.
+    default_rpc_settings: RpcConnectionSettings,
+
+    /// [`RpcConnectionSettings`] requested by each of the served sessions.
+    ///
+    /// The smallest of them (and [`WsSession::default_rpc_settings`]) are
+    /// used.
+    rooms_rpc_settings: HashMap<RoomId, RpcConnectionSettings>,
+
+    /// [`Capability`]s declared by client for each of the served sessions.
+    ///
+    /// [`Event`]s requiring undeclared [`Capability`] are not sent to client.
+    capabilities: HashMap<RoomId, HashSet<Capability>>,
+
     /// Timeout of receiving any messages from client.
     idle_timeout: Duration,
 
@@ -89,9 +145,21 @@ pub struct WsSession {
     /// from client.
     last_activity: Instant,
 
+    /// [`Encoding`] of messages negotiated with client during WebSocket
+    /// handshake.
+    encoding: Encoding,
+
+    /// Limiter of [`Command`]s received from client, so flooding client
+    /// doesn't overload [`RpcServer`]s it's connected to.
+    rate_limiter: CommandRateLimiter,
+
     /// Buffer where continuation WebSocket frames are accumulated.
     fragmentation_buffer: BytesMut,
 
+    /// [`Encoding`] of the message being accumulated in
+    /// [`WsSession::fragmentation_buffer`].
+    fragmentation_encoding: Encoding,
+
     /// Last number of [`ServerMsg::Ping`].
     last_ping_num: u32,
 
@@ -113,16 +181,29 @@ impl WsSession {
     /// Creates new [`WsSession`] for specified [`Member`].
     pub fn new(
         rooms: Box<dyn RpcServerRepository>,
+        token_authorizer: Option<Box<dyn TokenAuthorizer>>,
         idle_timeout: Duration,
         ping_interval: Duration,
+        rate_limit: &RateLimit,
+        encoding: Encoding,
     ) -> Self {
         Self {
             id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             rpc_server_repo: rooms,
+            token_authorizer,
             sessions: HashMap::new(),
+            default_rpc_settings: RpcConnectionSettings {
+                idle_timeout,
+                ping_interval,
+            },
+            rooms_rpc_settings: HashMap::new(),
+            capabilities: HashMap::new(),
             idle_timeout,
             last_activity: Instant::now(),
+            encoding,
+            rate_limiter: CommandRateLimiter::new(rate_limit),
             fragmentation_buffer: BytesMut::new(),
+            fragmentation_encoding: Encoding::Json,
             last_ping_num: 0,
             ping_interval,
             close_reason: None,
@@ -131,66 +212,113 @@ impl WsSession {
         }
     }
 
-    /// Handles text WebSocket messages.
-    fn handle_text(
+    /// Handles text (JSON encoded) and binary (encoded with the negotiated
+    /// binary [`Encoding`]) WebSocket messages.
+    fn handle_message(
         &mut self,
         ctx: &mut ws::WebsocketContext<Self>,
-        text: &str,
+        encoding: Encoding,
+        payload: &[u8],
     ) {
         self.last_activity = Instant::now();
-        match serde_json::from_str::<ClientMsg>(&text) {
+        if encoding.is_binary() && encoding != self.encoding {
+            warn!(
+                "{}: Received {} message, while {} encoding was negotiated",
+                self, encoding, self.encoding,
+            );
+            return;
+        }
+        match encoding.decode::<ClientMsg>(payload) {
             Ok(ClientMsg::Pong(n)) => {
                 debug!("{}: Received Pong: {}", self, n);
             }
             Ok(ClientMsg::Command { room_id, command }) => {
                 debug!("{}: Received Command: {:?}", self, command);
-                match command {
-                    Command::JoinRoom {
-                        member_id,
-                        credential,
-                    } => {
-                        self.handle_join_room(
-                            ctx, room_id, member_id, credential,
-                        );
+                match self.rate_limiter.check(&command) {
+                    Verdict::Accept => {
+                        self.handle_command(ctx, room_id, command);
                     }
-                    Command::LeaveRoom { member_id } => {
-                        self.handle_leave_room(
-                            ctx,
-                            &room_id,
-                            member_id,
-                            ClosedReason::Closed { normal: true },
+                    Verdict::Drop => {
+                        warn!(
+                            "{}: Dropped {:?} in Room [id = {}] exceeding \
+                             rate limit",
+                            self, command, room_id,
                         );
-                    }
-                    _ => {
-                        if let Some((member_id, room)) =
-                            self.sessions.get(&room_id)
-                        {
-                            room.send_command(member_id.clone(), command);
-                        } else {
+                        // Client awaits response on `JoinRoom`, so it should
+                        // be notified.
+                        if let Command::JoinRoom { .. } = command {
                             self.send_left_room(
                                 ctx,
                                 room_id,
-                                CloseReason::Finished,
+                                CloseReason::RateLimited,
                             );
-                            if self.sessions.is_empty() {
-                                self.close_in_place(
-                                    ctx,
-                                    &CloseDescription::new(
-                                        CloseReason::Rejected,
-                                    ),
-                                );
-                            }
                         }
                     }
+                    Verdict::Abuse => {
+                        warn!(
+                            "{}: Closing connection which persistently \
+                             exceeds rate limits",
+                            self,
+                        );
+                        self.close_in_place(
+                            ctx,
+                            &CloseDescription::new(CloseReason::RateLimited),
+                        );
+                    }
                 }
             }
             Err(err) => error!(
-                "{}: Error [{:?}] parsing client message: [{}]",
-                self, err, &text,
+                "{}: Error [{}] parsing client message: [{}]",
+                self,
+                err,
+                String::from_utf8_lossy(payload),
             ),
         }
     }
 
+    /// Handles [`Command`] for the `Room` with provided [`RoomId`], which is
+    /// within rate limits.
+    fn handle_command(
+        &mut self,
+        ctx: &mut ws::WebsocketContext<Self>,
+        room_id: RoomId,
+        command: Command,
+    ) {
+        match command {
+            Command::JoinRoom {
+                member_id,
+                credential,
+                last_seq,
+                handshake,
+            } => {
+                self.handle_join_room(
+                    ctx, room_id, member_id, credential, last_seq, handshake,
+                );
+            }
+            Command::LeaveRoom { member_id } => {
+                self.handle_leave_room(
+                    ctx,
+                    &room_id,
+                    member_id,
+                    ClosedReason::Closed { normal: true },
+                );
+            }
+            _ => {
+                if let Some((member_id, room)) = self.sessions.get(&room_id) {
+                    room.send_command(member_id.clone(), command);
+                } else {
+                    self.send_left_room(ctx, room_id, CloseReason::Finished);
+                    if self.sessions.is_empty() {
+                        self.close_in_place(
+                            ctx,
+                            &CloseDescription::new(CloseReason::Rejected),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     /// Updates [`RpcConnectionSettings`] of this [`WsSession`].
     ///
     /// Updates will be performed only if old settings are less then new one.
@@ -201,16 +329,26 @@ impl WsSession {
     /// Restarts heartbeater with a new [`RpcConnectionSettings`].
     fn update_rpc_settings(
         &mut self,
+        room_id: RoomId,
         new_settings: RpcConnectionSettings,
         ctx: &mut ws::WebsocketContext<Self>,
     ) {
+        self.rooms_rpc_settings.insert(room_id, new_settings);
+
+        let effective = self.rooms_rpc_settings.values().fold(
+            self.default_rpc_settings,
+            |acc, settings| RpcConnectionSettings {
+                idle_timeout: acc.idle_timeout.min(settings.idle_timeout),
+                ping_interval: acc.ping_interval.min(settings.ping_interval),
+            },
+        );
         let mut updated = false;
-        if new_settings.idle_timeout < self.idle_timeout {
-            self.idle_timeout = new_settings.idle_timeout;
+        if effective.idle_timeout != self.idle_timeout {
+            self.idle_timeout = effective.idle_timeout;
             updated = true;
         }
-        if new_settings.ping_interval < self.ping_interval {
-            self.ping_interval = new_settings.ping_interval;
+        if effective.ping_interval != self.ping_interval {
+            self.ping_interval = effective.ping_interval;
             updated = true;
         }
         if updated {
@@ -221,6 +359,14 @@ impl WsSession {
 
     /// Handler for [`Command::JoinRoom`].
     ///
+    /// Rejects client with [`CloseReason::IncompatibleProtocol`] if it speaks
+    /// a newer protocol version than [`PROTOCOL_VERSION`]. Client without
+    /// [`Handshake`] is served with [`Capability::BASELINE`].
+    ///
+    /// If provided [`Credential`] is a signed token, then authorizes it with
+    /// [`TokenAuthorizer`] instead of verifying it as [`Member`]'s
+    /// credentials.
+    ///
     /// Calls [`RpcServer::connection_established`], updates
     /// [`RpcConnectionSettings`] with [`RpcConnectionSettings`] returned from
     /// the [`RpcServer`].
@@ -232,17 +378,118 @@ impl WsSession {
         room_id: RoomId,
         member_id: MemberId,
         credential: Credential,
+        last_seq: Option<u32>,
+        handshake: Option<Handshake>,
+    ) {
+        let (protocol_version, capabilities) = handshake.map_or_else(
+            || (0, Capability::BASELINE.iter().copied().collect()),
+            |h| (h.protocol_version, h.capabilities),
+        );
+        if protocol_version > PROTOCOL_VERSION {
+            warn!(
+                "{}: Rejecting Rpc Session `{}/{}` speaking unsupported \
+                 protocol version {}",
+                self, room_id, member_id, protocol_version
+            );
+            self.reject_incompatible_protocol(ctx, room_id, protocol_version);
+            return;
+        }
+
+        let authorization = self.token_authorizer.as_ref().and_then(|a| {
+            a.authorize(room_id.clone(), member_id.clone(), credential.clone())
+        });
+        if let Some(authorization) = authorization {
+            authorization
+                .into_actor(self)
+                .map(move |result, this, ctx| match result {
+                    Ok(_) => {
+                        this.establish_connection(
+                            ctx,
+                            room_id,
+                            member_id,
+                            RpcAuthorization::Token,
+                            last_seq,
+                            capabilities,
+                        );
+                    }
+                    Err(err) => {
+                        error!(
+                            "{}: Failed to authorize Rpc Session `{}/{}` with \
+                             token cause: {}",
+                            this, room_id, member_id, err
+                        );
+                        let reason = match err {
+                            TokenError::RoomServiceMailbox(_) => {
+                                CloseReason::InternalError
+                            }
+                            _ => CloseReason::Rejected,
+                        };
+                        this.send_left_room(ctx, room_id, reason)
+                    }
+                })
+                .wait(ctx);
+        } else {
+            self.establish_connection(
+                ctx,
+                room_id,
+                member_id,
+                credential.into(),
+                last_seq,
+                capabilities,
+            );
+        }
+    }
+
+    /// Sends [`Event::RoomLeft`] with [`CloseReason::IncompatibleProtocol`] to
+    /// client, and closes WebSocket connection if it has no other sessions.
+    fn reject_incompatible_protocol(
+        &mut self,
+        ctx: &mut ws::WebsocketContext<Self>,
+        room_id: RoomId,
+        protocol_version: u32,
+    ) {
+        self.send_left_room(ctx, room_id, CloseReason::IncompatibleProtocol);
+        if self.sessions.is_empty() {
+            self.close_in_place(
+                ctx,
+                &CloseDescription::with_description(
+                    CloseReason::IncompatibleProtocol,
+                    format!(
+                        "Protocol version {} is not supported, the latest \
+                         supported one is {}",
+                        protocol_version, PROTOCOL_VERSION,
+                    ),
+                ),
+            );
+        }
+    }
+
+    /// Calls [`RpcServer::connection_established`] for [`Member`] joining
+    /// [`Room`] with provided [`RpcAuthorization`].
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    /// [`Room`]: crate::signalling::room::Room
+    fn establish_connection(
+        &mut self,
+        ctx: &mut ws::WebsocketContext<Self>,
+        room_id: RoomId,
+        member_id: MemberId,
+        authorization: RpcAuthorization,
+        last_seq: Option<u32>,
+        capabilities: HashSet<Capability>,
     ) {
         if let Some(room) = self.rpc_server_repo.get(&room_id) {
             room.connection_established(
                 member_id.clone(),
-                credential,
+                authorization,
                 Box::new(ctx.address()),
+                last_seq,
             )
             .into_actor(self)
-            .map(|result, this, ctx| match result {
+            .map(move |result, this, ctx| match result {
                 Ok(settings) => {
-                    this.update_rpc_settings(settings, ctx);
+                    this.update_rpc_settings(room_id.clone(), settings, ctx);
+                    this.capabilities.insert(room_id.clone(), capabilities);
                     this.sessions
                         .insert(room_id.clone(), (member_id.clone(), room));
                     if let Some(auth_timeout_handle) =
@@ -289,6 +536,8 @@ impl WsSession {
         _: MemberId,
         reason: ClosedReason,
     ) {
+        self.rooms_rpc_settings.remove(room_id);
+        self.capabilities.remove(room_id);
         if let Some((member, room)) = self.sessions.remove(&room_id) {
             ctx.spawn(room.connection_closed(member, reason).into_actor(self));
         }
@@ -346,13 +595,20 @@ impl WsSession {
                     );
                     self.fragmentation_buffer.clear();
                 }
+                self.fragmentation_encoding = Encoding::Json;
                 self.fragmentation_buffer.extend_from_slice(value.bytes());
             }
-            Item::FirstBinary(_) => {
-                error!(
-                    "{}: Received unexpected continuation-binary frame.",
-                    self
-                );
+            Item::FirstBinary(value) => {
+                if !self.fragmentation_buffer.is_empty() {
+                    error!(
+                        "{}: Received new continuation frame before \
+                         completing previous.",
+                        self
+                    );
+                    self.fragmentation_buffer.clear();
+                }
+                self.fragmentation_encoding = Encoding::MessagePack;
+                self.fragmentation_buffer.extend_from_slice(value.bytes());
             }
             Item::Continue(value) => {
                 if self.fragmentation_buffer.is_empty() {
@@ -368,12 +624,7 @@ impl WsSession {
             Item::Last(value) => {
                 self.fragmentation_buffer.extend_from_slice(value.bytes());
                 let frame = self.fragmentation_buffer.split();
-                match std::str::from_utf8(frame.as_ref()) {
-                    Ok(text) => self.handle_text(ctx, &text),
-                    Err(err) => {
-                        error!("{}: Could not parse ws frame: {}", self, err);
-                    }
-                }
+                self.handle_message(ctx, self.fragmentation_encoding, &frame);
             }
         }
     }
@@ -388,7 +639,7 @@ impl WsSession {
         self.close_reason = Some(InnerCloseReason::ByServer);
         ctx.close(Some(ws::CloseReason {
             code: ws::CloseCode::Normal,
-            description: Some(serde_json::to_string(reason).unwrap()),
+            description: Some(close_frame_reason(reason)),
         }));
         ctx.stop();
     }
@@ -425,27 +676,65 @@ impl WsSession {
 
     /// Sends [`ServerMsg::Ping`] increasing ping counter.
     fn send_ping(&mut self, ctx: &mut <Self as Actor>::Context) {
-        ctx.text(
-            serde_json::to_string(&ServerMsg::Ping(self.last_ping_num))
-                .unwrap(),
-        );
+        self.send_server_msg(ctx, &ServerMsg::Ping(self.last_ping_num));
         self.last_ping_num += 1;
     }
 
+    /// Sends [`ServerMsg`] to Web Client encoding it with the negotiated
+    /// [`Encoding`].
+    fn send_server_msg(
+        &self,
+        ctx: &mut <Self as Actor>::Context,
+        msg: &ServerMsg,
+    ) {
+        let payload = self.encoding.encode(msg).unwrap();
+        if self.encoding.is_binary() {
+            ctx.binary(payload);
+        } else {
+            ctx.text(String::from_utf8(payload).unwrap());
+        }
+    }
+
     /// Sends [`Event`] to Web Client.
+    ///
+    /// [`Event`]s without sequence number are not replayed on reconnection.
+    ///
+    /// [`Event`]s requiring a [`Capability`] which client hasn't declared are
+    /// skipped.
     fn send_event(
         &self,
         ctx: &mut <Self as Actor>::Context,
         room_id: RoomId,
+        seq: Option<u32>,
         event: Event,
     ) {
+        if let Some(capability) = event.required_capability() {
+            let is_supported = self
+                .capabilities
+                .get(&room_id)
+                .map_or(false, |caps| caps.contains(&capability));
+            if !is_supported {
+                debug!(
+                    "{}: Skipping Event #{:?} for Room [id = {}] requiring \
+                     {:?} capability",
+                    self, seq, room_id, capability
+                );
+                return;
+            }
+        }
+
         debug!(
-            "{}: Sending Event for Room [id = {}]: {:?}]",
-            self, room_id, event
+            "{}: Sending Event #{:?} for Room [id = {}]: {:?}]",
+            self, seq, room_id, event
+        );
+        self.send_server_msg(
+            ctx,
+            &ServerMsg::Event {
+                room_id,
+                seq,
+                event,
+            },
         );
-        let event = serde_json::to_string(&ServerMsg::Event { room_id, event })
-            .unwrap();
-        ctx.text(event);
     }
 
     /// Sends [`Event::RoomJoined`] to the client.
@@ -455,7 +744,7 @@ impl WsSession {
         room_id: RoomId,
         member_id: MemberId,
     ) {
-        self.send_event(ctx, room_id, Event::RoomJoined { member_id });
+        self.send_event(ctx, room_id, None, Event::RoomJoined { member_id });
     }
 
     /// Sends [`Event::RoomLeft`] to the client.
@@ -465,7 +754,30 @@ impl WsSession {
         room_id: RoomId,
         close_reason: CloseReason,
     ) {
-        self.send_event(ctx, room_id, Event::RoomLeft { close_reason });
+        self.send_left_room_described(
+            ctx,
+            room_id,
+            CloseDescription::new(close_reason),
+        );
+    }
+
+    /// Sends [`Event::RoomLeft`] with the provided [`CloseDescription`] to the
+    /// client.
+    fn send_left_room_described(
+        &self,
+        ctx: &mut <Self as Actor>::Context,
+        room_id: RoomId,
+        close_description: CloseDescription,
+    ) {
+        self.send_event(
+            ctx,
+            room_id,
+            None,
+            Event::RoomLeft {
+                close_reason: close_description.reason,
+                description: close_description.description,
+            },
+        );
     }
 
     /// Sends current [`RpcSettings`] to the client.
@@ -482,10 +794,7 @@ impl WsSession {
                 .try_into()
                 .expect("'ping_interval' should fit into u64"),
         };
-        ctx.text(
-            serde_json::to_string(&ServerMsg::RpcSettings(rpc_settings))
-                .unwrap(),
-        );
+        self.send_server_msg(ctx, &ServerMsg::RpcSettings(rpc_settings));
     }
 }
 
@@ -573,8 +882,43 @@ impl RpcConnection for Addr<WsSession> {
     /// Sends [`Event`] to Web Client.
     ///
     /// [`Event`]: medea_client_api_proto::Event
-    fn send_event(&self, room_id: RoomId, event: Event) {
-        self.do_send(EventMessage { room_id, event });
+    fn send_event(&self, room_id: RoomId, seq: u32, event: Event) {
+        self.do_send(EventMessage {
+            room_id,
+            seq,
+            event,
+        });
+    }
+
+    /// Sends [`UpdateRpcSettings`] to the [`WsSession`].
+    fn update_settings(
+        &self,
+        room_id: RoomId,
+        settings: RpcConnectionSettings,
+    ) {
+        self.do_send(UpdateRpcSettings { room_id, settings });
+    }
+}
+
+/// Maximum length in bytes of [Close] frame's reason.
+///
+/// [Close]: https://tools.ietf.org/html/rfc6455#section-5.5.1
+const MAX_CLOSE_REASON_LEN: usize = 123;
+
+/// Serializes provided [`CloseDescription`] into a reason of [Close] frame.
+///
+/// Human-readable description is omitted if it doesn't fit into the
+/// [`MAX_CLOSE_REASON_LEN`], since it's delivered with [`Event::RoomLeft`]
+/// anyway.
+///
+/// [Close]: https://tools.ietf.org/html/rfc6455#section-5.5.1
+fn close_frame_reason(close_description: &CloseDescription) -> String {
+    let reason = serde_json::to_string(close_description).unwrap();
+    if reason.len() <= MAX_CLOSE_REASON_LEN {
+        reason
+    } else {
+        serde_json::to_string(&CloseDescription::new(close_description.reason))
+            .unwrap()
     }
 }
 
@@ -598,8 +942,14 @@ impl Handler<CloseRoom> for WsSession {
         msg: CloseRoom,
         ctx: &mut Self::Context,
     ) -> Self::Result {
+        self.rooms_rpc_settings.remove(&msg.room_id);
+        self.capabilities.remove(&msg.room_id);
         if self.sessions.remove(&msg.room_id).is_some() {
-            self.send_left_room(ctx, msg.room_id, CloseReason::Finished);
+            self.send_left_room_described(
+                ctx,
+                msg.room_id,
+                msg.close_description.clone(),
+            );
             if self.sessions.is_empty() {
                 self.close_in_place(ctx, &msg.close_description);
             }
@@ -607,12 +957,36 @@ impl Handler<CloseRoom> for WsSession {
     }
 }
 
+/// Message which indicates that [`RpcConnectionSettings`] of the session
+/// with the provided [`RoomId`] have been changed.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct UpdateRpcSettings {
+    /// [`RoomId`] of the session which settings have been changed.
+    room_id: RoomId,
+
+    /// New [`RpcConnectionSettings`] of the session.
+    settings: RpcConnectionSettings,
+}
+
+impl Handler<UpdateRpcSettings> for WsSession {
+    type Result = ();
+
+    /// Recalculates [`RpcConnectionSettings`] of this [`WsSession`] if the
+    /// session with the provided [`RoomId`] is served by it.
+    fn handle(&mut self, msg: UpdateRpcSettings, ctx: &mut Self::Context) {
+        if self.sessions.contains_key(&msg.room_id) {
+            self.update_rpc_settings(msg.room_id, msg.settings, ctx);
+        }
+    }
+}
+
 impl Handler<EventMessage> for WsSession {
     type Result = ();
 
     /// Sends [`Event`] to Web Client.
     fn handle(&mut self, msg: EventMessage, ctx: &mut Self::Context) {
-        self.send_event(ctx, msg.room_id, msg.event);
+        self.send_event(ctx, msg.room_id, Some(msg.seq), msg.event);
     }
 }
 
@@ -625,14 +999,16 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSession {
     ) {
         match msg {
             Ok(msg) => match msg {
-                ws::Message::Text(text) => self.handle_text(ctx, &text),
+                ws::Message::Text(text) => {
+                    self.handle_message(ctx, Encoding::Json, text.as_bytes());
+                }
+                ws::Message::Binary(payload) => {
+                    self.handle_message(ctx, Encoding::MessagePack, &payload);
+                }
                 ws::Message::Close(reason) => self.handle_close(ctx, reason),
                 ws::Message::Continuation(item) => {
                     self.handle_continuation(ctx, item);
                 }
-                ws::Message::Binary(_) => {
-                    warn!("{}: Received binary message", self);
-                }
                 ws::Message::Ping(ping) => {
                     ctx.pong(ping.bytes());
                 }
@@ -644,9 +1020,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSession {
                 error!("{}: StreamHandler Error: {:?}", self, err);
                 self.close_in_place(
                     ctx,
-                    &CloseDescription {
-                        reason: CloseReason::InternalError,
-                    },
+                    &CloseDescription::new(CloseReason::InternalError),
                 );
             }
         };
@@ -669,6 +1043,7 @@ impl Display for WsSession {
 #[cfg(test)]
 mod test {
     use std::{
+        collections::HashSet,
         sync::Mutex,
         time::{Duration, Instant},
     };
@@ -685,19 +1060,27 @@ mod test {
         future, FutureExt as _, SinkExt as _, StreamExt as _,
     };
     use medea_client_api_proto::{
-        ClientMsg, CloseDescription, CloseReason as ProtoCloseReason, Command,
-        Event, IceCandidate, MemberId, PeerId, RpcSettings, ServerMsg,
+        encoding::Encoding, Capability, ClientMsg, CloseDescription,
+        CloseReason as ProtoCloseReason, Command, ConnectionQualityScore,
+        Event, Handshake, IceCandidate, MemberId, PeerId, RpcSettings,
+        ServerMsg, PROTOCOL_VERSION,
     };
     use tokio::time::timeout;
 
-    use crate::api::{
-        client::rpc_connection::{
-            ClosedReason, RpcConnection, RpcConnectionSettings,
+    use crate::{
+        api::{
+            client::rpc_connection::{
+                ClosedReason, RpcConnection, RpcConnectionSettings,
+            },
+            MockRpcServer, RpcServerError,
         },
-        MockRpcServer, RpcServerError,
+        conf::rpc::{CommandLimit, RateLimit},
     };
 
-    use super::{MockRpcServerRepository, WsSession};
+    use super::{
+        close_frame_reason, MockRpcServerRepository, WsSession,
+        MAX_CLOSE_REASON_LEN,
+    };
 
     type SharedOneshot<T> =
         (Mutex<Option<Sender<T>>>, Mutex<Option<Receiver<T>>>);
@@ -743,10 +1126,10 @@ mod test {
                 let expected_member_id = member_id.clone();
                 rpc_server
                     .expect_connection_established()
-                    .withf(move |member_id, _, _| {
+                    .withf(move |member_id, _, _, _| {
                         *member_id == expected_member_id
                     })
-                    .return_once(|_, _, _| {
+                    .return_once(|_, _, _, _| {
                         future::err(RpcServerError::Authorization).boxed_local()
                     });
                 rpc_server
@@ -758,8 +1141,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         }
 
@@ -773,6 +1159,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -785,8 +1173,10 @@ mod test {
             left_room_frame,
             into_frame(ServerMsg::Event {
                 room_id: "room_id".into(),
+                seq: None,
                 event: Event::RoomLeft {
                     close_reason: medea_client_api_proto::CloseReason::Rejected,
+                    description: None,
                 }
             })
         );
@@ -799,6 +1189,265 @@ mod test {
         assert_eq!(item, close_frame);
     }
 
+    // Client speaking newer protocol version is rejected and WebSocket
+    // connection is closed.
+    #[actix_rt::test]
+    async fn rejects_incompatible_protocol_version() {
+        let mut serv = test_server(|| -> WsSession {
+            WsSession::new(
+                Box::new(MockRpcServerRepository::new()),
+                None,
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
+            )
+        });
+
+        let mut client = serv.ws().await.unwrap();
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    last_seq: None,
+                    handshake: Some(Handshake {
+                        protocol_version: PROTOCOL_VERSION + 1,
+                        capabilities: HashSet::new(),
+                    }),
+                },
+            }))
+            .await
+            .unwrap();
+
+        let mut client = client.skip(2);
+        let left_room_frame = client.next().await.unwrap().unwrap();
+        assert_eq!(
+            left_room_frame,
+            into_frame(ServerMsg::Event {
+                room_id: "room_id".into(),
+                seq: None,
+                event: Event::RoomLeft {
+                    close_reason: ProtoCloseReason::IncompatibleProtocol,
+                    description: None,
+                }
+            })
+        );
+
+        let item = client.next().await.unwrap().unwrap();
+        let description = CloseDescription::with_description(
+            ProtoCloseReason::IncompatibleProtocol,
+            format!(
+                "Protocol version {} is not supported, the latest supported \
+                 one is {}",
+                PROTOCOL_VERSION + 1,
+                PROTOCOL_VERSION,
+            ),
+        );
+        assert_eq!(
+            item,
+            Frame::Close(Some(CloseReason {
+                code: CloseCode::Normal,
+                description: Some(serde_json::to_string(&description).unwrap()),
+            }))
+        );
+    }
+
+    // Events requiring capabilities, which were not declared by client, are
+    // not sent.
+    #[actix_rt::test]
+    async fn skips_events_requiring_undeclared_capabilities() {
+        lazy_static::lazy_static! {
+            static ref CHAN: SharedOneshot<Box<dyn RpcConnection>> = {
+                let (tx, rx) = oneshot::channel();
+                (Mutex::new(Some(tx)), Mutex::new(Some(rx)))
+            };
+        }
+
+        let mut serv = test_server(|| -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, connection, _| {
+                        let _ = CHAN
+                            .0
+                            .lock()
+                            .unwrap()
+                            .take()
+                            .unwrap()
+                            .send(connection);
+                        future::ok(RpcConnectionSettings {
+                            ping_interval: Duration::from_secs(10),
+                            idle_timeout: Duration::from_secs(10),
+                        })
+                        .boxed_local()
+                    },
+                );
+                rpc_server
+                    .expect_connection_closed()
+                    .returning(|_, _| future::ready(()).boxed_local());
+
+                Some(Box::new(rpc_server))
+            });
+
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                None,
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
+            )
+        });
+
+        let mut client = serv.ws().await.unwrap();
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    last_seq: None,
+                    handshake: Some(Handshake::new(vec![
+                        Capability::ConnectionQuality,
+                    ])),
+                },
+            }))
+            .await
+            .unwrap();
+
+        let rpc_connection: Box<dyn RpcConnection> =
+            CHAN.1.lock().unwrap().take().unwrap().await.unwrap();
+
+        rpc_connection.send_event(
+            "room_id".into(),
+            1,
+            Event::Custom {
+                payload: serde_json::json!({"foo": "bar"}),
+            },
+        );
+        rpc_connection.send_event(
+            "room_id".into(),
+            2,
+            Event::ConnectionQualityUpdated {
+                partner_member_id: "partner".into(),
+                quality_score: ConnectionQualityScore::High,
+            },
+        );
+
+        let item = client.skip(3).next().await.unwrap().unwrap();
+        assert_eq!(
+            item,
+            into_frame(ServerMsg::Event {
+                room_id: "room_id".into(),
+                seq: Some(2),
+                event: Event::ConnectionQualityUpdated {
+                    partner_member_id: "partner".into(),
+                    quality_score: ConnectionQualityScore::High,
+                },
+            })
+        );
+    }
+
+    // Client without handshake still receives events requiring baseline
+    // capabilities.
+    #[actix_rt::test]
+    async fn serves_client_without_handshake_with_baseline_capabilities() {
+        lazy_static::lazy_static! {
+            static ref CHAN: SharedOneshot<Box<dyn RpcConnection>> = {
+                let (tx, rx) = oneshot::channel();
+                (Mutex::new(Some(tx)), Mutex::new(Some(rx)))
+            };
+        }
+
+        let mut serv = test_server(|| -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, connection, _| {
+                        let _ = CHAN
+                            .0
+                            .lock()
+                            .unwrap()
+                            .take()
+                            .unwrap()
+                            .send(connection);
+                        future::ok(RpcConnectionSettings {
+                            ping_interval: Duration::from_secs(10),
+                            idle_timeout: Duration::from_secs(10),
+                        })
+                        .boxed_local()
+                    },
+                );
+                rpc_server
+                    .expect_connection_closed()
+                    .returning(|_, _| future::ready(()).boxed_local());
+
+                Some(Box::new(rpc_server))
+            });
+
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                None,
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
+            )
+        });
+
+        let mut client = serv.ws().await.unwrap();
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
+                },
+            }))
+            .await
+            .unwrap();
+
+        let rpc_connection: Box<dyn RpcConnection> =
+            CHAN.1.lock().unwrap().take().unwrap().await.unwrap();
+
+        rpc_connection.send_event(
+            "room_id".into(),
+            1,
+            Event::Custom {
+                payload: serde_json::json!({"foo": "bar"}),
+            },
+        );
+        rpc_connection.send_event(
+            "room_id".into(),
+            2,
+            Event::ConnectionQualityUpdated {
+                partner_member_id: "partner".into(),
+                quality_score: ConnectionQualityScore::High,
+            },
+        );
+
+        let item = client.skip(3).next().await.unwrap().unwrap();
+        assert_eq!(
+            item,
+            into_frame(ServerMsg::Event {
+                room_id: "room_id".into(),
+                seq: Some(2),
+                event: Event::ConnectionQualityUpdated {
+                    partner_member_id: "partner".into(),
+                    quality_score: ConnectionQualityScore::High,
+                },
+            })
+        );
+    }
+
     #[actix_rt::test]
     async fn sends_rpc_settings_and_pings() {
         let mut serv = test_server(|| -> WsSession {
@@ -807,7 +1456,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
@@ -824,8 +1473,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_millis(50),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -837,6 +1489,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -858,6 +1512,7 @@ mod test {
             item,
             into_frame(ServerMsg::Event {
                 room_id: "room_id".into(),
+                seq: None,
                 event: Event::RoomJoined {
                     member_id: "member_id".into(),
                 }
@@ -868,6 +1523,86 @@ mod test {
         assert_eq!(item, into_frame(ServerMsg::Ping(1)));
     }
 
+    // Messages are sent and received in binary frames when MessagePack
+    // encoding is negotiated.
+    #[actix_rt::test]
+    async fn uses_negotiated_binary_encoding() {
+        let mut serv = test_server(|| -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, _, _| {
+                        future::ok(RpcConnectionSettings {
+                            ping_interval: Duration::from_secs(10),
+                            idle_timeout: Duration::from_secs(10),
+                        })
+                        .boxed_local()
+                    },
+                );
+                rpc_server
+                    .expect_connection_closed()
+                    .returning(|_, _| future::ready(()).boxed_local());
+
+                Some(Box::new(rpc_server))
+            });
+
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                None,
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::MessagePack,
+            )
+        });
+
+        let mut client = serv.ws().await.unwrap();
+
+        let join_room = ClientMsg::Command {
+            room_id: "room_id".into(),
+            command: Command::JoinRoom {
+                member_id: "member_id".into(),
+                credential: "token".into(),
+                last_seq: None,
+                handshake: None,
+            },
+        };
+        client
+            .send(Message::Binary(
+                Encoding::MessagePack.encode(&join_room).unwrap().into(),
+            ))
+            .await
+            .unwrap();
+
+        let into_binary_frame = |msg: ServerMsg| {
+            Frame::Binary(Encoding::MessagePack.encode(&msg).unwrap().into())
+        };
+        let item = client.next().await.unwrap().unwrap();
+        assert_eq!(
+            item,
+            into_binary_frame(ServerMsg::RpcSettings(RpcSettings {
+                idle_timeout_ms: 5000,
+                ping_interval_ms: 5000,
+            })),
+        );
+
+        let item = client.next().await.unwrap().unwrap();
+        assert_eq!(item, into_binary_frame(ServerMsg::Ping(0)));
+
+        let item = client.next().await.unwrap().unwrap();
+        assert_eq!(
+            item,
+            into_binary_frame(ServerMsg::Event {
+                room_id: "room_id".into(),
+                seq: None,
+                event: Event::RoomJoined {
+                    member_id: "member_id".into(),
+                },
+            }),
+        );
+    }
+
     // WsSession is dropped and WebSocket connection is closed if no pongs
     // received for idle_timeout.
     #[actix_rt::test]
@@ -879,7 +1614,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
@@ -901,8 +1636,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_millis(100),
                 Duration::from_secs(10),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -914,6 +1652,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -951,7 +1691,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
@@ -972,8 +1712,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -985,6 +1728,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1046,6 +1791,120 @@ mod test {
         }
     }
 
+    // Commands exceeding rate limits are not passed to RpcServer, and
+    // WebSocket connection is closed once they are sent persistently.
+    #[actix_rt::test]
+    async fn closes_connection_exceeding_rate_limits() {
+        lazy_static::lazy_static! {
+            static ref CHAN: SharedUnbounded<Command> = {
+                let (tx, rx) = mpsc::unbounded();
+                (Mutex::new(tx), Mutex::new(Some(rx)))
+            };
+        }
+
+        let mut serv = test_server(|| -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, _, _| {
+                        future::ok(RpcConnectionSettings {
+                            idle_timeout: Duration::from_secs(10),
+                            ping_interval: Duration::from_secs(10),
+                        })
+                        .boxed_local()
+                    },
+                );
+                rpc_server
+                    .expect_connection_closed()
+                    .returning(|_, _| future::ready(()).boxed_local());
+
+                rpc_server.expect_send_command().returning(|_, command| {
+                    CHAN.0.lock().unwrap().unbounded_send(command).unwrap();
+                });
+
+                Some(Box::new(rpc_server))
+            });
+
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                None,
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+                &RateLimit {
+                    set_ice_candidate: CommandLimit::new(1, 1),
+                    max_dropped: 1,
+                    ..RateLimit::default()
+                },
+                Encoding::Json,
+            )
+        });
+
+        let mut client = serv.ws().await.unwrap();
+
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
+                },
+            }))
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            client
+                .send(into_message(ClientMsg::Command {
+                    room_id: "room_id".into(),
+                    command: Command::SetIceCandidate {
+                        peer_id: PeerId(15),
+                        candidate: IceCandidate {
+                            candidate: "asd".to_string(),
+                            sdp_m_line_index: Some(1),
+                            sdp_mid: Some("2".to_string()),
+                        },
+                    },
+                }))
+                .await
+                .unwrap();
+        }
+
+        let close_frame = timeout(
+            Duration::from_secs(1),
+            client
+                .filter_map(|frame| {
+                    future::ready(match frame.unwrap() {
+                        frame @ Frame::Close(_) => Some(frame),
+                        _ => None,
+                    })
+                })
+                .next(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            close_frame,
+            Frame::Close(Some(CloseReason {
+                code: CloseCode::Normal,
+                description: Some(String::from(r#"{"reason":"RateLimited"}"#)),
+            }))
+        );
+
+        let mut commands = CHAN.1.lock().unwrap().take().unwrap();
+        assert!(matches!(
+            commands.next().await,
+            Some(Command::SetIceCandidate { .. })
+        ));
+        assert!(timeout(Duration::from_millis(100), commands.next())
+            .await
+            .map_or(true, |command| command.is_none()));
+    }
+
     // WsSession is dropped and WebSocket connection is closed when
     // RpcConnection::close is called.
     #[actix_rt::test]
@@ -1063,7 +1922,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, connection, _| {
                         let _ = CHAN
                             .0
                             .lock()
@@ -1087,8 +1946,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -1100,6 +1962,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1111,9 +1975,10 @@ mod test {
         rpc_connection
             .close(
                 "room_id".into(),
-                CloseDescription {
-                    reason: ProtoCloseReason::Evicted,
-                },
+                CloseDescription::with_description(
+                    ProtoCloseReason::Evicted,
+                    "kicked".into(),
+                ),
             )
             .await;
         let mut client = client.skip(3);
@@ -1123,8 +1988,10 @@ mod test {
             left_room_frame,
             into_frame(ServerMsg::Event {
                 room_id: "room_id".into(),
+                seq: None,
                 event: Event::RoomLeft {
-                    close_reason: medea_client_api_proto::CloseReason::Finished,
+                    close_reason: medea_client_api_proto::CloseReason::Evicted,
+                    description: Some("kicked".into()),
                 }
             })
         );
@@ -1132,7 +1999,9 @@ mod test {
 
         let close_frame = Frame::Close(Some(CloseReason {
             code: CloseCode::Normal,
-            description: Some(String::from(r#"{"reason":"Evicted"}"#)),
+            description: Some(String::from(
+                r#"{"reason":"Evicted","description":"kicked"}"#,
+            )),
         }));
 
         assert_eq!(item, close_frame);
@@ -1155,7 +2024,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, connection, _| {
                         let _ = CHAN
                             .0
                             .lock()
@@ -1179,8 +2048,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -1191,6 +2063,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1201,6 +2075,7 @@ mod test {
 
         rpc_connection.send_event(
             "room_id".into(),
+            1,
             Event::SdpAnswerMade {
                 peer_id: PeerId(77),
                 sdp_answer: String::from("sdp_answer"),
@@ -1211,6 +2086,7 @@ mod test {
 
         let event = serde_json::to_string(&ServerMsg::Event {
             room_id: "room_id".into(),
+            seq: Some(1),
             event: Event::SdpAnswerMade {
                 peer_id: PeerId(77),
                 sdp_answer: "sdp_answer".to_string(),
@@ -1236,7 +2112,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, connection, _| {
                         let _ =
                             CHAN.0.lock().unwrap().unbounded_send(connection);
                         future::ok(RpcConnectionSettings {
@@ -1255,8 +2131,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -1267,6 +2146,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "alice".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1280,7 +2161,11 @@ mod test {
             peer_id: PeerId(0),
             sdp_answer: String::from("sdp_answer"),
         };
-        alice_connection.send_event("alice_room".into(), alice_event.clone());
+        alice_connection.send_event(
+            "alice_room".into(),
+            1,
+            alice_event.clone(),
+        );
 
         client
             .send(into_message(ClientMsg::Command {
@@ -1288,6 +2173,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "bob".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1299,7 +2186,7 @@ mod test {
             peer_id: PeerId(1),
             sdp_answer: String::from("sdp_answer"),
         };
-        bob_connection.send_event("bob_room".into(), bob_event.clone());
+        bob_connection.send_event("bob_room".into(), 1, bob_event.clone());
 
         let msgs: Vec<_> = client
             .filter_map(|f| async move {
@@ -1331,6 +2218,7 @@ mod test {
             msgs[1],
             ServerMsg::Event {
                 room_id: "alice_room".into(),
+                seq: None,
                 event: Event::RoomJoined {
                     member_id: "alice".into(),
                 }
@@ -1340,6 +2228,7 @@ mod test {
             msgs[2],
             ServerMsg::Event {
                 room_id: "alice_room".into(),
+                seq: Some(1),
                 event: alice_event,
             }
         );
@@ -1347,6 +2236,7 @@ mod test {
             msgs[3],
             ServerMsg::Event {
                 room_id: "bob_room".into(),
+                seq: None,
                 event: Event::RoomJoined {
                     member_id: "bob".into(),
                 }
@@ -1356,6 +2246,7 @@ mod test {
             msgs[4],
             ServerMsg::Event {
                 room_id: "bob_room".into(),
+                seq: Some(1),
                 event: bob_event,
             }
         );
@@ -1370,7 +2261,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().returning(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
@@ -1387,8 +2278,11 @@ mod test {
 
             WsSession::new(
                 Box::new(rpc_server_repo),
+                None,
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                &RateLimit::default(),
+                Encoding::Json,
             )
         });
 
@@ -1400,6 +2294,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member1".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1410,6 +2306,8 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member2".into(),
                     credential: "token".into(),
+                    last_seq: None,
+                    handshake: None,
                 },
             }))
             .await
@@ -1438,12 +2336,14 @@ mod test {
             client.map(|frame| frame.unwrap()).collect().await;
         assert!(frames.contains(&into_frame(ServerMsg::Event {
             room_id: "room1".into(),
+            seq: None,
             event: Event::RoomJoined {
                 member_id: "member1".into(),
             }
         })));
         assert!(frames.contains(&into_frame(ServerMsg::Event {
             room_id: "room2".into(),
+            seq: None,
             event: Event::RoomJoined {
                 member_id: "member2".into(),
             }
@@ -1456,4 +2356,22 @@ mod test {
             }))
         )
     }
+
+    #[test]
+    fn omits_too_long_description_from_close_frame() {
+        let short = CloseDescription::with_description(
+            ProtoCloseReason::Evicted,
+            "kicked".into(),
+        );
+        assert_eq!(
+            close_frame_reason(&short),
+            r#"{"reason":"Evicted","description":"kicked"}"#,
+        );
+
+        let long = CloseDescription::with_description(
+            ProtoCloseReason::Evicted,
+            "a".repeat(MAX_CLOSE_REASON_LEN),
+        );
+        assert_eq!(close_frame_reason(&long), r#"{"reason":"Evicted"}"#);
+    }
 }
@@ -0,0 +1,504 @@
+//! [Server-Sent Events][SSE] fallback transport of Client API for clients
+//! which cannot establish WebSocket connection (for example, because of
+//! proxies dropping WebSocket upgrades).
+//!
+//! It serves exactly the same [`WsSession`] as WebSocket transport does, only
+//! tunnelling its WebSocket frames: [`ServerMsg`]s are delivered to client as
+//! [SSE] events, while [`ClientMsg`]s are sent by client via HTTP `POST`
+//! requests. This way heartbeats, idle timeout and [`RpcConnection`] behave
+//! the same way for both transports.
+//!
+//! Protocol:
+//! 1. Client opens event stream with `GET /sse` request. The first `session`
+//!    event of this stream contains session token.
+//! 2. Client sends JSON encoded [`ClientMsg`]s with `POST /sse/{token}`
+//!    requests.
+//! 3. Every JSON encoded [`ServerMsg`] is sent as an unnamed (`message`) event.
+//! 4. JSON encoded [`CloseDescription`] is sent as `close` event right before
+//!    the stream is finished by server.
+//!
+//! [`ClientMsg`]: medea_client_api_proto::ClientMsg
+//! [`CloseDescription`]: medea_client_api_proto::CloseDescription
+//! [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+//! [`ServerMsg`]: medea_client_api_proto::ServerMsg
+//! [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use actix_codec::{Decoder as _, Encoder as _};
+use actix_http::{
+    error::PayloadError,
+    ws::{Codec, Frame, Message, ProtocolError},
+};
+use actix_web::{
+    http::header,
+    web::{Bytes, Data, Path},
+    HttpResponse,
+};
+use actix_web_actors::ws::WebsocketContext;
+use bytes::BytesMut;
+use futures::{
+    channel::mpsc, future, SinkExt as _, Stream, StreamExt as _,
+    TryStreamExt as _,
+};
+use medea_client_api_proto::encoding::Encoding;
+use rand::{distributions::Alphanumeric, Rng as _};
+
+use crate::{
+    api::client::{
+        server::Context,
+        session::{TokenAuthorizer, WsSession},
+    },
+    log::prelude::*,
+};
+
+/// Length of generated [`SseSessions`] tokens.
+const TOKEN_LEN: usize = 32;
+
+/// Maximum number of frames buffered for [`WsSession`] served via [SSE].
+///
+/// Once it's reached, `POST` requests wait for [`WsSession`] to process the
+/// buffered frames.
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+const FRAMES_BUFFER_SIZE: usize = 16;
+
+/// Sender of encoded WebSocket frames into [`WsSession`] served via [SSE].
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+type FramesSender = mpsc::Sender<Result<Bytes, PayloadError>>;
+
+/// Registry of all the [`WsSession`]s currently served via [SSE], keyed by
+/// their session tokens.
+///
+/// Shared between all the HTTP server workers, as `POST` requests may be
+/// handled by a worker other than the one serving the event stream.
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+#[derive(Clone, Debug, Default)]
+pub struct SseSessions(Arc<Mutex<HashMap<String, FramesSender>>>);
+
+impl SseSessions {
+    /// Registers new session, returning its token and the stream of frames
+    /// sent to it.
+    fn register(
+        &self,
+    ) -> (String, mpsc::Receiver<Result<Bytes, PayloadError>>) {
+        let (tx, rx) = mpsc::channel(FRAMES_BUFFER_SIZE);
+        let mut sessions = self.0.lock().unwrap();
+        let token = loop {
+            let token: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(TOKEN_LEN)
+                .collect();
+            if !sessions.contains_key(&token) {
+                break token;
+            }
+        };
+        sessions.insert(token.clone(), tx);
+        (token, rx)
+    }
+
+    /// Returns [`FramesSender`] of the session with the provided token, if
+    /// any.
+    fn get(&self, token: &str) -> Option<FramesSender> {
+        self.0.lock().unwrap().get(token).cloned()
+    }
+
+    /// Removes session with the provided token.
+    fn remove(&self, token: &str) {
+        self.0.lock().unwrap().remove(token);
+    }
+}
+
+/// Converter of WebSocket frames produced by [`WsSession`] into [SSE] events.
+///
+/// Unregisters the session from [`SseSessions`] once dropped.
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+struct EventStreamEncoder {
+    /// Token of the session which frames are converted.
+    token: String,
+
+    /// [`SseSessions`] registry the session is registered in.
+    sessions: SseSessions,
+
+    /// [`Codec`] decoding frames sent by [`WsSession`].
+    ///
+    /// Doesn't limit size of frames, as they are produced by [`WsSession`]
+    /// itself.
+    codec: Codec,
+
+    /// Buffer of not yet decoded bytes of frames.
+    buffer: BytesMut,
+}
+
+impl EventStreamEncoder {
+    /// Creates new [`EventStreamEncoder`] for the session with the provided
+    /// token.
+    fn new(token: String, sessions: SseSessions) -> Self {
+        Self {
+            token,
+            sessions,
+            codec: Codec::new().max_size(usize::MAX).client_mode(),
+            buffer: BytesMut::new(),
+        }
+    }
+
+    /// Converts the provided chunk of encoded WebSocket frames into [SSE]
+    /// events.
+    ///
+    /// Text frames are converted into unnamed events, and close frame is
+    /// converted into `close` event. All the other frames are not relevant for
+    /// client, so are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided chunk cannot be decoded as WebSocket frames.
+    ///
+    /// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+    fn encode(&mut self, chunk: &[u8]) -> Result<Bytes, ProtocolError> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = BytesMut::new();
+        while let Some(frame) = self.codec.decode(&mut self.buffer)? {
+            match frame {
+                Frame::Text(text) => write_event(&mut events, None, &text),
+                Frame::Close(reason) => {
+                    let description =
+                        reason.and_then(|r| r.description).unwrap_or_default();
+                    write_event(
+                        &mut events,
+                        Some("close"),
+                        description.as_bytes(),
+                    );
+                }
+                Frame::Binary(_)
+                | Frame::Continuation(_)
+                | Frame::Ping(_)
+                | Frame::Pong(_) => {}
+            }
+        }
+        Ok(events.freeze())
+    }
+}
+
+impl Drop for EventStreamEncoder {
+    fn drop(&mut self) {
+        self.sessions.remove(&self.token);
+    }
+}
+
+/// Writes [SSE] event with the provided name and data into the provided
+/// buffer.
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+fn write_event(buf: &mut BytesMut, name: Option<&str>, data: &[u8]) {
+    if let Some(name) = name {
+        buf.extend_from_slice(b"event: ");
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(b"\n");
+    }
+    for line in data.split(|b| *b == b'\n') {
+        buf.extend_from_slice(b"data: ");
+        buf.extend_from_slice(line);
+        buf.extend_from_slice(b"\n");
+    }
+    buf.extend_from_slice(b"\n");
+}
+
+/// Opens [SSE] stream serving new [`WsSession`].
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+pub async fn sse_index(state: Data<Context>) -> HttpResponse {
+    let (token, frames) = state.sse_sessions.register();
+    let session = WsSession::new(
+        Box::new(state.rooms.clone()),
+        state
+            .token_authorizer
+            .clone()
+            .map(|authorizer| Box::new(authorizer) as Box<dyn TokenAuthorizer>),
+        state.config.idle_timeout,
+        state.config.ping_interval,
+        &state.config.rate_limit,
+        Encoding::Json,
+    );
+    debug!("Starting WsSession served via SSE");
+
+    let mut session_event = BytesMut::new();
+    write_event(&mut session_event, Some("session"), token.as_bytes());
+
+    let mut encoder =
+        EventStreamEncoder::new(token, state.sse_sessions.clone());
+    let events = WebsocketContext::create(session, frames).map(move |chunk| {
+        chunk.and_then(|chunk| encoder.encode(&chunk).map_err(Into::into))
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .streaming(events_stream(session_event.freeze(), events))
+}
+
+/// Prepends the provided `session` event to the provided [SSE] events,
+/// omitting empty chunks.
+///
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+fn events_stream<S>(
+    session_event: Bytes,
+    events: S,
+) -> impl Stream<Item = Result<Bytes, actix_web::Error>>
+where
+    S: Stream<Item = Result<Bytes, actix_web::Error>>,
+{
+    futures::stream::once(future::ok(session_event))
+        .chain(events)
+        .try_filter(|chunk| future::ready(!chunk.is_empty()))
+}
+
+/// Passes JSON encoded [`ClientMsg`] to [`WsSession`] served via [SSE] with
+/// the provided token.
+///
+/// Waits for [`WsSession`] to process the previously sent messages if too
+/// many of them are buffered already.
+///
+/// Responds with `404 Not Found` if there is no such session.
+///
+/// [`ClientMsg`]: medea_client_api_proto::ClientMsg
+/// [SSE]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+pub async fn sse_message(
+    token: Path<String>,
+    state: Data<Context>,
+    body: Bytes,
+) -> HttpResponse {
+    let mut sender = if let Some(sender) = state.sse_sessions.get(&token) {
+        sender
+    } else {
+        return HttpResponse::NotFound().finish();
+    };
+    let text = match String::from_utf8(body.to_vec()) {
+        Ok(text) => text,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+    };
+
+    let mut frame = BytesMut::new();
+    if let Err(err) = Codec::new()
+        .client_mode()
+        .encode(Message::Text(text), &mut frame)
+    {
+        error!("Failed to encode SSE session message: {}", err);
+        return HttpResponse::InternalServerError().finish();
+    }
+    if sender.send(Ok(frame.freeze())).await.is_err() {
+        return HttpResponse::NotFound().finish();
+    }
+
+    HttpResponse::Accepted().finish()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use actix_http::ws::{CloseCode, CloseReason};
+    use actix_web::{
+        test::{self, TestRequest},
+        web, App,
+    };
+    use medea_client_api_proto::{
+        ClientMsg, CloseDescription, CloseReason as ProtoCloseReason, Command,
+        Event, ServerMsg,
+    };
+
+    use crate::{conf::Rpc, signalling::room_repo::RoomRepository};
+
+    use super::*;
+
+    /// Reads the provided event stream until it contains the provided
+    /// string.
+    async fn read_until<S>(events: &mut S, expected: &str) -> String
+    where
+        S: Stream<Item = Result<Bytes, actix_web::Error>> + Unpin,
+    {
+        let mut read = String::new();
+        while !read.contains(expected) {
+            let chunk =
+                tokio::time::timeout(Duration::from_secs(5), events.next())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .unwrap();
+            read.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+        read
+    }
+
+    #[actix_rt::test]
+    async fn serves_session_via_sse() {
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(Context {
+                    rooms: RoomRepository::new(HashMap::new()),
+                    token_authorizer: None,
+                    sse_sessions: SseSessions::default(),
+                    config: Rpc::default(),
+                }))
+                .route("/sse", web::get().to(sse_index))
+                .route("/sse/{token}", web::post().to(sse_message)),
+        )
+        .await;
+
+        let mut resp = test::call_service(
+            &mut app,
+            TestRequest::get().uri("/sse").to_request(),
+        )
+        .await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream",
+        );
+        let mut events = resp.take_body();
+
+        let read = read_until(&mut events, "\n\n").await;
+        let token = read
+            .strip_prefix("event: session\ndata: ")
+            .unwrap()
+            .trim_end()
+            .to_owned();
+        let ping = serde_json::to_string(&ServerMsg::Ping(0)).unwrap();
+        read_until(&mut events, &ping).await;
+
+        let join_room = serde_json::to_string(&ClientMsg::Command {
+            room_id: "unknown".into(),
+            command: Command::JoinRoom {
+                member_id: "member".into(),
+                credential: "test".into(),
+                last_seq: None,
+                handshake: None,
+            },
+        })
+        .unwrap();
+        let resp = test::call_service(
+            &mut app,
+            TestRequest::post()
+                .uri(&format!("/sse/{}", token))
+                .set_payload(join_room)
+                .to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), 202);
+
+        let room_left = serde_json::to_string(&ServerMsg::Event {
+            room_id: "unknown".into(),
+            seq: None,
+            event: Event::RoomLeft {
+                close_reason: ProtoCloseReason::Rejected,
+                description: None,
+            },
+        })
+        .unwrap();
+        read_until(&mut events, &room_left).await;
+        drop(events);
+
+        let resp = test::call_service(
+            &mut app,
+            TestRequest::post()
+                .uri(&format!("/sse/{}", token))
+                .set_payload("{}")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[test]
+    fn converts_frames_into_events() {
+        let sessions = SseSessions::default();
+        let (token, _frames) = sessions.register();
+        let mut encoder = EventStreamEncoder::new(token, sessions);
+
+        let ping = serde_json::to_string(&ServerMsg::Ping(3)).unwrap();
+        let close = serde_json::to_string(&CloseDescription::new(
+            ProtoCloseReason::Finished,
+        ))
+        .unwrap();
+        let mut codec = Codec::new();
+        let mut frames = BytesMut::new();
+        codec
+            .encode(Message::Text(ping.clone()), &mut frames)
+            .unwrap();
+        codec
+            .encode(Message::Ping(Bytes::from_static(b"ping")), &mut frames)
+            .unwrap();
+        codec
+            .encode(
+                Message::Close(Some(CloseReason {
+                    code: CloseCode::Normal,
+                    description: Some(close.clone()),
+                })),
+                &mut frames,
+            )
+            .unwrap();
+
+        // Frames may be split between chunks arbitrarily.
+        let (first, second) = frames.split_at(3);
+        assert!(encoder.encode(first).unwrap().is_empty());
+        assert_eq!(
+            encoder.encode(second).unwrap(),
+            format!("data: {}\n\nevent: close\ndata: {}\n\n", ping, close),
+        );
+    }
+
+    #[test]
+    fn converts_frames_larger_than_default_max_size() {
+        let sessions = SseSessions::default();
+        let (token, _frames) = sessions.register();
+        let mut encoder = EventStreamEncoder::new(token, sessions);
+
+        let msg = serde_json::to_string(&ServerMsg::Event {
+            room_id: "room_id".into(),
+            seq: Some(1),
+            event: Event::Custom {
+                payload: serde_json::Value::String("a".repeat(100_000)),
+            },
+        })
+        .unwrap();
+        let mut frames = BytesMut::new();
+        Codec::new()
+            .encode(Message::Text(msg.clone()), &mut frames)
+            .unwrap();
+
+        assert_eq!(
+            encoder.encode(&frames).unwrap(),
+            format!("data: {}\n\n", msg),
+        );
+    }
+
+    #[test]
+    fn bounds_frames_buffered_for_session() {
+        let sessions = SseSessions::default();
+        let (token, _frames) = sessions.register();
+        let mut sender = sessions.get(&token).unwrap();
+
+        let frame = || Ok(Bytes::from_static(b"frame"));
+        let mut sent = 0;
+        while sender.try_send(frame()).is_ok() {
+            sent += 1;
+            assert!(sent <= FRAMES_BUFFER_SIZE + 2);
+        }
+        assert!(sender.try_send(frame()).unwrap_err().is_full());
+    }
+
+    #[test]
+    fn unregisters_session_once_event_stream_is_dropped() {
+        let sessions = SseSessions::default();
+        let (token, _frames) = sessions.register();
+        assert_eq!(token.len(), TOKEN_LEN);
+        assert!(sessions.get(&token).is_some());
+
+        drop(EventStreamEncoder::new(token.clone(), sessions.clone()));
+        assert!(sessions.get(&token).is_none());
+    }
+}
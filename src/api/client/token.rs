@@ -0,0 +1,512 @@
+//! Signed [JWT]s which `Member`s may authenticate with instead of their
+//! credentials.
+//!
+//! [JWT]: https://jwt.io
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom as _,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use actix::{Addr, MailboxError};
+use derive_more::Display;
+use ed25519_dalek::{PublicKey, Signature, Verifier as _};
+use failure::Fail;
+use futures::future::{self, FutureExt as _, LocalBoxFuture};
+use hmac::{Hmac, Mac as _, NewMac as _};
+use medea_client_api_proto::{Credential, MemberId, RoomId};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::{
+    api::{
+        client::session::TokenAuthorizer,
+        control::{
+            credentials::Credentials,
+            member::MemberElement,
+            pipeline::Pipeline,
+            refs::{Fid, ToMember},
+            EndpointId, MemberSpec,
+        },
+    },
+    conf::rpc::Jwt as JwtConf,
+    signalling::room_service::{
+        ProvisionMember, RoomService, RoomServiceError,
+    },
+};
+
+/// Length of Ed25519 public key in bytes.
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// Errors of validating [JWT] presented by `Member`.
+///
+/// [JWT]: https://jwt.io
+#[derive(Debug, Display, Fail)]
+pub enum TokenError {
+    /// Provided string is not a [JWT] at all.
+    ///
+    /// [JWT]: https://jwt.io
+    #[display(fmt = "Token is malformed")]
+    Malformed,
+
+    /// Token is signed with an algorithm which is not supported or not
+    /// configured.
+    #[display(fmt = "Token signing algorithm `{}` is not supported", _0)]
+    UnsupportedAlgorithm(String),
+
+    /// Token's signature doesn't match its content.
+    #[display(fmt = "Token signature is invalid")]
+    InvalidSignature,
+
+    /// Token's claims cannot be parsed.
+    #[display(fmt = "Token claims are invalid: {}", _0)]
+    InvalidClaims(serde_json::Error),
+
+    /// Token's expiration time is out of the supported range.
+    #[display(fmt = "Token expiration time is invalid")]
+    InvalidExpiration,
+
+    /// Token is expired.
+    #[display(fmt = "Token is expired")]
+    Expired,
+
+    /// Token is issued for another `Room` or `Member`.
+    #[display(fmt = "Token is issued for `{}/{}`", _0, _1)]
+    WrongMember(RoomId, MemberId),
+
+    /// [`RoomService`] failed to create `Member`.
+    #[display(fmt = "Failed to provision Member: {}", _0)]
+    Provision(RoomServiceError),
+
+    /// [`RoomService`]'s [`MailboxError`].
+    #[display(fmt = "RoomService mailbox error: {:?}", _0)]
+    RoomServiceMailbox(MailboxError),
+}
+
+/// Error of creating [`TokenValidator`] from invalid settings.
+#[derive(Debug, Display, Fail)]
+#[display(fmt = "Ed25519 public key must be {} base64-encoded bytes", _0)]
+pub struct InvalidPublicKey(usize);
+
+/// Claims of [JWT] presented by `Member`.
+///
+/// [JWT]: https://jwt.io
+#[derive(Debug, Deserialize)]
+pub struct MemberClaims {
+    /// ID of `Room` which token is issued for.
+    pub room: RoomId,
+
+    /// ID of `Member` which token is issued for.
+    pub member: MemberId,
+
+    /// Expiration time of token as a number of seconds since Unix epoch.
+    pub exp: u64,
+
+    /// Pipeline template of `Member` which is created once token is
+    /// presented, if `Member` doesn't exist yet.
+    ///
+    /// Has the same format as `spec` of `Member` in static [Control API]
+    /// specs.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[serde(default)]
+    pub pipeline: HashMap<EndpointId, MemberElement>,
+}
+
+/// Header of [JWT].
+///
+/// [JWT]: https://jwt.io
+#[derive(Debug, Deserialize)]
+struct Header {
+    /// Signing algorithm of token.
+    alg: String,
+}
+
+/// Validator of [JWT]s signed with `HS256` or `EdDSA` algorithms.
+///
+/// [JWT]: https://jwt.io
+#[derive(Debug)]
+pub struct TokenValidator {
+    /// Secret to verify `HS256` signatures with.
+    hs256_secret: Option<Vec<u8>>,
+
+    /// Public key to verify `EdDSA` signatures with.
+    ed25519_public_key: Option<PublicKey>,
+
+    /// Allowed clock skew when checking expiration time.
+    leeway: Duration,
+}
+
+impl TokenValidator {
+    /// Creates new [`TokenValidator`] from provided settings.
+    ///
+    /// Returns [`None`] if authentication with tokens is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// With [`InvalidPublicKey`] if configured Ed25519 public key is not a
+    /// base64-encoded 32 bytes key of a valid curve point.
+    pub fn new(conf: &JwtConf) -> Result<Option<Self>, InvalidPublicKey> {
+        if !conf.is_enabled() {
+            return Ok(None);
+        }
+        let ed25519_public_key = conf
+            .ed25519_public_key
+            .as_ref()
+            .map(|key| {
+                base64::decode(key.as_ref())
+                    .ok()
+                    .and_then(|key| PublicKey::from_bytes(&key).ok())
+                    .ok_or(InvalidPublicKey(ED25519_PUBLIC_KEY_LEN))
+            })
+            .transpose()?;
+        Ok(Some(Self {
+            hs256_secret: conf
+                .hs256_secret
+                .as_ref()
+                .map(|secret| secret.as_bytes().to_vec()),
+            ed25519_public_key,
+            leeway: conf.leeway,
+        }))
+    }
+
+    /// Validates provided token and returns its [`MemberClaims`].
+    ///
+    /// # Errors
+    ///
+    /// With [`TokenError::Malformed`] if provided string is not a token.
+    ///
+    /// With [`TokenError::UnsupportedAlgorithm`] if token is signed with not
+    /// configured algorithm.
+    ///
+    /// With [`TokenError::InvalidSignature`] if token's signature is invalid.
+    ///
+    /// With [`TokenError::InvalidClaims`] if token's claims cannot be parsed.
+    ///
+    /// With [`TokenError::InvalidExpiration`] if token's expiration time
+    /// overflows.
+    ///
+    /// With [`TokenError::Expired`] if token is expired.
+    pub fn validate(&self, token: &str) -> Result<MemberClaims, TokenError> {
+        let mut parts = token.rsplitn(2, '.');
+        let signature = parts.next().and_then(decode_part);
+        let message = parts.next().ok_or(TokenError::Malformed)?;
+        let mut parts = message.splitn(2, '.');
+        let header = parts.next().and_then(decode_part);
+        let claims = parts.next().and_then(decode_part);
+        let (header, claims, signature) = match (header, claims, signature) {
+            (Some(header), Some(claims), Some(signature)) => {
+                (header, claims, signature)
+            }
+            _ => return Err(TokenError::Malformed),
+        };
+        let header: Header = serde_json::from_slice(&header)
+            .map_err(|_| TokenError::Malformed)?;
+
+        let is_valid = match (
+            header.alg.as_str(),
+            &self.hs256_secret,
+            &self.ed25519_public_key,
+        ) {
+            ("HS256", Some(secret), _) => {
+                let mut mac = Hmac::<Sha256>::new_varkey(secret)
+                    .expect("HMAC accepts keys of any length");
+                mac.update(message.as_bytes());
+                // Signature is compared in constant time.
+                mac.verify(&signature).is_ok()
+            }
+            ("EdDSA", _, Some(public_key)) => Signature::try_from(
+                signature.as_slice(),
+            )
+            .map_or(false, |signature| {
+                public_key.verify(message.as_bytes(), &signature).is_ok()
+            }),
+            _ => return Err(TokenError::UnsupportedAlgorithm(header.alg)),
+        };
+        if !is_valid {
+            return Err(TokenError::InvalidSignature);
+        }
+
+        let claims: MemberClaims = serde_json::from_slice(&claims)
+            .map_err(TokenError::InvalidClaims)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        // Overflowing expiration time can't be issued by a sane issuer.
+        let expires_at = Duration::from_secs(claims.exp)
+            .checked_add(self.leeway)
+            .ok_or(TokenError::InvalidExpiration)?;
+        if expires_at <= now {
+            return Err(TokenError::Expired);
+        }
+        Ok(claims)
+    }
+}
+
+/// Decodes base64url-encoded part of token.
+fn decode_part(part: &str) -> Option<Vec<u8>> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD).ok()
+}
+
+/// [`TokenAuthorizer`] which validates tokens with [`TokenValidator`] and
+/// provisions `Member`s via [`RoomService`].
+#[derive(Clone)]
+pub struct MemberTokenAuthorizer {
+    /// Validator of presented tokens.
+    validator: Arc<TokenValidator>,
+
+    /// [`RoomService`] which `Member`s are provisioned via.
+    room_service: Addr<RoomService>,
+}
+
+impl_debug_by_struct_name!(MemberTokenAuthorizer);
+
+impl MemberTokenAuthorizer {
+    /// Creates new [`MemberTokenAuthorizer`].
+    #[inline]
+    #[must_use]
+    pub fn new(
+        validator: Arc<TokenValidator>,
+        room_service: Addr<RoomService>,
+    ) -> Self {
+        Self {
+            validator,
+            room_service,
+        }
+    }
+}
+
+impl TokenAuthorizer for MemberTokenAuthorizer {
+    fn authorize(
+        &self,
+        room_id: RoomId,
+        member_id: MemberId,
+        token: Credential,
+    ) -> Option<LocalBoxFuture<'static, Result<(), TokenError>>> {
+        let claims = match self.validator.validate(&token.0) {
+            Ok(claims) => claims,
+            Err(TokenError::Malformed) => return None,
+            Err(e) => return Some(future::err(e).boxed_local()),
+        };
+        if claims.room != room_id || claims.member != member_id {
+            return Some(
+                future::err(TokenError::WrongMember(
+                    claims.room,
+                    claims.member,
+                ))
+                .boxed_local(),
+            );
+        }
+
+        // Token itself is never stored as `Member`'s credentials, so the
+        // provisioned `Member` gets random ones.
+        let spec = MemberSpec::new(
+            Pipeline::new(claims.pipeline),
+            Credentials::generate(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let room_service = self.room_service.clone();
+        Some(
+            async move {
+                let spec = spec.hash_credentials().await;
+                room_service
+                    .send(ProvisionMember {
+                        fid: Fid::<ToMember>::new(room_id, member_id),
+                        spec,
+                    })
+                    .await
+                    .map_err(TokenError::RoomServiceMailbox)?
+                    .map_err(TokenError::Provision)
+            }
+            .boxed_local(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use ed25519_dalek::{Keypair, SecretKey, Signer as _};
+
+    use super::*;
+
+    /// Returns Ed25519 [`Keypair`] derived from the provided seed.
+    fn keypair(seed: &[u8]) -> Keypair {
+        let secret = SecretKey::from_bytes(seed).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    /// Returns [`JwtConf`] with `HS256` secret and `EdDSA` public key derived
+    /// from the provided seed.
+    fn conf(ed25519_seed: &[u8]) -> JwtConf {
+        let public_key = keypair(ed25519_seed).public;
+        JwtConf {
+            hs256_secret: Some("secret".into()),
+            ed25519_public_key: Some(base64::encode(public_key).into()),
+            leeway: Duration::from_secs(0),
+        }
+    }
+
+    /// Returns `exp` claim of token expiring in provided number of seconds.
+    fn expires_in(secs: i64) -> i64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        now.as_secs() as i64 + secs
+    }
+
+    /// Encodes provided claims into unsigned token message.
+    fn message(alg: &str, claims: &serde_json::Value) -> String {
+        let encode = |v: &serde_json::Value| {
+            base64::encode_config(v.to_string(), base64::URL_SAFE_NO_PAD)
+        };
+        format!(
+            "{}.{}",
+            encode(&serde_json::json!({ "alg": alg, "typ": "JWT" })),
+            encode(claims),
+        )
+    }
+
+    /// Returns token signed with `HS256` and provided secret.
+    fn hs256_token(secret: &str, claims: &serde_json::Value) -> String {
+        let message = message("HS256", claims);
+        let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes()).unwrap();
+        mac.update(message.as_bytes());
+        let signature = base64::encode_config(
+            mac.finalize().into_bytes(),
+            base64::URL_SAFE_NO_PAD,
+        );
+        format!("{}.{}", message, signature)
+    }
+
+    /// Returns token signed with `EdDSA` and key derived from provided seed.
+    fn eddsa_token(seed: &[u8], claims: &serde_json::Value) -> String {
+        let message = message("EdDSA", claims);
+        let signature = keypair(seed).sign(message.as_bytes());
+        let signature = base64::encode_config(
+            &signature.to_bytes()[..],
+            base64::URL_SAFE_NO_PAD,
+        );
+        format!("{}.{}", message, signature)
+    }
+
+    fn claims(exp: i64) -> serde_json::Value {
+        serde_json::json!({
+            "room": "room",
+            "member": "member",
+            "exp": exp,
+            "pipeline": {
+                "publish": {
+                    "kind": "WebRtcPublishEndpoint",
+                    "spec": { "p2p": "Always" },
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn disabled_without_keys() {
+        assert!(TokenValidator::new(&JwtConf::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_public_key() {
+        let conf = JwtConf {
+            ed25519_public_key: Some("c2hvcnQ=".into()),
+            ..JwtConf::default()
+        };
+
+        assert!(TokenValidator::new(&conf).is_err());
+    }
+
+    #[test]
+    fn validates_hs256_tokens() {
+        let validator = TokenValidator::new(&conf(&[1; 32])).unwrap().unwrap();
+
+        let validated = validator
+            .validate(&hs256_token("secret", &claims(expires_in(60))))
+            .unwrap();
+        assert_eq!(validated.room, "room".into());
+        assert_eq!(validated.member, "member".into());
+        assert_eq!(validated.pipeline.len(), 1);
+
+        assert!(matches!(
+            validator.validate(&hs256_token("other", &claims(expires_in(60)))),
+            Err(TokenError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn validates_eddsa_tokens() {
+        let validator = TokenValidator::new(&conf(&[1; 32])).unwrap().unwrap();
+
+        assert!(validator
+            .validate(&eddsa_token(&[1; 32], &claims(expires_in(60))))
+            .is_ok());
+        assert!(matches!(
+            validator.validate(&eddsa_token(&[2; 32], &claims(expires_in(60)))),
+            Err(TokenError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn rejects_expired_tokens() {
+        let mut conf = conf(&[1; 32]);
+        let validator = TokenValidator::new(&conf).unwrap().unwrap();
+
+        assert!(matches!(
+            validator.validate(&hs256_token("secret", &claims(expires_in(-5)))),
+            Err(TokenError::Expired)
+        ));
+
+        conf.leeway = Duration::from_secs(10);
+        let validator = TokenValidator::new(&conf).unwrap().unwrap();
+        assert!(validator
+            .validate(&hs256_token("secret", &claims(expires_in(-5))))
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_overflowing_expiration() {
+        let mut conf = conf(&[1; 32]);
+        conf.leeway = Duration::from_secs(10);
+        let validator = TokenValidator::new(&conf).unwrap().unwrap();
+
+        let mut claims = claims(0);
+        claims["exp"] = u64::MAX.into();
+        assert!(matches!(
+            validator.validate(&hs256_token("secret", &claims)),
+            Err(TokenError::InvalidExpiration)
+        ));
+    }
+
+    #[test]
+    fn rejects_not_configured_algorithms() {
+        let conf = JwtConf {
+            hs256_secret: Some("secret".into()),
+            ..JwtConf::default()
+        };
+        let validator = TokenValidator::new(&conf).unwrap().unwrap();
+
+        assert!(matches!(
+            validator.validate(&eddsa_token(&[1; 32], &claims(expires_in(60)))),
+            Err(TokenError::UnsupportedAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn detects_malformed_tokens() {
+        let validator = TokenValidator::new(&conf(&[1; 32])).unwrap().unwrap();
+
+        for token in &["test", "a.b", "a.b.c", "!!.??.##"] {
+            assert!(matches!(
+                validator.validate(token),
+                Err(TokenError::Malformed)
+            ));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Circuit breaker which stops sending [`CallbackRequest`]s to a callback
+//! server after too many consecutive failures.
+//!
+//! [`CallbackRequest`]: crate::api::control::callback::CallbackRequest
+
+use std::time::{Duration, Instant};
+
+use crate::conf::control::CircuitBreaker as CircuitBreakerConf;
+
+/// State of [`CircuitBreaker`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    /// Requests are allowed.
+    Closed {
+        /// Number of consecutive failed requests.
+        failures: u32,
+    },
+
+    /// Requests are rejected until the provided [`Instant`].
+    Open {
+        /// [`Instant`] after which a single trial request is allowed.
+        until: Instant,
+    },
+
+    /// Single trial request is in flight, other requests are rejected until
+    /// its result is known.
+    HalfOpen,
+}
+
+/// Circuit breaker of a single callback server.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// Number of consecutive failures after which this [`CircuitBreaker`]
+    /// opens.
+    ///
+    /// `0` means that this [`CircuitBreaker`] never opens.
+    failure_threshold: u32,
+
+    /// Duration after which opened [`CircuitBreaker`] allows a trial request.
+    reset_timeout: Duration,
+
+    /// Current state of this [`CircuitBreaker`].
+    state: State,
+}
+
+impl CircuitBreaker {
+    /// Returns closed [`CircuitBreaker`] with provided settings.
+    pub fn new(conf: &CircuitBreakerConf) -> Self {
+        Self {
+            failure_threshold: conf.failure_threshold,
+            reset_timeout: conf.reset_timeout,
+            state: State::Closed { failures: 0 },
+        }
+    }
+
+    /// Indicates whether a request is allowed to be sent at the moment.
+    ///
+    /// Transitions opened [`CircuitBreaker`] into half-open state once its
+    /// reset timeout passes.
+    pub fn allow(&mut self) -> bool {
+        match self.state {
+            State::Closed { .. } => true,
+            State::Open { until } if Instant::now() >= until => {
+                self.state = State::HalfOpen;
+                true
+            }
+            State::Open { .. } | State::HalfOpen => false,
+        }
+    }
+
+    /// Records successful request, closing this [`CircuitBreaker`].
+    pub fn on_success(&mut self) {
+        self.state = State::Closed { failures: 0 };
+    }
+
+    /// Records failed request, opening this [`CircuitBreaker`] if failures
+    /// threshold is reached or a trial request has failed.
+    pub fn on_failure(&mut self) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+        let failures = match self.state {
+            State::Closed { failures } => failures + 1,
+            State::HalfOpen => self.failure_threshold,
+            State::Open { .. } => return,
+        };
+        self.state = if failures >= self.failure_threshold {
+            State::Open {
+                until: Instant::now() + self.reset_timeout,
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    fn breaker(
+        failure_threshold: u32,
+        reset_timeout: Duration,
+    ) -> CircuitBreaker {
+        CircuitBreaker::new(&CircuitBreakerConf {
+            failure_threshold,
+            reset_timeout,
+        })
+    }
+
+    #[test]
+    fn opens_after_threshold() {
+        let mut breaker = breaker(3, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(breaker.allow());
+            breaker.on_failure();
+        }
+        assert!(breaker.allow());
+        breaker.on_failure();
+        assert!(!breaker.allow());
+    }
+
+    #[test]
+    fn success_resets_failures() {
+        let mut breaker = breaker(2, Duration::from_secs(60));
+
+        breaker.on_failure();
+        breaker.on_success();
+        breaker.on_failure();
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn half_opens_after_reset_timeout() {
+        let mut breaker = breaker(1, Duration::from_millis(0));
+
+        breaker.on_failure();
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+
+        breaker.on_failure();
+        assert!(breaker.allow());
+        breaker.on_success();
+        assert!(breaker.allow());
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn never_opens_with_zero_threshold() {
+        let mut breaker = breaker(0, Duration::from_secs(60));
+
+        for _ in 0..100 {
+            breaker.on_failure();
+        }
+        assert!(breaker.allow());
+    }
+}
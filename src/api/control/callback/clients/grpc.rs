@@ -70,8 +70,9 @@ pub mod test {
     use medea_control_api_proto::grpc::callback::{
         callback_server::{Callback, CallbackServer as TonicCallbackServer},
         on_leave::Reason,
+        on_remove::Reason as OnRemoveReason,
         request::Event,
-        Request, Response,
+        OnStart, OnStop, Request, Response,
     };
     use tonic::{transport::Server, Status};
 
@@ -79,6 +80,13 @@ pub mod test {
     pub trait GrpcCallbackServer {
         fn on_join(&self, fid: &str) -> Result<(), ()>;
         fn on_leave(&self, fid: &str, event: Reason) -> Result<(), ()>;
+        fn on_start(&self, fid: &str, event: OnStart) -> Result<(), ()>;
+        fn on_stop(&self, fid: &str, event: OnStop) -> Result<(), ()>;
+        fn on_remove(
+            &self,
+            fid: &str,
+            reason: OnRemoveReason,
+        ) -> Result<(), ()>;
     }
 
     #[async_trait::async_trait]
@@ -94,6 +102,14 @@ pub mod test {
                     &request.fid,
                     Reason::from_i32(on_leave.reason).unwrap(),
                 ),
+                Event::OnStart(on_start) => {
+                    self.on_start(&request.fid, on_start)
+                }
+                Event::OnStop(on_stop) => self.on_stop(&request.fid, on_stop),
+                Event::OnRemove(on_remove) => self.on_remove(
+                    &request.fid,
+                    OnRemoveReason::from_i32(on_remove.reason).unwrap(),
+                ),
             }
             .map(|_| tonic::Response::new(Response {}))
             .map_err(|_| Status::internal(""))
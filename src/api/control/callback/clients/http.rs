@@ -0,0 +1,367 @@
+//! Implementation of HTTP client for sending [`CallbackRequest`]s.
+
+use async_trait::async_trait;
+use awc::{
+    http::{header, StatusCode},
+    Client,
+};
+use url::Url;
+
+use crate::api::control::callback::{
+    clients::{CallbackClient, CallbackClientError},
+    url::{HttpCallbackFormat, HttpCallbackUrl},
+    CallbackRequest,
+};
+
+/// Maximum number of redirects which will be followed while sending a single
+/// [`CallbackRequest`].
+const MAX_REDIRECTS: usize = 10;
+
+/// HTTP client for sending [`CallbackRequest`]s.
+///
+/// Sends [`CallbackRequest`]s via `POST` HTTP method in the format defined
+/// by [`HttpCallbackUrl`], follows redirects and considers any non-`2xx`
+/// response status code as an error.
+///
+/// `303 See Other` redirect is followed with `GET` HTTP method, as required
+/// by [RFC 7231][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc7231#section-6.4.4
+#[derive(Debug)]
+pub struct HttpCallbackClient {
+    /// URL which [`CallbackRequest`]s are sent to.
+    url: HttpCallbackUrl,
+}
+
+impl HttpCallbackClient {
+    /// Returns HTTP client for provided [`HttpCallbackUrl`].
+    #[inline]
+    pub fn new(url: HttpCallbackUrl) -> Self {
+        Self { url }
+    }
+
+    /// Serializes provided [`CallbackRequest`] into HTTP request body.
+    ///
+    /// # Errors
+    ///
+    /// With [`CallbackClientError::Serialization`] if serialization fails.
+    fn serialize(
+        &self,
+        request: &CallbackRequest,
+    ) -> Result<Vec<u8>, CallbackClientError> {
+        match self.url.format() {
+            HttpCallbackFormat::Json => serde_json::to_vec(request)
+                .map_err(|e| CallbackClientError::Serialization(e.to_string())),
+            HttpCallbackFormat::Yaml => serde_yaml::to_vec(request)
+                .map_err(|e| CallbackClientError::Serialization(e.to_string())),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl CallbackClient for HttpCallbackClient {
+    async fn send(
+        &self,
+        request: CallbackRequest,
+    ) -> Result<(), CallbackClientError> {
+        let body = self.serialize(&request)?;
+        let content_type = match self.url.format() {
+            HttpCallbackFormat::Json => "application/json",
+            HttpCallbackFormat::Yaml => "application/x-yaml",
+        };
+
+        // `awc::Client` is `!Send`, so it cannot be stored in this
+        // `CallbackClient`.
+        let client = Client::default();
+        let mut url = self.url.url().clone();
+        let mut see_other = false;
+        for _ in 0..=MAX_REDIRECTS {
+            let username = url.username().to_owned();
+            let password = url.password().map(ToOwned::to_owned);
+            let mut request_url = url.clone();
+            request_url.set_username("").ok();
+            request_url.set_password(None).ok();
+
+            let mut req = if see_other {
+                client.get(request_url.as_str())
+            } else {
+                client.post(request_url.as_str()).content_type(content_type)
+            };
+            if !username.is_empty() {
+                req = req.basic_auth(&username, password.as_deref());
+            }
+            let sending = if see_other {
+                req.send()
+            } else {
+                req.send_body(body.clone())
+            };
+            let resp = sending
+                .await
+                .map_err(|e| CallbackClientError::Http(e.to_string()))?;
+
+            let status = resp.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            if !status.is_redirection() || status == StatusCode::NOT_MODIFIED {
+                return Err(CallbackClientError::UnsuccessfulHttpStatus(
+                    status.as_u16(),
+                ));
+            }
+            if status == StatusCode::SEE_OTHER {
+                see_other = true;
+            }
+            url = resp
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| request_url.join(location).ok())
+                .map(|mut location: Url| {
+                    // Credentials are kept only for the same origin.
+                    if location.origin() == url.origin() {
+                        location.set_username(&username).ok();
+                        location.set_password(password.as_deref()).ok();
+                    }
+                    location
+                })
+                .ok_or_else(|| {
+                    CallbackClientError::UnsuccessfulHttpStatus(status.as_u16())
+                })?;
+        }
+
+        Err(CallbackClientError::TooManyRedirects(MAX_REDIRECTS))
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{
+        convert::TryFrom as _,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use actix_web::{test::TestServer, web, App, HttpRequest, HttpResponse};
+    use chrono::SecondsFormat;
+
+    use crate::api::control::{
+        callback::{
+            url::CallbackUrl, MediaDirection, MediaType, OnJoinEvent,
+            OnLeaveEvent, OnLeaveReason, OnStartEvent,
+        },
+        refs::StatefulFid,
+    };
+
+    use super::*;
+
+    /// `Authorization` header value of `user:pass` basic auth credentials.
+    const BASIC_AUTH: &str = "Basic dXNlcjpwYXNz";
+
+    /// Returns [`HttpCallbackClient`] sending requests to the provided path
+    /// of the provided [`TestServer`] with `user:pass` basic auth
+    /// credentials.
+    fn client(srv: &TestServer, path: &str) -> HttpCallbackClient {
+        let url = format!("http://user:pass@{}{}", srv.addr(), path);
+        match CallbackUrl::try_from(url).unwrap() {
+            CallbackUrl::Http(url) => HttpCallbackClient::new(url),
+            CallbackUrl::Grpc(_) => unreachable!(),
+        }
+    }
+
+    /// Returns [`HttpResponse`] redirecting to the provided location with
+    /// the provided status code.
+    fn redirect(status: StatusCode, location: &str) -> HttpResponse {
+        HttpResponse::build(status)
+            .header(header::LOCATION, location)
+            .finish()
+    }
+
+    /// Returns [`HttpResponse`] with `200 OK` status if the provided
+    /// [`HttpRequest`] has the provided `Authorization` header, or with
+    /// `401 Unauthorized` status otherwise.
+    fn expect_auth(req: &HttpRequest, expected: Option<&str>) -> HttpResponse {
+        let auth = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+        if auth == expected {
+            HttpResponse::Ok().finish()
+        } else {
+            HttpResponse::Unauthorized().finish()
+        }
+    }
+
+    fn request() -> CallbackRequest {
+        CallbackRequest::new(
+            StatefulFid::try_from("room/member".to_string()).unwrap(),
+            OnJoinEvent.into(),
+        )
+    }
+
+    #[test]
+    fn serializes_on_join_to_json() {
+        let request = CallbackRequest::new(
+            StatefulFid::try_from("room/member".to_string()).unwrap(),
+            OnJoinEvent.into(),
+        );
+        let at = request.at.to_rfc3339_opts(SecondsFormat::Micros, true);
+
+        let body: serde_json::Value =
+            serde_json::from_slice(&serde_json::to_vec(&request).unwrap())
+                .unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "element": "room/member",
+                "event": "on_join",
+                "at": at,
+                "seq": 0,
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_on_leave_to_yaml() {
+        let request = CallbackRequest::new(
+            StatefulFid::try_from("room/member".to_string()).unwrap(),
+            OnLeaveEvent::new(OnLeaveReason::LostConnection).into(),
+        );
+
+        let body: serde_yaml::Value =
+            serde_yaml::from_slice(&serde_yaml::to_vec(&request).unwrap())
+                .unwrap();
+
+        assert_eq!(body["element"].as_str(), Some("room/member"));
+        assert_eq!(body["event"].as_str(), Some("on_leave"));
+        assert_eq!(body["reason"].as_str(), Some("lost_connection"));
+    }
+
+    #[actix_rt::test]
+    async fn follows_redirects_keeping_basic_auth_on_same_origin() {
+        let srv = actix_web::test::start(|| {
+            App::new()
+                .route(
+                    "/redirect",
+                    web::post().to(|| async {
+                        redirect(StatusCode::TEMPORARY_REDIRECT, "/target")
+                    }),
+                )
+                .route(
+                    "/target",
+                    web::post().to(|req: HttpRequest| async move {
+                        expect_auth(&req, Some(BASIC_AUTH))
+                    }),
+                )
+        });
+
+        client(&srv, "/redirect").send(request()).await.unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn drops_basic_auth_on_redirect_to_other_origin() {
+        let other = actix_web::test::start(|| {
+            App::new().route(
+                "/target",
+                web::post().to(|req: HttpRequest| async move {
+                    expect_auth(&req, None)
+                }),
+            )
+        });
+        let target = format!("http://{}/target", other.addr());
+        let srv = actix_web::test::start(move || {
+            let target = target.clone();
+            App::new().route(
+                "/redirect",
+                web::post().to(move || {
+                    let target = target.clone();
+                    async move {
+                        redirect(StatusCode::PERMANENT_REDIRECT, &target)
+                    }
+                }),
+            )
+        });
+
+        client(&srv, "/redirect").send(request()).await.unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn follows_see_other_with_get() {
+        let srv = actix_web::test::start(|| {
+            App::new()
+                .route(
+                    "/redirect",
+                    web::post().to(|| async {
+                        redirect(StatusCode::SEE_OTHER, "/target")
+                    }),
+                )
+                .route(
+                    "/target",
+                    web::get().to(|req: HttpRequest| async move {
+                        expect_auth(&req, Some(BASIC_AUTH))
+                    }),
+                )
+        });
+
+        client(&srv, "/redirect").send(request()).await.unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn stops_after_max_redirects() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let srv = actix_web::test::start(move || {
+            let counter = Arc::clone(&counter);
+            App::new().route(
+                "/loop",
+                web::post().to(move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async { redirect(StatusCode::FOUND, "/loop") }
+                }),
+            )
+        });
+
+        let err = client(&srv, "/loop").send(request()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            CallbackClientError::TooManyRedirects(MAX_REDIRECTS),
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), MAX_REDIRECTS + 1);
+    }
+
+    #[actix_rt::test]
+    async fn fails_on_unsuccessful_status() {
+        let srv = actix_web::test::start(|| {
+            App::new().route(
+                "/",
+                web::post()
+                    .to(|| async { HttpResponse::BadGateway().finish() }),
+            )
+        });
+
+        let err = client(&srv, "/").send(request()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            CallbackClientError::UnsuccessfulHttpStatus(502),
+        ));
+    }
+
+    #[test]
+    fn serializes_on_start_to_json() {
+        let request = CallbackRequest::new(
+            StatefulFid::try_from("room/member/publish".to_string()).unwrap(),
+            OnStartEvent::new(MediaType::Video, MediaDirection::Publish).into(),
+        );
+
+        let body: serde_json::Value =
+            serde_json::from_slice(&serde_json::to_vec(&request).unwrap())
+                .unwrap();
+
+        assert_eq!(body["element"], "room/member/publish");
+        assert_eq!(body["event"], "on_start");
+        assert_eq!(body["media_type"], "video");
+        assert_eq!(body["media_direction"], "publish");
+    }
+}
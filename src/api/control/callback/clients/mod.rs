@@ -1,12 +1,13 @@
 //! Implementations of Control API callback clients for all protocols.
 
 pub mod grpc;
+pub mod http;
 
 use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
 use derive_more::{Display, From};
-use futures::future::{FutureExt, LocalBoxFuture};
+use futures::future::{self, FutureExt, LocalBoxFuture};
 
 use crate::{
     api::control::callback::{url::CallbackUrl, CallbackRequest},
@@ -25,6 +26,32 @@ pub enum CallbackClientError {
     /// Error while creating new [`CallbackClient`].
     #[display(fmt = "CallbackClientError: {}", _0)]
     TonicTransport(tonic::transport::Error),
+
+    /// HTTP client failed to send [`CallbackRequest`].
+    #[display(fmt = "HTTP request failed: {}", _0)]
+    #[from(ignore)]
+    Http(String),
+
+    /// HTTP callback server responded with non-`2xx` status code.
+    #[display(fmt = "HTTP callback server responded with {} status", _0)]
+    #[from(ignore)]
+    UnsuccessfulHttpStatus(u16),
+
+    /// HTTP callback server redirected request too many times.
+    #[display(fmt = "Too many redirects ({})", _0)]
+    #[from(ignore)]
+    TooManyRedirects(usize),
+
+    /// [`CallbackRequest`] failed to be serialized into HTTP request body.
+    #[display(fmt = "Failed to serialize HTTP callback body: {}", _0)]
+    #[from(ignore)]
+    Serialization(String),
+
+    /// [`CallbackRequest`] wasn't sent, because circuit breaker of its
+    /// [`CallbackUrl`] is open.
+    #[display(fmt = "Circuit breaker is open")]
+    #[from(ignore)]
+    CircuitBreakerOpen,
 }
 
 #[async_trait(?Send)]
@@ -65,6 +92,11 @@ impl CallbackClientFactory for CallbackClientFactoryImpl {
                     as Arc<dyn CallbackClient>)
             }
             .boxed_local(),
+            CallbackUrl::Http(http_url) => {
+                future::ok(Arc::new(http::HttpCallbackClient::new(http_url))
+                    as Arc<dyn CallbackClient>)
+                .boxed_local()
+            }
         }
     }
 }
@@ -0,0 +1,265 @@
+//! Store of undeliverable [`CallbackRequest`]s.
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write as _},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use derive_more::{Display, From};
+use redis::{AsyncCommands as _, ConnectionInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::control::callback::{url::CallbackUrl, CallbackRequest},
+    conf::control::{DeadLetter as DeadLetterConf, DeadLetterStoreKind},
+    utils::blocking_io,
+};
+
+/// Error of [`DeadLetterStore`].
+#[derive(Debug, Display, From)]
+pub enum DeadLetterStoreError {
+    /// I/O error of [`FileDeadLetterStore`].
+    #[display(fmt = "I/O error: {}", _0)]
+    Io(io::Error),
+
+    /// [Redis] error of [`RedisDeadLetterStore`].
+    ///
+    /// [Redis]: https://redis.io
+    #[display(fmt = "Redis error: {}", _0)]
+    Redis(redis::RedisError),
+
+    /// [`DeadLetter`] failed to be (de)serialized.
+    #[display(fmt = "Failed to (de)serialize dead letter: {}", _0)]
+    Serde(serde_json::Error),
+}
+
+/// [`CallbackRequest`] which failed to be delivered.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DeadLetter {
+    /// URL which [`CallbackRequest`] was sent to.
+    pub url: String,
+
+    /// Serialized [`CallbackRequest`].
+    pub request: serde_json::Value,
+
+    /// Number of performed delivery attempts.
+    pub attempts: u32,
+
+    /// Error of the last delivery attempt.
+    pub error: String,
+
+    /// Time at which [`CallbackRequest`] was considered undeliverable in
+    /// microseconds extended [RFC 3339] format.
+    ///
+    /// [RFC 3339]: https://tools.ietf.org/html/rfc3339
+    pub failed_at: String,
+}
+
+impl DeadLetter {
+    /// Returns [`DeadLetter`] for provided [`CallbackRequest`] failed to be
+    /// delivered to provided [`CallbackUrl`] at current time.
+    ///
+    /// # Errors
+    ///
+    /// With [`DeadLetterStoreError::Serde`] if [`CallbackRequest`] fails to be
+    /// serialized.
+    pub fn new(
+        url: &CallbackUrl,
+        request: &CallbackRequest,
+        attempts: u32,
+        error: String,
+    ) -> Result<Self, DeadLetterStoreError> {
+        Ok(Self {
+            url: url.to_string(),
+            request: serde_json::to_value(request)?,
+            attempts,
+            error,
+            failed_at: Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+        })
+    }
+}
+
+/// Store of undeliverable [`CallbackRequest`]s, which can be inspected later.
+#[async_trait(?Send)]
+pub trait DeadLetterStore: fmt::Debug + Send + Sync {
+    /// Puts provided [`DeadLetter`] into this [`DeadLetterStore`].
+    async fn push(
+        &self,
+        letter: DeadLetter,
+    ) -> Result<(), DeadLetterStoreError>;
+
+    /// Returns all [`DeadLetter`]s from this [`DeadLetterStore`] in order
+    /// they were put.
+    async fn list(&self) -> Result<Vec<DeadLetter>, DeadLetterStoreError>;
+}
+
+/// Returns [`DeadLetterStore`] configured by provided [`DeadLetterConf`].
+pub fn new_store(conf: &DeadLetterConf) -> Arc<dyn DeadLetterStore> {
+    match conf.store {
+        DeadLetterStoreKind::File => {
+            Arc::new(FileDeadLetterStore::new(conf.file.as_ref()))
+        }
+        DeadLetterStoreKind::Redis => Arc::new(RedisDeadLetterStore::new(
+            (&conf.redis).into(),
+            conf.redis_key.to_string(),
+        )),
+    }
+}
+
+/// [`DeadLetterStore`] which appends [`DeadLetter`]s to a file as
+/// [JSON Lines](http://jsonlines.org).
+#[derive(Debug)]
+pub struct FileDeadLetterStore {
+    /// Path to the file which [`DeadLetter`]s are appended to.
+    path: PathBuf,
+}
+
+impl FileDeadLetterStore {
+    /// Returns [`FileDeadLetterStore`] appending to the file at provided path.
+    #[inline]
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait(?Send)]
+impl DeadLetterStore for FileDeadLetterStore {
+    async fn push(
+        &self,
+        letter: DeadLetter,
+    ) -> Result<(), DeadLetterStoreError> {
+        let mut line = serde_json::to_vec(&letter)?;
+        line.push(b'\n');
+        let path = self.path.clone();
+        blocking_io(move || {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?
+                .write_all(&line)
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, DeadLetterStoreError> {
+        let path = self.path.clone();
+        let content = blocking_io(move || match fs::read_to_string(&path) {
+            Ok(content) => Ok(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(e),
+        })
+        .await?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(Into::into))
+            .collect()
+    }
+}
+
+/// [`DeadLetterStore`] which pushes [`DeadLetter`]s into a [Redis] list.
+///
+/// [Redis]: https://redis.io
+#[derive(Debug)]
+pub struct RedisDeadLetterStore {
+    /// Info for connecting to [Redis] database.
+    ///
+    /// [Redis]: https://redis.io
+    info: ConnectionInfo,
+
+    /// Key of the [Redis] list which [`DeadLetter`]s are pushed into.
+    ///
+    /// [Redis]: https://redis.io
+    key: String,
+}
+
+impl RedisDeadLetterStore {
+    /// Returns [`RedisDeadLetterStore`] pushing into the list with provided
+    /// `key`.
+    ///
+    /// Connection to [Redis] database is established lazily on each
+    /// operation, since undeliverable [`CallbackRequest`]s are rare.
+    ///
+    /// [Redis]: https://redis.io
+    #[inline]
+    pub fn new(info: ConnectionInfo, key: String) -> Self {
+        Self { info, key }
+    }
+
+    /// Establishes new connection to [Redis] database.
+    ///
+    /// [Redis]: https://redis.io
+    async fn connect(
+        &self,
+    ) -> Result<redis::aio::Connection, DeadLetterStoreError> {
+        Ok(redis::Client::open(self.info.clone())?
+            .get_async_connection()
+            .await?)
+    }
+}
+
+#[async_trait(?Send)]
+impl DeadLetterStore for RedisDeadLetterStore {
+    async fn push(
+        &self,
+        letter: DeadLetter,
+    ) -> Result<(), DeadLetterStoreError> {
+        let letter = serde_json::to_string(&letter)?;
+        self.connect()
+            .await?
+            .rpush::<_, _, ()>(&self.key, letter)
+            .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, DeadLetterStoreError> {
+        let letters: Vec<String> =
+            self.connect().await?.lrange(&self.key, 0, -1).await?;
+        letters
+            .iter()
+            .map(|letter| serde_json::from_str(letter).map_err(Into::into))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::convert::TryFrom as _;
+
+    use crate::api::control::{callback::OnJoinEvent, refs::StatefulFid};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn file_store_appends_and_lists_letters() {
+        let path = std::env::temp_dir()
+            .join(format!("medea_dead_letters_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store = FileDeadLetterStore::new(path.clone());
+        assert!(store.list().await.unwrap().is_empty());
+
+        let url =
+            CallbackUrl::try_from("grpc://127.0.0.1:6565".to_string()).unwrap();
+        let request = CallbackRequest::new(
+            StatefulFid::try_from("room/member".to_string()).unwrap(),
+            OnJoinEvent.into(),
+        );
+        let first = DeadLetter::new(&url, &request, 1, "foo".into()).unwrap();
+        let second = DeadLetter::new(&url, &request, 6, "bar".into()).unwrap();
+        store.push(first.clone()).await.unwrap();
+        store.push(second.clone()).await.unwrap();
+
+        let letters = store.list().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(letters, vec![first, second]);
+        assert_eq!(letters[0].url, "grpc://127.0.0.1:6565");
+        assert_eq!(letters[0].request["element"], "room/member");
+        assert_eq!(letters[0].request["event"], "on_join");
+    }
+}
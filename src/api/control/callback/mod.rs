@@ -1,15 +1,18 @@
 //! Control API callbacks implementation.
 
+pub mod circuit_breaker;
 pub mod clients;
+pub mod dead_letter;
 pub mod service;
 pub mod url;
 
 use actix::Message;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use derive_more::{Display, From};
 use medea_control_api_proto::grpc::callback as proto;
+use serde::{Serialize, Serializer};
 
-use crate::api::control::refs::StatefulFid;
+use crate::api::control::{refs::StatefulFid, ElementMeta};
 
 #[doc(inline)]
 pub use self::{
@@ -18,7 +21,7 @@ pub use self::{
 };
 
 /// Event for `on_leave` `Member` callback.
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct OnLeaveEvent {
     /// Reason of why `Member` was lost.
     reason: OnLeaveReason,
@@ -41,7 +44,8 @@ impl Into<proto::OnLeave> for OnLeaveEvent {
 }
 
 /// Reason of why `Member` was lost.
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OnLeaveReason {
     /// `Member` was normally disconnected.
     Disconnected,
@@ -51,6 +55,11 @@ pub enum OnLeaveReason {
 
     /// Server is shutting down.
     ServerShutdown,
+
+    /// `Member` was kicked out of the `Room` via [Control API].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    Kicked,
 }
 
 impl Into<proto::on_leave::Reason> for OnLeaveReason {
@@ -59,12 +68,13 @@ impl Into<proto::on_leave::Reason> for OnLeaveReason {
             Self::LostConnection => proto::on_leave::Reason::LostConnection,
             Self::ServerShutdown => proto::on_leave::Reason::ServerShutdown,
             Self::Disconnected => proto::on_leave::Reason::Disconnected,
+            Self::Kicked => proto::on_leave::Reason::Kicked,
         }
     }
 }
 
 /// `on_join` `Member` callback for Control API.
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct OnJoinEvent;
 
 impl Into<proto::OnJoin> for OnJoinEvent {
@@ -73,11 +83,127 @@ impl Into<proto::OnJoin> for OnJoinEvent {
     }
 }
 
+/// `on_start` `Endpoint` callback for Control API.
+#[derive(Clone, Debug, Serialize)]
+pub struct OnStartEvent {
+    /// [`MediaType`] of the traffic which starts flowing in some `Endpoint`.
+    media_type: MediaType,
+
+    /// [`MediaDirection`] of the `Endpoint` for which this callback was
+    /// received.
+    media_direction: MediaDirection,
+}
+
+impl OnStartEvent {
+    #[inline]
+    pub fn new(media_type: MediaType, media_direction: MediaDirection) -> Self {
+        Self {
+            media_type,
+            media_direction,
+        }
+    }
+}
+
+impl Into<proto::OnStart> for OnStartEvent {
+    fn into(self) -> proto::OnStart {
+        let media_type: proto::MediaType = self.media_type.into();
+        let media_direction: proto::MediaDirection =
+            self.media_direction.into();
+        proto::OnStart {
+            media_type: media_type as i32,
+            media_direction: media_direction as i32,
+        }
+    }
+}
+
+/// `on_stop` `Endpoint` callback for Control API.
+#[derive(Clone, Debug, Serialize)]
+pub struct OnStopEvent {
+    /// [`MediaType`] of the traffic which stops flowing in some `Endpoint`.
+    media_type: MediaType,
+
+    /// [`MediaDirection`] of the `Endpoint` for which this callback was
+    /// received.
+    media_direction: MediaDirection,
+}
+
+impl OnStopEvent {
+    #[inline]
+    pub fn new(media_type: MediaType, media_direction: MediaDirection) -> Self {
+        Self {
+            media_type,
+            media_direction,
+        }
+    }
+}
+
+impl Into<proto::OnStop> for OnStopEvent {
+    fn into(self) -> proto::OnStop {
+        let media_type: proto::MediaType = self.media_type.into();
+        let media_direction: proto::MediaDirection =
+            self.media_direction.into();
+        proto::OnStop {
+            media_type: media_type as i32,
+            media_direction: media_direction as i32,
+        }
+    }
+}
+
+/// `on_remove` `Room` callback for Control API.
+#[derive(Clone, Debug, Serialize)]
+pub struct OnRemoveEvent {
+    /// Reason of why `Room` was removed.
+    reason: OnRemoveReason,
+}
+
+impl OnRemoveEvent {
+    #[inline]
+    pub fn new(reason: OnRemoveReason) -> Self {
+        Self { reason }
+    }
+}
+
+impl Into<proto::OnRemove> for OnRemoveEvent {
+    fn into(self) -> proto::OnRemove {
+        let reason: proto::on_remove::Reason = self.reason.into();
+        proto::OnRemove {
+            reason: reason as i32,
+        }
+    }
+}
+
+/// Reason of why `Room` was removed automatically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnRemoveReason {
+    /// All the connected `Member`s have left `Room`.
+    AllLeft,
+
+    /// `Room` has been without connected `Member`s for the specified
+    /// duration.
+    EmptyTimeout,
+}
+
+impl Into<proto::on_remove::Reason> for OnRemoveReason {
+    fn into(self) -> proto::on_remove::Reason {
+        match self {
+            Self::AllLeft => proto::on_remove::Reason::AllLeft,
+            Self::EmptyTimeout => proto::on_remove::Reason::EmptyTimeout,
+        }
+    }
+}
+
 /// All callbacks which can happen.
-#[derive(Debug, From)]
+///
+/// Serialized with the name of callback in `event` field.
+#[derive(Clone, Debug, From, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
 pub enum CallbackEvent {
     OnJoin(OnJoinEvent),
     OnLeave(OnLeaveEvent),
+    OnStart(OnStartEvent),
+    OnStop(OnStopEvent),
+    OnRemove(OnRemoveEvent),
 }
 
 impl Into<proto::request::Event> for CallbackEvent {
@@ -89,6 +215,15 @@ impl Into<proto::request::Event> for CallbackEvent {
             Self::OnLeave(on_leave) => {
                 proto::request::Event::OnLeave(on_leave.into())
             }
+            Self::OnStart(on_start) => {
+                proto::request::Event::OnStart(on_start.into())
+            }
+            Self::OnStop(on_stop) => {
+                proto::request::Event::OnStop(on_stop.into())
+            }
+            Self::OnRemove(on_remove) => {
+                proto::request::Event::OnRemove(on_remove.into())
+            }
         }
     }
 }
@@ -97,7 +232,8 @@ impl Into<proto::request::Event> for CallbackEvent {
 ///
 /// This enum is used in [`MediaExchangeState`] of `Endpoint`s. That's why it
 /// represents a bitflag enum.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MediaType {
     /// Started/stopped audio traffic.
     Audio = 0b1,
@@ -137,6 +273,16 @@ impl MediaType {
     }
 }
 
+impl Into<proto::MediaType> for MediaType {
+    fn into(self) -> proto::MediaType {
+        match self {
+            Self::Audio => proto::MediaType::Audio,
+            Self::Video => proto::MediaType::Video,
+            Self::Both => proto::MediaType::Both,
+        }
+    }
+}
+
 impl From<&medea_client_api_proto::MediaType> for MediaType {
     fn from(media_type: &medea_client_api_proto::MediaType) -> Self {
         use medea_client_api_proto::MediaType as MediaTypeProto;
@@ -150,7 +296,8 @@ impl From<&medea_client_api_proto::MediaType> for MediaType {
 
 /// Media direction of the `Endpoint` for which `on_start` or `on_stop` Control
 /// API callback was received.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MediaDirection {
     /// `Endpoint` is a publisher.
     Publish,
@@ -159,13 +306,22 @@ pub enum MediaDirection {
     Play,
 }
 
+impl Into<proto::MediaDirection> for MediaDirection {
+    fn into(self) -> proto::MediaDirection {
+        match self {
+            Self::Publish => proto::MediaDirection::Publish,
+            Self::Play => proto::MediaDirection::Play,
+        }
+    }
+}
+
 /// Control API callback.
 ///
 /// Used for sending callbacks with [`CallbackClient::send`].
 ///
 /// [`CallbackClient::send`]:
 /// crate::api::control::callback::clients::CallbackClient::send
-#[derive(Debug, Message)]
+#[derive(Clone, Debug, Message)]
 #[rtype(result = "Result<(), CallbackClientError>")]
 pub struct CallbackRequest {
     /// FID (Full ID) of element with which event was occurred.
@@ -176,17 +332,79 @@ pub struct CallbackRequest {
 
     /// Time at which event occurred.
     at: DateTime<Utc>,
+
+    /// Sequence number of this [`CallbackRequest`] among all the
+    /// [`CallbackRequest`]s of its element.
+    ///
+    /// Assigned by [`CallbackService`] when [`CallbackRequest`] is emitted.
+    seq: u64,
+
+    /// Labels and metadata of the element with which event was occurred, so
+    /// receivers don't need to look them up.
+    meta: ElementMeta,
 }
 
 impl CallbackRequest {
     /// Returns [`CallbackRequest`] with provided fields and current time as
     /// `at`.
     pub fn new(element: StatefulFid, event: CallbackEvent) -> Self {
+        Self::new_at(element, event, Utc::now())
+    }
+
+    /// Returns [`CallbackRequest`] with provided fields.
+    pub fn new_at(
+        element: StatefulFid,
+        event: CallbackEvent,
+        at: DateTime<Utc>,
+    ) -> Self {
         Self {
             fid: element,
             event,
-            at: Utc::now(),
+            at,
+            seq: 0,
+            meta: ElementMeta::default(),
+        }
+    }
+
+    /// Attaches the provided labels and metadata of the element to this
+    /// [`CallbackRequest`].
+    #[must_use]
+    pub fn with_meta(mut self, meta: ElementMeta) -> Self {
+        self.meta = meta;
+        self
+    }
+}
+
+/// Serializes [`CallbackRequest`] with FID of its element in `element` field,
+/// [`CallbackEvent`] flattened, `at` in microseconds extended [RFC 3339]
+/// format, `seq` sequence number, and non-empty `labels` and `metadata` of
+/// its element.
+///
+/// [RFC 3339]: https://tools.ietf.org/html/rfc3339
+impl Serialize for CallbackRequest {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct SerializedRequest<'a> {
+            element: String,
+            #[serde(flatten)]
+            event: &'a CallbackEvent,
+            at: String,
+            seq: u64,
+            #[serde(flatten)]
+            meta: &'a ElementMeta,
+        }
+
+        SerializedRequest {
+            element: self.fid.to_string(),
+            event: &self.event,
+            at: self.at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            seq: self.seq,
+            meta: &self.meta,
         }
+        .serialize(serializer)
     }
 }
 
@@ -196,6 +414,9 @@ impl Into<proto::Request> for CallbackRequest {
             event: Some(self.event.into()),
             fid: self.fid.to_string(),
             at: self.at.to_rfc3339(),
+            seq: self.seq,
+            labels: self.meta.labels,
+            metadata: self.meta.metadata,
         }
     }
 }
@@ -1,50 +1,331 @@
 //! Service which stores and lazily creates [`CallbackRequest`] clients.
 
 use std::{
-    collections::hash_map::HashMap, fmt::Debug, marker::PhantomData, sync::Arc,
+    collections::{hash_map::HashMap, VecDeque},
+    fmt::Debug,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use actix::Arbiter;
-use tokio::sync::RwLock;
+use chrono::{DateTime, Utc};
+use rand::Rng as _;
+use tokio::{sync::RwLock, time};
 
 use crate::{
     api::control::{
         callback::{
+            circuit_breaker::CircuitBreaker,
             clients::{
                 CallbackClient, CallbackClientError, CallbackClientFactory,
             },
+            dead_letter::{self, DeadLetter, DeadLetterStore},
             url::CallbackUrl,
             CallbackEvent, CallbackRequest,
         },
         refs::StatefulFid,
+        ElementMeta,
     },
+    conf::control::Callbacks as CallbacksConf,
     log::prelude::*,
 };
 
-/// Service which stores and lazily creates [`CallbackRequest`] clients.
+/// [`CallbackRequest`] waiting for delivery in an [`ElementQueue`].
+#[derive(Debug)]
+struct QueuedRequest {
+    /// [`CallbackRequest`] to be delivered.
+    request: CallbackRequest,
+
+    /// [`CallbackUrl`] to deliver [`CallbackRequest`] to.
+    callback_url: CallbackUrl,
+}
+
+/// Queue of [`CallbackRequest`]s of a single element, which are delivered
+/// sequentially in order they were emitted.
 #[derive(Debug, Default)]
+struct ElementQueue {
+    /// Sequence number of the last emitted [`CallbackRequest`] of the element.
+    last_seq: u64,
+
+    /// [`CallbackRequest`]s waiting for delivery.
+    pending: VecDeque<QueuedRequest>,
+
+    /// Indicator whether [`CallbackRequest`]s of this [`ElementQueue`] are
+    /// being delivered at the moment.
+    is_delivering: bool,
+
+    /// Indicator whether the element of this [`ElementQueue`] has been
+    /// deleted, so this [`ElementQueue`] should be removed once drained.
+    is_removed: bool,
+}
+
+/// Service which stores and lazily creates [`CallbackRequest`] clients.
+///
+/// [`CallbackRequest`]s of the same element are delivered sequentially in
+/// order they were emitted, each one having a sequence number increased by
+/// `1`.
+///
+/// Failed [`CallbackRequest`]s are retried with exponential backoff, and
+/// those which couldn't be delivered at all are put into the
+/// [`DeadLetterStore`].
+#[derive(Debug)]
 pub struct CallbackService<B> {
     // TODO: Hashmap entries are not dropped anywhere. some kind of
     //       [expiring map](https://github.com/jhalterman/expiringmap)
     //       would fit here.
     clients: Arc<RwLock<HashMap<CallbackUrl, Arc<dyn CallbackClient>>>>,
+
+    /// [`ElementQueue`]s of all elements [`CallbackRequest`]s were emitted
+    /// for.
+    ///
+    /// Entries are kept after delivery to preserve sequence numbers, and are
+    /// removed with [`CallbackService::remove_queues`] once their elements
+    /// are deleted.
+    queues: Arc<Mutex<HashMap<StatefulFid, ElementQueue>>>,
+
+    /// [`CircuitBreaker`]s of all [`CallbackUrl`]s requests were sent to.
+    breakers: Arc<Mutex<HashMap<CallbackUrl, CircuitBreaker>>>,
+
+    /// Store of undeliverable [`CallbackRequest`]s.
+    dead_letters: Arc<dyn DeadLetterStore>,
+
+    /// Settings of [`CallbackRequest`]s delivery.
+    conf: Arc<CallbacksConf>,
+
     _factory: PhantomData<B>,
 }
 
+impl<B> CallbackService<B> {
+    /// Returns new [`CallbackService`] with provided delivery settings.
+    pub fn new(conf: &CallbacksConf) -> Self {
+        Self {
+            clients: Arc::default(),
+            queues: Arc::default(),
+            breakers: Arc::default(),
+            dead_letters: dead_letter::new_store(&conf.dead_letter),
+            conf: Arc::new(conf.clone()),
+            _factory: PhantomData,
+        }
+    }
+
+    /// Returns [`DeadLetterStore`] of undeliverable [`CallbackRequest`]s.
+    #[inline]
+    pub fn dead_letters(&self) -> &Arc<dyn DeadLetterStore> {
+        &self.dead_letters
+    }
+
+    /// Assigns the next sequence number of its element to provided
+    /// [`CallbackRequest`] and puts it into the [`ElementQueue`] of this
+    /// element.
+    ///
+    /// Returns `true` if delivery of this [`ElementQueue`] should be started.
+    fn enqueue(
+        &self,
+        mut request: CallbackRequest,
+        callback_url: CallbackUrl,
+    ) -> bool {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.entry(request.fid.clone()).or_default();
+        // Element has been created again before its previous `ElementQueue`
+        // was drained, so its sequence numbers are continued.
+        queue.is_removed = false;
+        queue.last_seq += 1;
+        request.seq = queue.last_seq;
+        queue.pending.push_back(QueuedRequest {
+            request,
+            callback_url,
+        });
+        !std::mem::replace(&mut queue.is_delivering, true)
+    }
+
+    /// Takes the next [`QueuedRequest`] from the [`ElementQueue`] of provided
+    /// element, marking it as not being delivered if it's empty.
+    ///
+    /// Empty [`ElementQueue`] of a deleted element is removed.
+    fn dequeue(&self, fid: &StatefulFid) -> Option<QueuedRequest> {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.get_mut(fid)?;
+        let next = queue.pending.pop_front();
+        if next.is_none() {
+            queue.is_delivering = false;
+            if queue.is_removed {
+                queues.remove(fid);
+            }
+        }
+        next
+    }
+
+    /// Removes [`ElementQueue`]s of the deleted element with provided
+    /// [`StatefulFid`] and of all its children.
+    ///
+    /// [`ElementQueue`]s still delivering [`CallbackRequest`]s are removed
+    /// once they're drained, so already emitted [`CallbackRequest`]s are not
+    /// lost. Sequence numbers of an element created again with the same
+    /// [`StatefulFid`] start from `1`, or continue from the last one if its
+    /// previous [`ElementQueue`] hasn't been drained yet.
+    pub fn remove_queues(&self, fid: &StatefulFid) {
+        self.queues.lock().unwrap().retain(|queue_fid, queue| {
+            if !queue_fid.is_within(fid) {
+                return true;
+            }
+            queue.is_removed = true;
+            queue.is_delivering
+        });
+    }
+
+    /// Returns sequence number of the last [`CallbackRequest`] emitted for
+    /// the element with provided [`StatefulFid`].
+    #[cfg(test)]
+    pub fn last_seq(&self, fid: &StatefulFid) -> Option<u64> {
+        self.queues
+            .lock()
+            .unwrap()
+            .get(fid)
+            .map(|queue| queue.last_seq)
+    }
+
+    /// Returns delay before provided retry `attempt` (starting from `1`) with
+    /// jitter applied.
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay = self.conf.backoff(attempt);
+        let jitter = self.conf.jitter.min(1.0);
+        if jitter > 0.0 {
+            delay.mul_f64(1.0 + rand::thread_rng().gen_range(-jitter, jitter))
+        } else {
+            delay
+        }
+    }
+
+    /// Indicates whether [`CircuitBreaker`] of provided [`CallbackUrl`]
+    /// allows to send a request.
+    fn is_allowed(&self, callback_url: &CallbackUrl) -> bool {
+        self.breakers
+            .lock()
+            .unwrap()
+            .entry(callback_url.clone())
+            .or_insert_with(|| CircuitBreaker::new(&self.conf.circuit_breaker))
+            .allow()
+    }
+
+    /// Records result of a request to provided [`CallbackUrl`] in its
+    /// [`CircuitBreaker`].
+    fn record_result(&self, callback_url: &CallbackUrl, is_ok: bool) {
+        if let Some(breaker) =
+            self.breakers.lock().unwrap().get_mut(callback_url)
+        {
+            if is_ok {
+                breaker.on_success();
+            } else {
+                breaker.on_failure();
+            }
+        }
+    }
+}
+
+impl<B> Default for CallbackService<B> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(&CallbacksConf::default())
+    }
+}
+
 impl<B> Clone for CallbackService<B> {
     fn clone(&self) -> Self {
         Self {
             clients: self.clients.clone(),
+            queues: self.queues.clone(),
+            breakers: self.breakers.clone(),
+            dead_letters: self.dead_letters.clone(),
+            conf: self.conf.clone(),
             _factory: PhantomData,
         }
     }
 }
 
 impl<B: CallbackClientFactory + 'static> CallbackService<B> {
+    /// Sends provided [`CallbackRequest`] once, if [`CircuitBreaker`] of
+    /// provided [`CallbackUrl`] allows it.
     async fn inner_send(
         &self,
         request: CallbackRequest,
         callback_url: CallbackUrl,
+    ) -> Result<(), CallbackClientError> {
+        if !self.is_allowed(&callback_url) {
+            return Err(CallbackClientError::CircuitBreakerOpen);
+        }
+        let res = self.send_to_client(request, callback_url.clone()).await;
+        self.record_result(&callback_url, res.is_ok());
+        res
+    }
+
+    /// Sends provided [`CallbackRequest`] retrying it with exponential
+    /// backoff, and puts it into the [`DeadLetterStore`] if all attempts
+    /// fail.
+    async fn send_with_retries(
+        &self,
+        request: CallbackRequest,
+        callback_url: CallbackUrl,
+    ) {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let err = match self
+                .inner_send(request.clone(), callback_url.clone())
+                .await
+            {
+                Ok(_) => return,
+                Err(e) => e,
+            };
+
+            if attempt > self.conf.max_retries {
+                error!(
+                    "Failed to send callback to [{}] after {} attempts \
+                     because {}. Putting it into dead-letter store.",
+                    callback_url, attempt, err,
+                );
+                let letter = DeadLetter::new(
+                    &callback_url,
+                    &request,
+                    attempt,
+                    err.to_string(),
+                );
+                let res = match letter {
+                    Ok(letter) => self.dead_letters.push(letter).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = res {
+                    error!(
+                        "Failed to put callback [{:?}] into dead-letter store \
+                         because {}.",
+                        request, e,
+                    );
+                }
+                return;
+            }
+
+            let delay = self.retry_delay(attempt);
+            warn!(
+                "Failed to send callback to [{}] (attempt {}/{}) because {}. \
+                 Retrying in {:?}.",
+                callback_url,
+                attempt,
+                self.conf.max_retries + 1,
+                err,
+                delay,
+            );
+            time::delay_for(delay).await;
+        }
+    }
+
+    /// Sends provided [`CallbackRequest`] with a [`CallbackClient`] of
+    /// provided [`CallbackUrl`].
+    ///
+    /// Will use existing [`CallbackClient`] or create new.
+    async fn send_to_client(
+        &self,
+        request: CallbackRequest,
+        callback_url: CallbackUrl,
     ) -> Result<(), CallbackClientError> {
         info!(
             "Sending CallbackRequest [{:?}] to [{}]",
@@ -78,41 +359,55 @@ impl<B: CallbackClientFactory + 'static> CallbackService<B> {
         Ok(())
     }
 
-    /// Asynchronously sends [`CallbackEvent`] for provided [`StatefulFid`] to
-    /// [`CallbackClient`] and waits for a response.
+    /// Spawns delivery of all [`QueuedRequest`]s from the [`ElementQueue`]
+    /// of provided element, one by one.
+    fn spawn_delivery(&self, fid: StatefulFid) {
+        let this = self.clone();
+        Arbiter::spawn(async move {
+            while let Some(queued) = this.dequeue(&fid) {
+                this.send_with_retries(queued.request, queued.callback_url)
+                    .await;
+            }
+        });
+    }
+
+    /// Emits [`CallbackEvent`] for provided [`StatefulFid`] with the provided
+    /// [`ElementMeta`] into its [`ElementQueue`] for asynchronous delivery to
+    /// [`CallbackClient`], retrying failed requests and putting undeliverable
+    /// ones into the [`DeadLetterStore`].
     ///
     /// Will use existing [`CallbackClient`] or create new.
-    ///
-    /// ## Errors
-    ///
-    /// With [`CallbackClientError`] if any errors happen during client creation
-    /// or request execution.
-    pub async fn send<T: Into<CallbackEvent> + 'static>(
+    pub fn do_send<T: Into<CallbackEvent>>(
         &self,
         callback_url: CallbackUrl,
         fid: StatefulFid,
+        meta: ElementMeta,
         event: T,
-    ) -> Result<(), CallbackClientError> {
-        self.inner_send(CallbackRequest::new(fid, event.into()), callback_url)
-            .await
+    ) {
+        self.do_send_at(callback_url, fid, meta, event, Utc::now());
     }
 
-    /// Asynchronously sends [`CallbackEvent`] for provided [`StatefulFid`] to
-    /// [`CallbackClient`] ignoring any potential errors.
+    /// Emits [`CallbackEvent`] which occurred at the provided time for
+    /// provided [`StatefulFid`] with the provided [`ElementMeta`] into its
+    /// [`ElementQueue`] for asynchronous delivery to [`CallbackClient`],
+    /// retrying failed requests and putting undeliverable ones into the
+    /// [`DeadLetterStore`].
     ///
     /// Will use existing [`CallbackClient`] or create new.
-    pub fn do_send<T: Into<CallbackEvent> + 'static>(
+    pub fn do_send_at<T: Into<CallbackEvent>>(
         &self,
         callback_url: CallbackUrl,
         fid: StatefulFid,
+        meta: ElementMeta,
         event: T,
+        at: DateTime<Utc>,
     ) {
-        let this = self.clone();
-        Arbiter::spawn(async move {
-            if let Err(e) = this.send(callback_url, fid, event).await {
-                error!("Failed to send callback because {:?}.", e);
-            }
-        })
+        let request =
+            CallbackRequest::new_at(fid, event.into(), at).with_meta(meta);
+        let fid = request.fid.clone();
+        if self.enqueue(request, callback_url) {
+            self.spawn_delivery(fid);
+        }
     }
 }
 
@@ -124,9 +419,12 @@ mod tests {
     use serial_test::serial;
     use tokio::time;
 
-    use crate::api::control::callback::{
-        clients::{MockCallbackClient, MockCallbackClientFactory},
-        OnJoinEvent,
+    use crate::{
+        api::control::callback::{
+            clients::{MockCallbackClient, MockCallbackClientFactory},
+            OnJoinEvent, OnLeaveEvent, OnLeaveReason,
+        },
+        conf::control::{CircuitBreaker as CircuitBreakerConf, DeadLetter},
     };
 
     use super::*;
@@ -183,4 +481,280 @@ mod tests {
             .collect();
         future::join_all(tasks).await;
     }
+
+    /// Returns [`MockCallbackClient`] which responds with provided results in
+    /// order.
+    fn client_mock(
+        mut results: Vec<Result<(), CallbackClientError>>,
+    ) -> Arc<dyn CallbackClient> {
+        let mut client_mock = MockCallbackClient::new();
+        client_mock
+            .expect_send()
+            .times(results.len())
+            .returning(move |_| future::ready(results.remove(0)).boxed_local());
+        Arc::new(client_mock)
+    }
+
+    /// Returns [`CallbacksConf`] with fast retries and a dead-letter file at
+    /// provided path.
+    fn fast_retries_conf(
+        max_retries: u32,
+        dead_letter_file: &str,
+    ) -> CallbacksConf {
+        CallbacksConf {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            jitter: 0.0,
+            dead_letter: DeadLetter {
+                file: std::env::temp_dir()
+                    .join(dead_letter_file)
+                    .to_string_lossy()
+                    .into_owned()
+                    .into(),
+                ..DeadLetter::default()
+            },
+            ..CallbacksConf::default()
+        }
+    }
+
+    /// Tests that failed [`CallbackRequest`] is retried until it succeeds.
+    #[actix_rt::test]
+    #[serial]
+    async fn retries_failed_request() {
+        let client = client_mock(vec![
+            Err(CallbackClientError::Http("foo".into())),
+            Err(CallbackClientError::Http("foo".into())),
+            Ok(()),
+        ]);
+        let client_builder_ctx = MockCallbackClientFactory::build_context();
+        client_builder_ctx
+            .expect()
+            .times(1)
+            .return_once(move |_| future::ok(client).boxed_local());
+
+        let callback_service =
+            CallbackService::<MockCallbackClientFactory>::new(
+                &fast_retries_conf(5, "medea_retries_failed_request.jsonl"),
+            );
+        callback_service
+            .send_with_retries(callback_request(), callback_url())
+            .await;
+
+        assert!(callback_service
+            .dead_letters()
+            .list()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Tests that [`CallbackRequest`] is put into the [`DeadLetterStore`]
+    /// once all retries fail.
+    #[actix_rt::test]
+    #[serial]
+    async fn puts_undeliverable_request_into_dead_letter_store() {
+        let client = client_mock(
+            (0..3)
+                .map(|_| Err(CallbackClientError::Http("foo".into())))
+                .collect(),
+        );
+        let client_builder_ctx = MockCallbackClientFactory::build_context();
+        client_builder_ctx
+            .expect()
+            .times(1)
+            .return_once(move |_| future::ok(client).boxed_local());
+
+        let conf = fast_retries_conf(2, "medea_undeliverable_request.jsonl");
+        let _ = std::fs::remove_file(conf.dead_letter.file.as_ref());
+        let callback_service =
+            CallbackService::<MockCallbackClientFactory>::new(&conf);
+        callback_service
+            .send_with_retries(callback_request(), callback_url())
+            .await;
+
+        let letters = callback_service.dead_letters().list().await.unwrap();
+        std::fs::remove_file(conf.dead_letter.file.as_ref()).unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].attempts, 3);
+        assert_eq!(letters[0].url, callback_url().to_string());
+        assert_eq!(letters[0].request["element"], "foo");
+    }
+
+    /// Tests that open [`CircuitBreaker`] rejects [`CallbackRequest`]s
+    /// without sending them.
+    #[actix_rt::test]
+    #[serial]
+    async fn circuit_breaker_rejects_requests() {
+        let client =
+            client_mock(vec![Err(CallbackClientError::Http("foo".into()))]);
+        let client_builder_ctx = MockCallbackClientFactory::build_context();
+        client_builder_ctx
+            .expect()
+            .times(1)
+            .return_once(move |_| future::ok(client).boxed_local());
+
+        let callback_service =
+            CallbackService::<MockCallbackClientFactory>::new(&CallbacksConf {
+                circuit_breaker: CircuitBreakerConf {
+                    failure_threshold: 1,
+                    reset_timeout: Duration::from_secs(60),
+                },
+                ..CallbacksConf::default()
+            });
+
+        assert!(matches!(
+            callback_service
+                .inner_send(callback_request(), callback_url())
+                .await,
+            Err(CallbackClientError::Http(_)),
+        ));
+        assert!(matches!(
+            callback_service
+                .inner_send(callback_request(), callback_url())
+                .await,
+            Err(CallbackClientError::CircuitBreakerOpen),
+        ));
+    }
+
+    /// Tests that [`ElementQueue`]s of deleted elements are removed once
+    /// drained.
+    #[actix_rt::test]
+    #[serial]
+    async fn removes_queues_of_deleted_elements() {
+        let client = client_mock(vec![Ok(()), Ok(()), Ok(())]);
+        let client_builder_ctx = MockCallbackClientFactory::build_context();
+        client_builder_ctx
+            .expect()
+            .times(1)
+            .return_once(move |_| future::ok(client).boxed_local());
+
+        let callback_service =
+            CallbackService::<MockCallbackClientFactory>::default();
+        let fid = |s: &str| StatefulFid::try_from(s.to_string()).unwrap();
+        for element in &["room/member", "room/member/publish", "room/other"] {
+            callback_service.do_send(
+                callback_url(),
+                fid(element),
+                ElementMeta::default(),
+                OnJoinEvent,
+            );
+        }
+        callback_service.remove_queues(&fid("room/member"));
+        assert_eq!(callback_service.queues.lock().unwrap().len(), 3);
+
+        time::delay_for(Duration::from_millis(100)).await;
+        let queues = callback_service.queues.lock().unwrap();
+        assert_eq!(queues.len(), 1);
+        assert!(queues.contains_key(&fid("room/other")));
+    }
+
+    /// Tests that [`CallbackRequest`]s of the same element are delivered in
+    /// order they were emitted with increasing sequence numbers, even if
+    /// the first one is delivered slower.
+    #[actix_rt::test]
+    #[serial]
+    async fn preserves_order_of_element_requests() {
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+
+        let mut client_mock = MockCallbackClient::new();
+        let delivered_clone = Arc::clone(&delivered);
+        client_mock.expect_send().times(3).returning(move |req| {
+            let delivered = Arc::clone(&delivered_clone);
+            async move {
+                let delay = if req.seq == 1 { 50 } else { 0 };
+                time::delay_for(Duration::from_millis(delay)).await;
+                delivered
+                    .lock()
+                    .unwrap()
+                    .push((req.fid.to_string(), req.seq));
+                Ok(())
+            }
+            .boxed_local()
+        });
+        let client_builder_ctx = MockCallbackClientFactory::build_context();
+        client_builder_ctx.expect().times(1).return_once(move |_| {
+            future::ok(Arc::new(client_mock) as Arc<dyn CallbackClient>)
+                .boxed_local()
+        });
+
+        let callback_service =
+            CallbackService::<MockCallbackClientFactory>::default();
+        let fid = StatefulFid::try_from("foo".to_string()).unwrap();
+        let meta = ElementMeta::default;
+        callback_service.do_send(
+            callback_url(),
+            fid.clone(),
+            meta(),
+            OnJoinEvent,
+        );
+        callback_service.do_send(
+            callback_url(),
+            fid.clone(),
+            meta(),
+            OnLeaveEvent::new(OnLeaveReason::Disconnected),
+        );
+        callback_service.do_send(callback_url(), fid, meta(), OnJoinEvent);
+        time::delay_for(Duration::from_millis(200)).await;
+
+        assert_eq!(
+            *delivered.lock().unwrap(),
+            vec![
+                ("foo".to_string(), 1),
+                ("foo".to_string(), 2),
+                ("foo".to_string(), 3),
+            ],
+        );
+    }
+
+    /// Tests that sequence numbers of an element created again while
+    /// [`CallbackRequest`]s of its previous incarnation are still being
+    /// delivered are continued, and its [`ElementQueue`] is kept.
+    #[actix_rt::test]
+    #[serial]
+    async fn continues_seq_of_element_recreated_while_delivering() {
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+
+        let mut client_mock = MockCallbackClient::new();
+        let delivered_clone = Arc::clone(&delivered);
+        client_mock.expect_send().times(3).returning(move |req| {
+            let delivered = Arc::clone(&delivered_clone);
+            async move {
+                let delay = if req.seq == 1 { 50 } else { 0 };
+                time::delay_for(Duration::from_millis(delay)).await;
+                delivered.lock().unwrap().push(req.seq);
+                Ok(())
+            }
+            .boxed_local()
+        });
+        let client_builder_ctx = MockCallbackClientFactory::build_context();
+        client_builder_ctx.expect().times(1).return_once(move |_| {
+            future::ok(Arc::new(client_mock) as Arc<dyn CallbackClient>)
+                .boxed_local()
+        });
+
+        let callback_service =
+            CallbackService::<MockCallbackClientFactory>::default();
+        let fid = StatefulFid::try_from("room/member".to_string()).unwrap();
+        let meta = ElementMeta::default;
+        callback_service.do_send(
+            callback_url(),
+            fid.clone(),
+            meta(),
+            OnJoinEvent,
+        );
+        callback_service.remove_queues(&fid);
+        callback_service.do_send(
+            callback_url(),
+            fid.clone(),
+            meta(),
+            OnJoinEvent,
+        );
+        time::delay_for(Duration::from_millis(200)).await;
+        assert_eq!(callback_service.last_seq(&fid), Some(2));
+
+        callback_service.do_send(callback_url(), fid, meta(), OnJoinEvent);
+        time::delay_for(Duration::from_millis(100)).await;
+        assert_eq!(*delivered.lock().unwrap(), vec![1, 2, 3]);
+    }
 }
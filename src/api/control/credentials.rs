@@ -0,0 +1,366 @@
+//! Credentials of [Control API]'s `Member` element.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{
+    collections::hash_map::RandomState,
+    convert::TryFrom,
+    hash::{BuildHasher as _, Hash as _, Hasher as _},
+};
+
+use actix_web::web;
+use argon2::{Config, Variant};
+use derive_more::{Display, From, Into};
+use failure::Fail;
+use medea_client_api_proto::Credential;
+use medea_control_api_proto::grpc::api as proto;
+use rand::{distributions::Alphanumeric, Rng as _};
+use serde::{Deserialize, Serialize};
+
+/// Length of generated [`Credential`]s.
+const CREDENTIALS_LEN: usize = 32;
+
+/// Length of salt used for hashing [`Credential`]s.
+const SALT_LEN: usize = 16;
+
+/// Prefixes of [PHC string]s of the supported [Argon2] variants.
+///
+/// [Argon2]: https://en.wikipedia.org/wiki/Argon2
+/// [PHC string]: https://tinyurl.com/y5rsqkgn
+const HASH_PREFIXES: [&str; 3] = ["$argon2id$", "$argon2i$", "$argon2d$"];
+
+/// Credentials of `Member` provided in its spec.
+#[derive(Clone, Debug, Deserialize, From, Serialize)]
+#[serde(untagged)]
+pub enum Credentials {
+    /// Plain [`Credential`], which is hashed once `Member` is created.
+    Plain(Credential),
+
+    /// Already hashed [`Credential`].
+    Hash {
+        /// [`CredentialHash`] of `Member`'s [`Credential`].
+        hash: CredentialHash,
+    },
+
+    /// Plain [`Credential`] hashed in advance with
+    /// [`Credentials::into_hashed()`].
+    ///
+    /// Serialized as [`Credentials::Hash`].
+    #[serde(skip_deserializing)]
+    Hashed {
+        /// Plain [`Credential`] of `Member`.
+        #[serde(skip_serializing)]
+        plain: Credential,
+
+        /// [`CredentialHash`] of the plain [`Credential`].
+        hash: CredentialHash,
+    },
+}
+
+impl Credentials {
+    /// Generates random alphanumeric [`Credentials::Plain`] with
+    /// [`CREDENTIALS_LEN`] length.
+    ///
+    /// This credentials are generated if no credentials are provided in
+    /// dynamic [Control API] spec of `Member`.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[must_use]
+    pub fn generate() -> Self {
+        Self::Plain(
+            rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(CREDENTIALS_LEN)
+                .collect::<String>()
+                .into(),
+        )
+    }
+
+    /// Returns plain [`Credential`], if it's known.
+    #[must_use]
+    pub fn plain(&self) -> Option<&Credential> {
+        match self {
+            Self::Plain(credential)
+            | Self::Hashed {
+                plain: credential, ..
+            } => Some(credential),
+            Self::Hash { .. } => None,
+        }
+    }
+
+    /// Returns [`CredentialHash`] of these [`Credentials`], hashing the plain
+    /// [`Credential`] if required.
+    ///
+    /// Note, that hashing is intentionally slow, so [`Credentials`] should be
+    /// hashed in advance with [`Credentials::into_hashed()`] before passing
+    /// them to actors.
+    #[must_use]
+    pub fn hash(&self) -> CredentialHash {
+        match self {
+            Self::Plain(credential) => CredentialHash::new(credential),
+            Self::Hash { hash } | Self::Hashed { hash, .. } => hash.clone(),
+        }
+    }
+
+    /// Hashes plain [`Credential`] of these [`Credentials`] in place, keeping
+    /// it known.
+    ///
+    /// Note, that hashing is intentionally slow.
+    pub fn hash_in_place(&mut self) {
+        if let Self::Plain(plain) = self {
+            let hash = CredentialHash::new(plain);
+            let plain = plain.clone();
+            *self = Self::Hashed { plain, hash };
+        }
+    }
+
+    /// Hashes plain [`Credential`] of these [`Credentials`] on the blocking
+    /// thread pool, so [`Credentials::hash()`] doesn't block afterwards.
+    pub async fn into_hashed(mut self) -> Self {
+        if let Self::Plain(_) = self {
+            self = blocking(move || {
+                self.hash_in_place();
+                self
+            })
+            .await;
+        }
+        self
+    }
+}
+
+impl TryFrom<proto::member::Credentials> for Credentials {
+    type Error = CredentialHashParseError;
+
+    fn try_from(from: proto::member::Credentials) -> Result<Self, Self::Error> {
+        use proto::member::Credentials as C;
+
+        Ok(match from {
+            C::Plain(plain) => Self::Plain(plain.into()),
+            C::Hash(hash) => Self::Hash {
+                hash: CredentialHash::try_from(hash)?,
+            },
+        })
+    }
+}
+
+impl TryFrom<proto::kick_request::Credentials> for Credentials {
+    type Error = CredentialHashParseError;
+
+    fn try_from(
+        from: proto::kick_request::Credentials,
+    ) -> Result<Self, Self::Error> {
+        use proto::kick_request::Credentials as C;
+
+        Ok(match from {
+            C::Plain(plain) => Self::Plain(plain.into()),
+            C::Hash(hash) => Self::Hash {
+                hash: CredentialHash::try_from(hash)?,
+            },
+        })
+    }
+}
+
+impl Into<proto::member::Credentials> for Credentials {
+    fn into(self) -> proto::member::Credentials {
+        use proto::member::Credentials as C;
+
+        match self {
+            Self::Plain(plain) => C::Plain(plain.0),
+            Self::Hash { hash } | Self::Hashed { hash, .. } => {
+                C::Hash(hash.into())
+            }
+        }
+    }
+}
+
+/// Error of parsing [`CredentialHash`].
+#[derive(Debug, Display, Fail)]
+#[display(fmt = "Credentials hash is not a PHC string of Argon2 hash")]
+pub struct CredentialHashParseError;
+
+/// [Argon2] hash of [`Credential`] encoded as [PHC string].
+///
+/// [Argon2]: https://en.wikipedia.org/wiki/Argon2
+/// [PHC string]: https://tinyurl.com/y5rsqkgn
+#[derive(
+    Clone, Debug, Deserialize, Display, Eq, Into, PartialEq, Serialize,
+)]
+#[serde(into = "String", try_from = "String")]
+pub struct CredentialHash(String);
+
+impl CredentialHash {
+    /// Hashes provided [`Credential`] with [Argon2id] and random salt.
+    ///
+    /// [Argon2id]: https://en.wikipedia.org/wiki/Argon2
+    #[must_use]
+    pub fn new(credential: &Credential) -> Self {
+        let salt: [u8; SALT_LEN] = rand::thread_rng().gen();
+        let config = Config {
+            variant: Variant::Argon2id,
+            ..Config::default()
+        };
+        Self(
+            argon2::hash_encoded(credential.0.as_bytes(), &salt, &config)
+                .expect("Default Argon2 config is always valid"),
+        )
+    }
+
+    /// Indicates whether provided [`Credential`] matches this
+    /// [`CredentialHash`].
+    ///
+    /// Hashes are compared in constant time.
+    #[must_use]
+    pub fn verify(&self, credential: &Credential) -> bool {
+        argon2::verify_encoded(&self.0, credential.0.as_bytes())
+            .unwrap_or(false)
+    }
+
+    /// Verifies provided [`Credential`] like [`CredentialHash::verify()`]
+    /// does, but on the blocking thread pool.
+    pub async fn verify_blocking(self, credential: Credential) -> bool {
+        blocking(move || self.verify(&credential)).await
+    }
+}
+
+impl TryFrom<String> for CredentialHash {
+    type Error = CredentialHashParseError;
+
+    fn try_from(hash: String) -> Result<Self, Self::Error> {
+        if HASH_PREFIXES.iter().any(|prefix| hash.starts_with(prefix)) {
+            Ok(Self(hash))
+        } else {
+            Err(CredentialHashParseError)
+        }
+    }
+}
+
+/// Fast keyed digest of the last [`Credential`] verified against some
+/// [`CredentialHash`], allowing to skip slow verification of the same
+/// [`Credential`] next time.
+///
+/// Digest is keyed with random keys, so it doesn't reveal the [`Credential`].
+#[derive(Debug, Default)]
+pub struct VerifiedCredential {
+    /// Random keys of the digest.
+    keys: RandomState,
+
+    /// Digest of the last verified [`Credential`].
+    digest: Option<u64>,
+}
+
+impl VerifiedCredential {
+    /// Remembers provided [`Credential`] as the verified one.
+    pub fn remember(&mut self, credential: &Credential) {
+        self.digest = Some(self.digest_of(credential));
+    }
+
+    /// Forgets the verified [`Credential`], if any.
+    pub fn forget(&mut self) {
+        self.digest = None;
+    }
+
+    /// Indicates whether provided [`Credential`] is the verified one.
+    #[must_use]
+    pub fn is(&self, credential: &Credential) -> bool {
+        self.digest == Some(self.digest_of(credential))
+    }
+
+    /// Calculates digest of provided [`Credential`].
+    fn digest_of(&self, credential: &Credential) -> u64 {
+        let mut hasher = self.keys.build_hasher();
+        credential.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Runs provided slow hashing operation on the blocking thread pool, so it
+/// doesn't block the current thread.
+///
+/// # Panics
+///
+/// If the provided operation panics.
+pub async fn blocking<F, T>(op: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    web::block(move || Ok::<_, ()>(op()))
+        .await
+        .expect("Hashing operation panicked")
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn verifies_hashed_credential() {
+        let hash = CredentialHash::new(&"qwerty".into());
+
+        assert!(!hash.to_string().contains("qwerty"));
+        assert!(hash.verify(&"qwerty".into()));
+        assert!(!hash.verify(&"qwertz".into()));
+        assert!(!hash.verify(&"".into()));
+    }
+
+    #[test]
+    fn salts_hashes() {
+        let credential = "qwerty".into();
+
+        assert_ne!(
+            CredentialHash::new(&credential),
+            CredentialHash::new(&credential),
+        );
+    }
+
+    #[test]
+    fn parses_pre_hashed_credentials() {
+        let hash = CredentialHash::new(&"qwerty".into()).to_string();
+        let credentials: Credentials =
+            serde_yaml::from_str(&format!("hash: \"{}\"", hash)).unwrap();
+
+        assert!(credentials.plain().is_none());
+        assert_eq!(credentials.hash().to_string(), hash);
+        assert!(credentials.hash().verify(&"qwerty".into()));
+    }
+
+    #[test]
+    fn parses_plain_credentials() {
+        let credentials: Credentials = serde_yaml::from_str("qwerty").unwrap();
+
+        assert_eq!(credentials.plain(), Some(&"qwerty".into()));
+        assert!(credentials.hash().verify(&"qwerty".into()));
+    }
+
+    #[actix_rt::test]
+    async fn hashes_plain_credentials_in_advance() {
+        let credentials =
+            Credentials::Plain("qwerty".into()).into_hashed().await;
+
+        assert!(matches!(credentials, Credentials::Hashed { .. }));
+        assert_eq!(credentials.plain(), Some(&"qwerty".into()));
+        assert!(credentials.hash().verify_blocking("qwerty".into()).await);
+        assert!(!serde_yaml::to_string(&credentials)
+            .unwrap()
+            .contains("qwerty"));
+    }
+
+    #[test]
+    fn remembers_verified_credential() {
+        let mut verified = VerifiedCredential::default();
+        assert!(!verified.is(&"qwerty".into()));
+
+        verified.remember(&"qwerty".into());
+        assert!(verified.is(&"qwerty".into()));
+        assert!(!verified.is(&"qwertz".into()));
+
+        verified.forget();
+        assert!(!verified.is(&"qwerty".into()));
+    }
+
+    #[test]
+    fn rejects_non_argon2_hashes() {
+        assert!(CredentialHash::try_from("qwerty".to_owned()).is_err());
+        assert!(serde_yaml::from_str::<Credentials>("hash: qwerty").is_err());
+    }
+}
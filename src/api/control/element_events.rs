@@ -0,0 +1,273 @@
+//! Lifecycle events of [Control API] elements, which can be watched.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use futures::channel::mpsc;
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::{api::control::refs::StatefulFid, log::prelude::*};
+
+/// Maximum number of [`ElementEvent`]s buffered for a single external
+/// watcher. Watchers falling behind further are dropped.
+const WATCHER_BUFFER: usize = 256;
+
+/// Kind of [`ElementEvent`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ElementEventKind {
+    /// Element has been created.
+    Created,
+
+    /// Element has been deleted.
+    Deleted,
+
+    /// `Member` has established its RPC connection.
+    MemberConnected,
+
+    /// `Member`'s RPC connection has been closed.
+    MemberDisconnected,
+}
+
+impl Into<proto::element_event::Kind> for ElementEventKind {
+    fn into(self) -> proto::element_event::Kind {
+        use proto::element_event::Kind;
+
+        match self {
+            Self::Created => Kind::Created,
+            Self::Deleted => Kind::Deleted,
+            Self::MemberConnected => Kind::MemberConnected,
+            Self::MemberDisconnected => Kind::MemberDisconnected,
+        }
+    }
+}
+
+/// Lifecycle event of a [Control API] element.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug)]
+pub struct ElementEvent {
+    /// FID (Full ID) of element with which event occurred.
+    pub fid: StatefulFid,
+
+    /// Kind of this [`ElementEvent`].
+    pub kind: ElementEventKind,
+
+    /// Time at which event occurred.
+    pub at: DateTime<Utc>,
+}
+
+impl Into<proto::ElementEvent> for ElementEvent {
+    fn into(self) -> proto::ElementEvent {
+        let kind: proto::element_event::Kind = self.kind.into();
+        proto::ElementEvent {
+            fid: self.fid.to_string(),
+            kind: kind as i32,
+            at: self.at.to_rfc3339(),
+        }
+    }
+}
+
+/// Subscriber of [`ElementEvent`]s.
+#[derive(Debug)]
+struct Watcher {
+    /// FID (Full ID) of element, whose [`ElementEvent`]s and [`ElementEvent`]s
+    /// of its nested elements this [`Watcher`] is interested in.
+    ///
+    /// [`None`] means all [`ElementEvent`]s.
+    scope: Option<String>,
+
+    /// Sender of [`ElementEvent`]s to this [`Watcher`].
+    tx: WatcherTx,
+}
+
+/// Sender of [`ElementEvent`]s to a [`Watcher`].
+#[derive(Debug)]
+enum WatcherTx {
+    /// Sender to an external [`Watcher`], which is dropped once its buffer
+    /// is full.
+    Bounded(mpsc::Sender<ElementEvent>),
+
+    /// Sender to an internal [`Watcher`], which never misses
+    /// [`ElementEvent`]s.
+    Unbounded(mpsc::UnboundedSender<ElementEvent>),
+}
+
+impl Watcher {
+    /// Indicates whether [`ElementEvent`] of provided element is in scope of
+    /// this [`Watcher`].
+    fn is_interested_in(&self, fid: &str) -> bool {
+        self.scope.as_ref().map_or(true, |scope| {
+            fid.strip_prefix(scope.as_str())
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Sends provided [`ElementEvent`] to this [`Watcher`].
+    ///
+    /// Returns `false` if this [`Watcher`] should be removed, because it
+    /// stopped listening or fell behind.
+    fn send(&mut self, event: ElementEvent) -> bool {
+        match &mut self.tx {
+            WatcherTx::Bounded(tx) => match tx.try_send(event) {
+                Ok(()) => true,
+                Err(e) => {
+                    if e.is_full() {
+                        warn!(
+                            "Dropping watcher of {:?} elements events, \
+                             because it falls behind",
+                            self.scope,
+                        );
+                    }
+                    false
+                }
+            },
+            WatcherTx::Unbounded(tx) => tx.unbounded_send(event).is_ok(),
+        }
+    }
+}
+
+/// Hub which [`ElementEvent`]s are published to and which delivers them to
+/// all the interested watchers.
+#[derive(Clone, Debug, Default)]
+pub struct ElementEvents(Arc<Mutex<Vec<Watcher>>>);
+
+impl ElementEvents {
+    /// Publishes [`ElementEvent`] of provided kind, occurred with provided
+    /// element at current time.
+    ///
+    /// Watchers which stopped listening or fell behind are removed.
+    pub fn publish<F: Into<StatefulFid>>(
+        &self,
+        fid: F,
+        kind: ElementEventKind,
+    ) {
+        let event = ElementEvent {
+            fid: fid.into(),
+            kind,
+            at: Utc::now(),
+        };
+        let fid = event.fid.to_string();
+        let mut watchers = self.0.lock().unwrap();
+        *watchers = watchers
+            .drain(..)
+            .filter_map(|mut watcher| {
+                if !watcher.is_interested_in(&fid)
+                    || watcher.send(event.clone())
+                {
+                    Some(watcher)
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    /// Returns [`mpsc::Receiver`] of [`ElementEvent`]s occurred with provided
+    /// element and all its nested elements.
+    ///
+    /// If no element is provided, then all [`ElementEvent`]s are received.
+    ///
+    /// [`mpsc::Receiver`] is ended if it falls behind for more than
+    /// [`WATCHER_BUFFER`] [`ElementEvent`]s.
+    pub fn watch(
+        &self,
+        scope: Option<StatefulFid>,
+    ) -> mpsc::Receiver<ElementEvent> {
+        let (tx, rx) = mpsc::channel(WATCHER_BUFFER);
+        self.add_watcher(scope, WatcherTx::Bounded(tx));
+        rx
+    }
+
+    /// Returns [`mpsc::UnboundedReceiver`] of all [`ElementEvent`]s, which is
+    /// never ended for falling behind.
+    ///
+    /// Intended for internal watchers only, which must not miss any
+    /// [`ElementEvent`].
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<ElementEvent> {
+        let (tx, rx) = mpsc::unbounded();
+        self.add_watcher(None, WatcherTx::Unbounded(tx));
+        rx
+    }
+
+    /// Adds new [`Watcher`] of the provided scope.
+    fn add_watcher(&self, scope: Option<StatefulFid>, tx: WatcherTx) {
+        self.0.lock().unwrap().push(Watcher {
+            scope: scope.map(|fid| fid.to_string()),
+            tx,
+        });
+    }
+
+    /// Removes all the watchers, ending their receivers.
+    pub fn close_all(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::convert::TryFrom as _;
+
+    use futures::StreamExt as _;
+
+    use super::*;
+
+    fn fid(fid: &str) -> StatefulFid {
+        StatefulFid::try_from(fid.to_string()).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn delivers_events_in_scope() {
+        let events = ElementEvents::default();
+        let mut room = events.watch(Some(fid("room")));
+        let mut all = events.watch(None);
+
+        events.publish(fid("room"), ElementEventKind::Created);
+        events.publish(fid("room2/member"), ElementEventKind::Created);
+        events.publish(fid("room/member"), ElementEventKind::MemberConnected);
+        drop(events);
+
+        let room: Vec<_> = room
+            .by_ref()
+            .map(|e| (e.fid.to_string(), e.kind))
+            .collect()
+            .await;
+        assert_eq!(
+            room,
+            vec![
+                ("room".to_string(), ElementEventKind::Created),
+                ("room/member".to_string(), ElementEventKind::MemberConnected),
+            ]
+        );
+        assert_eq!(all.by_ref().collect::<Vec<_>>().await.len(), 3);
+    }
+
+    #[test]
+    fn removes_closed_watchers() {
+        let events = ElementEvents::default();
+        drop(events.watch(None));
+        let _watcher = events.watch(Some(fid("room")));
+
+        events.publish(fid("room"), ElementEventKind::Deleted);
+
+        assert_eq!(events.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn removes_lagging_watchers() {
+        let events = ElementEvents::default();
+        let _watcher = events.watch(None);
+        let _subscriber = events.subscribe();
+
+        for _ in 0..=WATCHER_BUFFER {
+            events.publish(fid("room"), ElementEventKind::Created);
+        }
+        assert_eq!(events.0.lock().unwrap().len(), 2);
+
+        events.publish(fid("room"), ElementEventKind::Created);
+        let watchers = events.0.lock().unwrap();
+        assert_eq!(watchers.len(), 1);
+        assert!(matches!(watchers[0].tx, WatcherTx::Unbounded(_)));
+    }
+}
@@ -9,7 +9,7 @@ use std::convert::TryFrom;
 
 use derive_more::{Display, From, Into};
 use medea_control_api_proto::grpc::api as proto;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{member::MemberElement, TryFromProtobufError};
 
@@ -20,7 +20,16 @@ pub use webrtc_publish_endpoint::{WebRtcPublishEndpoint, WebRtcPublishId};
 
 /// ID of `Endpoint`.
 #[derive(
-    Clone, Debug, Deserialize, Display, Eq, From, Hash, Into, PartialEq,
+    Clone,
+    Debug,
+    Deserialize,
+    Display,
+    Eq,
+    From,
+    Hash,
+    Into,
+    PartialEq,
+    Serialize,
 )]
 pub struct Id(pub String);
 
@@ -66,6 +75,19 @@ impl Into<MemberElement> for EndpointSpec {
     }
 }
 
+impl From<MemberElement> for EndpointSpec {
+    fn from(from: MemberElement) -> Self {
+        match from {
+            MemberElement::WebRtcPublishEndpoint { spec } => {
+                Self::WebRtcPublish(spec)
+            }
+            MemberElement::WebRtcPlayEndpoint { spec } => {
+                Self::WebRtcPlay(spec)
+            }
+        }
+    }
+}
+
 impl TryFrom<(Id, proto::member::element::El)> for EndpointSpec {
     type Error = TryFromProtobufError;
 
@@ -80,7 +102,7 @@ impl TryFrom<(Id, proto::member::element::El)> for EndpointSpec {
                 Ok(Self::WebRtcPlay(play))
             }
             El::WebrtcPub(elem) => {
-                let publish = WebRtcPublishEndpoint::from(&elem);
+                let publish = WebRtcPublishEndpoint::try_from(&elem)?;
                 Ok(Self::WebRtcPublish(publish))
             }
         }
@@ -101,7 +123,7 @@ impl TryFrom<(Id, proto::create_request::El)> for EndpointSpec {
                 Ok(Self::WebRtcPlay(play))
             }
             El::WebrtcPub(elem) => {
-                let publish = WebRtcPublishEndpoint::from(&elem);
+                let publish = WebRtcPublishEndpoint::try_from(&elem)?;
                 Ok(Self::WebRtcPublish(publish))
             }
             El::Member(_) | El::Room(_) => {
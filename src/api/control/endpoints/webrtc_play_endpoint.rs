@@ -6,9 +6,13 @@ use std::convert::TryFrom;
 
 use derive_more::{Display, From, Into};
 use medea_control_api_proto::grpc::api as proto;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::api::control::{refs::SrcUri, TryFromProtobufError};
+use crate::api::control::{
+    callback::url::CallbackUrl, refs::SrcUri, ElementMeta, TryFromProtobufError,
+};
+
+use super::webrtc_publish_endpoint::parse_callback_url;
 
 /// ID of [`WebRtcPlayEndpoint`].
 #[derive(
@@ -17,7 +21,7 @@ use crate::api::control::{refs::SrcUri, TryFromProtobufError};
 pub struct WebRtcPlayId(String);
 
 /// Media element which is able to play media data for client via WebRTC.
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 pub struct WebRtcPlayEndpoint {
     /// Source URI in format `local://{room_id}/{member_id}/{endpoint_id}`.
     pub src: SrcUri,
@@ -25,6 +29,18 @@ pub struct WebRtcPlayEndpoint {
     /// Option to relay all media through a TURN server forcibly.
     #[serde(default)]
     pub force_relay: bool,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    #[serde(default)]
+    pub on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    #[serde(default)]
+    pub on_stop: Option<CallbackUrl>,
+
+    /// Labels and metadata of this [`WebRtcPlayEndpoint`].
+    #[serde(flatten)]
+    pub meta: ElementMeta,
 }
 
 impl TryFrom<&proto::WebRtcPlayEndpoint> for WebRtcPlayEndpoint {
@@ -36,6 +52,12 @@ impl TryFrom<&proto::WebRtcPlayEndpoint> for WebRtcPlayEndpoint {
         Ok(Self {
             src: SrcUri::try_from(value.src.clone())?,
             force_relay: value.force_relay,
+            on_start: parse_callback_url(&value.on_start)?,
+            on_stop: parse_callback_url(&value.on_stop)?,
+            meta: ElementMeta::new(
+                value.labels.clone(),
+                value.metadata.clone(),
+            ),
         })
     }
 }
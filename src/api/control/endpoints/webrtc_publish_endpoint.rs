@@ -2,12 +2,18 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+use std::convert::TryFrom;
+
 use derive_more::{Display, From, Into};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
 use medea_control_api_proto::grpc::api as proto;
 
+use crate::api::control::{
+    callback::url::CallbackUrl, ElementMeta, TryFromProtobufError,
+};
+
 /// ID of [`WebRtcPublishEndpoint`].
 #[derive(
     Clone, Debug, Deserialize, Display, Eq, Hash, PartialEq, From, Into,
@@ -15,7 +21,7 @@ use medea_control_api_proto::grpc::api as proto;
 pub struct WebRtcPublishId(String);
 
 /// Peer-to-peer mode of [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Deserialize, Debug)]
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize)]
 pub enum P2pMode {
     /// Always connect peer-to-peer.
     Always,
@@ -53,7 +59,7 @@ impl Into<proto::web_rtc_publish_endpoint::P2p> for P2pMode {
 
 /// Media element which is able to publish media data for another client via
 /// WebRTC.
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 pub struct WebRtcPublishEndpoint {
     /// Peer-to-peer mode of this [`WebRtcPublishEndpoint`].
     pub p2p: P2pMode,
@@ -69,11 +75,25 @@ pub struct WebRtcPublishEndpoint {
     /// Settings for the video media type of the [`WebRtcPublishEndpoint`].
     #[serde(default)]
     pub video_settings: VideoSettings,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    #[serde(default)]
+    pub on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    #[serde(default)]
+    pub on_stop: Option<CallbackUrl>,
+
+    /// Labels and metadata of this [`WebRtcPublishEndpoint`].
+    #[serde(flatten)]
+    pub meta: ElementMeta,
 }
 
 /// Publishing policy of the video or audio media type in the
 /// [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SmartDefault)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault,
+)]
 pub enum PublishPolicy {
     /// Specified media type __may__ be published.
     ///
@@ -134,7 +154,9 @@ impl From<PublishPolicy> for proto::web_rtc_publish_endpoint::PublishPolicy {
 }
 
 /// Settings for the audio media type of the [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
 pub struct AudioSettings {
     /// Publishing policy of the audio media type in the
     /// [`WebRtcPublishEndpoint`].
@@ -166,7 +188,9 @@ impl From<AudioSettings> for proto::web_rtc_publish_endpoint::AudioSettings {
 }
 
 /// Settings for the video media type of the [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
 pub struct VideoSettings {
     /// Publishing policy of the video media type in the
     /// [`WebRtcPublishEndpoint`].
@@ -197,9 +221,13 @@ impl From<VideoSettings> for proto::web_rtc_publish_endpoint::VideoSettings {
     }
 }
 
-impl From<&proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
-    fn from(value: &proto::WebRtcPublishEndpoint) -> Self {
-        Self {
+impl TryFrom<&proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
+    type Error = TryFromProtobufError;
+
+    fn try_from(
+        value: &proto::WebRtcPublishEndpoint,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             p2p: P2pMode::from(
                 proto::web_rtc_publish_endpoint::P2p::from_i32(value.p2p)
                     .unwrap_or_default(),
@@ -215,6 +243,58 @@ impl From<&proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
                 .map(VideoSettings::from)
                 .unwrap_or_default(),
             force_relay: value.force_relay,
+            on_start: parse_callback_url(&value.on_start)?,
+            on_stop: parse_callback_url(&value.on_stop)?,
+            meta: ElementMeta::new(
+                value.labels.clone(),
+                value.metadata.clone(),
+            ),
+        })
+    }
+}
+
+/// Patch of the [`WebRtcPublishEndpoint`] properties which can be updated
+/// without recreating it.
+///
+/// [`None`] properties are kept unchanged.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct WebRtcPublishEndpointPatch {
+    /// New option to relay all media through a TURN server forcibly.
+    #[serde(default)]
+    pub force_relay: Option<bool>,
+
+    /// New settings for the audio media type.
+    #[serde(default)]
+    pub audio_settings: Option<AudioSettings>,
+
+    /// New settings for the video media type.
+    #[serde(default)]
+    pub video_settings: Option<VideoSettings>,
+}
+
+impl From<&proto::WebRtcPublishEndpointPatch> for WebRtcPublishEndpointPatch {
+    fn from(from: &proto::WebRtcPublishEndpointPatch) -> Self {
+        Self {
+            force_relay: from.force_relay,
+            audio_settings: from.audio_settings.as_ref().map(Into::into),
+            video_settings: from.video_settings.as_ref().map(Into::into),
         }
     }
 }
+
+/// Parses optional [`CallbackUrl`] of `Endpoint` from its protobuf
+/// representation, where empty string means unset callback.
+///
+/// # Errors
+///
+/// With [`TryFromProtobufError::CallbackUrlParseErr`] if provided non-empty
+/// string is not a valid [`CallbackUrl`].
+pub(super) fn parse_callback_url(
+    url: &str,
+) -> Result<Option<CallbackUrl>, TryFromProtobufError> {
+    if url.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(CallbackUrl::try_from(url.to_owned())?))
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
     api::control::{
         callback::url::CallbackUrlParseError,
         grpc::server::GrpcControlApiError,
+        http::server::HttpControlApiError,
         refs::{
             fid::ParseFidError, local_uri::LocalUriParseError,
             src_uri::SrcParseError,
@@ -99,6 +100,12 @@ impl ErrorResponse {
             element_id: id,
         }
     }
+
+    /// Returns [`ErrorCode`] of this [`ErrorResponse`].
+    #[inline]
+    pub fn error_code(&self) -> &ErrorCode {
+        &self.error_code
+    }
 }
 
 impl Into<proto::Error> for ErrorResponse {
@@ -284,6 +291,67 @@ pub enum ErrorCode {
     #[display(fmt = "Encountered negative duration")]
     NegativeDuration = 1023,
 
+    /// Malformed request to HTTP REST Control API (unparsable body, unknown
+    /// query parameters, unsupported `Content-Type`, etc).
+    ///
+    /// Code: __1024__.
+    #[display(fmt = "Malformed request.")]
+    MalformedRequest = 1024,
+
+    /// Request to gRPC Control API doesn't provide valid authentication
+    /// credentials.
+    ///
+    /// Code: __1025__.
+    #[display(fmt = "Request is not authenticated.")]
+    Unauthenticated = 1025,
+
+    /// Provided credentials hash is not a [PHC string] of [Argon2] hash.
+    ///
+    /// Code: __1026__.
+    ///
+    /// [Argon2]: https://en.wikipedia.org/wiki/Argon2
+    /// [PHC string]: https://tinyurl.com/y5rsqkgn
+    #[display(fmt = "Invalid credentials hash.")]
+    InvalidCredentialsHash = 1026,
+
+    /// Static [Control API] spec can't be deserialized.
+    ///
+    /// Code: __1027__.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Malformed spec.")]
+    MalformedSpec = 1027,
+
+    /// Element doesn't have the version expected by request.
+    ///
+    /// Code: __1028__.
+    #[display(fmt = "Element version mismatch.")]
+    VersionMismatch = 1028,
+
+    /// Publish policy can't be updated in place from or to `Disabled`.
+    ///
+    /// Code: __1029__.
+    #[display(fmt = "Publish policy can't be updated from or to Disabled.")]
+    UnsupportedPublishPolicyUpdate = 1029,
+
+    /// `Member` is not connected to a media server.
+    ///
+    /// Code: __1030__.
+    #[display(fmt = "Member is not connected.")]
+    MemberNotConnected = 1030,
+
+    /// Payload of a custom event is not a valid JSON.
+    ///
+    /// Code: __1031__.
+    #[display(fmt = "Invalid custom event payload.")]
+    InvalidCustomEventPayload = 1031,
+
+    /// Description of kicking a `Member` is too long.
+    ///
+    /// Code: __1032__.
+    #[display(fmt = "Kick description is too long.")]
+    KickDescriptionIsTooLong = 1032,
+
     /// Unexpected server error.
     ///
     /// Use this [`ErrorCode`] only with [`ErrorResponse::unexpected`]
@@ -353,6 +421,9 @@ impl From<TryFromProtobufError> for ErrorResponse {
                 ),
                 Some(id),
             ),
+            E::InvalidCredentialsHash(id) => {
+                Self::new(ErrorCode::InvalidCredentialsHash, &id)
+            }
         }
     }
 }
@@ -423,6 +494,21 @@ impl From<RoomError> for ErrorResponse {
             E::EndpointAlreadyExists(id) => {
                 Self::new(ErrorCode::EndpointAlreadyExists, &id)
             }
+            E::VersionMismatch {
+                fid,
+                expected,
+                actual,
+            } => Self::with_explanation(
+                ErrorCode::VersionMismatch,
+                format!("Expected version {}, but was {}.", expected, actual),
+                Some(fid.to_string()),
+            ),
+            E::UnsupportedPublishPolicyUpdate(id) => {
+                Self::new(ErrorCode::UnsupportedPublishPolicyUpdate, &id)
+            }
+            E::MemberNotConnected(id) => {
+                Self::new(ErrorCode::MemberNotConnected, &id)
+            }
             E::WrongRoomId(_, _)
             | E::PeerNotFound(_)
             | E::CallbackClientError(_)
@@ -499,7 +585,8 @@ impl From<RoomServiceError> for ErrorResponse {
             ),
             E::RoomMailboxErr(_)
             | E::FailedToLoadStaticSpecs(_)
-            | E::TryFromElement(_) => Self::unexpected(&err),
+            | E::TryFromElement(_)
+            | E::SnapshotStore(_) => Self::unexpected(&err),
         }
     }
 }
@@ -516,3 +603,27 @@ impl From<GrpcControlApiError> for ErrorResponse {
         }
     }
 }
+
+impl From<HttpControlApiError> for ErrorResponse {
+    fn from(err: HttpControlApiError) -> Self {
+        use HttpControlApiError as E;
+
+        match err {
+            E::Fid(e) => e.into(),
+            E::TryFromProtobuf(e) => e.into(),
+            E::TryFromElement(e) => match e {
+                TryFromElementError::NotMember => {
+                    Self::without_id(ErrorCode::NotMemberInSpec)
+                }
+                TryFromElementError::NotRoom => {
+                    Self::without_id(ErrorCode::NotRoomInSpec)
+                }
+            },
+            E::MalformedRequest(text) => {
+                Self::with_explanation(ErrorCode::MalformedRequest, text, None)
+            }
+            E::RoomServiceError(e) => e.into(),
+            E::RoomServiceMailboxError(_) => Self::unexpected(&err),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Authentication of requests to [Control API] gRPC server.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use bytes::Bytes;
+use medea_control_api_proto::grpc::api as proto;
+use prost::Message as _;
+use subtle::ConstantTimeEq as _;
+use tonic::{Code, Interceptor, Request, Status};
+
+use crate::api::control::error_codes::{ErrorCode, ErrorResponse};
+
+/// Prefix of `authorization` metadata value carrying a bearer token.
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// Returns [`Interceptor`] which rejects requests not providing one of the
+/// provided `tokens` in `authorization` metadata as `Bearer <token>`.
+///
+/// If no `tokens` are provided, then all requests are allowed.
+pub fn interceptor(tokens: Vec<String>) -> Interceptor {
+    Interceptor::new(move |req: Request<()>| {
+        if is_authenticated(&req, &tokens) {
+            Ok(req)
+        } else {
+            Err(unauthenticated())
+        }
+    })
+}
+
+/// Indicates whether provided [`Request`] carries one of the provided
+/// `tokens`.
+fn is_authenticated<T>(req: &Request<T>, tokens: &[String]) -> bool {
+    is_authorized(
+        req.metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok()),
+        tokens,
+    )
+}
+
+/// Indicates whether provided value of `authorization` metadata (or HTTP
+/// header) is `Bearer <token>` with one of the provided `tokens`.
+///
+/// If no `tokens` are provided, then any value is allowed.
+///
+/// Tokens are compared in constant time, so they can't be guessed by
+/// measuring response time.
+pub fn is_authorized(authorization: Option<&str>, tokens: &[String]) -> bool {
+    if tokens.is_empty() {
+        return true;
+    }
+    let provided =
+        match authorization.and_then(|v| v.strip_prefix(BEARER_PREFIX)) {
+            Some(token) => token.trim(),
+            None => return false,
+        };
+    tokens.iter().fold(false, |matched, token| {
+        bool::from(token.as_bytes().ct_eq(provided.as_bytes())) | matched
+    })
+}
+
+/// Returns [`Status`] of an unauthenticated request, carrying
+/// [`ErrorCode::Unauthenticated`] encoded as [`proto::Error`] in its
+/// details.
+fn unauthenticated() -> Status {
+    let error: proto::Error =
+        ErrorResponse::without_id(ErrorCode::Unauthenticated).into();
+    let mut details = Vec::with_capacity(error.encoded_len());
+    error.encode(&mut details).unwrap();
+    Status::with_details(
+        Code::Unauthenticated,
+        error.text,
+        Bytes::from(details),
+    )
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    fn request(authorization: Option<&str>) -> Request<()> {
+        let mut req = Request::new(());
+        if let Some(value) = authorization {
+            req.metadata_mut()
+                .insert("authorization", value.parse().unwrap());
+        }
+        req
+    }
+
+    fn tokens() -> Vec<String> {
+        vec!["foo".to_owned(), "barbaz".to_owned()]
+    }
+
+    #[test]
+    fn allows_everything_without_tokens() {
+        assert!(is_authenticated(&request(None), &[]));
+        assert!(is_authenticated(&request(Some("Bearer foo")), &[]));
+    }
+
+    #[test]
+    fn allows_known_bearer_tokens() {
+        assert!(is_authenticated(&request(Some("Bearer foo")), &tokens()));
+        assert!(is_authenticated(&request(Some("Bearer barbaz")), &tokens()));
+    }
+
+    #[test]
+    fn rejects_missing_or_unknown_tokens() {
+        assert!(!is_authenticated(&request(None), &tokens()));
+        assert!(!is_authenticated(&request(Some("Bearer bar")), &tokens()));
+        assert!(!is_authenticated(&request(Some("Basic foo")), &tokens()));
+        assert!(!is_authenticated(&request(Some("foo")), &tokens()));
+    }
+
+    #[test]
+    fn rejects_with_unauthenticated_error_code() {
+        let status = unauthenticated();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        let error = proto::Error::decode(status.details()).unwrap();
+        assert_eq!(error.code, ErrorCode::Unauthenticated as u32);
+        assert_eq!(error.text, status.message());
+    }
+}
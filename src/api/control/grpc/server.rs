@@ -5,41 +5,63 @@
 use std::{
     collections::HashMap,
     convert::{From, TryFrom},
+    pin::Pin,
 };
 
 use actix::{Actor, Addr, Arbiter, Context, Handler, MailboxError};
 use async_trait::async_trait;
 use derive_more::{Display, From};
 use failure::Fail;
-use medea_client_api_proto::MemberId;
+use futures::{Stream, StreamExt as _};
+use medea_client_api_proto::{MemberId, RoomId};
 use medea_control_api_proto::grpc::{
     api as proto,
     api::control_api_server::{
         ControlApi, ControlApiServer as TonicControlApiServer,
     },
 };
-use tonic::{transport::Server, Status};
+use tonic::{
+    transport::{Certificate, Identity, Server, ServerTlsConfig},
+    Status,
+};
 
 use crate::{
     api::control::{
-        endpoints::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
+        credentials::Credentials,
+        element_events::ElementEvents,
+        endpoints::{
+            webrtc_publish_endpoint::WebRtcPublishEndpointPatch,
+            WebRtcPlayEndpoint, WebRtcPublishEndpoint,
+        },
         error_codes::{
             ErrorCode,
             ErrorCode::{ElementIdIsTooLong, ElementIdMismatch},
             ErrorResponse,
         },
+        grpc::auth,
         refs::{fid::ParseFidError, Fid, StatefulFid, ToMember, ToRoom},
-        EndpointId, EndpointSpec, MemberSpec, RoomSpec, TryFromProtobufError,
+        ApplyPolicy, EndpointId, EndpointSpec, MemberPatch, MemberSpec,
+        RoomSpec, TryFromProtobufError,
     },
+    conf::server::ControlApiGrpcTls,
     log::prelude::*,
     shutdown::ShutdownGracefully,
     signalling::room_service::{
-        CreateEndpointInRoom, CreateMemberInRoom, CreateRoom, DeleteElements,
-        Get, RoomService, RoomServiceError, Sids,
+        ApplyMemberInRoom, ApplyRoom, CreateEndpointInRoom, CreateMemberInRoom,
+        CreateRoom, DeleteElements, Get, KickMember, List, ListFilter,
+        ListedRoom, RoomOrigin, RoomService, RoomServiceError, RoomsPage,
+        SendCustomEvent, Sids, UpdateEndpointInRoom, UpdateMemberInRoom,
+        MAX_LIST_LIMIT,
     },
     AppContext,
 };
 
+/// Maximum length in bytes of `Member`'s kick description, so it fits into
+/// WebSocket [Close] frame's reason along with the close reason itself.
+///
+/// [Close]: https://tools.ietf.org/html/rfc6455#section-5.5.1
+const MAX_KICK_DESCRIPTION_LEN: usize = 80;
+
 /// Errors which can happen while processing requests to gRPC [Control API].
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
@@ -63,8 +85,34 @@ pub enum GrpcControlApiError {
     RoomServiceError(RoomServiceError),
 }
 
+/// Errors which can happen while starting gRPC [Control API] server.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Display, Fail, From)]
+pub enum GrpcServerStartError {
+    /// TLS certificate or key file failed to be read.
+    #[display(fmt = "Failed to read TLS file: {}", _0)]
+    Io(std::io::Error),
+
+    /// TLS configuration was rejected by gRPC server.
+    #[display(fmt = "Invalid TLS configuration: {}", _0)]
+    Tls(tonic::transport::Error),
+
+    /// Only one of TLS certificate and key is specified, or client CA
+    /// certificate is specified without them.
+    #[display(fmt = "Both TLS certificate and key must be specified")]
+    IncompleteTlsConfig,
+}
+
 /// Service which provides gRPC [Control API] implementation.
-struct ControlApiService(Addr<RoomService>);
+struct ControlApiService {
+    /// [`RoomService`] which requests are forwarded to.
+    room_service: Addr<RoomService>,
+
+    /// Hub of elements lifecycle events, which are streamed by `Watch`
+    /// method.
+    element_events: ElementEvents,
+}
 
 impl ControlApiService {
     /// Implementation of `Create` method for [`Room`].
@@ -72,7 +120,8 @@ impl ControlApiService {
         &self,
         spec: RoomSpec,
     ) -> Result<Sids, GrpcControlApiError> {
-        Ok(self.0.send(CreateRoom { spec }).await??)
+        let spec = spec.hash_credentials().await;
+        Ok(self.room_service.send(CreateRoom { spec }).await??)
     }
 
     /// Implementation of `Create` method for [`Member`] element.
@@ -81,13 +130,16 @@ impl ControlApiService {
         id: MemberId,
         parent_fid: Fid<ToRoom>,
         spec: MemberSpec,
+        expected_version: Option<u64>,
     ) -> Result<Sids, GrpcControlApiError> {
+        let spec = spec.hash_credentials().await;
         Ok(self
-            .0
+            .room_service
             .send(CreateMemberInRoom {
                 id,
                 parent_fid,
                 spec,
+                expected_version,
             })
             .await??)
     }
@@ -98,13 +150,15 @@ impl ControlApiService {
         id: EndpointId,
         parent_fid: Fid<ToMember>,
         spec: EndpointSpec,
+        expected_version: Option<u64>,
     ) -> Result<Sids, GrpcControlApiError> {
         Ok(self
-            .0
+            .room_service
             .send(CreateEndpointInRoom {
                 id,
                 parent_fid,
                 spec,
+                expected_version,
             })
             .await??)
     }
@@ -114,6 +168,7 @@ impl ControlApiService {
         &self,
         req: proto::CreateRequest,
     ) -> Result<Sids, ErrorResponse> {
+        let expected_version = expected_version(req.expected_version);
         let unparsed_parent_fid = req.parent_fid;
         let elem = if let Some(elem) = req.el {
             elem
@@ -134,7 +189,14 @@ impl ControlApiService {
                 proto::create_request::El::Member(member) => {
                     let id: MemberId = member.id.clone().into();
                     let member_spec = MemberSpec::try_from(member)?;
-                    Ok(self.create_member(id, parent_fid, member_spec).await?)
+                    Ok(self
+                        .create_member(
+                            id,
+                            parent_fid,
+                            member_spec,
+                            expected_version,
+                        )
+                        .await?)
                 }
                 _ => Err(ErrorResponse::new(ElementIdMismatch, &parent_fid)),
             },
@@ -147,9 +209,9 @@ impl ControlApiService {
                         play.id.into(),
                     ),
                     proto::create_request::El::WebrtcPub(publish) => (
-                        EndpointSpec::from(WebRtcPublishEndpoint::from(
+                        EndpointSpec::from(WebRtcPublishEndpoint::try_from(
                             &publish,
-                        )),
+                        )?),
                         publish.id.into(),
                     ),
                     _ => {
@@ -160,7 +222,79 @@ impl ControlApiService {
                     }
                 };
 
-                Ok(self.create_endpoint(id, parent_fid, endpoint_spec).await?)
+                Ok(self
+                    .create_endpoint(
+                        id,
+                        parent_fid,
+                        endpoint_spec,
+                        expected_version,
+                    )
+                    .await?)
+            }
+            StatefulFid::Endpoint(_) => {
+                Err(ErrorResponse::new(ElementIdIsTooLong, &parent_fid))
+            }
+        }
+    }
+
+    /// Applies element based on provided [`proto::ApplyRequest`].
+    async fn apply_element(
+        &self,
+        req: proto::ApplyRequest,
+    ) -> Result<Sids, ErrorResponse> {
+        let policy = ApplyPolicy::from(
+            proto::apply_request::Policy::from_i32(req.policy)
+                .unwrap_or_default(),
+        );
+        let expected_version = expected_version(req.expected_version);
+        let unparsed_parent_fid = req.parent_fid;
+        let elem = if let Some(elem) = req.el {
+            elem
+        } else {
+            return Err(ErrorResponse::new(
+                ErrorCode::NoElement,
+                &unparsed_parent_fid,
+            ));
+        };
+
+        if unparsed_parent_fid.is_empty() {
+            let spec = RoomSpec::try_from(elem)?.hash_credentials().await;
+            return Ok(self
+                .room_service
+                .send(ApplyRoom {
+                    spec,
+                    policy,
+                    expected_version,
+                })
+                .await
+                .map_err(GrpcControlApiError::from)??);
+        }
+
+        let parent_fid = StatefulFid::try_from(unparsed_parent_fid)?;
+        match parent_fid {
+            StatefulFid::Room(parent_fid) => match elem {
+                proto::apply_request::El::Member(member) => {
+                    let id: MemberId = member.id.clone().into();
+                    let spec =
+                        MemberSpec::try_from(member)?.hash_credentials().await;
+                    Ok(self
+                        .room_service
+                        .send(ApplyMemberInRoom {
+                            id,
+                            parent_fid,
+                            spec,
+                            policy,
+                            expected_version,
+                        })
+                        .await
+                        .map_err(GrpcControlApiError::from)??)
+                }
+                proto::apply_request::El::Room(_) => {
+                    Err(ErrorResponse::new(ElementIdMismatch, &parent_fid))
+                }
+            },
+            StatefulFid::Member(parent_fid) => {
+                Err(ErrorResponse::new(ElementIdMismatch, &parent_fid))
             }
             StatefulFid::Endpoint(_) => {
                 Err(ErrorResponse::new(ElementIdIsTooLong, &parent_fid))
@@ -168,17 +302,175 @@ impl ControlApiService {
         }
     }
 
+    /// Updates element based on provided [`proto::UpdateRequest`].
+    async fn update_element(
+        &self,
+        req: proto::UpdateRequest,
+    ) -> Result<(), ErrorResponse> {
+        use proto::update_request::Patch;
+
+        let expected_version = expected_version(req.expected_version);
+        let patch = if let Some(patch) = req.patch {
+            patch
+        } else {
+            return Err(ErrorResponse::new(ErrorCode::NoElement, &req.fid));
+        };
+
+        match (StatefulFid::try_from(req.fid)?, patch) {
+            (StatefulFid::Member(fid), Patch::Member(patch)) => {
+                let patch = MemberPatch::try_from((fid.member_id(), patch))?;
+                Ok(self
+                    .room_service
+                    .send(UpdateMemberInRoom {
+                        fid,
+                        patch,
+                        expected_version,
+                    })
+                    .await
+                    .map_err(GrpcControlApiError::from)??)
+            }
+            (StatefulFid::Endpoint(fid), Patch::WebrtcPub(patch)) => Ok(self
+                .room_service
+                .send(UpdateEndpointInRoom {
+                    fid,
+                    patch: WebRtcPublishEndpointPatch::from(&patch),
+                    expected_version,
+                })
+                .await
+                .map_err(GrpcControlApiError::from)??),
+            (fid, _) => Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+        }
+    }
+
+    /// Kicks `Member` by [`proto::KickRequest`].
+    async fn kick_member(
+        &self,
+        req: proto::KickRequest,
+    ) -> Result<Sids, ErrorResponse> {
+        use proto::kick_request::Credentials as ProtoCredentials;
+
+        let fid = match StatefulFid::try_from(req.fid)? {
+            StatefulFid::Member(fid) => fid,
+            fid => return Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+        };
+        if req.description.len() > MAX_KICK_DESCRIPTION_LEN {
+            return Err(ErrorResponse::with_explanation(
+                ErrorCode::KickDescriptionIsTooLong,
+                format!(
+                    "Description is {} bytes long, but at most {} bytes are \
+                     allowed.",
+                    req.description.len(),
+                    MAX_KICK_DESCRIPTION_LEN,
+                ),
+                Some(fid.to_string()),
+            ));
+        }
+        let credentials = match req.credentials {
+            Some(ProtoCredentials::Plain(plain)) if plain.is_empty() => {
+                Some(Credentials::generate())
+            }
+            Some(credentials) => {
+                Some(Credentials::try_from(credentials).map_err(|_| {
+                    ErrorResponse::new(ErrorCode::InvalidCredentialsHash, &fid)
+                })?)
+            }
+            None => None,
+        };
+        let credentials = match credentials {
+            Some(credentials) => Some(credentials.into_hashed().await),
+            None => None,
+        };
+
+        Ok(self
+            .room_service
+            .send(KickMember {
+                fid,
+                description: Some(req.description).filter(|d| !d.is_empty()),
+                credentials,
+                expected_version: expected_version(req.expected_version),
+            })
+            .await
+            .map_err(GrpcControlApiError::from)??)
+    }
+
+    /// Sends custom event by [`proto::CustomEventRequest`].
+    async fn deliver_custom_event(
+        &self,
+        req: proto::CustomEventRequest,
+    ) -> Result<(), ErrorResponse> {
+        let (room_fid, member_id) = match StatefulFid::try_from(req.fid)? {
+            StatefulFid::Room(fid) => (fid, None),
+            StatefulFid::Member(fid) => {
+                let (room_id, member_id) = fid.take_all();
+                (Fid::<ToRoom>::new(room_id), Some(member_id))
+            }
+            fid @ StatefulFid::Endpoint(_) => {
+                return Err(ErrorResponse::new(ElementIdMismatch, &fid))
+            }
+        };
+        let payload = serde_json::from_str(&req.payload).map_err(|e| {
+            ErrorResponse::with_explanation(
+                ErrorCode::InvalidCustomEventPayload,
+                e.to_string(),
+                None,
+            )
+        })?;
+
+        Ok(self
+            .room_service
+            .send(SendCustomEvent {
+                room_fid,
+                member_id,
+                payload,
+            })
+            .await
+            .map_err(GrpcControlApiError::from)??)
+    }
+
     /// Deletes element by [`proto::IdRequest`].
     async fn delete_element(
         &self,
         req: proto::IdRequest,
     ) -> Result<(), GrpcControlApiError> {
-        let mut delete_elements_msg = DeleteElements::new();
-        for id in req.fid {
-            let fid = StatefulFid::try_from(id)?;
-            delete_elements_msg.add_fid(fid);
+        // Selector matching no `Room`s deletes nothing, while request without
+        // both FIDs and selector is still rejected as empty.
+        let is_selector_empty = req.label_selector.is_empty();
+        let selected_rooms = self.select_rooms(req.label_selector).await?;
+        if !req.fid.is_empty() || is_selector_empty {
+            let mut delete_elements_msg = DeleteElements::new();
+            for id in req.fid {
+                let version = req
+                    .expected_versions
+                    .get(&id)
+                    .copied()
+                    .and_then(expected_version);
+                let fid = StatefulFid::try_from(id)?;
+                if let Some(version) = version {
+                    delete_elements_msg.expect_version(fid.clone(), version);
+                }
+                delete_elements_msg.add_fid(fid);
+            }
+            self.room_service
+                .send(delete_elements_msg.validate()?)
+                .await??;
+        }
+
+        for room_id in selected_rooms {
+            let fid = StatefulFid::from(Fid::<ToRoom>::new(room_id));
+            let mut delete_room_msg = DeleteElements::new();
+            if let Some(version) = req
+                .expected_versions
+                .get(&fid.to_string())
+                .copied()
+                .and_then(expected_version)
+            {
+                delete_room_msg.expect_version(fid.clone(), version);
+            }
+            delete_room_msg.add_fid(fid);
+            self.room_service
+                .send(delete_room_msg.validate()?)
+                .await??;
         }
-        self.0.send(delete_elements_msg.validate()?).await??;
         Ok(())
     }
 
@@ -193,18 +485,130 @@ impl ControlApiService {
             let fid = StatefulFid::try_from(id)?;
             fids.push(fid);
         }
+        for room_id in self.select_rooms(req.label_selector).await? {
+            let fid = Fid::<ToRoom>::new(room_id).into();
+            if !fids.contains(&fid) {
+                fids.push(fid);
+            }
+        }
 
-        let elements = self.0.send(Get(fids)).await??;
+        let elements = self.room_service.send(Get(fids)).await??;
 
         Ok(elements
             .into_iter()
             .map(|(id, value)| (id.to_string(), value))
             .collect())
     }
+
+    /// Implementation of `List` method.
+    async fn list_rooms(
+        &self,
+        req: proto::ListRequest,
+    ) -> Result<RoomsPage, GrpcControlApiError> {
+        Ok(self.room_service.send(List::from(req)).await??)
+    }
+
+    /// Returns [`RoomId`]s of all the [`Room`]s whose labels match the
+    /// provided label selector.
+    ///
+    /// Empty selector selects no [`Room`]s.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    async fn select_rooms(
+        &self,
+        selector: HashMap<String, String>,
+    ) -> Result<Vec<RoomId>, GrpcControlApiError> {
+        let mut selected = Vec::new();
+        if selector.is_empty() {
+            return Ok(selected);
+        }
+        let mut cursor = None;
+        loop {
+            let page = self
+                .room_service
+                .send(List {
+                    cursor,
+                    limit: MAX_LIST_LIMIT,
+                    deep: false,
+                    filter: ListFilter {
+                        labels: selector.clone(),
+                        ..ListFilter::default()
+                    },
+                })
+                .await??;
+            selected.extend(page.rooms.into_iter().map(|room| room.id));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return Ok(selected);
+            }
+        }
+    }
+}
+
+/// Returns expected version of element from the provided one of
+/// [`proto::CreateRequest`], [`proto::ApplyRequest`] or [`proto::IdRequest`].
+///
+/// `0` means that version is not checked.
+fn expected_version(version: u64) -> Option<u64> {
+    Some(version).filter(|v| *v != 0)
+}
+
+impl From<proto::ListRequest> for List {
+    fn from(req: proto::ListRequest) -> Self {
+        use proto::list_request::filter::Origin;
+
+        let filter = req.filter.unwrap_or_default();
+        let origin = match Origin::from_i32(filter.origin) {
+            Some(Origin::Static) => Some(RoomOrigin::Static),
+            Some(Origin::Dynamic) => Some(RoomOrigin::Dynamic),
+            Some(Origin::Any) | None => None,
+        };
+        let count = |v: Option<u32>| v.map(|v| v as usize);
+        Self {
+            cursor: Some(req.cursor)
+                .filter(|cursor| !cursor.is_empty())
+                .map(Into::into),
+            limit: req.limit as usize,
+            deep: req.deep,
+            filter: ListFilter {
+                min_members: count(filter.min_members),
+                max_members: count(filter.max_members),
+                min_connected_members: count(filter.min_connected_members),
+                max_connected_members: count(filter.max_connected_members),
+                origin,
+                labels: filter.labels,
+            },
+        }
+    }
+}
+
+impl Into<proto::list_response::RoomInfo> for ListedRoom {
+    fn into(self) -> proto::list_response::RoomInfo {
+        let room = self.element.and_then(|element| match element.el {
+            Some(proto::element::El::Room(room)) => Some(room),
+            _ => None,
+        });
+        proto::list_response::RoomInfo {
+            id: self.id.to_string(),
+            members: self.summary.members as u32,
+            connected_members: self.summary.connected_members as u32,
+            is_static: self.is_static,
+            labels: self.summary.meta.labels,
+            room,
+        }
+    }
 }
 
 #[async_trait]
 impl ControlApi for ControlApiService {
+    type WatchStream = Pin<
+        Box<
+            dyn Stream<Item = Result<proto::ElementEvent, Status>>
+                + Send
+                + Sync,
+        >,
+    >;
+
     async fn create(
         &self,
         request: tonic::Request<proto::CreateRequest>,
@@ -221,6 +625,66 @@ impl ControlApi for ControlApiService {
         Ok(tonic::Response::new(create_response))
     }
 
+    async fn apply(
+        &self,
+        request: tonic::Request<proto::ApplyRequest>,
+    ) -> Result<tonic::Response<proto::CreateResponse>, Status> {
+        debug!("Apply gRPC Request: [{:?}]", request);
+        let apply_response =
+            match self.apply_element(request.into_inner()).await {
+                Ok(sid) => proto::CreateResponse { sid, error: None },
+                Err(err) => proto::CreateResponse {
+                    sid: HashMap::new(),
+                    error: Some(err.into()),
+                },
+            };
+        Ok(tonic::Response::new(apply_response))
+    }
+
+    async fn update(
+        &self,
+        request: tonic::Request<proto::UpdateRequest>,
+    ) -> Result<tonic::Response<proto::Response>, Status> {
+        debug!("Update gRPC Request: [{:?}]", request);
+        let response = match self.update_element(request.into_inner()).await {
+            Ok(_) => proto::Response { error: None },
+            Err(e) => proto::Response {
+                error: Some(e.into()),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn kick(
+        &self,
+        request: tonic::Request<proto::KickRequest>,
+    ) -> Result<tonic::Response<proto::CreateResponse>, Status> {
+        debug!("Kick gRPC Request: [{:?}]", request);
+        let kick_response = match self.kick_member(request.into_inner()).await {
+            Ok(sid) => proto::CreateResponse { sid, error: None },
+            Err(err) => proto::CreateResponse {
+                sid: HashMap::new(),
+                error: Some(err.into()),
+            },
+        };
+        Ok(tonic::Response::new(kick_response))
+    }
+
+    async fn send_custom_event(
+        &self,
+        request: tonic::Request<proto::CustomEventRequest>,
+    ) -> Result<tonic::Response<proto::Response>, Status> {
+        debug!("SendCustomEvent gRPC Request: [{:?}]", request);
+        let response =
+            match self.deliver_custom_event(request.into_inner()).await {
+                Ok(_) => proto::Response { error: None },
+                Err(e) => proto::Response {
+                    error: Some(e.into()),
+                },
+            };
+        Ok(tonic::Response::new(response))
+    }
+
     async fn delete(
         &self,
         request: tonic::Request<proto::IdRequest>,
@@ -252,13 +716,64 @@ impl ControlApi for ControlApiService {
         };
         Ok(tonic::Response::new(response))
     }
+
+    async fn list(
+        &self,
+        request: tonic::Request<proto::ListRequest>,
+    ) -> Result<tonic::Response<proto::ListResponse>, Status> {
+        debug!("List gRPC Request: [{:?}]", request);
+        let response = match self.list_rooms(request.into_inner()).await {
+            Ok(page) => proto::ListResponse {
+                rooms: page.rooms.into_iter().map(Into::into).collect(),
+                next_cursor: page
+                    .next_cursor
+                    .map(|cursor| cursor.to_string())
+                    .unwrap_or_default(),
+                error: None,
+            },
+            Err(e) => proto::ListResponse {
+                rooms: Vec::new(),
+                next_cursor: String::new(),
+                error: Some(ErrorResponse::from(e).into()),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn watch(
+        &self,
+        request: tonic::Request<proto::WatchRequest>,
+    ) -> Result<tonic::Response<Self::WatchStream>, Status> {
+        debug!("Watch gRPC Request: [{:?}]", request);
+        let fid = request.into_inner().fid;
+        let scope = if fid.is_empty() {
+            None
+        } else {
+            Some(
+                StatefulFid::try_from(fid)
+                    .map_err(|e| Status::invalid_argument(e.to_string()))?,
+            )
+        };
+        let events = self
+            .element_events
+            .watch(scope)
+            .map(|event| Ok(event.into()));
+        Ok(tonic::Response::new(Box::pin(events)))
+    }
 }
 
 /// Actor wrapper for [`tonic`] gRPC server which provides dynamic [Control
 /// API].
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
-pub struct GrpcServer(Option<futures::channel::oneshot::Sender<()>>);
+pub struct GrpcServer {
+    /// Sender of the [`tonic`] gRPC server shutdown signal.
+    shutdown_tx: Option<futures::channel::oneshot::Sender<()>>,
+
+    /// Hub of elements lifecycle events, whose watchers are closed on
+    /// shutdown, so `Watch` streams don't prevent the server from stopping.
+    element_events: ElementEvents,
+}
 
 impl Actor for GrpcServer {
     type Context = Context<Self>;
@@ -280,7 +795,8 @@ impl Handler<ShutdownGracefully> for GrpcServer {
             "gRPC Control API server received ShutdownGracefully message so \
              shutting down.",
         );
-        if let Some(grpc_shutdown) = self.0.take() {
+        self.element_events.close_all();
+        if let Some(grpc_shutdown) = self.shutdown_tx.take() {
             grpc_shutdown.send(()).ok();
         }
     }
@@ -289,12 +805,27 @@ impl Handler<ShutdownGracefully> for GrpcServer {
 /// Run gRPC [Control API] server in actix actor.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
+///
+/// # Errors
+///
+/// With [`GrpcServerStartError`] if TLS is misconfigured.
 pub async fn run(
     room_service: Addr<RoomService>,
     app: &AppContext,
-) -> Addr<GrpcServer> {
-    let bind_ip = app.config.server.control.grpc.bind_ip.to_string();
-    let bind_port = app.config.server.control.grpc.bind_port;
+) -> Result<Addr<GrpcServer>, GrpcServerStartError> {
+    let conf = &app.config.server.control.grpc;
+    let bind_ip = conf.bind_ip.to_string();
+    let bind_port = conf.bind_port;
+
+    let mut server = Server::builder();
+    if let Some(tls) = tls_config(&conf.tls)? {
+        info!("Enabling TLS for gRPC server");
+        server = server.tls_config(tls)?;
+    }
+    if conf.auth_tokens.is_empty() {
+        warn!("gRPC server authentication is disabled");
+    }
+    let interceptor = auth::interceptor(conf.auth_tokens.clone());
 
     info!("Starting gRPC server on {}:{}", bind_ip, bind_port);
 
@@ -302,11 +833,17 @@ pub async fn run(
         futures::channel::oneshot::channel();
 
     let addr = format!("{}:{}", bind_ip, bind_port).parse().unwrap();
+    let element_events = app.element_events.clone();
+    let service = ControlApiService {
+        room_service,
+        element_events: element_events.clone(),
+    };
     Arbiter::spawn(async move {
-        Server::builder()
-            .add_service(TonicControlApiServer::new(ControlApiService(
-                room_service,
-            )))
+        server
+            .add_service(TonicControlApiServer::with_interceptor(
+                service,
+                interceptor,
+            ))
             .serve_with_shutdown(addr, async move {
                 grpc_shutdown_rx.await.ok();
             })
@@ -314,7 +851,33 @@ pub async fn run(
             .unwrap();
     });
 
-    GrpcServer::start_in_arbiter(&Arbiter::new(), move |_| {
-        GrpcServer(Some(grpc_shutdown_tx))
-    })
+    Ok(GrpcServer::start_in_arbiter(&Arbiter::new(), move |_| {
+        GrpcServer {
+            shutdown_tx: Some(grpc_shutdown_tx),
+            element_events,
+        }
+    }))
+}
+
+/// Builds [`ServerTlsConfig`] from provided [`ControlApiGrpcTls`] settings.
+///
+/// Returns [`None`] if TLS is not configured.
+fn tls_config(
+    conf: &ControlApiGrpcTls,
+) -> Result<Option<ServerTlsConfig>, GrpcServerStartError> {
+    let (cert, key) = match (&conf.cert, &conf.key) {
+        (Some(cert), Some(key)) => (cert, key),
+        (None, None) if conf.client_ca_cert.is_none() => return Ok(None),
+        _ => return Err(GrpcServerStartError::IncompleteTlsConfig),
+    };
+    let identity = Identity::from_pem(
+        std::fs::read(cert.as_ref())?,
+        std::fs::read(key.as_ref())?,
+    );
+    let mut tls = ServerTlsConfig::new().identity(identity);
+    if let Some(ca) = &conf.client_ca_cert {
+        tls = tls
+            .client_ca_root(Certificate::from_pem(std::fs::read(ca.as_ref())?));
+    }
+    Ok(Some(tls))
 }
@@ -0,0 +1,5 @@
+//! Implementation of [Control API] HTTP REST server.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+pub mod server;
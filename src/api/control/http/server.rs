@@ -0,0 +1,1167 @@
+//! Implementation of [Control API] HTTP REST server.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{
+    collections::HashMap,
+    convert::{From, TryFrom},
+    io,
+};
+
+use actix::{Actor, Addr, Handler, MailboxError, ResponseFuture};
+use actix_web::{
+    dev::{Server as ActixServer, Service, ServiceRequest, ServiceResponse},
+    http::{header, StatusCode},
+    middleware,
+    web::{self, Bytes, Data, Path, Query, ServiceConfig},
+    App, HttpMessage, HttpRequest, HttpResponse, HttpServer as ActixHttpServer,
+};
+use derive_more::{Display, From};
+use failure::Fail;
+use futures::{
+    future::{self, Either, Ready},
+    FutureExt as _,
+};
+use medea_client_api_proto::MemberId;
+use medea_control_api_proto::grpc::api as proto;
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::{
+    api::control::{
+        callback::url::CallbackUrl,
+        endpoints::{
+            webrtc_publish_endpoint::WebRtcPublishEndpointPatch,
+            WebRtcPlayEndpoint, WebRtcPublishEndpoint,
+        },
+        error_codes::{ErrorCode, ErrorResponse},
+        grpc::auth,
+        member::MemberElement,
+        pipeline::Pipeline,
+        refs::{fid::ParseFidError, Fid, StatefulFid, ToRoom},
+        ApplyPolicy, AutoRemove, ElementMeta, EndpointSpec, MemberPatch,
+        MemberSpec, RoomElement, RoomSpec, RootElement, TryFromElementError,
+        TryFromProtobufError,
+    },
+    conf::Conf,
+    log::prelude::*,
+    shutdown::ShutdownGracefully,
+    signalling::room_service::{
+        ApplyMemberInRoom, ApplyRoom, CreateEndpointInRoom, CreateMemberInRoom,
+        CreateRoom, DeleteElements, Get, List, ListFilter, ListedRoom,
+        RoomOrigin, RoomService, RoomServiceError, Sids, UpdateEndpointInRoom,
+        UpdateMemberInRoom,
+    },
+};
+
+/// Errors which can happen while processing requests to HTTP REST
+/// [Control API].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Display, Fail, From)]
+pub enum HttpControlApiError {
+    /// Error while parsing [`Fid`] of element.
+    Fid(ParseFidError),
+
+    /// Error which can happen while converting protobuf objects into
+    /// serializable [Control API] elements.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    TryFromProtobuf(TryFromProtobufError),
+
+    /// Error which can happen while converting request body into interior
+    /// [medea] [Control API] objects.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    /// [medea]: https://github.com/instrumentisto/medea
+    TryFromElement(TryFromElementError),
+
+    /// Request body or query string can't be parsed.
+    #[display(fmt = "Malformed request: {}", _0)]
+    #[from(ignore)]
+    MalformedRequest(String),
+
+    /// [`MailboxError`] for [`RoomService`].
+    #[display(fmt = "Room service mailbox error: {:?}", _0)]
+    RoomServiceMailboxError(MailboxError),
+
+    /// Wrapper around [`RoomServiceError`].
+    RoomServiceError(RoomServiceError),
+}
+
+/// Format of HTTP request and response bodies.
+///
+/// Detected by `Content-Type` header of request. Response is always encoded
+/// in the same format as request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    /// `application/json`.
+    Json,
+
+    /// `application/yaml`, `application/x-yaml` or `application/vnd.yaml`.
+    Yaml,
+}
+
+impl Format {
+    /// Detects [`Format`] of the provided request.
+    ///
+    /// Defaults to [`Format::Json`] if request has no `Content-Type` header.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`HttpControlApiError::MalformedRequest`] if provided
+    /// `Content-Type` is not supported.
+    fn of<R: HttpMessage>(request: &R) -> Result<Self, HttpControlApiError> {
+        let content_type =
+            if let Some(val) = request.headers().get(header::CONTENT_TYPE) {
+                val.to_str().unwrap_or_default()
+            } else {
+                return Ok(Self::Json);
+            };
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime {
+            "application/json" => Ok(Self::Json),
+            "application/yaml"
+            | "application/x-yaml"
+            | "application/vnd.yaml" => Ok(Self::Yaml),
+            _ => Err(HttpControlApiError::MalformedRequest(format!(
+                "Unsupported Content-Type: {}",
+                content_type
+            ))),
+        }
+    }
+
+    /// Returns MIME type of this [`Format`].
+    fn mime(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Yaml => "application/yaml",
+        }
+    }
+
+    /// Deserializes provided request body in this [`Format`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`HttpControlApiError::MalformedRequest`] if provided
+    /// body can't be deserialized.
+    fn deserialize<T: DeserializeOwned>(
+        self,
+        body: &[u8],
+    ) -> Result<T, HttpControlApiError> {
+        match self {
+            Self::Json => serde_json::from_slice(body).map_err(|e| {
+                HttpControlApiError::MalformedRequest(e.to_string())
+            }),
+            Self::Yaml => serde_yaml::from_slice(body).map_err(|e| {
+                HttpControlApiError::MalformedRequest(e.to_string())
+            }),
+        }
+    }
+
+    /// Builds [`HttpResponse`] with provided [`StatusCode`] and body
+    /// serialized in this [`Format`].
+    fn respond<T: Serialize>(
+        self,
+        status: StatusCode,
+        body: &T,
+    ) -> HttpResponse {
+        let serialized = match self {
+            Self::Json => serde_json::to_vec(body).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_vec(body).map_err(|e| e.to_string()),
+        };
+        match serialized {
+            Ok(body) => HttpResponse::build(status)
+                .content_type(self.mime())
+                .body(body),
+            Err(e) => {
+                error!("Failed to serialize HTTP Control API response: {}", e);
+                HttpResponse::InternalServerError().finish()
+            }
+        }
+    }
+}
+
+/// `Room` element in HTTP REST [Control API] request and response bodies.
+///
+/// Unlike [`RootElement`], doesn't contain ID, since it's provided in
+/// request path.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+enum RoomBody {
+    Room {
+        spec: Pipeline<MemberId, RoomElement>,
+        #[serde(default)]
+        auto_remove: AutoRemove,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        on_remove: Option<CallbackUrl>,
+        #[serde(flatten)]
+        meta: ElementMeta,
+    },
+}
+
+/// Any element returned by `GET` requests to HTTP REST [Control API].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Element {
+    /// `Room` element.
+    Room(RoomBody),
+
+    /// `Member` element.
+    Member(RoomElement),
+
+    /// `Endpoint` element.
+    Endpoint(MemberElement),
+}
+
+impl TryFrom<proto::Element> for Element {
+    type Error = TryFromProtobufError;
+
+    fn try_from(element: proto::Element) -> Result<Self, Self::Error> {
+        use proto::element::El;
+
+        match element.el {
+            Some(El::Room(room)) => {
+                let spec = RoomSpec::try_from(room)?;
+                Ok(Self::Room(RoomBody::Room {
+                    spec: spec.pipeline,
+                    auto_remove: spec.auto_remove,
+                    on_remove: spec.on_remove,
+                    meta: spec.meta,
+                }))
+            }
+            Some(El::Member(member)) => {
+                Ok(Self::Member(MemberSpec::try_from(member)?.into()))
+            }
+            Some(El::WebrtcPub(publish)) => Ok(Self::Endpoint(
+                EndpointSpec::from(WebRtcPublishEndpoint::try_from(&publish)?)
+                    .into(),
+            )),
+            Some(El::WebrtcPlay(play)) => Ok(Self::Endpoint(
+                EndpointSpec::from(WebRtcPlayEndpoint::try_from(&play)?).into(),
+            )),
+            None => Err(TryFromProtobufError::EmptyElement(String::new())),
+        }
+    }
+}
+
+/// Query parameters of `PUT` requests.
+#[derive(Debug, Deserialize)]
+struct ApplyParams {
+    /// Policy of applying spec, `apply` by default.
+    #[serde(default)]
+    policy: Policy,
+
+    /// Version which the element is expected to have for the spec to be
+    /// applied.
+    version: Option<u64>,
+}
+
+/// Query parameters of `POST`, `PATCH` and `DELETE` requests.
+#[derive(Debug, Deserialize)]
+struct VersionParams {
+    /// Version which the parent element (for `POST` requests), the updated
+    /// element (for `PATCH` requests) or the deleted element (for `DELETE`
+    /// requests) is expected to have.
+    version: Option<u64>,
+}
+
+impl VersionParams {
+    /// Parses [`VersionParams`] from the provided query string.
+    fn from_query(query: &str) -> Result<Self, HttpControlApiError> {
+        Query::<Self>::from_query(query)
+            .map(Query::into_inner)
+            .map_err(|e| HttpControlApiError::MalformedRequest(e.to_string()))
+    }
+}
+
+/// [`ApplyPolicy`] representation in query parameters of `PUT` requests.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Policy {
+    Apply,
+    Append,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::Apply
+    }
+}
+
+impl From<Policy> for ApplyPolicy {
+    fn from(from: Policy) -> Self {
+        match from {
+            Policy::Apply => Self::Apply,
+            Policy::Append => Self::Append,
+        }
+    }
+}
+
+/// Query parameters of `GET /` requests.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ListParams {
+    /// ID of `Room` after which listing should be continued.
+    cursor: Option<String>,
+
+    /// Maximum number of `Room`s to list.
+    limit: usize,
+
+    /// Indicator whether specs of the listed `Room`s should be returned.
+    deep: bool,
+
+    /// Minimum number of `Member`s in `Room`.
+    min_members: Option<usize>,
+
+    /// Maximum number of `Member`s in `Room`.
+    max_members: Option<usize>,
+
+    /// Minimum number of `Member`s connected to `Room`.
+    min_connected_members: Option<usize>,
+
+    /// Maximum number of `Member`s connected to `Room`.
+    max_connected_members: Option<usize>,
+
+    /// Origin of `Room`.
+    origin: Option<Origin>,
+
+    /// Labels which `Room` must have, in `key:value[,key:value]` format.
+    #[serde(deserialize_with = "deserialize_labels")]
+    labels: HashMap<String, String>,
+}
+
+/// Deserializes labels from `key:value[,key:value]` format of [`ListParams`].
+fn deserialize_labels<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<String, String>, D::Error> {
+    let labels = String::deserialize(deserializer)?;
+    labels
+        .split(',')
+        .filter(|label| !label.is_empty())
+        .map(|label| {
+            let mut parts = label.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) if !key.is_empty() => {
+                    Ok((key.to_owned(), value.to_owned()))
+                }
+                _ => Err(D::Error::custom(format!(
+                    "label `{}` is not in `key:value` format",
+                    label,
+                ))),
+            }
+        })
+        .collect()
+}
+
+impl From<ListParams> for List {
+    fn from(params: ListParams) -> Self {
+        Self {
+            cursor: params
+                .cursor
+                .filter(|cursor| !cursor.is_empty())
+                .map(Into::into),
+            limit: params.limit,
+            deep: params.deep,
+            filter: ListFilter {
+                min_members: params.min_members,
+                max_members: params.max_members,
+                min_connected_members: params.min_connected_members,
+                max_connected_members: params.max_connected_members,
+                origin: params.origin.map(Into::into),
+                labels: params.labels,
+            },
+        }
+    }
+}
+
+/// [`RoomOrigin`] representation in query parameters of `GET /` requests.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Origin {
+    Static,
+    Dynamic,
+}
+
+impl From<Origin> for RoomOrigin {
+    fn from(from: Origin) -> Self {
+        match from {
+            Origin::Static => Self::Static,
+            Origin::Dynamic => Self::Dynamic,
+        }
+    }
+}
+
+/// Successful response to `POST` and `PUT` requests.
+#[derive(Debug, Serialize)]
+struct SidsResponse {
+    /// URLs which can be used by `Member`s to connect to Medea media server
+    /// via Client API.
+    sid: Sids,
+}
+
+/// Successful response to `GET` requests.
+#[derive(Debug, Serialize)]
+struct ElementsResponse {
+    /// Requested elements by their FIDs.
+    elements: HashMap<String, Element>,
+
+    /// Versions of the requested elements by their FIDs.
+    versions: HashMap<String, u64>,
+}
+
+/// Successful response to `GET /` requests.
+#[derive(Debug, Serialize)]
+struct RoomsResponse {
+    /// Listed `Room`s in order of their IDs.
+    rooms: Vec<RoomInfo>,
+
+    /// Cursor to request the next page of `Room`s with.
+    ///
+    /// Omitted if there are no more `Room`s to list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+/// Summary of a `Room` listed by `GET /` request.
+#[derive(Debug, Serialize)]
+struct RoomInfo {
+    /// ID of `Room`.
+    id: String,
+
+    /// Number of `Member`s in `Room`.
+    members: usize,
+
+    /// Number of `Member`s connected to `Room`.
+    connected_members: usize,
+
+    /// Indicator whether `Room` was created from a static spec.
+    is_static: bool,
+
+    /// Labels of `Room`.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    labels: HashMap<String, String>,
+
+    /// Full spec of `Room`, returned only in deep mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    room: Option<Element>,
+}
+
+impl TryFrom<ListedRoom> for RoomInfo {
+    type Error = TryFromProtobufError;
+
+    fn try_from(room: ListedRoom) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: room.id.to_string(),
+            members: room.summary.members,
+            connected_members: room.summary.connected_members,
+            is_static: room.is_static,
+            labels: room.summary.meta.labels,
+            room: room.element.map(Element::try_from).transpose()?,
+        })
+    }
+}
+
+/// Successful response to `PATCH` and `DELETE` requests.
+#[derive(Debug, Serialize)]
+struct EmptyResponse {}
+
+/// Error response of HTTP REST [Control API].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: ErrorDetails,
+}
+
+/// Details of the error returned by HTTP REST [Control API].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Serialize)]
+struct ErrorDetails {
+    /// HTTP status code of error.
+    status: u16,
+
+    /// Concrete unique code of error.
+    code: u32,
+
+    /// Human-readable text description of error.
+    text: String,
+
+    /// Link to online documentation of error.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    doc: String,
+
+    /// Full ID of element that error is related to.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    element: String,
+}
+
+impl From<ErrorResponse> for ErrorDetails {
+    fn from(err: ErrorResponse) -> Self {
+        use ErrorCode as C;
+
+        let status = match err.error_code() {
+            C::RoomNotFound | C::MemberNotFound | C::EndpointNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            C::RoomAlreadyExists
+            | C::MemberAlreadyExists
+            | C::EndpointAlreadyExists => StatusCode::CONFLICT,
+            C::Unauthenticated => StatusCode::UNAUTHORIZED,
+            C::UnexpectedError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        let err: proto::Error = err.into();
+        Self {
+            status: status.as_u16(),
+            code: err.code,
+            text: err.text,
+            doc: err.doc,
+            element: err.element,
+        }
+    }
+}
+
+/// Converts provided result of request processing into [`HttpResponse`]
+/// encoded in the provided [`Format`].
+fn respond<T: Serialize, E: Into<ErrorResponse>>(
+    format: Format,
+    result: Result<T, E>,
+) -> HttpResponse {
+    match result {
+        Ok(body) => format.respond(StatusCode::OK, &body),
+        Err(e) => respond_error(format, e),
+    }
+}
+
+/// Converts provided error into [`HttpResponse`] encoded in the provided
+/// [`Format`].
+fn respond_error<E: Into<ErrorResponse>>(
+    format: Format,
+    err: E,
+) -> HttpResponse {
+    let details = ErrorDetails::from(err.into());
+    let status = StatusCode::from_u16(details.status)
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    format.respond(status, &ErrorBody { error: details })
+}
+
+/// Parses [`StatefulFid`]s from provided request path.
+///
+/// The last path segment may contain multiple comma-separated IDs, so
+/// multiple [`StatefulFid`]s will be returned.
+///
+/// # Errors
+///
+/// Errors with [`HttpControlApiError::Fid`] if some [`StatefulFid`] can't be
+/// parsed.
+fn parse_fids(path: &str) -> Result<Vec<StatefulFid>, HttpControlApiError> {
+    let (parent, last) = match path.rfind('/') {
+        Some(idx) => (&path[..=idx], &path[idx + 1..]),
+        None => ("", path),
+    };
+    last.split(',')
+        .map(|id| {
+            StatefulFid::try_from(format!("{}{}", parent, id))
+                .map_err(HttpControlApiError::from)
+        })
+        .collect()
+}
+
+/// Context for [`App`] which holds all the necessary dependencies.
+struct Context {
+    /// [`Addr`] of [`RoomService`] which all requests are forwarded to.
+    room_service: Addr<RoomService>,
+}
+
+/// Handles `POST /{fid}[?version={version}]` requests, which create new
+/// elements.
+async fn create(
+    request: HttpRequest,
+    fid: Path<String>,
+    body: Bytes,
+    state: Data<Context>,
+) -> HttpResponse {
+    let format = match Format::of(&request) {
+        Ok(format) => format,
+        Err(e) => return respond_error(Format::Json, e),
+    };
+    let result = create_element(
+        &state.room_service,
+        format,
+        request.query_string(),
+        fid.into_inner(),
+        &body,
+    )
+    .await
+    .map(|sid| SidsResponse { sid });
+    respond(format, result)
+}
+
+/// Creates element with provided FID from the request body.
+async fn create_element(
+    room_service: &Addr<RoomService>,
+    format: Format,
+    query: &str,
+    fid: String,
+    body: &[u8],
+) -> Result<Sids, HttpControlApiError> {
+    let expected_version = VersionParams::from_query(query)?.version;
+    match StatefulFid::try_from(fid)? {
+        StatefulFid::Room(fid) => {
+            let RoomBody::Room {
+                spec,
+                auto_remove,
+                on_remove,
+                meta,
+            } = format.deserialize(body)?;
+            let spec = RoomSpec::try_from(&RootElement::Room {
+                id: fid.take_room_id(),
+                spec,
+                auto_remove,
+                on_remove,
+                meta,
+            })?
+            .hash_credentials()
+            .await;
+            Ok(room_service.send(CreateRoom { spec }).await??)
+        }
+        StatefulFid::Member(fid) => {
+            let element: RoomElement = format.deserialize(body)?;
+            let spec = MemberSpec::try_from(&element)?.hash_credentials().await;
+            let (room_id, id) = fid.take_all();
+            Ok(room_service
+                .send(CreateMemberInRoom {
+                    id,
+                    parent_fid: Fid::<ToRoom>::new(room_id),
+                    spec,
+                    expected_version,
+                })
+                .await??)
+        }
+        StatefulFid::Endpoint(fid) => {
+            let element: MemberElement = format.deserialize(body)?;
+            let (room_id, member_id, id) = fid.take_all();
+            Ok(room_service
+                .send(CreateEndpointInRoom {
+                    id,
+                    parent_fid: Fid::<ToRoom>::new(room_id)
+                        .push_member_id(member_id),
+                    spec: element.into(),
+                    expected_version,
+                })
+                .await??)
+        }
+    }
+}
+
+/// Handles `PUT /{fid}?policy=(apply|append)[&version={version}]` requests,
+/// which apply specs to elements.
+async fn apply(
+    request: HttpRequest,
+    fid: Path<String>,
+    body: Bytes,
+    state: Data<Context>,
+) -> HttpResponse {
+    let format = match Format::of(&request) {
+        Ok(format) => format,
+        Err(e) => return respond_error(Format::Json, e),
+    };
+    let result = apply_element(
+        &state.room_service,
+        format,
+        request.query_string(),
+        fid.into_inner(),
+        &body,
+    )
+    .await
+    .map(|sid| SidsResponse { sid });
+    respond(format, result)
+}
+
+/// Applies spec from the request body to element with provided FID.
+async fn apply_element(
+    room_service: &Addr<RoomService>,
+    format: Format,
+    query: &str,
+    fid: String,
+    body: &[u8],
+) -> Result<Sids, HttpControlApiError> {
+    let params = Query::<ApplyParams>::from_query(query)
+        .map_err(|e| HttpControlApiError::MalformedRequest(e.to_string()))?;
+    let policy = params.policy.into();
+    let expected_version = params.version;
+
+    let fid = StatefulFid::try_from(fid)?;
+    match fid {
+        StatefulFid::Room(fid) => {
+            let RoomBody::Room {
+                spec,
+                auto_remove,
+                on_remove,
+                meta,
+            } = format.deserialize(body)?;
+            let spec = RoomSpec::try_from(&RootElement::Room {
+                id: fid.take_room_id(),
+                spec,
+                auto_remove,
+                on_remove,
+                meta,
+            })?
+            .hash_credentials()
+            .await;
+            Ok(room_service
+                .send(ApplyRoom {
+                    spec,
+                    policy,
+                    expected_version,
+                })
+                .await??)
+        }
+        StatefulFid::Member(fid) => {
+            let element: RoomElement = format.deserialize(body)?;
+            let spec = MemberSpec::try_from(&element)?.hash_credentials().await;
+            let (room_id, id) = fid.take_all();
+            Ok(room_service
+                .send(ApplyMemberInRoom {
+                    id,
+                    parent_fid: Fid::<ToRoom>::new(room_id),
+                    spec,
+                    policy,
+                    expected_version,
+                })
+                .await??)
+        }
+        StatefulFid::Endpoint(fid) => {
+            Err(ParseFidError::TooManyPaths(fid.to_string()).into())
+        }
+    }
+}
+
+/// Handles `PATCH /{fid}[?version={version}]` requests, which update
+/// properties of elements in place.
+async fn update(
+    request: HttpRequest,
+    fid: Path<String>,
+    body: Bytes,
+    state: Data<Context>,
+) -> HttpResponse {
+    let format = match Format::of(&request) {
+        Ok(format) => format,
+        Err(e) => return respond_error(Format::Json, e),
+    };
+    let result = update_element(
+        &state.room_service,
+        format,
+        request.query_string(),
+        fid.into_inner(),
+        &body,
+    )
+    .await
+    .map(|_| EmptyResponse {});
+    respond(format, result)
+}
+
+/// Updates element with provided FID with the patch from the request body.
+async fn update_element(
+    room_service: &Addr<RoomService>,
+    format: Format,
+    query: &str,
+    fid: String,
+    body: &[u8],
+) -> Result<(), HttpControlApiError> {
+    let expected_version = VersionParams::from_query(query)?.version;
+    match StatefulFid::try_from(fid)? {
+        StatefulFid::Room(fid) => {
+            Err(ParseFidError::MissingPath(fid.to_string()).into())
+        }
+        StatefulFid::Member(fid) => {
+            let patch: MemberPatch = format.deserialize(body)?;
+            Ok(room_service
+                .send(UpdateMemberInRoom {
+                    fid,
+                    patch,
+                    expected_version,
+                })
+                .await??)
+        }
+        StatefulFid::Endpoint(fid) => {
+            let patch: WebRtcPublishEndpointPatch = format.deserialize(body)?;
+            Ok(room_service
+                .send(UpdateEndpointInRoom {
+                    fid,
+                    patch,
+                    expected_version,
+                })
+                .await??)
+        }
+    }
+}
+
+/// Handles `GET /{fid}[,{fid}]` requests, which return specs of elements.
+async fn get(
+    request: HttpRequest,
+    fid: Path<String>,
+    state: Data<Context>,
+) -> HttpResponse {
+    let format = match Format::of(&request) {
+        Ok(format) => format,
+        Err(e) => return respond_error(Format::Json, e),
+    };
+    let result = get_elements(&state.room_service, &fid).await;
+    respond(format, result)
+}
+
+/// Returns elements with provided FIDs along with their versions.
+async fn get_elements(
+    room_service: &Addr<RoomService>,
+    fid: &str,
+) -> Result<ElementsResponse, HttpControlApiError> {
+    let fids = parse_fids(fid)?;
+    let elements = room_service.send(Get(fids)).await??;
+    let mut response = ElementsResponse {
+        elements: HashMap::new(),
+        versions: HashMap::new(),
+    };
+    for (fid, element) in elements {
+        response
+            .versions
+            .insert(fid.to_string(), version_of(&element));
+        response
+            .elements
+            .insert(fid.to_string(), Element::try_from(element)?);
+    }
+    Ok(response)
+}
+
+/// Returns version of the provided [`proto::Element`].
+fn version_of(element: &proto::Element) -> u64 {
+    use proto::element::El;
+
+    match &element.el {
+        Some(El::Room(room)) => room.version,
+        Some(El::Member(member)) => member.version,
+        Some(El::WebrtcPub(publish)) => publish.version,
+        Some(El::WebrtcPlay(play)) => play.version,
+        None => 0,
+    }
+}
+
+/// Handles `GET /` requests, which list existing `Room`s.
+async fn list(request: HttpRequest, state: Data<Context>) -> HttpResponse {
+    let format = match Format::of(&request) {
+        Ok(format) => format,
+        Err(e) => return respond_error(Format::Json, e),
+    };
+    let result = list_rooms(&state.room_service, request.query_string()).await;
+    respond(format, result)
+}
+
+/// Lists `Room`s according to the provided query string.
+async fn list_rooms(
+    room_service: &Addr<RoomService>,
+    query: &str,
+) -> Result<RoomsResponse, HttpControlApiError> {
+    let params = Query::<ListParams>::from_query(query)
+        .map_err(|e| HttpControlApiError::MalformedRequest(e.to_string()))?;
+    let page = room_service.send(List::from(params.into_inner())).await??;
+    Ok(RoomsResponse {
+        rooms: page
+            .rooms
+            .into_iter()
+            .map(RoomInfo::try_from)
+            .collect::<Result<_, _>>()?,
+        next_cursor: page.next_cursor.map(|cursor| cursor.to_string()),
+    })
+}
+
+/// Handles `DELETE /{fid}[,{fid}][?version={version}]` requests, which delete
+/// elements.
+async fn delete(
+    request: HttpRequest,
+    fid: Path<String>,
+    state: Data<Context>,
+) -> HttpResponse {
+    let format = match Format::of(&request) {
+        Ok(format) => format,
+        Err(e) => return respond_error(Format::Json, e),
+    };
+    let result =
+        delete_elements(&state.room_service, request.query_string(), &fid)
+            .await
+            .map(|_| EmptyResponse {});
+    respond(format, result)
+}
+
+/// Deletes elements with provided FIDs.
+///
+/// Expected version can be specified only if a single element is deleted.
+async fn delete_elements(
+    room_service: &Addr<RoomService>,
+    query: &str,
+    fid: &str,
+) -> Result<(), HttpControlApiError> {
+    let expected_version = VersionParams::from_query(query)?.version;
+    let fids = parse_fids(fid)?;
+    if expected_version.is_some() && fids.len() > 1 {
+        return Err(HttpControlApiError::MalformedRequest(
+            "`version` can be specified for a single element only".into(),
+        ));
+    }
+
+    let mut delete_elements_msg = DeleteElements::new();
+    for fid in fids {
+        if let Some(version) = expected_version {
+            delete_elements_msg.expect_version(fid.clone(), version);
+        }
+        delete_elements_msg.add_fid(fid);
+    }
+    room_service.send(delete_elements_msg.validate()?).await??;
+    Ok(())
+}
+
+/// Returns middleware which rejects requests not providing one of the
+/// provided `tokens` in `Authorization` header as `Bearer <token>`.
+///
+/// If no `tokens` are provided, then all requests are allowed.
+fn authenticate<S>(
+    tokens: Vec<String>,
+) -> impl Fn(
+    ServiceRequest,
+    &mut S,
+)
+    -> Either<S::Future, Ready<Result<ServiceResponse, actix_web::Error>>>
+       + Clone
+where
+    S: Service<
+        Request = ServiceRequest,
+        Response = ServiceResponse,
+        Error = actix_web::Error,
+    >,
+{
+    move |req, srv| {
+        let authorization = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+        if auth::is_authorized(authorization, &tokens) {
+            return Either::Left(srv.call(req));
+        }
+        let format = Format::of(&req).unwrap_or(Format::Json);
+        let mut resp = respond_error(
+            format,
+            ErrorResponse::without_id(ErrorCode::Unauthenticated),
+        );
+        resp.headers_mut().insert(
+            header::WWW_AUTHENTICATE,
+            header::HeaderValue::from_static("Bearer"),
+        );
+        Either::Right(future::ok(req.into_response(resp)))
+    }
+}
+
+/// HTTP REST [Control API] server.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+pub struct HttpServer(ActixServer);
+
+impl HttpServer {
+    /// Starts HTTP REST [Control API] server.
+    ///
+    /// # Errors
+    ///
+    /// Errors if binding [`ActixHttpServer`] to a listening address fails.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub fn run(
+        room_service: Addr<RoomService>,
+        config: &Conf,
+    ) -> io::Result<Addr<Self>> {
+        let server_addr = config.server.control.http.bind_addr();
+        let auth_tokens = config.server.control.http.auth_tokens.clone();
+        if auth_tokens.is_empty() {
+            warn!("HTTP REST server authentication is disabled");
+        }
+
+        let server = ActixHttpServer::new(move || {
+            App::new()
+                .data(Context {
+                    room_service: room_service.clone(),
+                })
+                .configure(Self::configure)
+                .wrap_fn(authenticate(auth_tokens.clone()))
+                .wrap(middleware::Logger::default())
+        })
+        .disable_signals()
+        .bind(server_addr)?
+        .run();
+
+        info!("Started Control API HTTP REST server on {}", server_addr);
+
+        Ok(Self(server).start())
+    }
+
+    /// Run external configuration as part of the application building
+    /// process.
+    fn configure(cfg: &mut ServiceConfig) {
+        cfg.service(web::resource("/").route(web::get().to(list)));
+        cfg.service(
+            web::resource("/{fid:.+}")
+                .route(web::post().to(create))
+                .route(web::put().to(apply))
+                .route(web::patch().to(update))
+                .route(web::get().to(get))
+                .route(web::delete().to(delete)),
+        );
+    }
+}
+
+impl Actor for HttpServer {
+    type Context = actix::Context<Self>;
+}
+
+impl Handler<ShutdownGracefully> for HttpServer {
+    type Result = ResponseFuture<()>;
+
+    fn handle(
+        &mut self,
+        _: ShutdownGracefully,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        info!(
+            "Control API HTTP REST server received ShutdownGracefully message \
+             so shutting down.",
+        );
+        self.0.stop(true).boxed_local()
+    }
+}
+
+#[cfg(test)]
+mod list_params_spec {
+    use super::*;
+
+    #[test]
+    fn parses_list_params() {
+        let params = Query::<ListParams>::from_query(
+            "cursor=room-1&limit=10&deep=true&max_connected_members=0&\
+             origin=static&labels=tenant:acme,env:prod",
+        )
+        .unwrap()
+        .into_inner();
+        let list = List::from(params);
+
+        assert_eq!(list.cursor.unwrap().to_string(), "room-1");
+        assert_eq!(list.limit, 10);
+        assert!(list.deep);
+        assert_eq!(list.filter.min_members, None);
+        assert_eq!(list.filter.max_connected_members, Some(0));
+        assert_eq!(list.filter.origin, Some(RoomOrigin::Static));
+        assert_eq!(
+            list.filter.labels,
+            hashmap! {
+                "tenant".to_string() => "acme".to_string(),
+                "env".to_string() => "prod".to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn defaults_list_params() {
+        let list = List::from(
+            Query::<ListParams>::from_query("").unwrap().into_inner(),
+        );
+
+        assert!(list.cursor.is_none());
+        assert_eq!(list.limit, 0);
+        assert!(!list.deep);
+        assert!(list.filter.origin.is_none());
+        assert!(list.filter.labels.is_empty());
+    }
+
+    #[test]
+    fn errors_on_malformed_list_params() {
+        assert!(Query::<ListParams>::from_query("limit=foo").is_err());
+        assert!(Query::<ListParams>::from_query("origin=foo").is_err());
+        assert!(Query::<ListParams>::from_query("labels=tenant").is_err());
+    }
+}
+
+#[cfg(test)]
+mod parse_fids_spec {
+    use super::*;
+
+    #[test]
+    fn parses_single_fid() {
+        let fids = parse_fids("room/member/endpoint").unwrap();
+        assert_eq!(fids.len(), 1);
+        assert_eq!(fids[0].to_string(), "room/member/endpoint");
+    }
+
+    #[test]
+    fn parses_multiple_fids_on_last_level() {
+        let fids = parse_fids("room/member-1,member-2").unwrap();
+        let fids: Vec<_> = fids.into_iter().map(|f| f.to_string()).collect();
+        assert_eq!(fids, vec!["room/member-1", "room/member-2"]);
+
+        let fids = parse_fids("room-1,room-2").unwrap();
+        let fids: Vec<_> = fids.into_iter().map(|f| f.to_string()).collect();
+        assert_eq!(fids, vec!["room-1", "room-2"]);
+    }
+
+    #[test]
+    fn errors_on_empty_id() {
+        assert!(parse_fids("room/member,").is_err());
+        assert!(parse_fids("room/member/endpoint/foo").is_err());
+    }
+}
+
+#[cfg(test)]
+mod authenticate_spec {
+    use actix_web::test::{self, TestRequest};
+
+    use super::*;
+
+    async fn call(authorization: Option<&str>) -> ServiceResponse {
+        let mut app = test::init_service(
+            App::new()
+                .wrap_fn(authenticate(vec!["foo".to_owned()]))
+                .route(
+                    "/",
+                    web::get().to(|| async { HttpResponse::Ok().finish() }),
+                ),
+        )
+        .await;
+        let mut req = TestRequest::get().uri("/");
+        if let Some(value) = authorization {
+            req = req.header(header::AUTHORIZATION, value);
+        }
+        test::call_service(&mut app, req.to_request()).await
+    }
+
+    #[actix_rt::test]
+    async fn rejects_requests_without_valid_token() {
+        let resp = call(None).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            resp.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            "Bearer",
+        );
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"]["code"], ErrorCode::Unauthenticated as u32);
+
+        let resp = call(Some("Bearer bar")).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_rt::test]
+    async fn allows_requests_with_valid_token() {
+        assert_eq!(call(Some("Bearer foo")).await.status(), StatusCode::OK);
+    }
+}
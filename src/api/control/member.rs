@@ -8,29 +8,27 @@ use std::{
     time::Duration,
 };
 
-use medea_client_api_proto::{Credential, MemberId as Id};
+use medea_client_api_proto::MemberId as Id;
 use medea_control_api_proto::grpc::api as proto;
-use rand::{distributions::Alphanumeric, Rng};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::api::control::{
     callback::url::CallbackUrl,
+    credentials::Credentials,
     endpoints::{
         webrtc_play_endpoint::WebRtcPlayEndpoint,
         webrtc_publish_endpoint::{WebRtcPublishEndpoint, WebRtcPublishId},
     },
     pipeline::Pipeline,
     room::RoomElement,
-    EndpointId, EndpointSpec, TryFromElementError, TryFromProtobufError,
-    WebRtcPlayId,
+    ElementMeta, EndpointId, EndpointSpec, TryFromElementError,
+    TryFromProtobufError, WebRtcPlayId,
 };
 
-const CREDENTIALS_LEN: usize = 32;
-
 /// Element of [`Member`]'s [`Pipeline`].
 ///
 /// [`Member`]: crate::signalling::elements::member::Member
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 #[serde(tag = "kind")]
 pub enum MemberElement {
     /// Represent [`WebRtcPublishEndpoint`].
@@ -53,7 +51,7 @@ pub struct MemberSpec {
     pipeline: Pipeline<EndpointId, MemberElement>,
 
     /// Credentials to authorize `Member` with.
-    credentials: Credential,
+    credentials: Credentials,
 
     /// URL to which `OnJoin` Control API callback will be sent.
     on_join: Option<CallbackUrl>,
@@ -74,6 +72,9 @@ pub struct MemberSpec {
 
     /// Interval of sending `Ping`s to the `Member` via Client API.
     ping_interval: Option<Duration>,
+
+    /// Labels and metadata of the `Member`.
+    meta: ElementMeta,
 }
 
 impl Into<RoomElement> for MemberSpec {
@@ -86,16 +87,18 @@ impl Into<RoomElement> for MemberSpec {
             idle_timeout: self.idle_timeout,
             reconnect_timeout: self.reconnect_timeout,
             ping_interval: self.ping_interval,
+            meta: self.meta,
         }
     }
 }
 
 impl MemberSpec {
-    /// Creates new [`MemberSpec`] with the given parameters.
+    /// Creates new [`MemberSpec`] with the given parameters and without any
+    /// labels and metadata.
     #[inline]
     pub fn new(
         pipeline: Pipeline<EndpointId, MemberElement>,
-        credentials: Credential,
+        credentials: Credentials,
         on_join: Option<CallbackUrl>,
         on_leave: Option<CallbackUrl>,
         idle_timeout: Option<Duration>,
@@ -110,6 +113,7 @@ impl MemberSpec {
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            meta: ElementMeta::default(),
         }
     }
 
@@ -125,6 +129,19 @@ impl MemberSpec {
         })
     }
 
+    /// Lookups [`WebRtcPlayEndpoint`] by ID.
+    pub fn get_play_endpoint_by_id(
+        &self,
+        id: WebRtcPlayId,
+    ) -> Option<&WebRtcPlayEndpoint> {
+        let e = self.pipeline.get(&id.into())?;
+        if let MemberElement::WebRtcPlayEndpoint { spec } = e {
+            Some(spec)
+        } else {
+            None
+        }
+    }
+
     /// Lookups [`WebRtcPublishEndpoint`] by ID.
     pub fn get_publish_endpoint_by_id(
         &self,
@@ -151,10 +168,17 @@ impl MemberSpec {
     }
 
     /// Returns credentials from this [`MemberSpec`].
-    pub fn credentials(&self) -> &Credential {
+    pub fn credentials(&self) -> &Credentials {
         &self.credentials
     }
 
+    /// Hashes plain credentials of this [`MemberSpec`] on the blocking thread
+    /// pool, so they're not hashed by `Room` itself.
+    pub async fn hash_credentials(mut self) -> Self {
+        self.credentials = self.credentials.into_hashed().await;
+        self
+    }
+
     /// Returns reference to `on_join` [`CallbackUrl`].
     pub fn on_join(&self) -> &Option<CallbackUrl> {
         &self.on_join
@@ -184,38 +208,34 @@ impl MemberSpec {
     pub fn ping_interval(&self) -> Option<Duration> {
         self.ping_interval
     }
+
+    /// Returns labels and metadata of the `Member`.
+    pub fn meta(&self) -> &ElementMeta {
+        &self.meta
+    }
 }
 
-/// Generates alphanumeric credentials for [`Member`] with
-/// [`CREDENTIALS_LEN`] length.
+/// Parses optional [`Duration`] field of the `Member` with the provided ID
+/// from its protobuf representation.
 ///
-/// This credentials will be generated if in dynamic [Control API] spec not
-/// provided credentials for [`Member`]. This logic you can find in [`TryFrom`]
-/// [`MemberProto`] implemented for [`MemberSpec`].
+/// # Errors
 ///
-/// [Control API]: https://tinyurl.com/yxsqplq7
-fn generate_member_credentials() -> Credential {
-    rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(CREDENTIALS_LEN)
-        .collect::<String>()
-        .into()
+/// With [`TryFromProtobufError::NegativeDuration`] if the provided duration is
+/// negative.
+fn parse_duration<T: TryInto<Duration>>(
+    duration: Option<T>,
+    member_id: &str,
+    field: &'static str,
+) -> Result<Option<Duration>, TryFromProtobufError> {
+    duration.map(TryInto::try_into).transpose().map_err(|_| {
+        TryFromProtobufError::NegativeDuration(member_id.into(), field)
+    })
 }
 
 impl TryFrom<proto::Member> for MemberSpec {
     type Error = TryFromProtobufError;
 
     fn try_from(member: proto::Member) -> Result<Self, Self::Error> {
-        fn parse_duration<T: TryInto<Duration>>(
-            duration: Option<T>,
-            member_id: &str,
-            field: &'static str,
-        ) -> Result<Option<Duration>, TryFromProtobufError> {
-            duration.map(TryInto::try_into).transpose().map_err(|_| {
-                TryFromProtobufError::NegativeDuration(member_id.into(), field)
-            })
-        }
-
         let mut pipeline = HashMap::new();
         for (id, member_element) in member.pipeline {
             if let Some(elem) = member_element.el {
@@ -227,10 +247,22 @@ impl TryFrom<proto::Member> for MemberSpec {
             }
         }
 
-        let mut credentials = Credential::from(member.credentials);
-        if credentials.0.is_empty() {
-            credentials = generate_member_credentials();
-        }
+        let credentials = match member.credentials {
+            Some(proto::member::Credentials::Plain(plain))
+                if plain.is_empty() =>
+            {
+                Credentials::generate()
+            }
+            Some(credentials) => {
+                let member_id = &member.id;
+                Credentials::try_from(credentials).map_err(|_| {
+                    TryFromProtobufError::InvalidCredentialsHash(
+                        member_id.clone(),
+                    )
+                })?
+            }
+            None => Credentials::generate(),
+        };
 
         let on_leave = {
             let on_leave = member.on_leave;
@@ -267,6 +299,53 @@ impl TryFrom<proto::Member> for MemberSpec {
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            meta: ElementMeta::new(member.labels, member.metadata),
+        })
+    }
+}
+
+/// Patch of the [`MemberSpec`] properties which can be updated without
+/// recreating the `Member`.
+///
+/// [`None`] properties are kept unchanged.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct MemberPatch {
+    /// New timeout of receiving heartbeat messages from the `Member` via
+    /// Client API.
+    #[serde(default, with = "humantime_serde")]
+    pub idle_timeout: Option<Duration>,
+
+    /// New timeout of the `Member` reconnecting via Client API.
+    #[serde(default, with = "humantime_serde")]
+    pub reconnect_timeout: Option<Duration>,
+
+    /// New interval of sending `Ping`s to the `Member` via Client API.
+    #[serde(default, with = "humantime_serde")]
+    pub ping_interval: Option<Duration>,
+}
+
+impl TryFrom<(&Id, proto::MemberPatch)> for MemberPatch {
+    type Error = TryFromProtobufError;
+
+    fn try_from(
+        (id, patch): (&Id, proto::MemberPatch),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            idle_timeout: parse_duration(
+                patch.idle_timeout,
+                &id.0,
+                "idle_timeout",
+            )?,
+            reconnect_timeout: parse_duration(
+                patch.reconnect_timeout,
+                &id.0,
+                "reconnect_timeout",
+            )?,
+            ping_interval: parse_duration(
+                patch.ping_interval,
+                &id.0,
+                "ping_interval",
+            )?,
         })
     }
 }
@@ -294,6 +373,7 @@ macro_rules! impl_try_from_proto_for_member {
 
 impl_try_from_proto_for_member!(proto::room::element::El);
 impl_try_from_proto_for_member!(proto::create_request::El);
+impl_try_from_proto_for_member!(proto::apply_request::El);
 
 impl TryFrom<&RoomElement> for MemberSpec {
     type Error = TryFromElementError;
@@ -310,6 +390,7 @@ impl TryFrom<&RoomElement> for MemberSpec {
                 idle_timeout,
                 reconnect_timeout,
                 ping_interval,
+                meta,
             } => Ok(Self {
                 pipeline: spec.clone(),
                 credentials: credentials.clone(),
@@ -318,6 +399,7 @@ impl TryFrom<&RoomElement> for MemberSpec {
                 idle_timeout: *idle_timeout,
                 reconnect_timeout: *reconnect_timeout,
                 ping_interval: *ping_interval,
+                meta: meta.clone(),
             }),
             _ => Err(TryFromElementError::NotMember),
         }
@@ -0,0 +1,82 @@
+//! Labels and metadata of [Control API] elements.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Labels and opaque metadata attached to a [Control API] element.
+///
+/// Neither of them affects media server's behavior: labels are used for
+/// selecting elements only, while metadata is just stored and returned as is.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ElementMeta {
+    /// Arbitrary key-value pairs which the element can be selected by.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+
+    /// Opaque metadata of the element.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub metadata: String,
+}
+
+impl ElementMeta {
+    /// Creates new [`ElementMeta`] with the provided labels and metadata.
+    #[inline]
+    #[must_use]
+    pub fn new(labels: HashMap<String, String>, metadata: String) -> Self {
+        Self { labels, metadata }
+    }
+
+    /// Indicates whether this [`ElementMeta`] has all the labels of the
+    /// provided label selector with exactly the same values.
+    ///
+    /// Empty selector matches any [`ElementMeta`].
+    #[must_use]
+    pub fn matches(&self, selector: &HashMap<String, String>) -> bool {
+        matches_labels(&self.labels, selector)
+    }
+}
+
+/// Indicates whether the provided labels contain all the labels of the
+/// provided label selector with exactly the same values.
+#[must_use]
+pub fn matches_labels(
+    labels: &HashMap<String, String>,
+    selector: &HashMap<String, String>,
+) -> bool {
+    selector
+        .iter()
+        .all(|(key, value)| labels.get(key) == Some(value))
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn selector_matches_subset_of_labels() {
+        let meta = ElementMeta::new(
+            hashmap! {
+                "tenant".to_string() => "acme".to_string(),
+                "env".to_string() => "prod".to_string(),
+            },
+            String::new(),
+        );
+
+        assert!(meta.matches(&HashMap::new()));
+        assert!(meta.matches(&hashmap! {
+            "tenant".to_string() => "acme".to_string(),
+        }));
+        assert!(!meta.matches(&hashmap! {
+            "tenant".to_string() => "other".to_string(),
+        }));
+        assert!(!meta.matches(&hashmap! {
+            "tenant".to_string() => "acme".to_string(),
+            "region".to_string() => "eu".to_string(),
+        }));
+    }
+}
@@ -3,24 +3,34 @@
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
 pub mod callback;
+pub mod credentials;
+pub mod element_events;
 pub mod endpoints;
 pub mod error_codes;
 pub mod grpc;
+pub mod http;
 pub mod member;
+pub mod meta;
 pub mod pipeline;
 pub mod refs;
 pub mod room;
+pub mod validation;
 
-use std::{convert::TryFrom as _, fs::File, io::Read as _, path::Path};
+use std::{
+    convert::TryFrom as _,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use actix::Addr;
 use derive_more::Display;
 use failure::{Error, Fail};
 use medea_client_api_proto::{MemberId, RoomId};
-use serde::Deserialize;
+use medea_control_api_proto::grpc::api as proto;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    api::control::callback::url::CallbackUrlParseError,
+    api::control::callback::url::{CallbackUrl, CallbackUrlParseError},
     log::prelude::*,
     signalling::room_service::{
         RoomService, RoomServiceError, StartStaticRooms,
@@ -36,8 +46,9 @@ pub use self::{
         webrtc_publish_endpoint::WebRtcPublishId, EndpointSpec,
         Id as EndpointId,
     },
-    member::MemberSpec,
-    room::{RoomElement, RoomSpec},
+    member::{MemberPatch, MemberSpec},
+    meta::ElementMeta,
+    room::{AutoRemove, RoomElement, RoomSpec},
 };
 
 /// Errors which may occur while deserializing protobuf spec.
@@ -86,6 +97,13 @@ pub enum TryFromProtobufError {
         _1
     )]
     NegativeDuration(String, &'static str),
+
+    /// `Member` element contains credentials hash which is not a valid
+    /// [Argon2] hash.
+    ///
+    /// [Argon2]: https://en.wikipedia.org/wiki/Argon2
+    #[display(fmt = "Member [id = {}] contains invalid credentials hash", _0)]
+    InvalidCredentialsHash(String),
 }
 
 impl From<SrcParseError> for TryFromProtobufError {
@@ -103,7 +121,7 @@ impl From<CallbackUrlParseError> for TryFromProtobufError {
 /// Root elements of [Control API] spec.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 #[serde(tag = "kind")]
 pub enum RootElement {
     /// Represents [`RoomSpec`].
@@ -111,9 +129,39 @@ pub enum RootElement {
     Room {
         id: RoomId,
         spec: Pipeline<MemberId, RoomElement>,
+        #[serde(default)]
+        auto_remove: AutoRemove,
+        on_remove: Option<CallbackUrl>,
+        #[serde(flatten)]
+        meta: ElementMeta,
     },
 }
 
+/// Policy of applying [Control API] spec to an already existing element.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ApplyPolicy {
+    /// Elements that exist, but are not specified in the provided spec will
+    /// be removed.
+    Apply,
+
+    /// Elements that exist, but are not specified in the provided spec will
+    /// be kept.
+    Append,
+}
+
+impl From<proto::apply_request::Policy> for ApplyPolicy {
+    fn from(from: proto::apply_request::Policy) -> Self {
+        use proto::apply_request::Policy;
+
+        match from {
+            Policy::Apply => Self::Apply,
+            Policy::Append => Self::Append,
+        }
+    }
+}
+
 /// Errors that can occur when we try transform some spec from `Element`.
 /// This error used in all [`TryFrom`] of Control API.
 ///
@@ -202,32 +250,70 @@ impl From<serde_yaml::Error> for LoadStaticControlSpecsError {
 pub fn load_from_yaml_file<P: AsRef<Path>>(
     path: P,
 ) -> Result<RoomSpec, LoadStaticControlSpecsError> {
-    let mut file = File::open(path)?;
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)?;
-    let parsed: RootElement = serde_yaml::from_str(&buf)?;
-    let room = RoomSpec::try_from(&parsed)?;
-    Ok(room)
+    parse_yaml_spec(&fs::read_to_string(path)?)
 }
 
-/// Loads all [`RoomSpec`] from YAML files from provided path.
+/// Parses [`RoomSpec`] from provided YAML.
+///
+/// # Errors
+///
+/// Errors with [`LoadStaticControlSpecsError::YamlDeserializationError`] if
+/// YAML deserialization fails.
+///
+/// Errors with [`LoadStaticControlSpecsError::TryFromElementError`] if
+/// [`RoomSpec`] conversation fails.
+pub fn parse_yaml_spec(
+    yaml: &str,
+) -> Result<RoomSpec, LoadStaticControlSpecsError> {
+    let parsed: RootElement = serde_yaml::from_str(yaml)?;
+    Ok(RoomSpec::try_from(&parsed)?)
+}
+
+/// Reads contents of all static [Control API] spec files from provided path.
+///
+/// Directories and hidden files (like ones left by text editors) are skipped.
 ///
 /// # Errors
 ///
-/// Errors with [`LoadStateControlSpecsError::SpecDirReadError`] if reading
+/// Errors with [`LoadStaticControlSpecsError::SpecDirReadError`] if reading
 /// provided [`Path`] fails.
-pub fn load_static_specs_from_dir<P: AsRef<Path>>(
+///
+/// Errors with [`LoadStaticControlSpecsError::IoError`] if reading of some
+/// spec file fails.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+pub fn read_static_specs_dir<P: AsRef<Path>>(
     path: P,
-) -> Result<Vec<RoomSpec>, LoadStaticControlSpecsError> {
-    let mut specs = Vec::new();
-    for entry in std::fs::read_dir(path)
+) -> Result<Vec<(PathBuf, String)>, LoadStaticControlSpecsError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)
         .map_err(LoadStaticControlSpecsError::SpecDirReadError)?
     {
         let entry = entry?;
-        let spec = load_from_yaml_file(entry.path())?;
-        specs.push(spec)
+        let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if is_hidden || !entry.file_type()?.is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let content = fs::read_to_string(&path)?;
+        files.push((path, content));
     }
-    Ok(specs)
+    Ok(files)
+}
+
+/// Loads all [`RoomSpec`] from YAML files from provided path.
+///
+/// # Errors
+///
+/// Errors with [`LoadStaticControlSpecsError::SpecDirReadError`] if reading
+/// provided [`Path`] fails.
+pub fn load_static_specs_from_dir<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<RoomSpec>, LoadStaticControlSpecsError> {
+    read_static_specs_dir(path)?
+        .into_iter()
+        .map(|(_, content)| parse_yaml_spec(&content))
+        .collect()
 }
 
 /// Starts all [`Room`]s from static [Control API] specs.
@@ -8,10 +8,10 @@ use std::{
     iter::IntoIterator,
 };
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Entity that represents some pipeline of spec.
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 pub struct Pipeline<K: Hash + Eq, V> {
     pipeline: HashMap<K, V>,
 }
@@ -28,6 +28,12 @@ impl<K: Hash + Eq, V> Pipeline<K, V> {
         self.into_iter()
     }
 
+    /// Iterates over pipeline by mutable reference.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.pipeline.iter_mut()
+    }
+
     /// Lookups element of [`Pipeline`] by ID.
     #[inline]
     pub fn get(&self, id: &K) -> Option<&V> {
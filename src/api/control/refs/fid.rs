@@ -90,6 +90,27 @@ impl StatefulFid {
             StatefulFid::Endpoint(uri) => uri.room_id(),
         }
     }
+
+    /// Indicates whether this [`StatefulFid`] refers to the element with the
+    /// provided [`StatefulFid`] or to any of its children.
+    pub fn is_within(&self, parent: &StatefulFid) -> bool {
+        match (self, parent) {
+            (_, StatefulFid::Room(parent)) => {
+                self.room_id() == parent.room_id()
+            }
+            (StatefulFid::Member(fid), StatefulFid::Member(parent)) => {
+                fid == parent
+            }
+            (StatefulFid::Endpoint(fid), StatefulFid::Member(parent)) => {
+                fid.room_id() == parent.room_id()
+                    && fid.member_id() == parent.member_id()
+            }
+            (StatefulFid::Endpoint(fid), StatefulFid::Endpoint(parent)) => {
+                fid == parent
+            }
+            _ => false,
+        }
+    }
 }
 
 impl TryFrom<String> for StatefulFid {
@@ -172,6 +193,24 @@ mod specs {
         }
     }
 
+    #[test]
+    fn checks_whether_fid_is_within_another() {
+        let fid = |s: &str| StatefulFid::try_from(s.to_string()).unwrap();
+
+        assert!(fid("room/member/endpoint").is_within(&fid("room")));
+        assert!(fid("room/member/endpoint").is_within(&fid("room/member")));
+        assert!(
+            fid("room/member/endpoint").is_within(&fid("room/member/endpoint"))
+        );
+        assert!(fid("room/member").is_within(&fid("room/member")));
+        assert!(fid("room").is_within(&fid("room")));
+
+        assert!(!fid("room").is_within(&fid("room/member")));
+        assert!(!fid("room/member").is_within(&fid("room/member/endpoint")));
+        assert!(!fid("room/member2/endpoint").is_within(&fid("room/member")));
+        assert!(!fid("room2/member").is_within(&fid("room")));
+    }
+
     #[test]
     fn returns_error_on_too_many_paths() {
         for fid_str in &[
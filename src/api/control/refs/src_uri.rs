@@ -12,7 +12,7 @@ use failure::Fail;
 use medea_client_api_proto::{MemberId, RoomId};
 use serde::{
     de::{self, Deserializer, Error, Visitor},
-    Deserialize,
+    Deserialize, Serialize, Serializer,
 };
 
 use crate::api::control::{
@@ -53,7 +53,7 @@ pub enum SrcParseError {
 /// crate::api::control::endpoints::WebRtcPublishEndpoint
 /// [Control API]: https://tinyurl.com/yxsqplq7
 /// [`EndpointId`]: crate::api::control::EndpointId
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SrcUri {
     /// ID of [`Room`].
     ///
@@ -140,6 +140,21 @@ impl<'de> Deserialize<'de> for SrcUri {
     }
 }
 
+/// [Serde] serializer for [`SrcUri`].
+///
+/// Serializes [`SrcUri`] into string with pattern:
+/// `local://room_id/member_id/publish_endpoint_id`.
+///
+/// [Serde]: serde
+impl Serialize for SrcUri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 impl fmt::Display for SrcUri {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -2,14 +2,20 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
-use std::{collections::HashMap, convert::TryFrom, time::Duration};
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+    time::Duration,
+};
 
-use medea_client_api_proto::{Credential, MemberId, RoomId as Id};
+use medea_client_api_proto::{MemberId, RoomId as Id};
 use medea_control_api_proto::grpc::api as proto;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::api::control::{
-    callback::url::CallbackUrl, EndpointId, TryFromProtobufError,
+    callback::url::CallbackUrl,
+    credentials::{self, Credentials},
+    ElementMeta, EndpointId, TryFromProtobufError,
 };
 
 use super::{
@@ -21,14 +27,14 @@ use super::{
 /// Element of [`Room`]'s [`Pipeline`].
 ///
 /// [`Room`]: crate::signalling::room::Room
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 #[serde(tag = "kind")]
 pub enum RoomElement {
     /// Represent [`MemberSpec`].
     /// Can transform into [`MemberSpec`] by `MemberSpec::try_from`.
     Member {
         spec: Pipeline<EndpointId, MemberElement>,
-        credentials: Credential,
+        credentials: Credentials,
         on_leave: Option<CallbackUrl>,
         on_join: Option<CallbackUrl>,
         #[serde(default, with = "humantime_serde")]
@@ -37,9 +43,95 @@ pub enum RoomElement {
         reconnect_timeout: Option<Duration>,
         #[serde(default, with = "humantime_serde")]
         ping_interval: Option<Duration>,
+        #[serde(flatten)]
+        meta: ElementMeta,
     },
 }
 
+/// Rule of automatic removing of [`Room`].
+///
+/// [`Room`]: crate::signalling::room::Room
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoRemove {
+    /// [`Room`] is kept until it's deleted explicitly.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    Never,
+
+    /// [`Room`] is removed once all its connected `Member`s have left it.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    AllLeft,
+
+    /// [`Room`] is removed once it has been without connected `Member`s for
+    /// the provided [`Duration`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    EmptyFor(#[serde(with = "humantime_serde")] Duration),
+}
+
+impl Default for AutoRemove {
+    #[inline]
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+impl AutoRemove {
+    /// Parses [`AutoRemove`] from its protobuf representation.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`TryFromProtobufError::NegativeDuration`] if `empty_for`
+    /// duration is negative.
+    fn try_from_proto(
+        proto: Option<proto::room::AutoRemove>,
+        room_id: &str,
+    ) -> Result<Self, TryFromProtobufError> {
+        use proto::room::auto_remove::Kind;
+
+        let proto = if let Some(proto) = proto {
+            proto
+        } else {
+            return Ok(Self::Never);
+        };
+        Ok(match Kind::from_i32(proto.kind) {
+            Some(Kind::Never) | None => Self::Never,
+            Some(Kind::AllLeft) => Self::AllLeft,
+            Some(Kind::EmptyFor) => Self::EmptyFor(
+                proto
+                    .empty_for
+                    .map(TryInto::try_into)
+                    .transpose()
+                    .map_err(|_| {
+                        TryFromProtobufError::NegativeDuration(
+                            room_id.into(),
+                            "auto_remove.empty_for",
+                        )
+                    })?
+                    .unwrap_or_default(),
+            ),
+        })
+    }
+}
+
+impl Into<proto::room::AutoRemove> for AutoRemove {
+    fn into(self) -> proto::room::AutoRemove {
+        use proto::room::auto_remove::Kind;
+
+        let (kind, empty_for) = match self {
+            Self::Never => (Kind::Never, None),
+            Self::AllLeft => (Kind::AllLeft, None),
+            Self::EmptyFor(duration) => (Kind::EmptyFor, Some(duration.into())),
+        };
+        proto::room::AutoRemove {
+            kind: kind as i32,
+            empty_for,
+        }
+    }
+}
+
 /// [Control API]'s `Room` element specification.
 ///
 /// Newtype for [`RootElement::Room`].
@@ -49,6 +141,48 @@ pub enum RoomElement {
 pub struct RoomSpec {
     pub id: Id,
     pub pipeline: Pipeline<MemberId, RoomElement>,
+
+    /// Rule of automatic removing of this `Room`.
+    pub auto_remove: AutoRemove,
+
+    /// URL of the callback to fire when this `Room` is removed automatically.
+    pub on_remove: Option<CallbackUrl>,
+
+    /// Labels and metadata of this `Room`.
+    pub meta: ElementMeta,
+}
+
+impl TryFrom<proto::Room> for RoomSpec {
+    type Error = TryFromProtobufError;
+
+    fn try_from(room: proto::Room) -> Result<Self, Self::Error> {
+        let mut pipeline = HashMap::new();
+        for (id, room_element) in room.pipeline {
+            if let Some(elem) = room_element.el {
+                let member =
+                    MemberSpec::try_from((MemberId(id.clone()), elem))?;
+                pipeline.insert(id.into(), member.into());
+            } else {
+                return Err(TryFromProtobufError::EmptyElement(id));
+            }
+        }
+
+        let auto_remove =
+            AutoRemove::try_from_proto(room.auto_remove, &room.id)?;
+        let on_remove = if room.on_remove.is_empty() {
+            None
+        } else {
+            Some(CallbackUrl::try_from(room.on_remove)?)
+        };
+
+        Ok(Self {
+            id: room.id.into(),
+            pipeline: Pipeline::new(pipeline),
+            auto_remove,
+            on_remove,
+            meta: ElementMeta::new(room.labels, room.metadata),
+        })
+    }
 }
 
 impl TryFrom<proto::create_request::El> for RoomSpec {
@@ -58,24 +192,7 @@ impl TryFrom<proto::create_request::El> for RoomSpec {
         use proto::create_request::El;
 
         let id = match proto {
-            El::Room(room) => {
-                let mut pipeline = HashMap::new();
-                for (id, room_element) in room.pipeline {
-                    if let Some(elem) = room_element.el {
-                        let member =
-                            MemberSpec::try_from((MemberId(id.clone()), elem))?;
-                        pipeline.insert(id.into(), member.into());
-                    } else {
-                        return Err(TryFromProtobufError::EmptyElement(id));
-                    }
-                }
-
-                let pipeline = Pipeline::new(pipeline);
-                return Ok(Self {
-                    id: room.id.into(),
-                    pipeline,
-                });
-            }
+            El::Room(room) => return Self::try_from(room),
             El::Member(member) => member.id,
             El::WebrtcPub(webrtc_pub) => webrtc_pub.id,
             El::WebrtcPlay(webrtc_play) => webrtc_play.id,
@@ -88,6 +205,24 @@ impl TryFrom<proto::create_request::El> for RoomSpec {
     }
 }
 
+impl TryFrom<proto::apply_request::El> for RoomSpec {
+    type Error = TryFromProtobufError;
+
+    fn try_from(proto: proto::apply_request::El) -> Result<Self, Self::Error> {
+        use proto::apply_request::El;
+
+        match proto {
+            El::Room(room) => Self::try_from(room),
+            El::Member(member) => {
+                Err(TryFromProtobufError::ExpectedOtherElement(
+                    String::from("Room"),
+                    member.id,
+                ))
+            }
+        }
+    }
+}
+
 impl RoomSpec {
     /// Returns all [`MemberSpec`]s of this [`RoomSpec`].
     ///
@@ -108,12 +243,37 @@ impl RoomSpec {
         Ok(members)
     }
 
+    /// Hashes plain credentials of all the `Member`s of this [`RoomSpec`] on
+    /// the blocking thread pool, so they're not hashed by `Room` itself.
+    pub async fn hash_credentials(mut self) -> Self {
+        credentials::blocking(move || {
+            for (_, element) in self.pipeline.iter_mut() {
+                let RoomElement::Member { credentials, .. } = element;
+                credentials.hash_in_place();
+            }
+            self
+        })
+        .await
+    }
+
     /// Returns ID of this [`RoomSpec`]
     pub fn id(&self) -> &Id {
         &self.id
     }
 }
 
+impl From<RoomSpec> for RootElement {
+    fn from(spec: RoomSpec) -> Self {
+        Self::Room {
+            id: spec.id,
+            spec: spec.pipeline,
+            auto_remove: spec.auto_remove,
+            on_remove: spec.on_remove,
+            meta: spec.meta,
+        }
+    }
+}
+
 impl TryFrom<&RootElement> for RoomSpec {
     type Error = TryFromElementError;
 
@@ -121,11 +281,140 @@ impl TryFrom<&RootElement> for RoomSpec {
     #[allow(unreachable_patterns)]
     fn try_from(from: &RootElement) -> Result<Self, Self::Error> {
         match from {
-            RootElement::Room { id, spec } => Ok(Self {
+            RootElement::Room {
+                id,
+                spec,
+                auto_remove,
+                on_remove,
+                meta,
+            } => Ok(Self {
                 id: id.clone(),
                 pipeline: spec.clone(),
+                auto_remove: *auto_remove,
+                on_remove: on_remove.clone(),
+                meta: meta.clone(),
             }),
             _ => Err(TryFromElementError::NotRoom),
         }
     }
 }
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    fn parse(yaml: &str) -> RoomSpec {
+        let root: RootElement = serde_yaml::from_str(yaml).unwrap();
+        RoomSpec::try_from(&root).unwrap()
+    }
+
+    #[test]
+    fn parses_auto_remove_rule() {
+        let spec = parse(
+            r#"
+            kind: Room
+            id: test
+            spec:
+              pipeline: {}
+            "#,
+        );
+        assert_eq!(spec.auto_remove, AutoRemove::Never);
+        assert!(spec.on_remove.is_none());
+
+        let spec = parse(
+            r#"
+            kind: Room
+            id: test
+            auto_remove: all_left
+            on_remove: grpc://127.0.0.1:9090
+            spec:
+              pipeline: {}
+            "#,
+        );
+        assert_eq!(spec.auto_remove, AutoRemove::AllLeft);
+        assert_eq!(
+            spec.on_remove.unwrap().to_string(),
+            "grpc://127.0.0.1:9090"
+        );
+
+        let spec = parse(
+            r#"
+            kind: Room
+            id: test
+            auto_remove:
+              empty_for: 1m 30s
+            spec:
+              pipeline: {}
+            "#,
+        );
+        assert_eq!(
+            spec.auto_remove,
+            AutoRemove::EmptyFor(Duration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn parses_labels_and_metadata() {
+        let spec = parse(
+            r#"
+            kind: Room
+            id: test
+            labels:
+              tenant: acme
+            metadata: '{"topic":"standup"}'
+            spec:
+              pipeline:
+                host:
+                  kind: Member
+                  credentials: test
+                  labels:
+                    role: host
+                  spec:
+                    pipeline:
+                      publish:
+                        kind: WebRtcPublishEndpoint
+                        spec:
+                          p2p: Always
+                          metadata: camera
+            "#,
+        );
+        assert_eq!(
+            spec.meta,
+            ElementMeta::new(
+                hashmap! {"tenant".to_string() => "acme".to_string()},
+                r#"{"topic":"standup"}"#.to_string(),
+            ),
+        );
+
+        let members = spec.members().unwrap();
+        let host = members.get(&MemberId::from("host")).unwrap();
+        assert_eq!(
+            host.meta().labels,
+            hashmap! {"role".to_string() => "host".to_string()},
+        );
+        assert!(host.meta().metadata.is_empty());
+
+        let (_, publish) = host.publish_endpoints().next().unwrap();
+        assert!(publish.meta.labels.is_empty());
+        assert_eq!(publish.meta.metadata, "camera");
+    }
+
+    #[test]
+    fn converts_auto_remove_rule_to_and_from_proto() {
+        for rule in &[
+            AutoRemove::Never,
+            AutoRemove::AllLeft,
+            AutoRemove::EmptyFor(Duration::from_secs(5)),
+        ] {
+            let proto: proto::room::AutoRemove = (*rule).into();
+            assert_eq!(
+                AutoRemove::try_from_proto(Some(proto), "test").unwrap(),
+                *rule
+            );
+        }
+        assert_eq!(
+            AutoRemove::try_from_proto(None, "test").unwrap(),
+            AutoRemove::Never
+        );
+    }
+}
@@ -0,0 +1,426 @@
+//! Validation of static [Control API] specs without starting them.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom as _,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use medea_client_api_proto::RoomId;
+use medea_control_api_proto::grpc::api as proto;
+use serde_yaml::Value;
+
+use crate::{
+    api::control::{
+        error_codes::{ErrorCode, ErrorResponse},
+        read_static_specs_dir,
+        refs::{Fid, SrcUri, ToEndpoint, ToMember},
+        MemberSpec, RoomSpec, RootElement,
+    },
+    conf::Rpc as RpcConf,
+    signalling::elements::{parse_members, MembersLoadError},
+};
+
+/// Problem found in a static [Control API] spec file.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug)]
+pub struct SpecProblem {
+    /// Path to the spec file with this [`SpecProblem`].
+    pub file: PathBuf,
+
+    /// Error describing this [`SpecProblem`], as it would be returned by
+    /// [Control API].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub error: proto::Error,
+}
+
+impl fmt::Display for SpecProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let element = if self.error.element.is_empty() {
+            "-"
+        } else {
+            self.error.element.as_str()
+        };
+        write!(
+            f,
+            "{}: {}: [{}] {}",
+            self.file.display(),
+            element,
+            self.error.code,
+            self.error.text,
+        )
+    }
+}
+
+/// Validates static [Control API] spec file, or all spec files of the
+/// directory, at provided path.
+///
+/// Specs are parsed the same way as on server startup, so returned
+/// [`SpecProblem`]s are exactly the ones which would prevent these specs
+/// from being started.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+pub fn validate_specs<P: AsRef<Path>>(
+    path: P,
+    rpc_conf: &RpcConf,
+) -> Vec<SpecProblem> {
+    let path = path.as_ref();
+    let files = if path.is_dir() {
+        read_static_specs_dir(path).map_err(|e| ErrorResponse::unexpected(&e))
+    } else {
+        fs::read_to_string(path)
+            .map(|content| vec![(path.to_path_buf(), content)])
+            .map_err(|e| ErrorResponse::unexpected(&e))
+    };
+    let files = match files {
+        Ok(files) => files,
+        Err(e) => {
+            return vec![SpecProblem {
+                file: path.to_path_buf(),
+                error: e.into(),
+            }];
+        }
+    };
+
+    let mut files = files;
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    // Specs of the same `Room` fail server startup, so the first file is
+    // considered the owner of the `Room`, and the others are reported.
+    let mut rooms: HashMap<RoomId, PathBuf> = HashMap::new();
+    let mut problems = Vec::new();
+    for (file, content) in files {
+        match validate_spec(&content, rpc_conf) {
+            Ok(room_id) => {
+                if let Some(first) = rooms.get(&room_id) {
+                    problems.push(SpecProblem {
+                        error: ErrorResponse::with_explanation(
+                            ErrorCode::RoomAlreadyExists,
+                            format!(
+                                "Room is already specified in {}",
+                                first.display(),
+                            ),
+                            Some(room_id.to_string()),
+                        )
+                        .into(),
+                        file,
+                    });
+                } else {
+                    rooms.insert(room_id, file);
+                }
+            }
+            Err(errors) => {
+                problems.extend(errors.into_iter().map(|error| SpecProblem {
+                    file: file.clone(),
+                    error,
+                }));
+            }
+        }
+    }
+    problems
+}
+
+/// Validates provided static [Control API] spec in YAML format, returning
+/// [`RoomId`] of the valid spec.
+///
+/// All invalid `local://` source URIs, and all `Member`s and endpoints which
+/// fail to be loaded, are reported at once, while other problems are
+/// reported one at a time, since they prevent further parsing. Errors are
+/// attributed to the `Room` whenever its ID can be read from the YAML.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+fn validate_spec(
+    yaml: &str,
+    rpc_conf: &RpcConf,
+) -> Result<RoomId, Vec<proto::Error>> {
+    let value: Value = serde_yaml::from_str(yaml).map_err(|e| {
+        vec![ErrorResponse::with_explanation(
+            ErrorCode::MalformedSpec,
+            e.to_string(),
+            None,
+        )
+        .into()]
+    })?;
+    let room_id = value["id"].as_str().map(ToOwned::to_owned);
+    let spec_error = |code, e: &dyn fmt::Display| {
+        vec![ErrorResponse::with_explanation(
+            code,
+            e.to_string(),
+            room_id.clone(),
+        )
+        .into()]
+    };
+
+    let invalid_src_uris = invalid_src_uris(&value);
+    if !invalid_src_uris.is_empty() {
+        return Err(invalid_src_uris);
+    }
+
+    let root: RootElement = serde_yaml::from_str(yaml)
+        .map_err(|e| spec_error(ErrorCode::MalformedSpec, &e))?;
+    let spec = RoomSpec::try_from(&root)
+        .map_err(|e| spec_error(ErrorCode::NotRoomInSpec, &e))?;
+
+    let invalid_members = invalid_members(&spec);
+    if !invalid_members.is_empty() {
+        return Err(invalid_members);
+    }
+    match parse_members(&spec, rpc_conf) {
+        Ok(_) => Ok(spec.id().clone()),
+        Err(e) => Err(vec![ErrorResponse::from(e).into()]),
+    }
+}
+
+/// Returns errors of all `Member`s of the provided [`RoomSpec`] which are not
+/// `Member`s actually, and of all `WebRtcPlayEndpoint`s referring to not
+/// existing `Member`s or `WebRtcPublishEndpoint`s.
+///
+/// Errors are sorted by the element they relate to.
+fn invalid_members(spec: &RoomSpec) -> Vec<proto::Error> {
+    let mut members = HashMap::new();
+    let mut errors: Vec<proto::Error> = Vec::new();
+    for (id, element) in &spec.pipeline {
+        match MemberSpec::try_from(element) {
+            Ok(member) => {
+                members.insert(id, member);
+            }
+            Err(e) => errors.push(
+                ErrorResponse::from(MembersLoadError::TryFromError(
+                    e,
+                    Fid::<ToMember>::new(spec.id.clone(), id.clone()).into(),
+                ))
+                .into(),
+            ),
+        }
+    }
+
+    for member in members.values() {
+        for (_, play) in member.play_endpoints() {
+            let src = &play.src;
+            let error = match members.get(&src.member_id) {
+                Some(publisher) => {
+                    if publisher
+                        .get_publish_endpoint_by_id(src.endpoint_id.clone())
+                        .is_some()
+                    {
+                        continue;
+                    }
+                    ErrorResponse::new(
+                        ErrorCode::EndpointNotFound,
+                        &Fid::<ToEndpoint>::new(
+                            spec.id.clone(),
+                            src.member_id.clone(),
+                            src.endpoint_id.clone().into(),
+                        ),
+                    )
+                }
+                // Not a `Member` element is reported above already.
+                None if spec.pipeline.get(&src.member_id).is_some() => {
+                    continue;
+                }
+                None => ErrorResponse::new(
+                    ErrorCode::MemberNotFound,
+                    &Fid::<ToMember>::new(
+                        spec.id.clone(),
+                        src.member_id.clone(),
+                    ),
+                ),
+            };
+            errors.push(error.into());
+        }
+    }
+    errors.sort_by(|a, b| a.element.cmp(&b.element));
+    errors
+}
+
+/// Returns errors of all invalid `src` URIs of `WebRtcPlayEndpoint`s in the
+/// provided raw YAML spec of `Room`.
+fn invalid_src_uris(room: &Value) -> Vec<proto::Error> {
+    let room_id = room["id"].as_str().unwrap_or_default();
+    let members = match room["spec"]["pipeline"].as_mapping() {
+        Some(members) => members,
+        None => return Vec::new(),
+    };
+
+    let mut errors = Vec::new();
+    for (member_id, member) in members {
+        let endpoints = match member["spec"]["pipeline"].as_mapping() {
+            Some(endpoints) => endpoints,
+            None => continue,
+        };
+        for (endpoint_id, endpoint) in endpoints {
+            if endpoint["kind"].as_str() != Some("WebRtcPlayEndpoint") {
+                continue;
+            }
+            let src = match endpoint["spec"]["src"].as_str() {
+                Some(src) => src,
+                None => continue,
+            };
+            if let Err(e) = SrcUri::try_from(src.to_owned()) {
+                let mut error: proto::Error = ErrorResponse::from(e).into();
+                error.element = format!(
+                    "{}/{}/{}",
+                    room_id,
+                    member_id.as_str().unwrap_or_default(),
+                    endpoint_id.as_str().unwrap_or_default(),
+                );
+                error.text = format!("{} [src = {}]", error.text, src);
+                errors.push(error);
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    const VALID_SPEC: &str =
+        include_str!("../../../tests/specs/pub-sub-video-call.yml");
+
+    fn codes(yaml: &str) -> Vec<u32> {
+        validate_spec(yaml, &RpcConf::default())
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| e.code)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_spec() {
+        assert!(codes(VALID_SPEC).is_empty());
+    }
+
+    #[test]
+    fn reports_all_invalid_src_uris() {
+        let spec = VALID_SPEC
+            .replace(
+                "local://pub-sub-video-call/caller/publish",
+                "remote://pub-sub-video-call/caller/publish",
+            )
+            .replace(
+                "      spec:\n        pipeline:\n          publish:",
+                "      spec:\n        pipeline:\n          play:\n            \
+                 kind: WebRtcPlayEndpoint\n            spec:\n              \
+                 src: \"local://pub-sub-video-call\"\n          publish:",
+            );
+        let errors = validate_spec(&spec, &RpcConf::default()).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        let caller = errors
+            .iter()
+            .find(|e| e.element == "pub-sub-video-call/caller/play")
+            .unwrap();
+        assert_eq!(caller.code, ErrorCode::NotSourceUri as u32);
+        let responder = errors
+            .iter()
+            .find(|e| e.element == "pub-sub-video-call/responder/play")
+            .unwrap();
+        assert_eq!(responder.code, ErrorCode::ElementIdIsNotLocal as u32);
+        assert!(responder.text.contains("remote://"));
+    }
+
+    #[test]
+    fn reports_missing_publishers() {
+        let spec = VALID_SPEC.replace(
+            "local://pub-sub-video-call/caller/publish",
+            "local://pub-sub-video-call/callee/publish",
+        );
+        let errors = validate_spec(&spec, &RpcConf::default()).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::MemberNotFound as u32);
+        assert_eq!(errors[0].element, "pub-sub-video-call/callee");
+    }
+
+    #[test]
+    fn reports_all_invalid_members() {
+        let spec = VALID_SPEC
+            .replace(
+                "local://pub-sub-video-call/caller/publish",
+                "local://pub-sub-video-call/callee/publish",
+            )
+            .replace(
+                "          play:\n",
+                "          screen:\n            \
+                 kind: WebRtcPlayEndpoint\n            spec:\n              \
+                 src: \"local://pub-sub-video-call/caller/screen\"\n          \
+                 play:\n",
+            );
+        let errors = validate_spec(&spec, &RpcConf::default()).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].code, ErrorCode::MemberNotFound as u32);
+        assert_eq!(errors[0].element, "pub-sub-video-call/callee");
+        assert_eq!(errors[1].code, ErrorCode::EndpointNotFound as u32);
+        assert_eq!(errors[1].element, "pub-sub-video-call/caller/screen");
+    }
+
+    #[test]
+    fn reports_malformed_specs() {
+        assert_eq!(
+            codes("kind: Room\nid: [\n"),
+            vec![ErrorCode::MalformedSpec as u32],
+        );
+        assert_eq!(
+            codes(&VALID_SPEC.replace("kind: Room", "kind: Hall")),
+            vec![ErrorCode::MalformedSpec as u32],
+        );
+    }
+
+    #[test]
+    fn attributes_spec_errors_to_room() {
+        let errors = validate_spec(
+            &VALID_SPEC.replace("kind: Room", "kind: Hall"),
+            &RpcConf::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(errors[0].element, "pub-sub-video-call");
+    }
+
+    #[test]
+    fn validates_specs_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("valid.yml"), VALID_SPEC).unwrap();
+        fs::write(dir.path().join("invalid.yml"), "kind: Room\n").unwrap();
+
+        let problems = validate_specs(dir.path(), &RpcConf::default());
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file, dir.path().join("invalid.yml"));
+        assert!(problems[0].to_string().starts_with(&format!(
+            "{}: -: [1027]",
+            problems[0].file.display()
+        )));
+    }
+
+    #[test]
+    fn reports_duplicated_rooms() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.yml"), VALID_SPEC).unwrap();
+        fs::write(dir.path().join("b.yml"), VALID_SPEC).unwrap();
+
+        let problems = validate_specs(dir.path(), &RpcConf::default());
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file, dir.path().join("b.yml"));
+        assert_eq!(problems[0].error.code, ErrorCode::RoomAlreadyExists as u32);
+        assert_eq!(problems[0].error.element, "pub-sub-video-call");
+    }
+
+    #[test]
+    fn reports_unreadable_path() {
+        let problems = validate_specs("/non/existent", &RpcConf::default());
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].error.code, ErrorCode::UnexpectedError as u32);
+    }
+}
@@ -7,11 +7,11 @@ use std::fmt::Debug;
 
 use actix::MailboxError;
 use futures::future::LocalBoxFuture;
-use medea_client_api_proto::{Command, Credential, MemberId};
+use medea_client_api_proto::{Command, MemberId};
 
 use crate::{
     api::client::rpc_connection::{
-        ClosedReason, RpcConnection, RpcConnectionSettings,
+        ClosedReason, RpcAuthorization, RpcConnection, RpcConnectionSettings,
     },
     signalling::room::RoomError,
 };
@@ -45,12 +45,17 @@ pub trait RpcServer: Debug + Send {
     /// [`Member`]. Transport should consider dropping connection if message
     /// result is err.
     ///
+    /// `last_seq` is a sequence number of the last [`Event`] received by the
+    /// reconnecting client, so only the [`Event`]s it has missed are replayed.
+    ///
+    /// [`Event`]: medea_client_api_proto::Event
     /// [`Member`]: crate::signalling::elements::member::Member
     fn connection_established(
         &self,
         member_id: MemberId,
-        credential: Credential,
+        authorization: RpcAuthorization,
         connection: Box<dyn RpcConnection>,
+        last_seq: Option<u32>,
     ) -> LocalBoxFuture<'static, Result<RpcConnectionSettings, RpcServerError>>;
 
     /// Send signal of existing [`RpcConnection`] of specified [`Member`] being
@@ -2,9 +2,13 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+use std::{borrow::Cow, convert::TryFrom as _, time::Duration};
+
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
+use crate::conf::Redis;
+
 /// [Control API] settings.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
@@ -16,27 +20,342 @@ pub struct ControlApi {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     #[default = "specs/"]
     pub static_specs_dir: String,
+
+    /// Interval of checking static [Control API] specs dir for changes.
+    ///
+    /// The dir is polled rather than watched, so changes are picked up with
+    /// up to this delay.
+    ///
+    /// New spec files start new `Room`s, changed ones are applied to their
+    /// running `Room`s and removed ones remove their `Room`s.
+    ///
+    /// `0` disables reloading of static [Control API] specs.
+    ///
+    /// Defaults to `5s`.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[default(Duration::from_secs(5))]
+    #[serde(with = "humantime_serde")]
+    pub static_specs_reload_interval: Duration,
+
+    /// Settings of [Control API] callbacks delivery.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub callbacks: Callbacks,
+
+    /// Settings of snapshots of [Control API] elements, which are restored
+    /// on server startup.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub snapshot: Snapshot,
+}
+
+/// Settings of [Control API] callbacks delivery.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Callbacks {
+    /// Maximum number of retries of a failed callback request before it's
+    /// considered undeliverable and put into the dead-letter store.
+    ///
+    /// `0` means that a callback request is attempted only once.
+    ///
+    /// Defaults to `5`.
+    #[default = 5]
+    pub max_retries: u32,
+
+    /// Delay before the first retry of a failed callback request.
+    ///
+    /// Defaults to `500ms`.
+    #[default(Duration::from_millis(500))]
+    #[serde(with = "humantime_serde")]
+    pub initial_backoff: Duration,
+
+    /// Maximum delay between retries of a failed callback request.
+    ///
+    /// Defaults to `30s`.
+    #[default(Duration::from_secs(30))]
+    #[serde(with = "humantime_serde")]
+    pub max_backoff: Duration,
+
+    /// Multiplier which delay is multiplied by after each retry.
+    ///
+    /// Defaults to `2.0`.
+    #[default = 2.0]
+    pub backoff_multiplier: f64,
+
+    /// Fraction of a delay which is randomly added to or subtracted from
+    /// it, so retries of different callback requests don't happen at the
+    /// same time.
+    ///
+    /// Defaults to `0.2`.
+    #[default = 0.2]
+    pub jitter: f64,
+
+    /// Per-URL circuit breaker settings.
+    pub circuit_breaker: CircuitBreaker,
+
+    /// Settings of the store for undeliverable callback requests.
+    pub dead_letter: DeadLetter,
+}
+
+impl Callbacks {
+    /// Returns delay before the provided retry `attempt` (starting from `1`)
+    /// without jitter applied.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let delay = self.initial_backoff.as_secs_f64()
+            * self.backoff_multiplier.max(1.0).powi(exp);
+        if delay.is_finite() && delay < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_backoff
+        }
+    }
+}
+
+/// Settings of a per-URL circuit breaker, which stops sending callback
+/// requests to a callback server after too many consecutive failures.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct CircuitBreaker {
+    /// Number of consecutive failures after which circuit breaker opens.
+    ///
+    /// `0` disables circuit breaker.
+    ///
+    /// Defaults to `5`.
+    #[default = 5]
+    pub failure_threshold: u32,
+
+    /// Duration after which opened circuit breaker allows a trial callback
+    /// request.
+    ///
+    /// Defaults to `30s`.
+    #[default(Duration::from_secs(30))]
+    #[serde(with = "humantime_serde")]
+    pub reset_timeout: Duration,
+}
+
+/// Kind of the store for undeliverable callback requests.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeadLetterStoreKind {
+    /// Undeliverable callback requests are appended to a file as
+    /// [JSON Lines](http://jsonlines.org).
+    File,
+
+    /// Undeliverable callback requests are pushed into a [Redis] list.
+    ///
+    /// [Redis]: https://redis.io
+    Redis,
+}
+
+impl Default for DeadLetterStoreKind {
+    #[inline]
+    fn default() -> Self {
+        Self::File
+    }
+}
+
+/// Settings of the store for undeliverable callback requests.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct DeadLetter {
+    /// Kind of the store to put undeliverable callback requests into.
+    ///
+    /// Defaults to `file`.
+    pub store: DeadLetterStoreKind,
+
+    /// Path to the file which undeliverable callback requests are appended
+    /// to, if `file` store is used.
+    ///
+    /// Defaults to `callbacks_dead_letters.jsonl`.
+    #[default = "callbacks_dead_letters.jsonl"]
+    pub file: Cow<'static, str>,
+
+    /// Key of the [Redis] list which undeliverable callback requests are
+    /// pushed into, if `redis` store is used.
+    ///
+    /// Defaults to `medea:callbacks:dead_letters`.
+    ///
+    /// [Redis]: https://redis.io
+    #[default = "medea:callbacks:dead_letters"]
+    pub redis_key: Cow<'static, str>,
+
+    /// [Redis] database settings, if `redis` store is used.
+    ///
+    /// [Redis]: https://redis.io
+    pub redis: Redis,
+}
+
+/// Kind of the store for snapshots of [Control API] elements.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotStoreKind {
+    /// Snapshots are not taken.
+    None,
+
+    /// Snapshots are written to a file as JSON.
+    File,
+
+    /// Snapshots are stored in a [Redis] key as JSON.
+    ///
+    /// [Redis]: https://redis.io
+    Redis,
+}
+
+impl Default for SnapshotStoreKind {
+    #[inline]
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// Settings of snapshots of [Control API] elements.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Snapshot {
+    /// Kind of the store to save snapshots into.
+    ///
+    /// Defaults to `none`, so snapshots are not taken.
+    pub store: SnapshotStoreKind,
+
+    /// Path to the file which snapshots are written to, if `file` store is
+    /// used.
+    ///
+    /// Defaults to `rooms_snapshot.json`.
+    #[default = "rooms_snapshot.json"]
+    pub file: Cow<'static, str>,
+
+    /// Key of [Redis] which snapshots are stored in, if `redis` store is
+    /// used.
+    ///
+    /// Defaults to `medea:rooms:snapshot`.
+    ///
+    /// [Redis]: https://redis.io
+    #[default = "medea:rooms:snapshot"]
+    pub redis_key: Cow<'static, str>,
+
+    /// [Redis] database settings, if `redis` store is used.
+    ///
+    /// [Redis]: https://redis.io
+    pub redis: Redis,
+
+    /// Delay between a change of [Control API] elements and saving a
+    /// snapshot, so bursts of changes are saved at once.
+    ///
+    /// Defaults to `1s`.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[default(Duration::from_secs(1))]
+    #[serde(with = "humantime_serde")]
+    pub save_delay: Duration,
 }
 
 #[cfg(test)]
 mod spec {
+    use std::time::Duration;
+
     use serial_test::serial;
 
     use crate::{conf::Conf, overrided_by_env_conf};
 
+    use super::{Callbacks, DeadLetterStoreKind, SnapshotStoreKind};
+
     #[test]
     #[serial]
     fn overrides_defaults() {
         let default_conf = Conf::default();
         let env_conf = overrided_by_env_conf!(
             "MEDEA_CONTROL__STATIC_SPECS_DIR" => "test/",
+            "MEDEA_CONTROL__STATIC_SPECS_RELOAD_INTERVAL" => "0s",
         );
 
         assert_ne!(
             default_conf.control.static_specs_dir,
             env_conf.control.static_specs_dir
         );
+        assert_ne!(
+            default_conf.control.static_specs_reload_interval,
+            env_conf.control.static_specs_reload_interval
+        );
 
         assert_eq!(env_conf.control.static_specs_dir, "test/");
+        assert_eq!(
+            env_conf.control.static_specs_reload_interval,
+            Duration::from_secs(0)
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn callbacks_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_CONTROL__CALLBACKS__MAX_RETRIES" => "10",
+            "MEDEA_CONTROL__CALLBACKS__INITIAL_BACKOFF" => "1s",
+            "MEDEA_CONTROL__CALLBACKS__CIRCUIT_BREAKER__FAILURE_THRESHOLD" => "0",
+            "MEDEA_CONTROL__CALLBACKS__DEAD_LETTER__STORE" => "redis",
+            "MEDEA_CONTROL__CALLBACKS__DEAD_LETTER__REDIS__PORT" => "1234",
+        );
+        let default = &default_conf.control.callbacks;
+        let env = &env_conf.control.callbacks;
+
+        assert_ne!(default.max_retries, env.max_retries);
+        assert_ne!(default.initial_backoff, env.initial_backoff);
+        assert_ne!(
+            default.circuit_breaker.failure_threshold,
+            env.circuit_breaker.failure_threshold,
+        );
+        assert_ne!(default.dead_letter.store, env.dead_letter.store);
+        assert_ne!(default.dead_letter.redis.port, env.dead_letter.redis.port);
+
+        assert_eq!(env.max_retries, 10);
+        assert_eq!(env.initial_backoff, Duration::from_secs(1));
+        assert_eq!(env.circuit_breaker.failure_threshold, 0);
+        assert_eq!(env.dead_letter.store, DeadLetterStoreKind::Redis);
+        assert_eq!(env.dead_letter.redis.port, 1234);
+    }
+
+    #[test]
+    #[serial]
+    fn snapshot_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_CONTROL__SNAPSHOT__STORE" => "file",
+            "MEDEA_CONTROL__SNAPSHOT__FILE" => "/tmp/snapshot.json",
+            "MEDEA_CONTROL__SNAPSHOT__SAVE_DELAY" => "100ms",
+        );
+        let default = &default_conf.control.snapshot;
+        let env = &env_conf.control.snapshot;
+
+        assert_ne!(default.store, env.store);
+        assert_ne!(default.file, env.file);
+        assert_ne!(default.save_delay, env.save_delay);
+
+        assert_eq!(env.store, SnapshotStoreKind::File);
+        assert_eq!(env.file, "/tmp/snapshot.json");
+        assert_eq!(env.save_delay, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let conf = Callbacks {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            backoff_multiplier: 2.0,
+            ..Callbacks::default()
+        };
+
+        assert_eq!(conf.backoff(1), Duration::from_millis(100));
+        assert_eq!(conf.backoff(2), Duration::from_millis(200));
+        assert_eq!(conf.backoff(3), Duration::from_millis(400));
+        assert_eq!(conf.backoff(4), Duration::from_millis(500));
+        assert_eq!(conf.backoff(100), Duration::from_millis(500));
     }
 }
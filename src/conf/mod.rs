@@ -27,7 +27,7 @@ pub use self::{
 
 /// CLI argument that is responsible for holding application configuration
 /// file path.
-static APP_CONF_PATH_CMD_ARG_NAME: &str = "--conf";
+pub static APP_CONF_PATH_CMD_ARG_NAME: &str = "--conf";
 /// Environment variable that is responsible for holding application
 /// configuration file path.
 static APP_CONF_PATH_ENV_VAR_NAME: &str = "MEDEA_CONF";
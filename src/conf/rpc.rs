@@ -1,12 +1,12 @@
 //! RPC connection settings.
 
-use std::time::Duration;
+use std::{borrow::Cow, time::Duration};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use smart_default::SmartDefault;
 
 /// RPC connection settings.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, SmartDefault)]
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
 #[serde(default)]
 pub struct Rpc {
     /// Duration, after which remote RPC client will be considered idle
@@ -40,6 +40,158 @@ pub struct Rpc {
     #[default(Duration::from_secs(3))]
     #[serde(with = "humantime_serde")]
     pub ping_interval: Duration,
+
+    /// Maximum number of the latest `Event`s kept for each `Member` to replay
+    /// them to its client reconnecting within `reconnect_timeout`.
+    ///
+    /// Client which has missed more `Event`s than this is resynchronized with
+    /// a full state snapshot instead.
+    ///
+    /// Defaults to `256`.
+    #[default(256)]
+    pub replay_buffer_size: usize,
+
+    /// Settings of authenticating `Member`s with signed [JWT]s.
+    ///
+    /// [JWT]: https://jwt.io
+    pub jwt: Jwt,
+
+    /// Limits of `Command`s accepted from a single RPC connection.
+    pub rate_limit: RateLimit,
+}
+
+/// Settings of authenticating `Member`s with signed [JWT]s instead of their
+/// credentials.
+///
+/// `Member` which doesn't exist yet is created once it presents a valid
+/// token, so it's not required to create it via Control API in advance.
+///
+/// Authentication with tokens is enabled if at least one verification key is
+/// specified.
+///
+/// [JWT]: https://jwt.io
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Jwt {
+    /// Secret to verify tokens signed with HMAC using SHA-256 (`HS256`)
+    /// with.
+    ///
+    /// Defaults to `None`.
+    pub hs256_secret: Option<Cow<'static, str>>,
+
+    /// Base64-encoded public key to verify tokens signed with Ed25519
+    /// (`EdDSA`) with.
+    ///
+    /// Defaults to `None`.
+    pub ed25519_public_key: Option<Cow<'static, str>>,
+
+    /// Allowed clock skew when checking expiration time of tokens.
+    ///
+    /// Defaults to `0s`.
+    #[default(Duration::from_secs(0))]
+    #[serde(with = "humantime_serde")]
+    pub leeway: Duration,
+}
+
+impl Jwt {
+    /// Indicates whether authentication with tokens is enabled.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.hs256_secret.is_some() || self.ed25519_public_key.is_some()
+    }
+}
+
+/// Limits of `Command`s accepted from a single RPC connection.
+///
+/// `Command`s exceeding these limits are dropped without reaching `Room`.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct RateLimit {
+    /// Limit of `JoinRoom` commands.
+    ///
+    /// Defaults to `1` per second with bursts of `5`.
+    #[default(CommandLimit::new(1, 5))]
+    pub join_room: CommandLimit,
+
+    /// Limit of `SetIceCandidate` commands.
+    ///
+    /// Defaults to `50` per second with bursts of `100`.
+    #[default(CommandLimit::new(50, 100))]
+    pub set_ice_candidate: CommandLimit,
+
+    /// Limit of `UpdateTracks` commands.
+    ///
+    /// Defaults to `10` per second with bursts of `20`.
+    #[default(CommandLimit::new(10, 20))]
+    pub update_tracks: CommandLimit,
+
+    /// Limit of `AddPeerConnectionMetrics` commands.
+    ///
+    /// Defaults to `20` per second with bursts of `50`.
+    #[default(CommandLimit::new(20, 50))]
+    pub add_peer_connection_metrics: CommandLimit,
+
+    /// Maximum number of `Command`s allowed to be dropped within
+    /// `abuse_period`, after which RPC connection is closed.
+    ///
+    /// Defaults to `100`.
+    #[default(100)]
+    pub max_dropped: u32,
+
+    /// Period to count dropped `Command`s within.
+    ///
+    /// Defaults to `10s`.
+    #[default(Duration::from_secs(10))]
+    #[serde(with = "humantime_serde")]
+    pub abuse_period: Duration,
+}
+
+/// [Token bucket][1] limit of a single `Command` kind.
+///
+/// Zero `per_second` disables the limit. Zero `burst` with non-zero
+/// `per_second` is rejected, since it would drop all `Command`s.
+///
+/// [1]: https://en.wikipedia.org/wiki/Token_bucket
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct CommandLimit {
+    /// Number of `Command`s allowed per second on average.
+    pub per_second: u32,
+
+    /// Maximum number of `Command`s allowed in a row.
+    pub burst: u32,
+}
+
+impl CommandLimit {
+    /// Creates new [`CommandLimit`] with the provided rate and burst.
+    #[inline]
+    #[must_use]
+    pub const fn new(per_second: u32, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+}
+
+impl<'de> Deserialize<'de> for CommandLimit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// [`CommandLimit`] which is not validated yet.
+        #[derive(Deserialize)]
+        struct Raw {
+            per_second: u32,
+            burst: u32,
+        }
+
+        let Raw { per_second, burst } = Raw::deserialize(deserializer)?;
+        if burst == 0 && per_second > 0 {
+            return Err(de::Error::custom(
+                "`burst` must be positive if `per_second` is, otherwise all \
+                 commands are dropped",
+            ));
+        }
+        Ok(Self::new(per_second, burst))
+    }
 }
 
 #[cfg(test)]
@@ -53,6 +205,8 @@ mod spec {
         overrided_by_env_conf,
     };
 
+    use super::CommandLimit;
+
     #[test]
     #[serial]
     fn overrides_defaults() {
@@ -60,6 +214,7 @@ mod spec {
         let env_conf = overrided_by_env_conf!(
             "MEDEA_RPC__IDLE_TIMEOUT" => "20s",
             "MEDEA_RPC__RECONNECT_TIMEOUT" => "30s",
+            "MEDEA_RPC__REPLAY_BUFFER_SIZE" => "16",
         );
 
         assert_ne!(default_conf.rpc.idle_timeout, env_conf.rpc.idle_timeout);
@@ -70,6 +225,70 @@ mod spec {
 
         assert_eq!(env_conf.rpc.idle_timeout, Duration::from_secs(20));
         assert_eq!(env_conf.rpc.reconnect_timeout, Duration::from_secs(30));
+        assert_ne!(
+            default_conf.rpc.replay_buffer_size,
+            env_conf.rpc.replay_buffer_size
+        );
+        assert_eq!(env_conf.rpc.replay_buffer_size, 16);
+    }
+
+    #[test]
+    #[serial]
+    fn overrides_jwt_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_RPC__JWT__HS256_SECRET" => "secret",
+            "MEDEA_RPC__JWT__LEEWAY" => "5s",
+        );
+
+        assert!(!default_conf.rpc.jwt.is_enabled());
+        assert!(env_conf.rpc.jwt.is_enabled());
+        assert_eq!(env_conf.rpc.jwt.hs256_secret.unwrap(), "secret");
+        assert_eq!(env_conf.rpc.jwt.ed25519_public_key, None);
+        assert_eq!(env_conf.rpc.jwt.leeway, Duration::from_secs(5));
+    }
+
+    #[test]
+    #[serial]
+    fn overrides_rate_limit_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_RPC__RATE_LIMIT__UPDATE_TRACKS__PER_SECOND" => "1",
+            "MEDEA_RPC__RATE_LIMIT__UPDATE_TRACKS__BURST" => "2",
+            "MEDEA_RPC__RATE_LIMIT__MAX_DROPPED" => "5",
+        );
+
+        assert_ne!(
+            default_conf.rpc.rate_limit.update_tracks,
+            env_conf.rpc.rate_limit.update_tracks
+        );
+        assert_eq!(
+            env_conf.rpc.rate_limit.update_tracks,
+            CommandLimit::new(1, 2)
+        );
+        assert_eq!(
+            env_conf.rpc.rate_limit.set_ice_candidate,
+            default_conf.rpc.rate_limit.set_ice_candidate
+        );
+        assert_eq!(env_conf.rpc.rate_limit.max_dropped, 5);
+    }
+
+    #[test]
+    #[serial]
+    fn rejects_zero_burst_rate_limit() {
+        let vars = [
+            ("MEDEA_RPC__RATE_LIMIT__JOIN_ROOM__PER_SECOND", "1"),
+            ("MEDEA_RPC__RATE_LIMIT__JOIN_ROOM__BURST", "0"),
+        ];
+        for (var, value) in &vars {
+            std::env::set_var(var, value);
+        }
+        let conf = Conf::parse();
+        for (var, _) in &vars {
+            std::env::remove_var(var);
+        }
+
+        assert!(conf.is_err());
     }
 
     #[test]
@@ -1,8 +1,15 @@
 //! Settings for application servers.
 
-use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs as _};
+use std::{
+    borrow::Cow,
+    fmt,
+    net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs as _},
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use smart_default::SmartDefault;
 
 /// [Client API] servers settings.
@@ -72,6 +79,11 @@ pub struct ControlApiServer {
     ///
     /// [Control API]: https://tinyurl.com/yxsqplq7
     pub grpc: ControlApiGrpcServer,
+
+    /// [Control API] HTTP REST server settings.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub http: ControlApiHttpServer,
 }
 
 /// [Control API] gRPC server settings.
@@ -91,6 +103,136 @@ pub struct ControlApiGrpcServer {
     /// Defaults to `6565`.
     #[default = 6565]
     pub bind_port: u16,
+
+    /// Static bearer tokens, one of which must be provided by [Control API]
+    /// clients in `authorization` metadata as `Bearer <token>`.
+    ///
+    /// May be specified as a comma-separated string.
+    ///
+    /// If empty, then no authentication is performed.
+    ///
+    /// Defaults to empty list.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[serde(deserialize_with = "deserialize_tokens")]
+    pub auth_tokens: Vec<String>,
+
+    /// TLS settings of gRPC server.
+    pub tls: ControlApiGrpcTls,
+}
+
+/// TLS settings of [Control API] gRPC server.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct ControlApiGrpcTls {
+    /// Path to PEM-encoded certificate (chain) of gRPC server.
+    ///
+    /// TLS is enabled only if both `cert` and `key` are specified.
+    ///
+    /// Defaults to `None`.
+    pub cert: Option<Cow<'static, str>>,
+
+    /// Path to PEM-encoded private key of gRPC server.
+    ///
+    /// Defaults to `None`.
+    pub key: Option<Cow<'static, str>>,
+
+    /// Path to PEM-encoded CA certificate to verify client certificates
+    /// with.
+    ///
+    /// If specified, then clients must present a certificate signed by this
+    /// CA.
+    ///
+    /// Defaults to `None`.
+    pub client_ca_cert: Option<Cow<'static, str>>,
+}
+
+/// Deserializes list of tokens either from a sequence or from a
+/// comma-separated string (as environment variables can't represent
+/// sequences).
+fn deserialize_tokens<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct TokensVisitor;
+
+    impl<'de> Visitor<'de> for TokensVisitor {
+        type Value = Vec<String>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("sequence of strings or comma-separated string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(v.split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(ToOwned::to_owned)
+                .collect())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut tokens = Vec::new();
+            while let Some(token) = seq.next_element()? {
+                tokens.push(token);
+            }
+            Ok(tokens)
+        }
+    }
+
+    deserializer.deserialize_any(TokensVisitor)
+}
+
+/// [Control API] HTTP REST server settings.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct ControlApiHttpServer {
+    /// IP address to bind HTTP REST server to.
+    ///
+    /// HTTP REST server performs no TLS, so it's bound to the loopback
+    /// interface by default.
+    ///
+    /// Defaults to `127.0.0.1`.
+    #[default(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)))]
+    pub bind_ip: IpAddr,
+
+    /// Port to bind HTTP REST server to.
+    ///
+    /// Defaults to `6566`.
+    #[default = 6566]
+    pub bind_port: u16,
+
+    /// Static bearer tokens, one of which must be provided by [Control API]
+    /// clients in `Authorization` header as `Bearer <token>`.
+    ///
+    /// May be specified as a comma-separated string.
+    ///
+    /// If empty, then no authentication is performed.
+    ///
+    /// Defaults to empty list.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[serde(deserialize_with = "deserialize_tokens")]
+    pub auth_tokens: Vec<String>,
+}
+
+impl ControlApiHttpServer {
+    /// Builds [`SocketAddr`] from `bind_ip` and `bind_port`.
+    #[inline]
+    pub fn bind_addr(&self) -> SocketAddr {
+        (self.bind_ip, self.bind_port)
+            .to_socket_addrs()
+            .unwrap()
+            .next()
+            .unwrap()
+    }
 }
 
 /// Settings for application servers.
@@ -178,4 +320,85 @@ mod control_grpc_spec {
             Ipv4Addr::new(182, 98, 12, 48)
         );
     }
+
+    #[test]
+    #[serial]
+    fn overrides_auth_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_SERVER__CONTROL__GRPC__AUTH_TOKENS" => "foo, bar",
+            "MEDEA_SERVER__CONTROL__GRPC__TLS__CERT" => "/tls/server.crt",
+            "MEDEA_SERVER__CONTROL__GRPC__TLS__KEY" => "/tls/server.key",
+            "MEDEA_SERVER__CONTROL__GRPC__TLS__CLIENT_CA_CERT" => "/tls/ca.crt",
+        );
+
+        assert!(default_conf.server.control.grpc.auth_tokens.is_empty());
+        assert!(default_conf.server.control.grpc.tls.cert.is_none());
+        assert!(default_conf.server.control.grpc.tls.key.is_none());
+        assert!(default_conf
+            .server
+            .control
+            .grpc
+            .tls
+            .client_ca_cert
+            .is_none());
+
+        let grpc = env_conf.server.control.grpc;
+        assert_eq!(grpc.auth_tokens, vec!["foo", "bar"]);
+        assert_eq!(grpc.tls.cert.as_deref(), Some("/tls/server.crt"));
+        assert_eq!(grpc.tls.key.as_deref(), Some("/tls/server.key"));
+        assert_eq!(grpc.tls.client_ca_cert.as_deref(), Some("/tls/ca.crt"));
+    }
+}
+
+#[cfg(test)]
+mod control_http_spec {
+    use std::net::Ipv4Addr;
+
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults_and_gets_bind_addr() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_SERVER__CONTROL__HTTP__BIND_IP" => "182.98.12.48",
+            "MEDEA_SERVER__CONTROL__HTTP__BIND_PORT" => "44444",
+        );
+
+        assert_ne!(
+            default_conf.server.control.http.bind_ip,
+            env_conf.server.control.http.bind_ip
+        );
+        assert_ne!(
+            default_conf.server.control.http.bind_port,
+            env_conf.server.control.http.bind_port
+        );
+        assert_eq!(env_conf.server.control.http.bind_port, 44444);
+        assert_eq!(
+            env_conf.server.control.http.bind_ip,
+            Ipv4Addr::new(182, 98, 12, 48)
+        );
+        assert_eq!(
+            env_conf.server.control.http.bind_addr(),
+            "182.98.12.48:44444".parse().unwrap(),
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn overrides_auth_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_SERVER__CONTROL__HTTP__AUTH_TOKENS" => "foo, bar",
+        );
+
+        assert!(default_conf.server.control.http.auth_tokens.is_empty());
+        assert_eq!(
+            env_conf.server.control.http.auth_tokens,
+            vec!["foo", "bar"]
+        );
+    }
 }
@@ -17,8 +17,11 @@ pub mod turn;
 use std::sync::Arc;
 
 use crate::{
-    api::control::callback::{
-        clients::CallbackClientFactoryImpl, service::CallbackService,
+    api::control::{
+        callback::{
+            clients::CallbackClientFactoryImpl, service::CallbackService,
+        },
+        element_events::ElementEvents,
     },
     conf::Conf,
     turn::TurnAuthService,
@@ -39,16 +42,23 @@ pub struct AppContext {
     ///
     /// [`CallbackEvent`]: crate::api::control::callbacks::CallbackEvent
     pub callbacks: CallbackService<CallbackClientFactoryImpl>,
+
+    /// Hub of [Control API] elements lifecycle events.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub element_events: ElementEvents,
 }
 
 impl AppContext {
     /// Creates new [`AppContext`].
     #[inline]
     pub fn new(config: Conf, turn: Arc<dyn TurnAuthService>) -> Self {
+        let callbacks = CallbackService::new(&config.control.callbacks);
         Self {
             config: Arc::new(config),
             turn_service: turn,
-            callbacks: CallbackService::default(),
+            callbacks,
+            element_events: ElementEvents::default(),
         }
     }
 }
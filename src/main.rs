@@ -1,22 +1,40 @@
 //! Medea media server application.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, env, path::PathBuf, process, sync::Arc};
 
 use actix::{Actor, Arbiter, System};
 use failure::Error;
 use futures::FutureExt as _;
 use medea::{
-    api::{client::server::Server, control::grpc},
-    conf::Conf,
+    api::{
+        client::{
+            server::Server,
+            token::{MemberTokenAuthorizer, TokenValidator},
+        },
+        control::{grpc, http, validation::validate_specs},
+    },
+    conf::{Conf, APP_CONF_PATH_CMD_ARG_NAME},
     log::{self, prelude::*},
     shutdown::{self, GracefulShutdown},
-    signalling::{room_repo::RoomRepository, room_service::RoomService},
+    signalling::{
+        room_repo::RoomRepository,
+        room_service::{RestoreRooms, RoomService},
+    },
     turn::new_turn_auth_service,
     AppContext,
 };
 
+/// Name of CLI subcommand validating [`Conf`] and static [Control API] specs
+/// without starting the server.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+const VALIDATE_CMD: &str = "validate";
+
 fn main() -> Result<(), Error> {
     dotenv::dotenv().ok();
+    if env::args().nth(1).as_deref() == Some(VALIDATE_CMD) {
+        process::exit(validate());
+    }
     let config = Conf::parse()?;
 
     if let Some(lvl) = config.log.level() {
@@ -46,10 +64,23 @@ fn main() -> Result<(), Error> {
             .start();
 
             medea::api::control::start_static_rooms(&room_service).await?;
+            // Server should start even if snapshot cannot be restored.
+            if let Err(e) = room_service.send(RestoreRooms).await? {
+                error!("Rooms are not restored from snapshot: {}", e);
+            }
 
+            let token_authorizer =
+                TokenValidator::new(&config.rpc.jwt)?.map(|validator| {
+                    MemberTokenAuthorizer::new(
+                        Arc::new(validator),
+                        room_service.clone(),
+                    )
+                });
+            let http_server =
+                http::server::HttpServer::run(room_service.clone(), &config)?;
             let grpc_server =
-                grpc::server::run(room_service, &app_context).await;
-            let server = Server::run(room_repo, config)?;
+                grpc::server::run(room_service, &app_context).await?;
+            let server = Server::run(room_repo, token_authorizer, config)?;
 
             shutdown::subscribe(
                 &graceful_shutdown,
@@ -57,6 +88,12 @@ fn main() -> Result<(), Error> {
                 shutdown::Priority(1),
             );
 
+            shutdown::subscribe(
+                &graceful_shutdown,
+                http_server.recipient(),
+                shutdown::Priority(1),
+            );
+
             shutdown::subscribe(
                 &graceful_shutdown,
                 server.recipient(),
@@ -71,3 +108,57 @@ fn main() -> Result<(), Error> {
     );
     sys.run().map_err(Into::into)
 }
+
+/// Validates [`Conf`] and static [Control API] specs, printing all the found
+/// problems to `stderr`.
+///
+/// Usage: `medea validate [--conf <file>] [<specs path>]`, where specs path is
+/// either a spec file or a directory of spec files, and defaults to
+/// `control.static_specs_dir` option.
+///
+/// Returns exit code of the process: `0` if everything is valid, or `1`
+/// otherwise.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+fn validate() -> i32 {
+    let config = match Conf::parse() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {}", e);
+            return 1;
+        }
+    };
+    let mut is_valid = true;
+    if let Err(e) = TokenValidator::new(&config.rpc.jwt) {
+        eprintln!("Invalid configuration: rpc.jwt: {}", e);
+        is_valid = false;
+    }
+
+    let mut args = env::args().skip(2);
+    let mut specs_path = None;
+    while let Some(arg) = args.next() {
+        if arg == APP_CONF_PATH_CMD_ARG_NAME {
+            args.next();
+        } else if specs_path.is_none() {
+            specs_path = Some(PathBuf::from(arg));
+        }
+    }
+    let specs_path = specs_path
+        .unwrap_or_else(|| PathBuf::from(&config.control.static_specs_dir));
+
+    let problems = validate_specs(&specs_path, &config.rpc);
+    for problem in &problems {
+        eprintln!("{}", problem);
+    }
+    is_valid &= problems.is_empty();
+
+    if is_valid {
+        println!(
+            "Configuration and specs at {} are valid",
+            specs_path.display()
+        );
+        0
+    } else {
+        1
+    }
+}
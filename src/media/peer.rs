@@ -362,6 +362,10 @@ pub enum TrackChange {
 
     /// ICE restart request.
     IceRestart,
+
+    /// Update of the indicator whether this [`Peer`] must be forcibly
+    /// connected through TURN.
+    ForceRelay(bool),
 }
 
 impl TrackChange {
@@ -372,7 +376,9 @@ impl TrackChange {
     fn as_new_track(&self, partner_member_id: MemberId) -> Option<Track> {
         match self.as_track_update(partner_member_id) {
             TrackUpdate::Added(track) => Some(track),
-            TrackUpdate::Updated(_) | TrackUpdate::IceRestart => None,
+            TrackUpdate::Updated(_)
+            | TrackUpdate::IceRestart
+            | TrackUpdate::ForceRelay(_) => None,
         }
     }
 
@@ -381,7 +387,7 @@ impl TrackChange {
         match self {
             Self::AddSendTrack(track) => TrackUpdate::Added(Track {
                 id: track.id,
-                media_type: track.media_type.clone(),
+                media_type: track.media_type(),
                 direction: Direction::Send {
                     receivers: vec![partner_member_id],
                     mid: track.mid(),
@@ -389,7 +395,7 @@ impl TrackChange {
             }),
             Self::AddRecvTrack(track) => TrackUpdate::Added(Track {
                 id: track.id,
-                media_type: track.media_type.clone(),
+                media_type: track.media_type(),
                 direction: Direction::Recv {
                     sender: partner_member_id,
                     mid: track.mid(),
@@ -400,6 +406,9 @@ impl TrackChange {
                 TrackUpdate::Updated(track_patch.clone())
             }
             Self::IceRestart => TrackUpdate::IceRestart,
+            Self::ForceRelay(is_force_relayed) => {
+                TrackUpdate::ForceRelay(*is_force_relayed)
+            }
         }
     }
 
@@ -408,7 +417,8 @@ impl TrackChange {
         match self {
             Self::AddSendTrack(_)
             | Self::AddRecvTrack(_)
-            | Self::IceRestart => false,
+            | Self::IceRestart
+            | Self::ForceRelay(_) => false,
             Self::TrackPatch(_) | Self::PartnerTrackPatch(_) => true,
         }
     }
@@ -444,6 +454,15 @@ impl<T> TrackChangeHandler for Peer<T> {
                 patch.enabled_general = Some(rx.is_media_exchange_enabled());
             };
         }
+        if let Some(required) = patch.required {
+            let track = self
+                .senders()
+                .get(&patch.id)
+                .or_else(|| self.receivers().get(&patch.id));
+            if let Some(track) = track {
+                track.set_required(required);
+            }
+        }
 
         TrackChange::TrackPatch(patch)
     }
@@ -480,6 +499,14 @@ impl<T> TrackChangeHandler for Peer<T> {
     fn on_ice_restart(&mut self) -> Self::Output {
         TrackChange::IceRestart
     }
+
+    /// Sets [`Context::is_force_relayed`] to the provided value.
+    #[inline]
+    fn on_force_relay(&mut self, is_force_relayed: bool) -> Self::Output {
+        self.context.is_force_relayed = is_force_relayed;
+
+        TrackChange::ForceRelay(is_force_relayed)
+    }
 }
 
 /// Deduper of the [`TrackPatchEvent`]s.
@@ -1008,17 +1035,34 @@ impl<'a> PeerChangesScheduler<'a> {
         }
     }
 
+    /// Schedules provided [`TrackPatchEvent`]s made by Media Server as
+    /// [`TrackChange::TrackPatch`].
+    pub fn server_patch_tracks(&mut self, patches: Vec<TrackPatchEvent>) {
+        for patch in patches {
+            self.schedule_change(TrackChange::TrackPatch(patch));
+        }
+    }
+
     /// Schedules [`TrackChange::IceRestart`].
     #[inline]
     pub fn restart_ice(&mut self) {
         self.schedule_change(TrackChange::IceRestart);
     }
 
+    /// Schedules [`TrackChange::ForceRelay`] along with the
+    /// [`TrackChange::IceRestart`], so new relaying policy is applied by the
+    /// remote `Peer`.
+    pub fn set_force_relay(&mut self, is_force_relayed: bool) {
+        self.schedule_change(TrackChange::ForceRelay(is_force_relayed));
+        self.restart_ice();
+    }
+
     /// Schedules `send` tracks adding to `self` and `recv` tracks for this
     /// `send` to `partner_peer`.
     ///
     /// Tracks will be added based on [`WebRtcPublishEndpoint::audio_settings`]
-    /// and [`WebRtcPublishEndpoint::video_settings`].
+    /// and [`WebRtcPublishEndpoint::video_settings`], and remembered in the
+    /// provided [`WebRtcPublishEndpoint`] as published by it.
     pub fn add_publisher(
         &mut self,
         src: &WebRtcPublishEndpoint,
@@ -1033,6 +1077,7 @@ impl<'a> PeerChangesScheduler<'a> {
                     required: audio_settings.publish_policy.required(),
                 }),
             ));
+            src.add_track_id(track_audio.id);
             self.add_sender(Rc::clone(&track_audio));
             partner_peer
                 .as_changes_scheduler()
@@ -1048,6 +1093,7 @@ impl<'a> PeerChangesScheduler<'a> {
                     source_kind: MediaSourceKind::Device,
                 }),
             ));
+            src.add_track_id(camera_video_track.id);
             self.add_sender(Rc::clone(&camera_video_track));
             partner_peer
                 .as_changes_scheduler()
@@ -1059,6 +1105,7 @@ impl<'a> PeerChangesScheduler<'a> {
                     source_kind: MediaSourceKind::Display,
                 }),
             ));
+            src.add_track_id(display_video_track.id);
             self.add_sender(Rc::clone(&display_video_track));
             partner_peer
                 .as_changes_scheduler()
@@ -1392,12 +1439,14 @@ pub mod tests {
                 id: TrackId(0),
                 enabled_individual: None,
                 enabled_general: None,
+                required: None,
             }),
             TrackChange::IceRestart,
             TrackChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(0),
                 enabled_individual: None,
                 enabled_general: None,
+                required: None,
             }),
         ];
 
@@ -1458,16 +1507,19 @@ pub mod tests {
                 id: TrackId(0),
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             }),
             TrackChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(0),
                 enabled_general: Some(true),
                 enabled_individual: Some(true),
+                required: None,
             }),
             TrackChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(1),
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             }),
         ];
         peer.as_changes_scheduler().patch_tracks(vec![
@@ -1498,6 +1550,78 @@ pub mod tests {
         }
     }
 
+    /// Checks that [`PeerChangesScheduler::set_force_relay`] updates
+    /// [`Peer`] with a [`TrackChange::ForceRelay`] followed by a
+    /// [`TrackChange::IceRestart`].
+    #[test]
+    fn force_relay_update_restarts_ice() {
+        let mut negotiation_sub = MockPeerUpdatesSubscriber::new();
+        negotiation_sub
+            .expect_negotiation_needed()
+            .times(1)
+            .returning(|peer_id| assert_eq!(peer_id, PeerId(0)));
+        let mut peer = Peer::new(
+            PeerId(0),
+            MemberId::from("member-1"),
+            PeerId(1),
+            MemberId::from("member-2"),
+            false,
+            Rc::new(negotiation_sub),
+        );
+
+        peer.as_changes_scheduler().set_force_relay(true);
+        assert!(!peer.is_force_relayed());
+        peer.commit_scheduled_changes();
+
+        assert!(peer.is_force_relayed());
+        let updates = peer.get_updates();
+        assert_eq!(updates.len(), 2);
+        assert!(matches!(updates[0], TrackUpdate::ForceRelay(true)));
+        assert!(matches!(updates[1], TrackUpdate::IceRestart));
+    }
+
+    /// Checks that server-sourced [`TrackPatchEvent`] with `required` flag
+    /// updates [`MediaTrack`] and may be applied forcibly.
+    #[test]
+    fn server_track_patch_updates_required() {
+        let mut peer_updates_sub = MockPeerUpdatesSubscriber::new();
+        peer_updates_sub
+            .expect_negotiation_needed()
+            .returning(|_: PeerId| {});
+        peer_updates_sub.expect_force_update().times(1).returning(
+            |peer_id, changes| {
+                assert_eq!(peer_id, PeerId(0));
+                assert_eq!(changes.len(), 1);
+                if let TrackUpdate::Updated(patch) = &changes[0] {
+                    assert_eq!(patch.id, TrackId(0));
+                    assert_eq!(patch.required, Some(false));
+                } else {
+                    unreachable!();
+                }
+            },
+        );
+        let mut peer = Peer::new(
+            PeerId(0),
+            MemberId::from("member-1"),
+            PeerId(1),
+            MemberId::from("member-2"),
+            false,
+            Rc::new(peer_updates_sub),
+        );
+        let track = media_track(0);
+        peer.as_changes_scheduler().add_sender(Rc::clone(&track));
+        peer.commit_scheduled_changes();
+        let mut peer = peer.start_as_offerer();
+
+        let mut patch = TrackPatchEvent::new(TrackId(0));
+        patch.required = Some(false);
+        peer.as_changes_scheduler().server_patch_tracks(vec![patch]);
+        peer.inner_force_commit_scheduled_changes();
+
+        assert!(!track.media_type().required());
+        assert!(peer.context.track_changes_queue.is_empty());
+    }
+
     /// Tests for the [`TrackPatchDeduper`].
     mod track_patch_deduper {
         use super::*;
@@ -1512,11 +1636,13 @@ pub mod tests {
                 id: TrackId(2),
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             });
             let whitelisted_patch = TrackChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(1),
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
+                required: None,
             });
             let mut patches =
                 vec![whitelisted_patch.clone(), filtered_patch.clone()];
@@ -1539,26 +1665,31 @@ pub mod tests {
                     id: TrackId(1),
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
+                    required: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(2),
                     enabled_general: Some(false),
                     enabled_individual: Some(false),
+                    required: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: Some(false),
                     enabled_individual: Some(false),
+                    required: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: None,
                     enabled_individual: None,
+                    required: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(2),
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
+                    required: None,
                 },
             ]
             .into_iter()
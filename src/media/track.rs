@@ -13,7 +13,7 @@ use medea_client_api_proto::{MediaType, TrackId as Id};
 pub struct MediaTrack {
     pub id: Id,
     mid: RefCell<Option<String>>,
-    pub media_type: MediaType,
+    media_type: RefCell<MediaType>,
     transceiver_enabled: Cell<bool>,
     media_exchange_state: RefCell<MediaExchangeState>,
 }
@@ -26,12 +26,28 @@ impl MediaTrack {
         Self {
             id,
             mid: RefCell::new(None),
-            media_type,
+            media_type: RefCell::new(media_type),
             transceiver_enabled: Cell::new(true),
             media_exchange_state: RefCell::new(MediaExchangeState::new()),
         }
     }
 
+    /// Returns [`MediaType`] of this [`MediaTrack`].
+    #[inline]
+    #[must_use]
+    pub fn media_type(&self) -> MediaType {
+        self.media_type.borrow().clone()
+    }
+
+    /// Sets indicator whether this [`MediaTrack`] is required to call
+    /// starting.
+    pub fn set_required(&self, required: bool) {
+        match &mut *self.media_type.borrow_mut() {
+            MediaType::Audio(audio) => audio.required = required,
+            MediaType::Video(video) => video.required = required,
+        }
+    }
+
     #[inline]
     pub fn set_mid(&self, mid: String) {
         self.mid.borrow_mut().replace(mid);
@@ -5,12 +5,20 @@
 pub mod webrtc;
 
 use derive_more::From;
+use medea_client_api_proto::PeerId;
 use medea_control_api_proto::grpc::api as proto;
 use medea_macro::enum_delegate;
 
-use crate::signalling::elements::endpoints::webrtc::{
-    play_endpoint::WeakWebRtcPlayEndpoint,
-    publish_endpoint::WeakWebRtcPublishEndpoint,
+use crate::{
+    api::control::{
+        callback::{url::CallbackUrl, MediaDirection, MediaType},
+        refs::{Fid, ToEndpoint},
+        ElementMeta,
+    },
+    signalling::elements::endpoints::webrtc::{
+        play_endpoint::WeakWebRtcPlayEndpoint,
+        publish_endpoint::WeakWebRtcPublishEndpoint,
+    },
 };
 
 /// Enum which can store all kinds of [Medea] endpoints.
@@ -18,6 +26,23 @@ use crate::signalling::elements::endpoints::webrtc::{
 /// [Medea]: https://github.com/instrumentisto/medea
 #[enum_delegate(pub fn is_force_relayed(&self) -> bool)]
 #[enum_delegate(pub fn has_traffic_callback(&self) -> bool)]
+#[enum_delegate(pub fn get_on_start(&self) -> Option<CallbackUrl>)]
+#[enum_delegate(pub fn get_on_stop(&self) -> Option<CallbackUrl>)]
+#[enum_delegate(pub fn meta(&self) -> ElementMeta)]
+#[enum_delegate(
+    pub fn traffic_started(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType>
+)]
+#[enum_delegate(
+    pub fn traffic_stopped(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType>
+)]
 #[derive(Clone, Debug, From)]
 pub enum Endpoint {
     WebRtcPublishEndpoint(webrtc::WebRtcPublishEndpoint),
@@ -25,6 +50,30 @@ pub enum Endpoint {
 }
 
 impl Endpoint {
+    /// Returns [`MediaDirection`] of this [`Endpoint`].
+    pub fn direction(&self) -> MediaDirection {
+        match self {
+            Self::WebRtcPublishEndpoint(_) => MediaDirection::Publish,
+            Self::WebRtcPlayEndpoint(_) => MediaDirection::Play,
+        }
+    }
+
+    /// Returns [`Fid`] of this [`Endpoint`].
+    ///
+    /// # Panics
+    ///
+    /// If pointer to the owner `Member` has been dropped.
+    pub fn fid(&self) -> Fid<ToEndpoint> {
+        match self {
+            Self::WebRtcPublishEndpoint(publish) => {
+                publish.owner().get_fid_to_endpoint(publish.id().into())
+            }
+            Self::WebRtcPlayEndpoint(play) => {
+                play.owner().get_fid_to_endpoint(play.id().into())
+            }
+        }
+    }
+
     /// Returns [`Weak`] reference to this [`Endpoint`].
     pub fn downgrade(&self) -> WeakEndpoint {
         match self {
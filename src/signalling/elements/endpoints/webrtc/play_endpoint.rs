@@ -10,11 +10,17 @@ use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
     api::control::{
-        endpoints::webrtc_play_endpoint::WebRtcPlayId as Id, refs::SrcUri,
+        callback::{url::CallbackUrl, MediaType},
+        endpoints::webrtc_play_endpoint::WebRtcPlayId as Id,
+        refs::SrcUri,
+        ElementMeta,
     },
-    signalling::elements::{
-        endpoints::webrtc::publish_endpoint::WeakWebRtcPublishEndpoint,
-        member::WeakMember, Member,
+    signalling::{
+        elements::{
+            endpoints::webrtc::publish_endpoint::WeakWebRtcPublishEndpoint,
+            member::WeakMember, Member,
+        },
+        peers::EndpointTrafficState,
     },
 };
 
@@ -48,6 +54,18 @@ struct WebRtcPlayEndpointInner {
     /// Indicator whether only `relay` ICE candidates are allowed for this
     /// [`WebRtcPlayEndpoint`].
     is_force_relayed: bool,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    on_stop: Option<CallbackUrl>,
+
+    /// Labels and metadata of this [`WebRtcPlayEndpoint`].
+    meta: ElementMeta,
+
+    /// Traffic state of the [`Peer`] of this [`WebRtcPlayEndpoint`].
+    traffic_state: EndpointTrafficState,
 }
 
 impl WebRtcPlayEndpointInner {
@@ -110,6 +128,10 @@ impl WebRtcPlayEndpoint {
             owner,
             peer_id: None,
             is_force_relayed,
+            on_start: None,
+            on_stop: None,
+            meta: ElementMeta::default(),
+            traffic_state: EndpointTrafficState::default(),
         })))
     }
 
@@ -169,12 +191,74 @@ impl WebRtcPlayEndpoint {
     }
 
     /// Returns `true` if `on_start` or `on_stop` callback is set.
-    #[allow(clippy::unused_self)]
     #[inline]
     pub fn has_traffic_callback(&self) -> bool {
-        // TODO: Must depend on on_start/on_stop endpoint callbacks, when those
-        //       will be added (#91).
-        true
+        let inner = self.0.borrow();
+        inner.on_start.is_some() || inner.on_stop.is_some()
+    }
+
+    /// Sets `on_start` and `on_stop` [`CallbackUrl`]s of this
+    /// [`WebRtcPlayEndpoint`].
+    pub fn set_callback_urls(
+        &self,
+        on_start: Option<CallbackUrl>,
+        on_stop: Option<CallbackUrl>,
+    ) {
+        let mut inner = self.0.borrow_mut();
+        inner.on_start = on_start;
+        inner.on_stop = on_stop;
+    }
+
+    /// Returns [`CallbackUrl`] to which Medea should send `OnStart` callback.
+    pub fn get_on_start(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_start.clone()
+    }
+
+    /// Returns [`CallbackUrl`] to which Medea should send `OnStop` callback.
+    pub fn get_on_stop(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_stop.clone()
+    }
+
+    /// Returns labels and metadata of this [`WebRtcPlayEndpoint`].
+    pub fn meta(&self) -> ElementMeta {
+        self.0.borrow().meta.clone()
+    }
+
+    /// Sets labels and metadata of this [`WebRtcPlayEndpoint`].
+    pub fn set_meta(&self, meta: ElementMeta) {
+        self.0.borrow_mut().meta = meta;
+    }
+
+    /// Marks provided [`MediaType`] as started in the [`Peer`] with provided
+    /// [`PeerId`].
+    ///
+    /// Returns [`MediaType`] which has started flowing in this
+    /// [`WebRtcPlayEndpoint`], or `None` if it was already flowing.
+    pub fn traffic_started(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType> {
+        self.0
+            .borrow_mut()
+            .traffic_state
+            .started(peer_id, media_type)
+    }
+
+    /// Marks provided [`MediaType`] as stopped in the [`Peer`] with provided
+    /// [`PeerId`].
+    ///
+    /// Returns [`MediaType`] which has stopped flowing in this
+    /// [`WebRtcPlayEndpoint`], or `None` if it wasn't flowing.
+    pub fn traffic_stopped(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType> {
+        self.0
+            .borrow_mut()
+            .traffic_state
+            .stopped(peer_id, media_type)
     }
 
     /// Downgrades [`WebRtcPlayEndpoint`] to [`WeakWebRtcPlayEndpoint`] weak
@@ -223,12 +307,22 @@ impl Into<proto::member::Element> for WebRtcPlayEndpoint {
 
 impl Into<proto::WebRtcPlayEndpoint> for WebRtcPlayEndpoint {
     fn into(self) -> proto::WebRtcPlayEndpoint {
+        let meta = self.meta();
         proto::WebRtcPlayEndpoint {
-            on_start: String::new(),
-            on_stop: String::new(),
+            on_start: self
+                .get_on_start()
+                .map(|c| c.to_unredacted_string())
+                .unwrap_or_default(),
+            on_stop: self
+                .get_on_stop()
+                .map(|c| c.to_unredacted_string())
+                .unwrap_or_default(),
             src: self.src_uri().to_string(),
             id: self.id().to_string(),
             force_relay: self.is_force_relayed(),
+            version: 0,
+            labels: meta.labels,
+            metadata: meta.metadata,
         }
     }
 }
@@ -6,16 +6,23 @@ use std::{
     rc::{Rc, Weak},
 };
 
-use medea_client_api_proto::PeerId;
+use medea_client_api_proto::{PeerId, TrackId};
 use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
-    api::control::endpoints::webrtc_publish_endpoint::{
-        AudioSettings, P2pMode, VideoSettings, WebRtcPublishId as Id,
+    api::control::{
+        callback::{url::CallbackUrl, MediaType},
+        endpoints::webrtc_publish_endpoint::{
+            AudioSettings, P2pMode, VideoSettings, WebRtcPublishId as Id,
+        },
+        ElementMeta,
     },
-    signalling::elements::{
-        endpoints::webrtc::play_endpoint::WeakWebRtcPlayEndpoint,
-        member::WeakMember, Member,
+    signalling::{
+        elements::{
+            endpoints::webrtc::play_endpoint::WeakWebRtcPlayEndpoint,
+            member::WeakMember, Member,
+        },
+        peers::EndpointTrafficState,
     },
 };
 
@@ -51,6 +58,22 @@ struct WebRtcPublishEndpointInner {
     /// while removing [`WebRtcPublishEndpoint`] for removing all [`Peer`]s of
     /// this [`WebRtcPublishEndpoint`].
     peer_ids: HashSet<PeerId>,
+
+    /// [`TrackId`]s of all the `MediaTrack`s published by this
+    /// [`WebRtcPublishEndpoint`] into its [`Peer`]s.
+    track_ids: HashSet<TrackId>,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    on_stop: Option<CallbackUrl>,
+
+    /// Labels and metadata of this [`WebRtcPublishEndpoint`].
+    meta: ElementMeta,
+
+    /// Traffic state of all [`Peer`]s of this [`WebRtcPublishEndpoint`].
+    traffic_state: EndpointTrafficState,
 }
 
 impl Drop for WebRtcPublishEndpointInner {
@@ -92,7 +115,8 @@ impl WebRtcPublishEndpointInner {
     }
 
     fn reset(&mut self) {
-        self.peer_ids = HashSet::new()
+        self.peer_ids = HashSet::new();
+        self.track_ids = HashSet::new();
     }
 
     #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -133,6 +157,11 @@ impl WebRtcPublishEndpoint {
             audio_settings,
             video_settings,
             peer_ids: HashSet::new(),
+            track_ids: HashSet::new(),
+            on_start: None,
+            on_stop: None,
+            meta: ElementMeta::default(),
+            traffic_state: EndpointTrafficState::default(),
         })))
     }
 
@@ -170,9 +199,21 @@ impl WebRtcPublishEndpoint {
         self.0.borrow().peer_ids()
     }
 
+    /// Adds [`TrackId`] of the `MediaTrack` published by this
+    /// [`WebRtcPublishEndpoint`].
+    pub fn add_track_id(&self, track_id: TrackId) {
+        self.0.borrow_mut().track_ids.insert(track_id);
+    }
+
+    /// Indicates whether `MediaTrack` with the provided [`TrackId`] is
+    /// published by this [`WebRtcPublishEndpoint`].
+    pub fn is_track_published(&self, track_id: TrackId) -> bool {
+        self.0.borrow().track_ids.contains(&track_id)
+    }
+
     /// Resets state of this [`WebRtcPublishEndpoint`].
     ///
-    /// _Atm this only resets `peer_ids`._
+    /// _Atm this only resets `peer_ids` and `track_ids`._
     pub fn reset(&self) {
         self.0.borrow_mut().reset()
     }
@@ -213,13 +254,83 @@ impl WebRtcPublishEndpoint {
         self.0.borrow().is_force_relayed
     }
 
+    /// Sets indicator whether only `relay` ICE candidates are allowed for this
+    /// [`WebRtcPublishEndpoint`].
+    pub fn set_force_relayed(&self, is_force_relayed: bool) {
+        self.0.borrow_mut().is_force_relayed = is_force_relayed;
+    }
+
     /// Returns `true` if `on_start` or `on_stop` callback is set.
-    #[allow(clippy::unused_self)]
     #[inline]
     pub fn has_traffic_callback(&self) -> bool {
-        // TODO: Must depend on on_start/on_stop endpoint callbacks, when those
-        //       will be added (#91).
-        true
+        let inner = self.0.borrow();
+        inner.on_start.is_some() || inner.on_stop.is_some()
+    }
+
+    /// Sets `on_start` and `on_stop` [`CallbackUrl`]s of this
+    /// [`WebRtcPublishEndpoint`].
+    pub fn set_callback_urls(
+        &self,
+        on_start: Option<CallbackUrl>,
+        on_stop: Option<CallbackUrl>,
+    ) {
+        let mut inner = self.0.borrow_mut();
+        inner.on_start = on_start;
+        inner.on_stop = on_stop;
+    }
+
+    /// Returns [`CallbackUrl`] to which Medea should send `OnStart` callback.
+    pub fn get_on_start(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_start.clone()
+    }
+
+    /// Returns [`CallbackUrl`] to which Medea should send `OnStop` callback.
+    pub fn get_on_stop(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_stop.clone()
+    }
+
+    /// Returns labels and metadata of this [`WebRtcPublishEndpoint`].
+    pub fn meta(&self) -> ElementMeta {
+        self.0.borrow().meta.clone()
+    }
+
+    /// Sets labels and metadata of this [`WebRtcPublishEndpoint`].
+    pub fn set_meta(&self, meta: ElementMeta) {
+        self.0.borrow_mut().meta = meta;
+    }
+
+    /// Marks provided [`MediaType`] as started in the [`Peer`] with provided
+    /// [`PeerId`].
+    ///
+    /// Returns [`MediaType`] which has started flowing in this
+    /// [`WebRtcPublishEndpoint`], or `None` if it was already flowing in some
+    /// other [`Peer`].
+    pub fn traffic_started(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType> {
+        self.0
+            .borrow_mut()
+            .traffic_state
+            .started(peer_id, media_type)
+    }
+
+    /// Marks provided [`MediaType`] as stopped in the [`Peer`] with provided
+    /// [`PeerId`].
+    ///
+    /// Returns [`MediaType`] which has stopped flowing in this
+    /// [`WebRtcPublishEndpoint`], or `None` if it's still flowing in some
+    /// other [`Peer`].
+    pub fn traffic_stopped(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType> {
+        self.0
+            .borrow_mut()
+            .traffic_state
+            .stopped(peer_id, media_type)
     }
 
     /// Returns [`AudioSettings`] of this [`WebRtcPublishEndpoint`].
@@ -232,6 +343,16 @@ impl WebRtcPublishEndpoint {
         self.0.borrow().video_settings
     }
 
+    /// Sets [`AudioSettings`] of this [`WebRtcPublishEndpoint`].
+    pub fn set_audio_settings(&self, audio_settings: AudioSettings) {
+        self.0.borrow_mut().audio_settings = audio_settings;
+    }
+
+    /// Sets [`VideoSettings`] of this [`WebRtcPublishEndpoint`].
+    pub fn set_video_settings(&self, video_settings: VideoSettings) {
+        self.0.borrow_mut().video_settings = video_settings;
+    }
+
     /// Downgrades [`WebRtcPublishEndpoint`] to weak pointer
     /// [`WeakWebRtcPublishEndpoint`].
     pub fn downgrade(&self) -> WeakWebRtcPublishEndpoint {
@@ -271,14 +392,24 @@ impl WeakWebRtcPublishEndpoint {
 impl Into<proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
     fn into(self) -> proto::WebRtcPublishEndpoint {
         let p2p: proto::web_rtc_publish_endpoint::P2p = self.p2p().into();
+        let meta = self.meta();
         proto::WebRtcPublishEndpoint {
             p2p: p2p as i32,
             id: self.id().to_string(),
             force_relay: self.is_force_relayed(),
             audio_settings: Some(self.audio_settings().into()),
             video_settings: Some(self.video_settings().into()),
-            on_stop: String::new(),
-            on_start: String::new(),
+            on_stop: self
+                .get_on_stop()
+                .map(|c| c.to_unredacted_string())
+                .unwrap_or_default(),
+            on_start: self
+                .get_on_start()
+                .map(|c| c.to_unredacted_string())
+                .unwrap_or_default(),
+            version: 0,
+            labels: meta.labels,
+            metadata: meta.metadata,
         }
     }
 }
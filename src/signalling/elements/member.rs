@@ -18,10 +18,11 @@ use medea_control_api_proto::grpc::api as proto;
 use crate::{
     api::control::{
         callback::url::CallbackUrl,
+        credentials::{CredentialHash, VerifiedCredential},
         endpoints::WebRtcPlayEndpoint as WebRtcPlayEndpointSpec,
         refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
-        EndpointId, MemberSpec, RoomSpec, TryFromElementError, WebRtcPlayId,
-        WebRtcPublishId,
+        ElementMeta, EndpointId, MemberSpec, RoomSpec, TryFromElementError,
+        WebRtcPlayId, WebRtcPublishId,
     },
     conf::Rpc as RpcConf,
     log::prelude::*,
@@ -80,8 +81,18 @@ struct MemberInner {
     /// All [`WebRtcPlayEndpoint`]s of this [`Member`].
     sinks: HashMap<WebRtcPlayId, WebRtcPlayEndpoint>,
 
-    /// Credentials for this [`Member`].
-    credentials: Credential,
+    /// Hash of credentials for this [`Member`].
+    ///
+    /// Plain credentials are never stored.
+    credentials: CredentialHash,
+
+    /// Last [`Credential`] verified against
+    /// [`MemberInner::credentials`].
+    verified_credential: VerifiedCredential,
+
+    /// Indicator whether some [`Credential`] is being verified against
+    /// [`MemberInner::credentials`] at the moment.
+    is_verifying_credential: bool,
 
     /// URL to which `on_join` Control API callback will be sent.
     on_join: Option<CallbackUrl>,
@@ -102,6 +113,9 @@ struct MemberInner {
 
     /// Interval of sending heartbeat `Ping`s to the [`Member`] via Client API.
     ping_interval: Duration,
+
+    /// Labels and metadata of this [`Member`].
+    meta: ElementMeta,
 }
 
 impl Member {
@@ -111,7 +125,7 @@ impl Member {
     /// function.
     pub fn new(
         id: MemberId,
-        credentials: Credential,
+        credentials: CredentialHash,
         room_id: RoomId,
         idle_timeout: Duration,
         reconnect_timeout: Duration,
@@ -122,12 +136,15 @@ impl Member {
             srcs: HashMap::new(),
             sinks: HashMap::new(),
             credentials,
+            verified_credential: VerifiedCredential::default(),
+            is_verifying_credential: false,
             room_id,
             on_leave: None,
             on_join: None,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            meta: ElementMeta::default(),
         })))
     }
 
@@ -174,6 +191,7 @@ impl Member {
             .ok_or_else(|| MembersLoadError::MemberNotFound(self.get_fid()))?;
 
         this_member.set_callback_urls(&this_member_spec);
+        this_member.set_meta(this_member_spec.meta().clone());
 
         for (spec_play_name, spec_play_endpoint) in
             this_member_spec.play_endpoints()
@@ -212,6 +230,11 @@ impl Member {
                     this_member.downgrade(),
                     spec_play_endpoint.force_relay,
                 );
+                new_play_endpoint.set_callback_urls(
+                    spec_play_endpoint.on_start.clone(),
+                    spec_play_endpoint.on_stop.clone(),
+                );
+                new_play_endpoint.set_meta(spec_play_endpoint.meta.clone());
 
                 self.insert_sink(new_play_endpoint.clone());
 
@@ -226,6 +249,11 @@ impl Member {
                     publisher_endpoint.audio_settings,
                     publisher_endpoint.video_settings,
                 );
+                new_publish.set_callback_urls(
+                    publisher_endpoint.on_start.clone(),
+                    publisher_endpoint.on_stop.clone(),
+                );
+                new_publish.set_meta(publisher_endpoint.meta.clone());
 
                 let new_self_play = WebRtcPlayEndpoint::new(
                     spec_play_name,
@@ -234,6 +262,11 @@ impl Member {
                     this_member.downgrade(),
                     spec_play_endpoint.force_relay,
                 );
+                new_self_play.set_callback_urls(
+                    spec_play_endpoint.on_start.clone(),
+                    spec_play_endpoint.on_stop.clone(),
+                );
+                new_self_play.set_meta(spec_play_endpoint.meta.clone());
 
                 new_publish.add_sink(new_self_play.downgrade());
 
@@ -249,14 +282,17 @@ impl Member {
             .publish_endpoints()
             .filter(|(endpoint_id, _)| self.srcs().get(endpoint_id).is_none())
             .for_each(|(endpoint_id, e)| {
-                self.insert_src(WebRtcPublishEndpoint::new(
+                let src = WebRtcPublishEndpoint::new(
                     endpoint_id,
                     e.p2p,
                     this_member.downgrade(),
                     e.force_relay,
                     e.audio_settings,
                     e.video_settings,
-                ));
+                );
+                src.set_callback_urls(e.on_start.clone(), e.on_stop.clone());
+                src.set_meta(e.meta.clone());
+                self.insert_src(src);
             });
 
         Ok(())
@@ -295,16 +331,69 @@ impl Member {
             .for_each(|(_, p)| p.reset());
     }
 
+    /// Returns [`PeerId`]s of all the [`Peer`]s of this [`Member`]'s
+    /// `Endpoint`s.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    pub fn peer_ids(&self) -> Vec<PeerId> {
+        self.sinks()
+            .values()
+            .filter_map(WebRtcPlayEndpoint::peer_id)
+            .chain(
+                self.srcs()
+                    .values()
+                    .flat_map(WebRtcPublishEndpoint::peer_ids),
+            )
+            .collect()
+    }
+
     /// Returns [`MemberId`] of this [`Member`].
     pub fn id(&self) -> MemberId {
         self.0.borrow().id.clone()
     }
 
-    /// Returns credentials of this [`Member`].
-    pub fn credentials(&self) -> Credential {
+    /// Returns hash of credentials of this [`Member`].
+    pub fn credentials_hash(&self) -> CredentialHash {
         self.0.borrow().credentials.clone()
     }
 
+    /// Replaces credentials of this [`Member`] with the provided ones.
+    pub fn set_credentials(&self, credentials: CredentialHash) {
+        let mut inner = self.0.borrow_mut();
+        inner.credentials = credentials;
+        inner.verified_credential.forget();
+    }
+
+    /// Remembers provided [`Credential`] as the one verified against
+    /// credentials of this [`Member`].
+    pub fn remember_verified_credential(&self, credential: &Credential) {
+        self.0.borrow_mut().verified_credential.remember(credential);
+    }
+
+    /// Indicates whether provided [`Credential`] is known to be valid for
+    /// this [`Member`] without verifying it slowly.
+    pub fn is_verified_credential(&self, credential: &Credential) -> bool {
+        self.0.borrow().verified_credential.is(credential)
+    }
+
+    /// Marks this [`Member`] as having some [`Credential`] being verified
+    /// slowly.
+    ///
+    /// Returns `false` if another [`Credential`] is being verified already,
+    /// so only one slow verification per [`Member`] runs at a time.
+    pub fn start_credential_verification(&self) -> bool {
+        !std::mem::replace(
+            &mut self.0.borrow_mut().is_verifying_credential,
+            true,
+        )
+    }
+
+    /// Marks slow verification of some [`Credential`] of this [`Member`] as
+    /// finished.
+    pub fn finish_credential_verification(&self) {
+        self.0.borrow_mut().is_verifying_credential = false;
+    }
+
     /// Returns all srcs of this [`Member`].
     pub fn srcs(&self) -> HashMap<WebRtcPublishId, WebRtcPublishEndpoint> {
         self.0.borrow().srcs.clone()
@@ -403,6 +492,8 @@ impl Member {
             member.downgrade(),
             spec.force_relay,
         );
+        sink.set_callback_urls(spec.on_start, spec.on_stop);
+        sink.set_meta(spec.meta);
 
         src.add_sink(sink.downgrade());
         member.insert_sink(sink);
@@ -482,6 +573,29 @@ impl Member {
         self.0.borrow_mut().on_leave = spec.on_leave().clone();
         self.0.borrow_mut().on_join = spec.on_join().clone();
     }
+
+    /// Returns labels and metadata of this [`Member`].
+    pub fn meta(&self) -> ElementMeta {
+        self.0.borrow().meta.clone()
+    }
+
+    /// Sets labels and metadata of this [`Member`].
+    pub fn set_meta(&self, meta: ElementMeta) {
+        self.0.borrow_mut().meta = meta;
+    }
+
+    /// Sets Client API RPC settings of this [`Member`].
+    pub fn set_rpc_settings(
+        &self,
+        idle_timeout: Duration,
+        reconnect_timeout: Duration,
+        ping_interval: Duration,
+    ) {
+        let mut inner = self.0.borrow_mut();
+        inner.idle_timeout = idle_timeout;
+        inner.reconnect_timeout = reconnect_timeout;
+        inner.ping_interval = ping_interval;
+    }
 }
 
 /// Weak pointer to [`Member`].
@@ -515,7 +629,7 @@ impl WeakMember {
 /// Errors with [`MembersLoadError`] if loading [`Member`] fails.
 pub fn parse_members(
     room_spec: &RoomSpec,
-    rpc_conf: RpcConf,
+    rpc_conf: &RpcConf,
 ) -> Result<HashMap<MemberId, Member>, MembersLoadError> {
     let members_spec = room_spec.members().map_err(|e| {
         MembersLoadError::TryFromError(
@@ -529,7 +643,7 @@ pub fn parse_members(
         .map(|(id, member)| {
             let new_member = Member::new(
                 id.clone(),
-                member.credentials().clone(),
+                member.credentials().hash(),
                 room_spec.id.clone(),
                 member.idle_timeout().unwrap_or(rpc_conf.idle_timeout),
                 member
@@ -537,6 +651,9 @@ pub fn parse_members(
                     .unwrap_or(rpc_conf.reconnect_timeout),
                 member.ping_interval().unwrap_or(rpc_conf.ping_interval),
             );
+            if let Some(plain) = member.credentials().plain() {
+                new_member.remember_verified_credential(plain);
+            }
             (id.clone(), new_member)
         })
         .collect();
@@ -581,22 +698,28 @@ impl Into<proto::Member> for Member {
                     .map(|(id, publish)| (id.to_string(), publish.into())),
             )
             .collect();
+        let meta = self.meta();
 
         proto::Member {
             id: self.id().to_string(),
-            credentials: self.credentials().to_string(),
+            credentials: Some(proto::member::Credentials::Hash(
+                self.credentials_hash().into(),
+            )),
             on_leave: self
                 .get_on_leave()
-                .map(|c| c.to_string())
+                .map(|c| c.to_unredacted_string())
                 .unwrap_or_default(),
             on_join: self
                 .get_on_join()
-                .map(|c| c.to_string())
+                .map(|c| c.to_unredacted_string())
                 .unwrap_or_default(),
             reconnect_timeout: Some(self.get_reconnect_timeout().into()),
             idle_timeout: Some(self.get_idle_timeout().into()),
             ping_interval: Some(self.get_ping_interval().into()),
             pipeline: member_pipeline,
+            version: 0,
+            labels: meta.labels,
+            metadata: meta.metadata,
         }
     }
 }
@@ -672,7 +795,7 @@ mod tests {
         let room_element: RootElement =
             serde_yaml::from_str(TEST_SPEC).unwrap();
         let room_spec = RoomSpec::try_from(&room_element).unwrap();
-        parse_members(&room_spec, RpcConf::default()).unwrap()
+        parse_members(&room_spec, &RpcConf::default()).unwrap()
     }
 
     #[test]
@@ -9,6 +9,7 @@ pub mod peers;
 pub mod room;
 pub mod room_repo;
 pub mod room_service;
+pub mod snapshot;
 
 #[doc(inline)]
 pub use self::room::Room;
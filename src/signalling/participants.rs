@@ -7,7 +7,10 @@
 //! [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
 //! [`ParticipantService`]: crate::signalling::participants::ParticipantService
 
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
 
 use actix::{
     fut::wrap_future, AsyncContext, Context, ContextFutureSpawner as _,
@@ -17,17 +20,19 @@ use derive_more::Display;
 use failure::Fail;
 use futures::future::{self, FutureExt as _, LocalBoxFuture};
 use medea_client_api_proto::{
-    CloseDescription, CloseReason, Credential, Event, MemberId, RoomId,
+    CloseDescription, CloseReason, Event, MemberId, RoomId,
 };
 
 use crate::{
     api::{
         client::rpc_connection::{
             ClosedReason, RpcConnection, RpcConnectionClosed,
+            RpcConnectionSettings,
         },
         control::{
-            refs::{Fid, ToEndpoint, ToMember},
-            MemberSpec, RoomSpec,
+            element_events::{ElementEventKind, ElementEvents},
+            refs::{Fid, StatefulFid, ToEndpoint, ToMember},
+            EndpointId, MemberPatch, MemberSpec, RoomSpec,
         },
     },
     conf::Rpc as RpcConf,
@@ -66,6 +71,90 @@ impl From<MemberError> for ParticipantServiceErr {
     }
 }
 
+/// Outcome of establishing [`RpcConnection`] with some [`Member`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Resumption {
+    /// [`Member`] had no session to resume, or its client hasn't asked to
+    /// resume it, so nothing is replayed.
+    Started,
+
+    /// [`Member`] resumed its session, and all the [`Event`]s missed by its
+    /// client have been replayed.
+    Replayed,
+
+    /// [`Member`] resumed its session, but the [`Event`]s missed by its
+    /// client are not buffered anymore, so it should be resynchronized with a
+    /// full state snapshot.
+    Overflowed,
+}
+
+/// Bounded buffer of the latest [`Event`]s sent to some [`Member`], which
+/// allows to replay them to its reconnecting client.
+#[derive(Debug)]
+struct ReplayBuffer {
+    /// Sequence number of the last [`Event`] pushed into this
+    /// [`ReplayBuffer`].
+    last_seq: u32,
+
+    /// Maximum number of [`Event`]s kept in this [`ReplayBuffer`].
+    capacity: usize,
+
+    /// Buffered [`Event`]s along with their sequence numbers, starting from
+    /// the oldest one.
+    events: VecDeque<(u32, Event)>,
+}
+
+impl ReplayBuffer {
+    /// Creates new empty [`ReplayBuffer`] keeping up to `capacity` [`Event`]s.
+    fn new(capacity: usize) -> Self {
+        Self {
+            last_seq: 0,
+            capacity,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Pushes the provided [`Event`] into this [`ReplayBuffer`], evicting the
+    /// oldest one if it's full.
+    ///
+    /// Returns sequence number assigned to the pushed [`Event`].
+    fn push(&mut self, event: Event) -> u32 {
+        self.last_seq += 1;
+        if self.capacity > 0 {
+            if self.events.len() == self.capacity {
+                self.events.pop_front();
+            }
+            self.events.push_back((self.last_seq, event));
+        }
+        self.last_seq
+    }
+
+    /// Returns all the [`Event`]s pushed after the one with the provided
+    /// sequence number.
+    ///
+    /// Returns [`None`] if some of them have been evicted already, or if the
+    /// provided sequence number is unknown to this [`ReplayBuffer`].
+    fn events_after(&self, seq: u32) -> Option<Vec<(u32, Event)>> {
+        if seq > self.last_seq {
+            return None;
+        }
+        let oldest_seq = self
+            .events
+            .front()
+            .map_or(self.last_seq + 1, |(seq, _)| *seq);
+        if seq + 1 < oldest_seq {
+            return None;
+        }
+        Some(
+            self.events
+                .iter()
+                .filter(|(event_seq, _)| *event_seq > seq)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
 /// Participant is [`Member`] with [`RpcConnection`]. [`ParticipantService`]
 /// stores [`Member`]s and associated [`RpcConnection`]s, handles
 /// [`RpcConnection`] authorization, establishment, message sending.
@@ -84,6 +173,10 @@ pub struct ParticipantService {
     //       as the set of all possible RpcConnection types is not closed.
     connections: HashMap<MemberId, Box<dyn RpcConnection>>,
 
+    /// [`ReplayBuffer`]s of the [`Event`]s sent to [`Member`]s, kept while
+    /// their [`RpcConnection`]s exist (including the lost ones).
+    replay_buffers: HashMap<MemberId, ReplayBuffer>,
+
     /// Stores [`RpcConnection`] drop tasks.
     /// If [`RpcConnection`] is lost, [`Room`] waits for `connect_timeout`
     /// before dropping it irrevocably in case it gets reestablished.
@@ -94,6 +187,10 @@ pub struct ParticipantService {
     /// If nothing provided into [`Member`] element spec then this values will
     /// be used.
     rpc_conf: RpcConf,
+
+    /// Hub which lifecycle events of [`Member`]s, their endpoints and
+    /// [`RpcConnection`]s are published to.
+    element_events: ElementEvents,
 }
 
 impl ParticipantService {
@@ -108,13 +205,56 @@ impl ParticipantService {
     ) -> Result<Self, MembersLoadError> {
         Ok(Self {
             room_id: room_spec.id().clone(),
-            members: parse_members(room_spec, context.config.rpc)?,
+            members: parse_members(room_spec, &context.config.rpc)?,
             connections: HashMap::new(),
+            replay_buffers: HashMap::new(),
             drop_connection_tasks: HashMap::new(),
-            rpc_conf: context.config.rpc,
+            rpc_conf: context.config.rpc.clone(),
+            element_events: context.element_events.clone(),
         })
     }
 
+    /// Publishes [`ElementEventKind`] event of the element with provided
+    /// [`StatefulFid`].
+    pub fn publish_element_event<F: Into<StatefulFid>>(
+        &self,
+        fid: F,
+        kind: ElementEventKind,
+    ) {
+        self.element_events.publish(fid, kind);
+    }
+
+    /// Publishes [`ElementEventKind`] event of provided [`Member`] and all its
+    /// endpoints.
+    fn publish_member_tree_event(
+        &self,
+        member: &Member,
+        kind: ElementEventKind,
+    ) {
+        let endpoint_ids = member
+            .srcs()
+            .into_iter()
+            .map(|(id, _)| EndpointId::from(id))
+            .chain(
+                member
+                    .sinks()
+                    .into_iter()
+                    .map(|(id, _)| EndpointId::from(id)),
+            );
+        if kind == ElementEventKind::Created {
+            self.publish_element_event(member.get_fid(), kind);
+        }
+        for endpoint_id in endpoint_ids {
+            self.publish_element_event(
+                member.get_fid_to_endpoint(endpoint_id),
+                kind,
+            );
+        }
+        if kind == ElementEventKind::Deleted {
+            self.publish_element_event(member.get_fid(), kind);
+        }
+    }
+
     /// Lookups [`Member`] by provided [`MemberId`].
     ///
     /// ## Errors
@@ -164,35 +304,14 @@ impl ParticipantService {
         self.members.clone()
     }
 
-    /// Lookups [`Member`] by provided [`MemberId`] and credentials.
-    ///
-    /// # Errors
-    ///
-    /// Errors with [`RoomError::AuthorizationError`] if lookup by [`MemberId`]
-    /// fails or if [`Member`] was found, but incorrect credentials were
-    /// provided.
-    pub fn get_member_by_id_and_credentials(
-        &self,
-        member_id: &MemberId,
-        credentials: &Credential,
-    ) -> Result<Member, RoomError> {
-        let member = self
-            .get_member_by_id(member_id)
-            .map_err(|_| RoomError::AuthorizationError)?;
-        if &member.credentials() == credentials {
-            Ok(member)
-        } else {
-            Err(RoomError::AuthorizationError)
-        }
-    }
-
     /// Checks if [`Member`] has __active__ [`RpcConnection`].
     pub fn member_has_connection(&self, member_id: &MemberId) -> bool {
         self.connections.contains_key(member_id)
             && !self.drop_connection_tasks.contains_key(member_id)
     }
 
-    /// Sends [`Event`] to specified remote [`Member`].
+    /// Sends [`Event`] to specified remote [`Member`], storing it in the
+    /// [`Member`]'s [`ReplayBuffer`].
     ///
     /// # Errors
     ///
@@ -203,24 +322,75 @@ impl ParticipantService {
         member_id: MemberId,
         event: Event,
     ) -> Result<(), RoomError> {
-        self.connections.get(&member_id).map_or(
-            Err(RoomError::ConnectionNotExists(member_id)),
-            |conn| {
-                conn.send_event(self.room_id.clone(), event);
-                Ok(())
-            },
-        )
+        let conn = if let Some(conn) = self.connections.get(&member_id) {
+            conn
+        } else {
+            return Err(RoomError::ConnectionNotExists(member_id));
+        };
+        let capacity = self.rpc_conf.replay_buffer_size;
+        let seq = self
+            .replay_buffers
+            .entry(member_id)
+            .or_insert_with(|| ReplayBuffer::new(capacity))
+            .push(event.clone());
+        conn.send_event(self.room_id.clone(), seq, event);
+        Ok(())
+    }
+
+    /// Replays [`Event`]s missed by the client of the [`Member`] with the
+    /// provided [`MemberId`] to its new [`RpcConnection`].
+    ///
+    /// `last_seq` is a sequence number of the last [`Event`] received by the
+    /// client. If it's [`None`], then the client doesn't resume its session,
+    /// so nothing is replayed to it.
+    fn replay_events(
+        &mut self,
+        member_id: &MemberId,
+        last_seq: Option<u32>,
+        conn: &dyn RpcConnection,
+    ) -> Resumption {
+        let last_seq = match last_seq {
+            Some(last_seq) => last_seq,
+            None => return Resumption::Started,
+        };
+        let missed = match self.replay_buffers.get(member_id) {
+            Some(buf) => buf.events_after(last_seq),
+            None if last_seq == 0 => Some(Vec::new()),
+            None => None,
+        };
+        if let Some(missed) = missed {
+            debug!(
+                "Replaying {} missed Events to Member [id = {}].",
+                missed.len(),
+                member_id,
+            );
+            for (seq, event) in missed {
+                conn.send_event(self.room_id.clone(), seq, event);
+            }
+            Resumption::Replayed
+        } else {
+            debug!(
+                "Missed Events of Member [id = {}] cannot be replayed.",
+                member_id,
+            );
+            Resumption::Overflowed
+        }
     }
 
     /// Saves provided [`RpcConnection`].
     /// If [`Member`] already has any other [`RpcConnection`],
-    /// then it will be closed.
+    /// then it will be closed and the [`Event`]s sent after the provided
+    /// `last_seq` will be replayed to the new one.
     pub fn connection_established(
         &mut self,
         ctx: &mut Context<Room>,
         member_id: MemberId,
         conn: Box<dyn RpcConnection>,
-    ) -> LocalBoxFuture<'static, Result<Member, ParticipantServiceErr>> {
+        last_seq: Option<u32>,
+    ) -> LocalBoxFuture<
+        'static,
+        Result<(Member, Resumption), ParticipantServiceErr>,
+    > {
         let member = match self.get_member_by_id(&member_id) {
             Err(err) => {
                 return Box::pin(future::err(err));
@@ -238,6 +408,7 @@ impl ParticipantService {
             {
                 ctx.cancel_future(handler);
             }
+            let resumption = self.replay_events(&member_id, last_seq, &*conn);
             self.insert_connection(member_id, conn);
             Box::pin(
                 connection
@@ -245,11 +416,15 @@ impl ParticipantService {
                         self.room_id.clone(),
                         CloseDescription::new(CloseReason::Reconnected),
                     )
-                    .map(move |_| Ok(member)),
+                    .map(move |_| Ok((member, resumption))),
             )
         } else {
+            self.publish_element_event(
+                member.get_fid(),
+                ElementEventKind::MemberConnected,
+            );
             self.insert_connection(member_id, conn);
-            Box::pin(future::ok(member))
+            Box::pin(future::ok((member, Resumption::Started)))
         }
     }
 
@@ -262,6 +437,16 @@ impl ParticipantService {
         self.connections.insert(member_id, conn);
     }
 
+    /// Removes [`RpcConnection`] of the [`Member`] with the provided
+    /// [`MemberId`] along with its [`ReplayBuffer`].
+    fn remove_connection(
+        &mut self,
+        member_id: &MemberId,
+    ) -> Option<Box<dyn RpcConnection>> {
+        self.replay_buffers.remove(member_id);
+        self.connections.remove(member_id)
+    }
+
     /// If [`ClosedReason::Closed`], then removes [`RpcConnection`] associated
     /// with specified user [`Member`] from the storage and closes the room.
     /// If [`ClosedReason::Lost`], then creates delayed task that emits
@@ -276,7 +461,12 @@ impl ParticipantService {
         match reason {
             ClosedReason::Closed { .. } => {
                 debug!("Connection for member [id = {}] removed.", member_id);
-                self.connections.remove(&member_id);
+                if self.remove_connection(&member_id).is_some() {
+                    self.publish_element_event(
+                        self.get_fid_to_member(member_id),
+                        ElementEventKind::MemberDisconnected,
+                    );
+                }
                 // TODO: we have no way to handle absence of RpcConnection right
                 //       now.
             }
@@ -316,12 +506,18 @@ impl ParticipantService {
             ctx.cancel_future(handle);
         });
 
+        self.replay_buffers.clear();
         let room_id = self.room_id.clone();
+        let element_events = self.element_events.clone();
         // closing all RpcConnection's
         let close_rpc_connections =
             future::join_all(self.connections.drain().fold(
                 Vec::new(),
-                |mut futs, (_, mut connection)| {
+                |mut futs, (member_id, mut connection)| {
+                    element_events.publish(
+                        Fid::<ToMember>::new(room_id.clone(), member_id),
+                        ElementEventKind::MemberDisconnected,
+                    );
                     futs.push(connection.close(
                         room_id.clone(),
                         CloseDescription::new(CloseReason::Finished),
@@ -343,31 +539,49 @@ impl ParticipantService {
         member_id: &MemberId,
         ctx: &mut Context<Room>,
     ) {
-        self.close_member_connection(member_id, ctx);
-        self.members.remove(member_id);
+        self.close_member_connection(
+            member_id,
+            CloseDescription::new(CloseReason::Evicted),
+            ctx,
+        );
+        if let Some(member) = self.members.remove(member_id) {
+            self.publish_member_tree_event(&member, ElementEventKind::Deleted);
+        }
     }
 
-    /// Closes [`RpcConnection`] with [`Member`] with a provided [`MemberId`]/
+    /// Closes [`RpcConnection`] with [`Member`] with a provided [`MemberId`]
+    /// using the provided [`CloseDescription`].
+    ///
+    /// Returns `false` if [`Member`] has no [`RpcConnection`] to close.
     pub fn close_member_connection(
         &mut self,
         member_id: &MemberId,
+        close_description: CloseDescription,
         ctx: &mut Context<Room>,
-    ) {
+    ) -> bool {
         if let Some(drop) = self.drop_connection_tasks.remove(member_id) {
             ctx.cancel_future(drop);
         }
 
-        if let Some(mut conn) = self.connections.remove(member_id) {
-            wrap_future::<_, Room>(conn.close(
-                self.room_id.clone(),
-                CloseDescription::new(CloseReason::Evicted),
-            ))
+        if let Some(mut conn) = self.remove_connection(member_id) {
+            self.publish_element_event(
+                self.get_fid_to_member(member_id.clone()),
+                ElementEventKind::MemberDisconnected,
+            );
+            wrap_future::<_, Room>(
+                conn.close(self.room_id.clone(), close_description),
+            )
             .spawn(ctx);
+            true
+        } else {
+            false
         }
     }
 
-    /// Inserts given [`Member`] into [`ParticipantService`].
+    /// Inserts given [`Member`] into [`ParticipantService`] and publishes
+    /// [`ElementEventKind::Created`] events of it and all its endpoints.
     pub fn insert_member(&mut self, id: MemberId, member: Member) {
+        self.publish_member_tree_event(&member, ElementEventKind::Created);
         self.members.insert(id, member);
     }
 
@@ -377,6 +591,111 @@ impl ParticipantService {
         self.members.iter()
     }
 
+    /// Builds new [`Member`] with all its [`WebRtcPublishEndpoint`]s from the
+    /// provided [`MemberSpec`].
+    ///
+    /// [`WebRtcPlayEndpoint`]s are not created, since their sources may not
+    /// exist yet, and the built [`Member`] is not inserted into this
+    /// [`ParticipantService`].
+    pub fn build_member(&self, id: MemberId, spec: &MemberSpec) -> Member {
+        let member = Member::new(
+            id,
+            spec.credentials().hash(),
+            self.room_id.clone(),
+            spec.idle_timeout().unwrap_or(self.rpc_conf.idle_timeout),
+            spec.reconnect_timeout()
+                .unwrap_or(self.rpc_conf.reconnect_timeout),
+            spec.ping_interval().unwrap_or(self.rpc_conf.ping_interval),
+        );
+        if let Some(plain) = spec.credentials().plain() {
+            member.remember_verified_credential(plain);
+        }
+
+        member.set_callback_urls(spec);
+        member.set_meta(spec.meta().clone());
+
+        for (id, publish) in spec.publish_endpoints() {
+            let src = WebRtcPublishEndpoint::new(
+                id,
+                publish.p2p,
+                member.downgrade(),
+                publish.force_relay,
+                publish.audio_settings,
+                publish.video_settings,
+            );
+            src.set_callback_urls(
+                publish.on_start.clone(),
+                publish.on_stop.clone(),
+            );
+            src.set_meta(publish.meta.clone());
+            member.insert_src(src);
+        }
+
+        member
+    }
+
+    /// Updates callbacks, labels, metadata and Client API RPC settings of the
+    /// provided [`Member`] with the ones from the provided [`MemberSpec`].
+    ///
+    /// RPC settings which are not specified in the [`MemberSpec`] are reset to
+    /// the defaults from config.
+    pub fn update_member_settings(&self, member: &Member, spec: &MemberSpec) {
+        member.set_callback_urls(spec);
+        member.set_meta(spec.meta().clone());
+        self.set_member_rpc_settings(
+            member,
+            spec.idle_timeout().unwrap_or(self.rpc_conf.idle_timeout),
+            spec.reconnect_timeout()
+                .unwrap_or(self.rpc_conf.reconnect_timeout),
+            spec.ping_interval().unwrap_or(self.rpc_conf.ping_interval),
+        );
+    }
+
+    /// Updates Client API RPC settings of the provided [`Member`] with the
+    /// ones specified in the provided [`MemberPatch`].
+    pub fn patch_member_settings(&self, member: &Member, patch: &MemberPatch) {
+        self.set_member_rpc_settings(
+            member,
+            patch
+                .idle_timeout
+                .unwrap_or_else(|| member.get_idle_timeout()),
+            patch
+                .reconnect_timeout
+                .unwrap_or_else(|| member.get_reconnect_timeout()),
+            patch
+                .ping_interval
+                .unwrap_or_else(|| member.get_ping_interval()),
+        );
+    }
+
+    /// Sets Client API RPC settings of the provided [`Member`].
+    ///
+    /// If heartbeat settings have been changed, then propagates them to the
+    /// [`RpcConnection`] of this [`Member`] (if any).
+    fn set_member_rpc_settings(
+        &self,
+        member: &Member,
+        idle_timeout: Duration,
+        reconnect_timeout: Duration,
+        ping_interval: Duration,
+    ) {
+        let is_heartbeat_changed = member.get_idle_timeout() != idle_timeout
+            || member.get_ping_interval() != ping_interval;
+        member.set_rpc_settings(idle_timeout, reconnect_timeout, ping_interval);
+
+        if is_heartbeat_changed {
+            if let Some(conn) = self.connections.get(&member.id()) {
+                conn.update_settings(
+                    self.room_id.clone(),
+                    RpcConnectionSettings {
+                        idle_timeout,
+                        ping_interval,
+                    },
+                );
+            }
+        }
+    }
+
     /// Creates new [`Member`] in this [`ParticipantService`].
     ///
     /// This function will check that new [`Member`]'s ID is not present in
@@ -396,29 +715,7 @@ impl ParticipantService {
                 self.get_fid_to_member(id),
             ));
         }
-        let signalling_member = Member::new(
-            id.clone(),
-            spec.credentials().clone(),
-            self.room_id.clone(),
-            spec.idle_timeout().unwrap_or(self.rpc_conf.idle_timeout),
-            spec.reconnect_timeout()
-                .unwrap_or(self.rpc_conf.reconnect_timeout),
-            spec.ping_interval().unwrap_or(self.rpc_conf.ping_interval),
-        );
-
-        signalling_member.set_callback_urls(spec);
-
-        for (id, publish) in spec.publish_endpoints() {
-            let signalling_publish = WebRtcPublishEndpoint::new(
-                id.clone(),
-                publish.p2p,
-                signalling_member.downgrade(),
-                publish.force_relay,
-                publish.audio_settings,
-                publish.video_settings,
-            );
-            signalling_member.insert_src(signalling_publish);
-        }
+        let signalling_member = self.build_member(id.clone(), spec);
 
         for (id, play) in spec.play_endpoints() {
             let partner_member = self.get_member(&play.src.member_id)?;
@@ -439,6 +736,8 @@ impl ParticipantService {
                 signalling_member.downgrade(),
                 play.force_relay,
             );
+            sink.set_callback_urls(play.on_start.clone(), play.on_stop.clone());
+            sink.set_meta(play.meta.clone());
 
             signalling_member.insert_sink(sink);
         }
@@ -459,7 +758,18 @@ impl ParticipantService {
 mod test {
     use std::time::Duration;
 
-    use crate::{api::control::pipeline::Pipeline, conf::Conf};
+    use medea_client_api_proto::PeerId;
+
+    use crate::{
+        api::{
+            client::rpc_connection::MockRpcConnection,
+            control::{
+                credentials::Credentials, pipeline::Pipeline, AutoRemove,
+                ElementMeta,
+            },
+        },
+        conf::Conf,
+    };
 
     use super::*;
 
@@ -467,6 +777,9 @@ mod test {
         let room_spec = RoomSpec {
             id: RoomId::from("test"),
             pipeline: Pipeline::new(HashMap::new()),
+            auto_remove: AutoRemove::default(),
+            on_remove: None,
+            meta: ElementMeta::default(),
         };
         let ctx = AppContext::new(
             Conf::default(),
@@ -484,7 +797,7 @@ mod test {
 
         let test_member_spec = MemberSpec::new(
             Pipeline::new(HashMap::new()),
-            "w/e".into(),
+            Credentials::Plain("w/e".into()),
             None,
             None,
             None,
@@ -526,7 +839,7 @@ mod test {
 
         let test_member_spec = MemberSpec::new(
             Pipeline::new(HashMap::new()),
-            "w/e".into(),
+            Credentials::Plain("w/e".into()),
             None,
             None,
             Some(idle_timeout),
@@ -544,4 +857,101 @@ mod test {
         assert_eq!(test_member.get_idle_timeout(), idle_timeout);
         assert_eq!(test_member.get_reconnect_timeout(), reconnect_timeout);
     }
+
+    fn sdp_answer_made(peer_id: u32) -> Event {
+        Event::SdpAnswerMade {
+            peer_id: PeerId(peer_id),
+            sdp_answer: String::new(),
+        }
+    }
+
+    /// Tests that [`ReplayBuffer`] returns the [`Event`]s pushed after the
+    /// provided sequence number only if none of them have been evicted.
+    #[test]
+    fn replay_buffer_returns_missed_events() {
+        let mut buffer = ReplayBuffer::new(2);
+        assert_eq!(buffer.events_after(0), Some(Vec::new()));
+
+        for peer_id in 1..=3 {
+            assert_eq!(buffer.push(sdp_answer_made(peer_id)), peer_id);
+        }
+
+        assert_eq!(buffer.events_after(3), Some(Vec::new()));
+        assert_eq!(buffer.events_after(2), Some(vec![(3, sdp_answer_made(3))]));
+        assert_eq!(
+            buffer.events_after(1),
+            Some(vec![(2, sdp_answer_made(2)), (3, sdp_answer_made(3))]),
+        );
+        assert_eq!(buffer.events_after(0), None);
+        assert_eq!(buffer.events_after(4), None);
+    }
+
+    /// Tests that [`Event`]s sent to [`Member`] are sequenced and replayed to
+    /// its new [`RpcConnection`] starting from the last one received by the
+    /// client.
+    #[test]
+    fn replays_missed_events_to_new_connection() {
+        let mut members = empty_participants_service();
+        let member_id = MemberId::from("member");
+
+        let mut lost_conn = MockRpcConnection::new();
+        lost_conn
+            .expect_send_event()
+            .withf(|_, seq, _| *seq == 1 || *seq == 2)
+            .times(2)
+            .return_const(());
+        members.insert_connection(member_id.clone(), Box::new(lost_conn));
+        members
+            .send_event_to_member(member_id.clone(), sdp_answer_made(1))
+            .unwrap();
+        members
+            .send_event_to_member(member_id.clone(), sdp_answer_made(2))
+            .unwrap();
+
+        let mut new_conn = MockRpcConnection::new();
+        new_conn
+            .expect_send_event()
+            .withf(|_, seq, event| *seq == 2 && *event == sdp_answer_made(2))
+            .times(1)
+            .return_const(());
+        assert_eq!(
+            members.replay_events(&member_id, Some(1), &new_conn),
+            Resumption::Replayed,
+        );
+        assert_eq!(
+            members.replay_events(&member_id, None, &new_conn),
+            Resumption::Started,
+        );
+    }
+
+    /// Tests that [`Resumption::Overflowed`] is reported only when the
+    /// [`Event`]s missed by the client are not buffered anymore.
+    #[test]
+    fn reports_overflow_only_when_events_are_evicted() {
+        let mut members = empty_participants_service();
+        let member_id = MemberId::from("member");
+
+        let new_conn = MockRpcConnection::new();
+        assert_eq!(
+            members.replay_events(&member_id, Some(0), &new_conn),
+            Resumption::Replayed,
+        );
+        assert_eq!(
+            members.replay_events(&member_id, Some(1), &new_conn),
+            Resumption::Overflowed,
+        );
+
+        let mut buf = ReplayBuffer::new(1);
+        buf.push(sdp_answer_made(1));
+        buf.push(sdp_answer_made(2));
+        members.replay_buffers.insert(member_id.clone(), buf);
+        assert_eq!(
+            members.replay_events(&member_id, Some(2), &new_conn),
+            Resumption::Replayed,
+        );
+        assert_eq!(
+            members.replay_events(&member_id, Some(0), &new_conn),
+            Resumption::Overflowed,
+        );
+    }
 }
@@ -1,6 +1,10 @@
 //! Implementation of the [`MediaTrafficState`] which will be used for
 //! the storing started/stopped [`MediaType`]s.
 
+use std::collections::HashMap;
+
+use medea_client_api_proto::PeerId;
+
 use crate::api::control::callback::MediaType;
 
 /// Traffic state of all [`MediaType`]s for some `Endpoint`.
@@ -85,6 +89,56 @@ impl MediaTrafficState {
     }
 }
 
+/// Traffic state of some `Endpoint` aggregated from the [`MediaTrafficState`]s
+/// of all its `Peer`s.
+///
+/// [`MediaType`] is considered started in an `Endpoint` if it's started in at
+/// least one of its `Peer`s.
+#[derive(Clone, Debug, Default)]
+pub struct EndpointTrafficState(HashMap<PeerId, MediaTrafficState>);
+
+impl EndpointTrafficState {
+    /// Sets provided [`MediaType`] to the started state for the `Peer` with
+    /// provided [`PeerId`].
+    ///
+    /// Returns [`MediaType`] which was started in the whole `Endpoint`, or
+    /// `None` if nothing has changed.
+    pub fn started(
+        &mut self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType> {
+        let before = self.merged();
+        self.0
+            .entry(peer_id)
+            .or_insert_with(MediaTrafficState::new)
+            .started(media_type);
+        get_diff_added(before, self.merged())
+    }
+
+    /// Sets provided [`MediaType`] to the stopped state for the `Peer` with
+    /// provided [`PeerId`].
+    ///
+    /// Returns [`MediaType`] which was stopped in the whole `Endpoint`, or
+    /// `None` if nothing has changed.
+    pub fn stopped(
+        &mut self,
+        peer_id: PeerId,
+        media_type: MediaType,
+    ) -> Option<MediaType> {
+        let before = self.merged();
+        if let Some(state) = self.0.get_mut(&peer_id) {
+            state.stopped(media_type);
+        }
+        get_diff_removed(before, self.merged())
+    }
+
+    /// Returns [`MediaTrafficState`] of all `Peer`s merged together.
+    fn merged(&self) -> MediaTrafficState {
+        MediaTrafficState(self.0.values().fold(0, |acc, state| acc | state.0))
+    }
+}
+
 /// Returns [`MediaType`] which was started based on [`MediaTrafficState`]
 /// before and [`MediaTrafficState`] after. Returns `Some(MediaType)` if `after`
 /// contains [`MediaType`] that is not present in `before` and `None` otherwise.
@@ -108,6 +162,43 @@ pub fn get_diff_removed(
     MediaTrafficState(before.0 & !after.0).get_started_media_type()
 }
 
+#[cfg(test)]
+mod endpoint_traffic_state_tests {
+    use super::*;
+
+    #[test]
+    fn started_once_for_many_peers() {
+        let mut state = EndpointTrafficState::default();
+
+        assert_eq!(
+            state.started(PeerId(1), MediaType::Audio),
+            Some(MediaType::Audio)
+        );
+        assert_eq!(state.started(PeerId(2), MediaType::Audio), None);
+        assert_eq!(
+            state.started(PeerId(2), MediaType::Both),
+            Some(MediaType::Video)
+        );
+    }
+
+    #[test]
+    fn stopped_only_when_stopped_in_all_peers() {
+        let mut state = EndpointTrafficState::default();
+        state.started(PeerId(1), MediaType::Both);
+        state.started(PeerId(2), MediaType::Audio);
+
+        assert_eq!(
+            state.stopped(PeerId(1), MediaType::Both),
+            Some(MediaType::Video)
+        );
+        assert_eq!(state.stopped(PeerId(3), MediaType::Both), None);
+        assert_eq!(
+            state.stopped(PeerId(2), MediaType::Both),
+            Some(MediaType::Audio)
+        );
+    }
+}
+
 #[cfg(test)]
 mod tracks_state_tests {
     use super::*;
@@ -15,8 +15,8 @@ use std::{
 use derive_more::Display;
 use futures::{future, Stream};
 use medea_client_api_proto::{
-    stats::RtcStat, Incrementable, MemberId, PeerConnectionState, PeerId,
-    RoomId, TrackId,
+    stats::RtcStat, Incrementable, MediaSourceKind, MediaType, MemberId,
+    PeerConnectionState, PeerId, RoomId, TrackId, TrackPatchEvent,
 };
 
 use crate::{
@@ -35,6 +35,7 @@ use crate::{
 };
 
 pub use self::{
+    media_traffic_state::EndpointTrafficState,
     metrics::{PeersMetricsEvent, PeersMetricsEventHandler},
     traffic_watcher::{
         build_peers_traffic_watcher, FlowMetricSource,
@@ -372,6 +373,123 @@ impl PeersService {
         Ok(())
     }
 
+    /// Propagates current publish policies of the provided
+    /// [`WebRtcPublishEndpoint`] to the `MediaTrack`s published by it,
+    /// notifying both sides with [`TrackPatchEvent`]s.
+    ///
+    /// Only `required` flag of the already published `MediaTrack`s may be
+    /// changed this way.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if some [`Peer`] of the
+    /// provided [`WebRtcPublishEndpoint`] doesn't exist in [`PeerRepository`].
+    pub fn update_publish_policy(
+        &self,
+        src: &WebRtcPublishEndpoint,
+    ) -> Result<(), RoomError> {
+        let audio_required = src.audio_settings().publish_policy.required();
+        let video_required = src.video_settings().publish_policy.required();
+
+        for peer_id in src.peer_ids() {
+            let (patches, partner_peer_id) =
+                self.peers.map_peer_by_id_mut(peer_id, |peer| {
+                    let patches: Vec<_> = peer
+                        .senders()
+                        .values()
+                        .filter(|track| src.is_track_published(track.id))
+                        .filter_map(|track| {
+                            let required = match track.media_type() {
+                                MediaType::Audio(_) => audio_required,
+                                MediaType::Video(video) => {
+                                    match video.source_kind {
+                                        MediaSourceKind::Device => {
+                                            video_required
+                                        }
+                                        MediaSourceKind::Display => {
+                                            return None
+                                        }
+                                    }
+                                }
+                            };
+                            if track.media_type().required() == required {
+                                return None;
+                            }
+                            let mut patch = TrackPatchEvent::new(track.id);
+                            patch.required = Some(required);
+                            Some(patch)
+                        })
+                        .collect();
+                    if !patches.is_empty() {
+                        peer.as_changes_scheduler()
+                            .server_patch_tracks(patches.clone());
+                        peer.force_commit_scheduled_changes();
+                    }
+                    (patches, peer.partner_peer_id())
+                })?;
+
+            if !patches.is_empty() {
+                self.peers.map_peer_by_id_mut(partner_peer_id, |peer| {
+                    peer.as_changes_scheduler().server_patch_tracks(patches);
+                    peer.force_commit_scheduled_changes();
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Switches all the [`Peer`]s of the provided [`WebRtcPublishEndpoint`]
+    /// to (or from) relaying media through a TURN server, according to its
+    /// current [`WebRtcPublishEndpoint::is_force_relayed`].
+    ///
+    /// [`Peer`] remains force relayed while at least one of its [`Endpoint`]s
+    /// requires it. Changes are applied with an ICE restart.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if some [`Peer`] of the
+    /// provided [`WebRtcPublishEndpoint`] doesn't exist in [`PeerRepository`].
+    pub fn update_force_relay(
+        &self,
+        src: &WebRtcPublishEndpoint,
+    ) -> Result<(), RoomError> {
+        for peer_id in src.peer_ids() {
+            let (is_force_relayed, has_traffic_callback) =
+                self.peers.map_peer_by_id_mut(peer_id, |peer| {
+                    let endpoints: Vec<_> = peer
+                        .endpoints()
+                        .into_iter()
+                        .filter_map(|e| e.upgrade())
+                        .collect();
+                    let is_force_relayed =
+                        endpoints.iter().any(Endpoint::is_force_relayed);
+                    let has_traffic_callback =
+                        endpoints.iter().any(Endpoint::has_traffic_callback);
+                    if peer.is_force_relayed() == is_force_relayed {
+                        (None, has_traffic_callback)
+                    } else {
+                        peer.as_changes_scheduler()
+                            .set_force_relay(is_force_relayed);
+                        (Some(is_force_relayed), has_traffic_callback)
+                    }
+                })?;
+
+            if let Some(is_force_relayed) = is_force_relayed {
+                if has_traffic_callback {
+                    self.peers_traffic_watcher.update_peer_turn_watching(
+                        self.room_id.clone(),
+                        peer_id,
+                        is_force_relayed,
+                    );
+                }
+                self.commit_scheduled_changes(peer_id)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Creates [`Peer`] for endpoints if [`Peer`] between endpoint's members
     /// doesn't exist.
     ///
@@ -526,6 +644,14 @@ impl PeersService {
         Ok(())
     }
 
+    /// Unregisters [`Room`] of this [`PeersService`] from the
+    /// [`PeerTrafficWatcher`].
+    #[inline]
+    pub fn unregister_room(&self) {
+        self.peers_traffic_watcher
+            .unregister_room(self.room_id.clone());
+    }
+
     /// Returns [`Stream`] of [`PeerMetricsEvent`]s from underlying
     /// [`PeerMetricsService`].
     pub fn subscribe_to_metrics_events(
@@ -741,6 +867,8 @@ mod tests {
 
     use crate::{
         api::control::{
+            callback::url::CallbackUrl,
+            credentials::CredentialHash,
             endpoints::webrtc_publish_endpoint::{
                 AudioSettings, P2pMode, VideoSettings,
             },
@@ -836,6 +964,12 @@ mod tests {
         move |peer| peer.id() == PeerId(peer_id)
     }
 
+    /// Returns [`CallbackUrl`] to set as `on_start` callback of the test
+    /// endpoints.
+    fn callback_url() -> CallbackUrl {
+        CallbackUrl::try_from(String::from("grpc://127.0.0.1:9099")).unwrap()
+    }
+
     /// Checks that newly created [`Peer`] will be created in the
     /// [`PeerMetricsService`] and [`PeerTrafficWatcher`].
     #[actix_rt::test]
@@ -889,7 +1023,7 @@ mod tests {
 
         let publisher = Member::new(
             "publisher".into(),
-            "test".into(),
+            CredentialHash::new(&"test".into()),
             "test".into(),
             Duration::from_secs(10),
             Duration::from_secs(10),
@@ -897,7 +1031,7 @@ mod tests {
         );
         let receiver = Member::new(
             "receiver".into(),
-            "test".into(),
+            CredentialHash::new(&"test".into()),
             "test".into(),
             Duration::from_secs(10),
             Duration::from_secs(10),
@@ -911,6 +1045,7 @@ mod tests {
             AudioSettings::default(),
             VideoSettings::default(),
         );
+        publish.set_callback_urls(Some(callback_url()), None);
         let play = WebRtcPlayEndpoint::new(
             "play-publisher".to_string().into(),
             SrcUri::try_from("local://test/publisher/publish".to_string())
@@ -919,6 +1054,7 @@ mod tests {
             receiver.downgrade(),
             false,
         );
+        play.set_callback_urls(Some(callback_url()), None);
 
         let (src_peer_id, sink_peer_id) = peers_service
             .clone()
@@ -994,7 +1130,7 @@ mod tests {
 
         let publisher = Member::new(
             "publisher".into(),
-            "test".into(),
+            CredentialHash::new(&"test".into()),
             "test".into(),
             Duration::from_secs(10),
             Duration::from_secs(10),
@@ -1002,7 +1138,7 @@ mod tests {
         );
         let receiver = Member::new(
             "receiver".into(),
-            "test".into(),
+            CredentialHash::new(&"test".into()),
             "test".into(),
             Duration::from_secs(10),
             Duration::from_secs(10),
@@ -1016,6 +1152,7 @@ mod tests {
             AudioSettings::default(),
             VideoSettings::default(),
         );
+        publish.set_callback_urls(Some(callback_url()), None);
         let play = WebRtcPlayEndpoint::new(
             "play-publisher".to_string().into(),
             SrcUri::try_from("local://test/publisher/publish".to_string())
@@ -1024,6 +1161,7 @@ mod tests {
             receiver.downgrade(),
             false,
         );
+        play.set_callback_urls(Some(callback_url()), None);
 
         let (src_peer_id, sink_peer_id) = peers_service
             .clone()
@@ -1044,6 +1182,7 @@ mod tests {
             AudioSettings::default(),
             VideoSettings::default(),
         );
+        publish.set_callback_urls(Some(callback_url()), None);
         let play = WebRtcPlayEndpoint::new(
             "play-publisher".to_string().into(),
             SrcUri::try_from("local://test/publisher/publish".to_string())
@@ -1052,6 +1191,7 @@ mod tests {
             publisher.downgrade(),
             false,
         );
+        play.set_callback_urls(Some(callback_url()), None);
 
         let (src_peer_id, sink_peer_id) = peers_service
             .clone()
@@ -92,6 +92,15 @@ pub trait PeerTrafficWatcher: Debug + Send + Sync {
     /// to process traffic flow events of this `Peer` anymore.
     fn unregister_peers(&self, room_id: RoomId, peers_ids: Vec<PeerId>);
 
+    /// Starts or stops expecting [`FlowMetricSource::Coturn`] traffic reports
+    /// for the already registered `Peer`.
+    fn update_peer_turn_watching(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        should_watch_turn: bool,
+    );
+
     /// Notifies [`PeerTrafficWatcher`] that some `Peer` traffic flowing.
     fn traffic_flows(
         &self,
@@ -162,6 +171,21 @@ impl PeerTrafficWatcher for Addr<PeersTrafficWatcherImpl> {
         self.do_send(UnregisterPeers { room_id, peers_ids })
     }
 
+    /// Sends [`UpdatePeerTurnWatching`] message to
+    /// [`PeersTrafficWatcherImpl`].
+    fn update_peer_turn_watching(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        should_watch_turn: bool,
+    ) {
+        self.do_send(UpdatePeerTurnWatching {
+            room_id,
+            peer_id,
+            should_watch_turn,
+        })
+    }
+
     /// Sends [`TrafficFlows`] message to [`PeersTrafficWatcherImpl`].
     fn traffic_flows(
         &self,
@@ -670,6 +694,44 @@ impl Handler<UnregisterPeers> for PeersTrafficWatcherImpl {
     }
 }
 
+/// Adds or removes [`FlowMetricSource::Coturn`] to/from tracked sources of
+/// [`PeerStat`] with provided [`PeerId`].
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct UpdatePeerTurnWatching {
+    /// [`RoomId`] of [`Room`] which owns the [`PeerStat`].
+    room_id: RoomId,
+
+    /// [`PeerId`] of the [`PeerStat`] to be updated.
+    peer_id: PeerId,
+
+    /// Indicator whether [`FlowMetricSource::Coturn`] should be tracked.
+    should_watch_turn: bool,
+}
+
+impl Handler<UpdatePeerTurnWatching> for PeersTrafficWatcherImpl {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: UpdatePeerTurnWatching,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let peer = self
+            .stats
+            .get_mut(&msg.room_id)
+            .and_then(|room| room.peers.get_mut(&msg.peer_id));
+        if let Some(peer) = peer {
+            if msg.should_watch_turn {
+                peer.tracked_sources.insert(FlowMetricSource::Coturn);
+            } else {
+                peer.tracked_sources.remove(&FlowMetricSource::Coturn);
+                peer.received_sources.remove(&FlowMetricSource::Coturn);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::{channel::mpsc, stream::LocalBoxStream, StreamExt};
@@ -4,8 +4,8 @@
 use std::collections::HashMap;
 
 use medea_client_api_proto::{
-    CommandHandler, Credential, Event, IceCandidate, MemberId, NegotiationRole,
-    PeerId, PeerMetrics, TrackId, TrackPatchCommand,
+    CommandHandler, Credential, Event, Handshake, IceCandidate, MemberId,
+    NegotiationRole, PeerId, PeerMetrics, TrackId, TrackPatchCommand,
 };
 
 use crate::{
@@ -19,7 +19,13 @@ impl CommandHandler for Room {
     type Output = Result<(), RoomError>;
 
     #[inline]
-    fn on_join_room(&mut self, _: MemberId, _: Credential) -> Self::Output {
+    fn on_join_room(
+        &mut self,
+        _: MemberId,
+        _: Credential,
+        _: Option<u32>,
+        _: Option<Handshake>,
+    ) -> Self::Output {
         unreachable!("Room can't receive Command::JoinRoom")
     }
 
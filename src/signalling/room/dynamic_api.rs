@@ -6,32 +6,43 @@
 use std::collections::{HashMap, HashSet};
 
 use actix::{
-    fut, ActorFuture as _, AsyncContext as _, Context,
-    ContextFutureSpawner as _, Handler, Message, WrapFuture as _,
+    fut, ActorFuture as _, AsyncContext as _, AtomicResponse, Context,
+    ContextFutureSpawner as _, Handler, Message, MessageResponse,
+    WrapFuture as _,
+};
+use medea_client_api_proto::{
+    CloseDescription, CloseReason, Credential, Event, MemberId, PeerId,
 };
-use medea_client_api_proto::{MemberId, PeerId};
 use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
     api::control::{
+        callback::{url::CallbackUrl, OnLeaveEvent, OnLeaveReason},
+        credentials::{self, Credentials},
+        element_events::ElementEventKind,
         endpoints::{
+            webrtc_publish_endpoint::{
+                PublishPolicy, WebRtcPublishEndpointPatch,
+            },
             WebRtcPlayEndpoint as WebRtcPlayEndpointSpec,
             WebRtcPublishEndpoint as WebRtcPublishEndpointSpec,
         },
-        refs::StatefulFid,
-        EndpointId, EndpointSpec, MemberSpec, WebRtcPlayId, WebRtcPublishId,
+        refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
+        ApplyPolicy, AutoRemove, ElementMeta, EndpointId, EndpointSpec,
+        MemberPatch, MemberSpec, RoomSpec, WebRtcPlayId, WebRtcPublishId,
     },
     log::prelude::*,
     signalling::{
         elements::{
             endpoints::webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
             member::MemberError,
+            MembersLoadError,
         },
         room::ActFuture,
     },
 };
 
-use super::{Room, RoomError};
+use super::{Room, RoomError, State};
 
 impl Room {
     /// Deletes [`Member`] from this [`Room`] by [`MemberId`].
@@ -41,23 +52,15 @@ impl Room {
             member_id, self.id
         );
         if let Ok(member) = self.members.get_member_by_id(member_id) {
-            let peers: HashSet<PeerId> = member
-                .sinks()
-                .values()
-                .filter_map(WebRtcPlayEndpoint::peer_id)
-                .chain(
-                    member
-                        .srcs()
-                        .values()
-                        .flat_map(WebRtcPublishEndpoint::peer_ids),
-                )
-                .collect();
+            let peers: HashSet<PeerId> =
+                member.peer_ids().into_iter().collect();
 
             // Send PeersRemoved to `Member`s which have related to this
             // `Member` `Peer`s.
             self.remove_peers(&member.id(), &peers, ctx);
 
             self.members.delete_member(member_id, ctx);
+            self.callbacks.remove_queues(&member.get_fid().into());
 
             debug!(
                 "Member [id = {}] deleted from Room [id = {}].",
@@ -76,28 +79,29 @@ impl Room {
         let endpoint_id =
             if let Ok(member) = self.members.get_member_by_id(member_id) {
                 let play_id = endpoint_id.into();
+                let mut is_deleted = false;
                 if let Some(endpoint) = member.take_sink(&play_id) {
+                    is_deleted = true;
                     if let Some(peer_id) = endpoint.peer_id() {
-                        let removed_peers =
-                            self.peers.remove_peers(member_id, &[peer_id]);
-                        for (member_id, peers) in removed_peers {
-                            self.member_peers_removed(
-                                peers.into_iter().map(|p| p.id()).collect(),
-                                member_id,
-                            )
-                            .map(|_, _, _| ())
-                            .spawn(ctx);
-                        }
+                        self.remove_peers(member_id, &[peer_id], ctx);
                     }
                 }
 
                 let publish_id = String::from(play_id).into();
                 if let Some(endpoint) = member.take_src(&publish_id) {
+                    is_deleted = true;
                     let peer_ids = endpoint.peer_ids();
                     self.remove_peers(member_id, &peer_ids, ctx);
                 }
 
-                publish_id.into()
+                let endpoint_id: EndpointId = publish_id.into();
+                if is_deleted {
+                    let fid = member.get_fid_to_endpoint(endpoint_id.clone());
+                    self.callbacks.remove_queues(&fid.clone().into());
+                    self.members
+                        .publish_element_event(fid, ElementEventKind::Deleted);
+                }
+                endpoint_id
             } else {
                 endpoint_id
             };
@@ -150,6 +154,8 @@ impl Room {
             spec.audio_settings,
             spec.video_settings,
         );
+        endpoint.set_callback_urls(spec.on_start.clone(), spec.on_stop.clone());
+        endpoint.set_meta(spec.meta.clone());
 
         debug!(
             "Create WebRtcPublishEndpoint [id = {}] for Member [id = {}] in \
@@ -159,6 +165,10 @@ impl Room {
             self.id
         );
 
+        self.members.publish_element_event(
+            member.get_fid_to_endpoint(endpoint.id().into()),
+            ElementEventKind::Created,
+        );
         member.insert_src(endpoint);
 
         Ok(())
@@ -214,6 +224,8 @@ impl Room {
             member.downgrade(),
             spec.force_relay,
         );
+        sink.set_callback_urls(spec.on_start, spec.on_stop);
+        sink.set_meta(spec.meta);
 
         src.add_sink(sink.downgrade());
 
@@ -225,6 +237,10 @@ impl Room {
             self.id
         );
 
+        self.members.publish_element_event(
+            member.get_fid_to_endpoint(sink.id().into()),
+            ElementEventKind::Created,
+        );
         member.insert_sink(sink);
 
         Ok(Box::pin(fut::ready(()).map(
@@ -240,8 +256,11 @@ impl Room {
                                      room_id: {}] will be stopped.",
                                     e, member_id, this.id,
                                 );
-                                this.members
-                                    .close_member_connection(&member_id, ctx);
+                                this.members.close_member_connection(
+                                    &member_id,
+                                    CloseDescription::new(CloseReason::Evicted),
+                                    ctx,
+                                );
                             }
                         },
                     ));
@@ -251,11 +270,310 @@ impl Room {
         )))
     }
 
+    /// Checks that all [`WebRtcPlayEndpoint`]s from the provided
+    /// [`MemberSpec`]s will have their sources after applying these
+    /// [`MemberSpec`]s to this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`MemberError::EndpointNotFound`] if some
+    /// [`WebRtcPlayEndpoint`] refers to a [`WebRtcPublishEndpoint`] which
+    /// won't exist.
+    fn validate_apply(
+        &self,
+        specs: &HashMap<MemberId, MemberSpec>,
+        policy: ApplyPolicy,
+        remove_unspecified_members: bool,
+    ) -> Result<(), RoomError> {
+        let mut srcs: HashSet<(MemberId, WebRtcPublishId)> = HashSet::new();
+        for (member_id, member) in self.members.iter_members() {
+            if let Some(spec) = specs.get(member_id) {
+                if policy == ApplyPolicy::Append {
+                    srcs.extend(
+                        member
+                            .srcs()
+                            .into_iter()
+                            .map(|(id, _)| id)
+                            .filter(|id| {
+                                spec.get_play_endpoint_by_id(
+                                    String::from(id.clone()).into(),
+                                )
+                                .is_none()
+                            })
+                            .map(|id| (member_id.clone(), id)),
+                    );
+                }
+            } else if policy == ApplyPolicy::Append
+                || !remove_unspecified_members
+            {
+                srcs.extend(
+                    member
+                        .srcs()
+                        .into_iter()
+                        .map(|(id, _)| (member_id.clone(), id)),
+                );
+            }
+        }
+        for (member_id, spec) in specs {
+            srcs.extend(
+                spec.publish_endpoints()
+                    .map(|(id, _)| (member_id.clone(), id)),
+            );
+        }
+
+        for spec in specs.values() {
+            for (_, play) in spec.play_endpoints() {
+                let src =
+                    (play.src.member_id.clone(), play.src.endpoint_id.clone());
+                if !srcs.contains(&src) {
+                    return Err(MemberError::EndpointNotFound(
+                        Fid::<ToEndpoint>::new(
+                            self.id.clone(),
+                            src.0,
+                            src.1.into(),
+                        ),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies provided [`MemberSpec`]s to this [`Room`].
+    ///
+    /// Creates [`Member`]s and `Endpoint`s which don't exist yet, recreates
+    /// `Endpoint`s whose specs have been changed, and updates callbacks,
+    /// labels, metadata and RPC settings of the already existing [`Member`]s
+    /// and `Endpoint`s. Credentials of the already existing [`Member`]s are
+    /// left untouched.
+    ///
+    /// With [`ApplyPolicy::Apply`] all `Endpoint`s of the applied [`Member`]s
+    /// which are not specified in their [`MemberSpec`]s are removed, and, if
+    /// `remove_unspecified_members` is `true`, all [`Member`]s which are not
+    /// specified in `specs` are removed as well.
+    ///
+    /// Returns plain credentials of all the applied [`Member`]s, if they are
+    /// known. Plain credentials of the already existing [`Member`]s are
+    /// known only if they are specified in `specs` and have been verified
+    /// with [`Room::verify_specified_credentials()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::MemberError`] if some [`WebRtcPlayEndpoint`]
+    /// from `specs` refers to a [`WebRtcPublishEndpoint`] which won't exist
+    /// after applying. Nothing is changed in this [`Room`] in such case.
+    ///
+    /// Errors with [`RoomError::EndpointAlreadyExists`] if some `Endpoint`
+    /// cannot be created because of conflicting ID. `specs` are applied
+    /// partially in such case: all the removals, updates and creations made
+    /// before the failed one are kept, so callers should commit versions of
+    /// the changed elements regardless of the result.
+    fn apply_members(
+        &mut self,
+        specs: &HashMap<MemberId, MemberSpec>,
+        policy: ApplyPolicy,
+        remove_unspecified_members: bool,
+        ctx: &mut Context<Self>,
+    ) -> Result<HashMap<MemberId, Option<Credential>>, RoomError> {
+        self.validate_apply(specs, policy, remove_unspecified_members)?;
+        let remove_unspecified = policy == ApplyPolicy::Apply;
+
+        if remove_unspecified && remove_unspecified_members {
+            let unspecified_members: Vec<_> = self
+                .members
+                .iter_members()
+                .map(|(id, _)| id.clone())
+                .filter(|id| !specs.contains_key(id))
+                .collect();
+            for member_id in unspecified_members {
+                self.delete_member(&member_id, ctx);
+            }
+        }
+
+        // Sinks of the recreated `WebRtcPublishEndpoint`s, which should be
+        // restored after their sources are recreated.
+        let mut orphaned_sinks = Vec::new();
+        for (member_id, spec) in specs {
+            let member =
+                if let Ok(member) = self.members.get_member_by_id(member_id) {
+                    member
+                } else {
+                    continue;
+                };
+            self.members.update_member_settings(&member, spec);
+
+            for (id, sink) in member.sinks() {
+                let is_actual =
+                    spec.get_play_endpoint_by_id(id.clone()).map_or_else(
+                        || {
+                            let publish_id = String::from(id.clone()).into();
+                            !remove_unspecified
+                                && spec
+                                    .get_publish_endpoint_by_id(publish_id)
+                                    .is_none()
+                        },
+                        |play| {
+                            sink.set_callback_urls(
+                                play.on_start.clone(),
+                                play.on_stop.clone(),
+                            );
+                            sink.set_meta(play.meta.clone());
+                            sink.src_uri() == play.src
+                                && sink.is_force_relayed() == play.force_relay
+                        },
+                    );
+                if !is_actual {
+                    self.delete_endpoint(member_id, id.into(), ctx);
+                }
+            }
+
+            for (id, src) in member.srcs() {
+                let publish = spec.get_publish_endpoint_by_id(id.clone());
+                let is_actual = publish.map_or_else(
+                    || {
+                        let play_id = String::from(id.clone()).into();
+                        !remove_unspecified
+                            && spec.get_play_endpoint_by_id(play_id).is_none()
+                    },
+                    |publish| {
+                        src.set_callback_urls(
+                            publish.on_start.clone(),
+                            publish.on_stop.clone(),
+                        );
+                        src.set_meta(publish.meta.clone());
+                        src.p2p() == publish.p2p
+                            && src.is_force_relayed() == publish.force_relay
+                            && src.audio_settings() == publish.audio_settings
+                            && src.video_settings() == publish.video_settings
+                    },
+                );
+                if !is_actual {
+                    if publish.is_some() {
+                        orphaned_sinks.extend(src.sinks().into_iter().map(
+                            |sink| {
+                                (
+                                    sink.owner().id(),
+                                    sink.id(),
+                                    WebRtcPlayEndpointSpec {
+                                        src: sink.src_uri(),
+                                        force_relay: sink.is_force_relayed(),
+                                        on_start: sink.get_on_start(),
+                                        on_stop: sink.get_on_stop(),
+                                        meta: sink.meta(),
+                                    },
+                                )
+                            },
+                        ));
+                    }
+                    self.delete_endpoint(member_id, id.into(), ctx);
+                }
+            }
+        }
+
+        let mut created_members = HashSet::new();
+        for (member_id, spec) in specs {
+            if let Ok(member) = self.members.get_member_by_id(member_id) {
+                for (id, publish) in spec.publish_endpoints() {
+                    if member.get_src_by_id(&id).is_none() {
+                        self.create_src_endpoint(member_id, id, publish)?;
+                    }
+                }
+            } else {
+                let member = self.members.build_member(member_id.clone(), spec);
+                self.members.insert_member(member_id.clone(), member);
+                created_members.insert(member_id);
+                debug!(
+                    "Member [id = {}] created in Room [id = {}].",
+                    member_id, self.id
+                );
+            }
+        }
+
+        let specified_sinks = specs.iter().flat_map(|(member_id, spec)| {
+            spec.play_endpoints()
+                .map(move |(id, play)| (member_id.clone(), id, play.clone()))
+        });
+        for (member_id, id, play) in
+            specified_sinks.chain(orphaned_sinks).collect::<Vec<_>>()
+        {
+            let member = self.members.get_member(&member_id)?;
+            if member.get_sink_by_id(&id).is_none() {
+                self.create_sink_endpoint(&member_id, id, play)?
+                    .map(|_, _, _| ())
+                    .spawn(ctx);
+            }
+        }
+
+        specs
+            .iter()
+            .map(|(id, spec)| {
+                let member = self.members.get_member(id)?;
+                let credentials = spec
+                    .credentials()
+                    .plain()
+                    .filter(|c| member.is_verified_credential(c));
+                Ok((id.clone(), credentials.cloned()))
+            })
+            .collect()
+    }
+
+    /// Verifies plain credentials from the provided [`MemberSpec`]s against
+    /// credentials of the existing [`Member`]s on the blocking thread pool,
+    /// remembering the valid ones.
+    ///
+    /// Credentials already known to be valid are not verified again.
+    fn verify_specified_credentials(
+        &self,
+        specs: &HashMap<MemberId, MemberSpec>,
+    ) -> ActFuture<()> {
+        let (members, unverified): (Vec<_>, Vec<_>) = specs
+            .iter()
+            .filter_map(|(id, spec)| {
+                let plain = spec.credentials().plain()?;
+                let member = self.members.get_member_by_id(id).ok()?;
+                if member.is_verified_credential(plain) {
+                    return None;
+                }
+                let hash = member.credentials_hash();
+                Some((member, (hash, plain.clone())))
+            })
+            .unzip();
+        if unverified.is_empty() {
+            return Box::pin(fut::ready(()));
+        }
+
+        Box::pin(
+            credentials::blocking(move || {
+                unverified
+                    .into_iter()
+                    .map(|(hash, plain)| {
+                        if hash.verify(&plain) {
+                            Some(plain)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .into_actor(self)
+            .map(move |verified, _, _| {
+                for (member, plain) in members.into_iter().zip(verified) {
+                    if let Some(plain) = plain {
+                        member.remember_verified_credential(&plain);
+                    }
+                }
+            }),
+        )
+    }
+
     /// Removes [`Peer`]s and call [`Room::member_peers_removed`] for every
     /// [`Member`].
     ///
     /// This will delete [`Peer`]s from [`PeerRepository`] and send
-    /// [`Event::PeersRemoved`] event to [`Member`].
+    /// [`Event::PeersRemoved`] event to [`Member`]. Traffic of the removed
+    /// [`Peer`]s is stopped before, so `OnStop` callbacks are sent.
     fn remove_peers<'a, Peers: IntoIterator<Item = &'a PeerId>>(
         &mut self,
         member_id: &MemberId,
@@ -263,8 +581,10 @@ impl Room {
         ctx: &mut Context<Self>,
     ) {
         debug!("Remove peers.");
+        let peer_ids: Vec<_> = peer_ids_to_remove.into_iter().collect();
+        self.peers_traffic_stopped(peer_ids.iter().map(|id| **id));
         self.peers
-            .remove_peers(&member_id, peer_ids_to_remove)
+            .remove_peers(&member_id, peer_ids)
             .into_iter()
             .for_each(|(member_id, peers)| {
                 self.member_peers_removed(
@@ -275,6 +595,140 @@ impl Room {
                 .spawn(ctx);
             });
     }
+
+    /// Returns serialized states of this [`Room`] and all its elements, which
+    /// are compared for detecting the changed elements.
+    fn elements_states(&self) -> HashMap<StatefulFid, proto::Element> {
+        let mut states = HashMap::new();
+        for member in self.members.members().values() {
+            for (id, sink) in member.sinks() {
+                states.insert(
+                    member.get_fid_to_endpoint(id.into()).into(),
+                    sink.into(),
+                );
+            }
+            for (id, src) in member.srcs() {
+                states.insert(
+                    member.get_fid_to_endpoint(id.into()).into(),
+                    src.into(),
+                );
+            }
+            states.insert(member.get_fid().into(), member.clone().into());
+        }
+        states.insert(Fid::<ToRoom>::new(self.id.clone()).into(), self.into());
+        states
+    }
+
+    /// Bumps versions of this [`Room`] and all its elements, which have been
+    /// changed since the provided states were taken with
+    /// [`Room::elements_states`].
+    ///
+    /// Changing of an element changes states, and so versions, of all its
+    /// parent elements as well.
+    pub(super) fn commit_versions(
+        &mut self,
+        before: &HashMap<StatefulFid, proto::Element>,
+    ) {
+        let after = self.elements_states();
+        if &after == before {
+            return;
+        }
+        self.revision += 1;
+        let revision = self.revision;
+        self.versions.retain(|fid, _| after.contains_key(fid));
+        for (fid, state) in after {
+            if before.get(&fid) != Some(&state) {
+                self.versions.insert(fid, revision);
+            }
+        }
+    }
+
+    /// Returns version of the element with the provided [`StatefulFid`].
+    ///
+    /// Not existing elements have `0` version.
+    fn version(&self, fid: &StatefulFid) -> u64 {
+        self.versions.get(fid).copied().unwrap_or_default()
+    }
+
+    /// Checks that the element with the provided [`StatefulFid`] has the
+    /// `expected` version, if any.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::VersionMismatch`] if the element has another
+    /// version.
+    fn check_version(
+        &self,
+        fid: StatefulFid,
+        expected: Option<u64>,
+    ) -> Result<(), RoomError> {
+        match expected {
+            Some(expected) if expected != self.version(&fid) => {
+                Err(RoomError::VersionMismatch {
+                    actual: self.version(&fid),
+                    fid,
+                    expected,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that elements of this [`Room`] have the provided expected
+    /// versions.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::VersionMismatch`] if some element has another
+    /// version.
+    fn check_versions(
+        &self,
+        expected: &HashMap<StatefulFid, u64>,
+    ) -> Result<(), RoomError> {
+        for (fid, version) in expected {
+            self.check_version(fid.clone(), Some(*version))?;
+        }
+        Ok(())
+    }
+
+    /// Fills versions of the provided serialized [`proto::Room`] and all its
+    /// elements.
+    fn fill_room_versions(&self, room: &mut proto::Room) {
+        let fid = Fid::<ToRoom>::new(self.id.clone());
+        room.version = self.version(&fid.clone().into());
+        for (id, element) in &mut room.pipeline {
+            if let Some(proto::room::element::El::Member(member)) =
+                &mut element.el
+            {
+                self.fill_member_versions(
+                    &fid.clone().push_member_id(id.clone().into()),
+                    member,
+                );
+            }
+        }
+    }
+
+    /// Fills versions of the provided serialized [`proto::Member`] and all
+    /// its `Endpoint`s.
+    fn fill_member_versions(
+        &self,
+        fid: &Fid<ToMember>,
+        member: &mut proto::Member,
+    ) {
+        use proto::member::element::El;
+
+        member.version = self.version(&fid.clone().into());
+        for (id, element) in &mut member.pipeline {
+            let version = self.version(
+                &fid.clone().push_endpoint_id(id.clone().into()).into(),
+            );
+            match &mut element.el {
+                Some(El::WebrtcPlay(play)) => play.version = version,
+                Some(El::WebrtcPub(publish)) => publish.version = version,
+                None => {}
+            }
+        }
+    }
 }
 
 impl Into<proto::Room> for &Room {
@@ -288,6 +742,16 @@ impl Into<proto::Room> for &Room {
         proto::Room {
             id: self.id().to_string(),
             pipeline,
+            auto_remove: Some(self.auto_remove.into()),
+            on_remove: self
+                .on_remove
+                .as_ref()
+                .map(CallbackUrl::to_unredacted_string)
+                .unwrap_or_default(),
+            // Filled by `Room::fill_room_versions()`.
+            version: 0,
+            labels: self.meta.labels.clone(),
+            metadata: self.meta.metadata.clone(),
         }
     }
 }
@@ -325,8 +789,14 @@ impl Handler<SerializeProto> for Room {
             match &fid {
                 StatefulFid::Room(room_fid) => {
                     if room_fid.room_id() == &self.id {
-                        let current_room: proto::Element = (&*self).into();
-                        serialized.insert(fid, current_room);
+                        let mut room: proto::Room = (&*self).into();
+                        self.fill_room_versions(&mut room);
+                        serialized.insert(
+                            fid,
+                            proto::Element {
+                                el: Some(proto::element::El::Room(room)),
+                            },
+                        );
                     } else {
                         return Err(RoomError::WrongRoomId(
                             fid,
@@ -335,9 +805,15 @@ impl Handler<SerializeProto> for Room {
                     }
                 }
                 StatefulFid::Member(member_fid) => {
-                    let member =
-                        self.members.get_member(member_fid.member_id())?;
-                    serialized.insert(fid, member.into());
+                    let mut member: proto::Member =
+                        self.members.get_member(member_fid.member_id())?.into();
+                    self.fill_member_versions(member_fid, &mut member);
+                    serialized.insert(
+                        fid,
+                        proto::Element {
+                            el: Some(proto::element::El::Member(member)),
+                        },
+                    );
                 }
                 StatefulFid::Endpoint(endpoint_fid) => {
                     let member =
@@ -345,7 +821,18 @@ impl Handler<SerializeProto> for Room {
                     let endpoint = member.get_endpoint_by_id(
                         endpoint_fid.endpoint_id().to_string(),
                     )?;
-                    serialized.insert(fid, endpoint.into());
+                    let mut element: proto::Element = endpoint.into();
+                    let version = self.version(&fid);
+                    match &mut element.el {
+                        Some(proto::element::El::WebrtcPlay(play)) => {
+                            play.version = version;
+                        }
+                        Some(proto::element::El::WebrtcPub(publish)) => {
+                            publish.version = version;
+                        }
+                        _ => {}
+                    }
+                    serialized.insert(fid, element);
                 }
             }
         }
@@ -354,15 +841,97 @@ impl Handler<SerializeProto> for Room {
     }
 }
 
+/// Summary of this [`Room`]'s state, used for listing [`Room`]s and
+/// enforcing their [`AutoRemove`] rules.
+#[derive(Clone, Debug, MessageResponse)]
+pub struct RoomSummary {
+    /// Number of [`Member`]s in this [`Room`].
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub members: usize,
+
+    /// Number of [`Member`]s having an active [`RpcConnection`] with this
+    /// [`Room`].
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+    pub connected_members: usize,
+
+    /// Rule of automatic removing of this [`Room`].
+    pub auto_remove: AutoRemove,
+
+    /// URL of the callback to fire when this [`Room`] is removed
+    /// automatically.
+    pub on_remove: Option<CallbackUrl>,
+
+    /// Labels and metadata of this [`Room`].
+    pub meta: ElementMeta,
+
+    /// Indicator whether this [`Room`] is operating, i.e. it's not being
+    /// closed due to server shutdown.
+    pub is_operating: bool,
+}
+
+/// Message for retrieving [`RoomSummary`] of this [`Room`].
+#[derive(Message)]
+#[rtype(result = "RoomSummary")]
+pub struct Summarize;
+
+impl Handler<Summarize> for Room {
+    type Result = RoomSummary;
+
+    fn handle(&mut self, _: Summarize, _: &mut Self::Context) -> RoomSummary {
+        let mut summary = RoomSummary {
+            members: 0,
+            connected_members: 0,
+            auto_remove: self.auto_remove,
+            on_remove: self.on_remove.clone(),
+            meta: self.meta.clone(),
+            is_operating: matches!(self.state, State::Started),
+        };
+        for (id, _) in self.members.iter_members() {
+            summary.members += 1;
+            if self.members.member_has_connection(id) {
+                summary.connected_members += 1;
+            }
+        }
+        summary
+    }
+}
+
+/// Signal for checking that elements of this [`Room`] have the provided
+/// expected versions.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct CheckVersions(pub HashMap<StatefulFid, u64>);
+
+impl Handler<CheckVersions> for Room {
+    type Result = Result<(), RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: CheckVersions,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        self.check_versions(&msg.0)
+    }
+}
+
 /// Signal for deleting elements from this [`Room`].
+///
+/// Elements are deleted only if all the provided expected versions match the
+/// actual versions of the elements.
 #[derive(Message, Debug)]
-#[rtype(result = "()")]
-pub struct Delete(pub Vec<StatefulFid>);
+#[rtype(result = "Result<(), RoomError>")]
+pub struct Delete(pub Vec<StatefulFid>, pub HashMap<StatefulFid, u64>);
 
 impl Handler<Delete> for Room {
-    type Result = ();
+    type Result = Result<(), RoomError>;
+
+    fn handle(&mut self, msg: Delete, ctx: &mut Self::Context) -> Self::Result {
+        self.check_versions(&msg.1)?;
+        let before = self.elements_states();
 
-    fn handle(&mut self, msg: Delete, ctx: &mut Self::Context) {
         let mut member_ids = Vec::new();
         let mut endpoint_ids = Vec::new();
         for id in msg.0 {
@@ -385,13 +954,19 @@ impl Handler<Delete> for Room {
             let (_, member_id, endpoint_id) = fid.take_all();
             self.delete_endpoint(&member_id, endpoint_id, ctx);
         });
+
+        self.commit_versions(&before);
+        Ok(())
     }
 }
 
 /// Signal for creating new `Member` in this [`Room`].
+///
+/// `Member` is created only if this [`Room`] has the provided expected version,
+/// if any.
 #[derive(Message, Debug)]
 #[rtype(result = "Result<(), RoomError>")]
-pub struct CreateMember(pub MemberId, pub MemberSpec);
+pub struct CreateMember(pub MemberId, pub MemberSpec, pub Option<u64>);
 
 impl Handler<CreateMember> for Room {
     type Result = Result<(), RoomError>;
@@ -401,10 +976,452 @@ impl Handler<CreateMember> for Room {
         msg: CreateMember,
         _: &mut Self::Context,
     ) -> Self::Result {
-        self.members.create_member(msg.0.clone(), &msg.1)?;
+        let CreateMember(id, spec, expected_version) = msg;
+        self.check_version(
+            Fid::<ToRoom>::new(self.id.clone()).into(),
+            expected_version,
+        )?;
+        let before = self.elements_states();
+        self.members.create_member(id.clone(), &spec)?;
+        self.commit_versions(&before);
+        debug!("Member [id = {}] created in Room [id = {}].", id, self.id);
+        Ok(())
+    }
+}
+
+/// Signal for provisioning `Member` authenticated with a signed token in this
+/// [`Room`].
+///
+/// Creates new `Member` from the provided [`MemberSpec`] if it doesn't exist
+/// yet. Existing `Member` is left untouched.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct ProvisionMember(pub MemberId, pub MemberSpec);
+
+impl Handler<ProvisionMember> for Room {
+    type Result = Result<(), RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: ProvisionMember,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let ProvisionMember(id, spec) = msg;
+        if self.members.get_member_by_id(&id).is_ok() {
+            return Ok(());
+        }
+        let before = self.elements_states();
+        self.members.create_member(id.clone(), &spec)?;
+        self.commit_versions(&before);
+        debug!(
+            "Member [id = {}] provisioned in Room [id = {}].",
+            id, self.id
+        );
+        Ok(())
+    }
+}
+
+/// Signal for continuing revisions of this [`Room`] restored from a snapshot
+/// from the provided one.
+///
+/// Versions of this [`Room`] and all its elements are bumped past the provided
+/// revision, so they never repeat the ones seen before the restart.
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct SeedRevision(pub u64);
+
+impl Handler<SeedRevision> for Room {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: SeedRevision,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        self.revision = self.revision.max(msg.0);
+        self.commit_versions(&HashMap::new());
+    }
+}
+
+/// Signal for applying [`RoomSpec`] to this [`Room`].
+///
+/// [`RoomSpec`] is applied only if this [`Room`] has the provided expected
+/// version, if any.
+///
+/// Returns plain credentials (if known) of all [`Member`]s from the applied
+/// [`RoomSpec`].
+#[derive(Message, Debug)]
+#[rtype(result = "Result<HashMap<MemberId, Option<Credential>>, RoomError>")]
+pub struct ApplyRoomSpec(pub RoomSpec, pub ApplyPolicy, pub Option<u64>);
+
+impl Handler<ApplyRoomSpec> for Room {
+    type Result = AtomicResponse<
+        Self,
+        Result<HashMap<MemberId, Option<Credential>>, RoomError>,
+    >;
+
+    /// Verifies plain credentials of the existing `Member`s before applying
+    /// [`RoomSpec`], not handling other messages meanwhile.
+    fn handle(
+        &mut self,
+        msg: ApplyRoomSpec,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let ApplyRoomSpec(spec, policy, expected_version) = msg;
+        let fid: StatefulFid = Fid::<ToRoom>::new(self.id.clone()).into();
+        let specs =
+            self.check_version(fid.clone(), expected_version)
+                .and_then(|_| {
+                    spec.members().map_err(|e| {
+                        MembersLoadError::TryFromError(e, fid).into()
+                    })
+                });
+        let specs = match specs {
+            Ok(specs) => specs,
+            Err(e) => return AtomicResponse::new(Box::pin(fut::err(e))),
+        };
+
+        AtomicResponse::new(Box::pin(
+            self.verify_specified_credentials(&specs).map(
+                move |_, this, ctx| {
+                    let before = this.elements_states();
+                    let credentials =
+                        this.apply_members(&specs, policy, true, ctx);
+                    if credentials.is_ok() {
+                        this.auto_remove = spec.auto_remove;
+                        this.on_remove = spec.on_remove;
+                        this.meta = spec.meta;
+                        debug!("Spec applied to Room [id = {}].", this.id);
+                    }
+                    // Spec may be applied partially on error, so versions of
+                    // the already changed elements should be bumped anyway.
+                    this.commit_versions(&before);
+                    credentials
+                },
+            ),
+        ))
+    }
+}
+
+/// Signal for applying [`MemberSpec`] to the `Member` of this [`Room`].
+///
+/// [`MemberSpec`] is applied only if the `Member` has the provided expected
+/// version, if any.
+///
+/// Returns plain credentials (if known) of the applied `Member`.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<HashMap<MemberId, Option<Credential>>, RoomError>")]
+pub struct ApplyMember(
+    pub MemberId,
+    pub MemberSpec,
+    pub ApplyPolicy,
+    pub Option<u64>,
+);
+
+impl Handler<ApplyMember> for Room {
+    type Result = AtomicResponse<
+        Self,
+        Result<HashMap<MemberId, Option<Credential>>, RoomError>,
+    >;
+
+    /// Verifies plain credentials of the existing `Member` before applying
+    /// [`MemberSpec`], not handling other messages meanwhile.
+    fn handle(
+        &mut self,
+        msg: ApplyMember,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let ApplyMember(id, spec, policy, expected_version) = msg;
+        if let Err(e) = self.check_version(
+            Fid::<ToRoom>::new(self.id.clone())
+                .push_member_id(id.clone())
+                .into(),
+            expected_version,
+        ) {
+            return AtomicResponse::new(Box::pin(fut::err(e)));
+        }
+
+        let specs = hashmap! {id.clone() => spec};
+        AtomicResponse::new(Box::pin(
+            self.verify_specified_credentials(&specs).map(
+                move |_, this, ctx| {
+                    let before = this.elements_states();
+                    let credentials =
+                        this.apply_members(&specs, policy, false, ctx);
+                    // Spec may be applied partially on error, so versions of
+                    // the already changed elements should be bumped anyway.
+                    this.commit_versions(&before);
+                    if credentials.is_ok() {
+                        debug!(
+                            "Spec applied to Member [id = {}] in Room [id = \
+                             {}].",
+                            id, this.id
+                        );
+                    }
+                    credentials
+                },
+            ),
+        ))
+    }
+}
+
+/// Signal for updating properties of the `Member` of this [`Room`] in place.
+///
+/// [`MemberPatch`] is applied only if the `Member` has the provided expected
+/// version, if any.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct UpdateMember(pub MemberId, pub MemberPatch, pub Option<u64>);
+
+impl Handler<UpdateMember> for Room {
+    type Result = Result<(), RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateMember,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let UpdateMember(id, patch, expected_version) = msg;
+        self.check_version(
+            Fid::<ToRoom>::new(self.id.clone())
+                .push_member_id(id.clone())
+                .into(),
+            expected_version,
+        )?;
+        let member = self.members.get_member_by_id(&id)?;
+
+        let before = self.elements_states();
+        self.members.patch_member_settings(&member, &patch);
+        self.commit_versions(&before);
+        debug!("Member [id = {}] updated in Room [id = {}].", id, self.id);
+
+        Ok(())
+    }
+}
+
+/// Signal for kicking the `Member` out of this [`Room`].
+///
+/// Closes `RpcConnection` of the `Member` with [`CloseReason::Evicted`] and
+/// the provided human-readable description, and replaces its credentials with
+/// the provided ones, if any. The `Member` itself is kept in this [`Room`].
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct Kick {
+    pub member_id: MemberId,
+    pub description: Option<String>,
+    pub credentials: Option<Credentials>,
+
+    /// Version which the `Member` is expected to have for being kicked.
+    ///
+    /// Not checked if [`None`].
+    pub expected_version: Option<u64>,
+}
+
+impl Handler<Kick> for Room {
+    type Result = Result<(), RoomError>;
+
+    /// Rotates credentials of the kicked `Member` before closing its
+    /// `RpcConnection`, so it can't reconnect with the old ones.
+    ///
+    /// `on_leave` callback is fired with [`OnLeaveReason::Kicked`] only if the
+    /// `Member` was connected.
+    fn handle(&mut self, msg: Kick, ctx: &mut Self::Context) -> Self::Result {
+        let Kick {
+            member_id,
+            description,
+            credentials,
+            expected_version,
+        } = msg;
+        self.check_version(
+            Fid::<ToRoom>::new(self.id.clone())
+                .push_member_id(member_id.clone())
+                .into(),
+            expected_version,
+        )?;
+        let member = self.members.get_member_by_id(&member_id)?;
+
+        let before = self.elements_states();
+        if let Some(credentials) = credentials {
+            member.set_credentials(credentials.hash());
+        }
+        self.commit_versions(&before);
+
+        let close_description = description.map_or_else(
+            || CloseDescription::new(CloseReason::Evicted),
+            |description| {
+                CloseDescription::with_description(
+                    CloseReason::Evicted,
+                    description,
+                )
+            },
+        );
+        let was_connected = self.members.close_member_connection(
+            &member_id,
+            close_description,
+            ctx,
+        );
+        if was_connected {
+            if let Some(on_leave_url) = member.get_on_leave() {
+                self.callbacks.do_send(
+                    on_leave_url,
+                    member.get_fid().into(),
+                    member.meta(),
+                    OnLeaveEvent::new(OnLeaveReason::Kicked),
+                );
+            }
+            self.remove_disconnected_member_peers(&member_id, ctx);
+        }
+        debug!(
+            "Member [id = {}] kicked from Room [id = {}].",
+            member_id, self.id
+        );
+
+        Ok(())
+    }
+}
+
+/// Signal for sending [`Event::Custom`] with the provided payload to the
+/// `Member` of this [`Room`], or to all its connected `Member`s if no
+/// [`MemberId`] is provided.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct SendCustomEvent(pub Option<MemberId>, pub serde_json::Value);
+
+impl Handler<SendCustomEvent> for Room {
+    type Result = Result<(), RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: SendCustomEvent,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let SendCustomEvent(member_id, payload) = msg;
+
+        if let Some(member_id) = member_id {
+            let member = self.members.get_member_by_id(&member_id)?;
+            if !self.members.member_has_connection(&member_id) {
+                return Err(RoomError::MemberNotConnected(member.get_fid()));
+            }
+            self.members
+                .send_event_to_member(member_id, Event::Custom { payload })
+        } else {
+            let connected: Vec<_> = self
+                .members
+                .iter_members()
+                .map(|(id, _)| id.clone())
+                .filter(|id| self.members.member_has_connection(id))
+                .collect();
+            for member_id in connected {
+                self.members.send_event_to_member(
+                    member_id,
+                    Event::Custom {
+                        payload: payload.clone(),
+                    },
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Signal for updating properties of the `WebRtcPublishEndpoint` of this
+/// [`Room`] in place, without recreating its `Peer`s.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct UpdateEndpoint {
+    pub member_id: MemberId,
+    pub endpoint_id: WebRtcPublishId,
+    pub patch: WebRtcPublishEndpointPatch,
+
+    /// Version which the `Endpoint` is expected to have for the
+    /// [`WebRtcPublishEndpointPatch`] to be applied.
+    ///
+    /// Not checked if [`None`].
+    pub expected_version: Option<u64>,
+}
+
+impl Handler<UpdateEndpoint> for Room {
+    type Result = Result<(), RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateEndpoint,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let UpdateEndpoint {
+            member_id,
+            endpoint_id,
+            patch,
+            expected_version,
+        } = msg;
+        let member = self.members.get_member_by_id(&member_id)?;
+        let fid = member.get_fid_to_endpoint(endpoint_id.clone().into());
+        self.check_version(fid.clone().into(), expected_version)?;
+        let src = member
+            .get_src_by_id(&endpoint_id)
+            .ok_or_else(|| MemberError::EndpointNotFound(fid.clone()))?;
+
+        let is_disabled = |policy| policy == PublishPolicy::Disabled;
+        let is_audio_toggled = patch.audio_settings.map_or(false, |audio| {
+            is_disabled(audio.publish_policy)
+                != is_disabled(src.audio_settings().publish_policy)
+        });
+        let is_video_toggled = patch.video_settings.map_or(false, |video| {
+            is_disabled(video.publish_policy)
+                != is_disabled(src.video_settings().publish_policy)
+        });
+        if is_audio_toggled || is_video_toggled {
+            return Err(RoomError::UnsupportedPublishPolicyUpdate(fid));
+        }
+
+        let before = self.elements_states();
+        let (prev_force_relay, prev_audio, prev_video) = (
+            src.is_force_relayed(),
+            src.audio_settings(),
+            src.video_settings(),
+        );
+        let is_force_relay_changed = patch
+            .force_relay
+            .map_or(false, |force_relay| force_relay != prev_force_relay);
+        let is_policy_changed =
+            patch.audio_settings.is_some() || patch.video_settings.is_some();
+        if let Some(force_relay) = patch.force_relay {
+            src.set_force_relayed(force_relay);
+        }
+        if let Some(audio) = patch.audio_settings {
+            src.set_audio_settings(audio);
+        }
+        if let Some(video) = patch.video_settings {
+            src.set_video_settings(video);
+        }
+
+        let mut result = Ok(());
+        if is_force_relay_changed {
+            result = self.peers.update_force_relay(&src);
+        }
+        if is_policy_changed {
+            result =
+                result.and_then(|_| self.peers.update_publish_policy(&src));
+        }
+        if let Err(e) = result {
+            // `Endpoint` is rolled back, and its `Peer`s which have been
+            // updated already are reverted on a best-effort basis.
+            src.set_force_relayed(prev_force_relay);
+            src.set_audio_settings(prev_audio);
+            src.set_video_settings(prev_video);
+            if is_force_relay_changed {
+                let _ = self.peers.update_force_relay(&src);
+            }
+            if is_policy_changed {
+                let _ = self.peers.update_publish_policy(&src);
+            }
+            return Err(e);
+        }
+
+        self.commit_versions(&before);
         debug!(
-            "Member [id = {}] created in Room [id = {}].",
-            msg.0, self.id
+            "Endpoint [id = {}] updated in Room [id = {}].",
+            fid, self.id
         );
         Ok(())
     }
@@ -417,6 +1434,12 @@ pub struct CreateEndpoint {
     pub member_id: MemberId,
     pub endpoint_id: EndpointId,
     pub spec: EndpointSpec,
+
+    /// Version which the `Member` is expected to have for the `Endpoint` to
+    /// be created.
+    ///
+    /// Not checked if [`None`].
+    pub expected_version: Option<u64>,
 }
 
 impl Handler<CreateEndpoint> for Room {
@@ -427,7 +1450,17 @@ impl Handler<CreateEndpoint> for Room {
         msg: CreateEndpoint,
         _: &mut Self::Context,
     ) -> Self::Result {
-        match msg.spec {
+        if let Err(e) = self.check_version(
+            Fid::<ToRoom>::new(self.id.clone())
+                .push_member_id(msg.member_id.clone())
+                .into(),
+            msg.expected_version,
+        ) {
+            return Box::pin(fut::err(e));
+        }
+
+        let before = self.elements_states();
+        let result: Self::Result = match msg.spec {
             EndpointSpec::WebRtcPlay(endpoint) => {
                 match self.create_sink_endpoint(
                     &msg.member_id,
@@ -449,7 +1482,9 @@ impl Handler<CreateEndpoint> for Room {
                     Box::pin(fut::ok(()))
                 }
             }
-        }
+        };
+        self.commit_versions(&before);
+        result
     }
 }
 
@@ -6,11 +6,11 @@ mod dynamic_api;
 mod peer_events_handler;
 mod rpc_server;
 
-use std::{pin::Pin, rc::Rc, sync::Arc, time::Duration};
+use std::{collections::HashMap, pin::Pin, rc::Rc, sync::Arc, time::Duration};
 
 use actix::{
-    fut, Actor, ActorFuture, Addr, AsyncContext as _, Context, Handler,
-    MailboxError, WrapFuture as _,
+    fut, Actor, ActorFuture, Addr, AsyncContext as _, Context,
+    ContextFutureSpawner as _, Handler, MailboxError, WrapFuture as _,
 };
 use derive_more::{Display, From};
 use failure::Fail;
@@ -22,11 +22,12 @@ use medea_client_api_proto::{
 use crate::{
     api::control::{
         callback::{
-            CallbackClientError, CallbackClientFactoryImpl, CallbackService,
-            OnLeaveEvent, OnLeaveReason,
+            url::CallbackUrl, CallbackClientError, CallbackClientFactoryImpl,
+            CallbackService, OnLeaveEvent, OnLeaveReason,
         },
         refs::{Fid, StatefulFid, ToEndpoint, ToMember},
-        room::RoomSpec,
+        room::{AutoRemove, RoomSpec},
+        ElementMeta,
     },
     log::prelude::*,
     media::{peer::PeerUpdatesSubscriber, Peer, PeerError, Stable},
@@ -42,7 +43,9 @@ use crate::{
 };
 
 pub use dynamic_api::{
-    Close, CreateEndpoint, CreateMember, Delete, SerializeProto,
+    ApplyMember, ApplyRoomSpec, CheckVersions, Close, CreateEndpoint,
+    CreateMember, Delete, Kick, ProvisionMember, RoomSummary, SeedRevision,
+    SendCustomEvent, SerializeProto, Summarize, UpdateEndpoint, UpdateMember,
 };
 
 /// Ergonomic type alias for using [`ActorFuture`] for [`Room`].
@@ -95,6 +98,43 @@ pub enum RoomError {
     #[display(fmt = "Endpoint [id = {}] already exists.", _0)]
     EndpointAlreadyExists(Fid<ToEndpoint>),
 
+    /// Version of the element doesn't match the one expected by request.
+    ///
+    /// Not existing elements have `0` version.
+    #[display(
+        fmt = "Element [id = {}] has version {}, but {} is expected",
+        fid,
+        actual,
+        expected
+    )]
+    #[from(ignore)]
+    VersionMismatch {
+        /// [`StatefulFid`] of the element with mismatched version.
+        fid: StatefulFid,
+
+        /// Version expected by request.
+        expected: u64,
+
+        /// Actual version of the element.
+        actual: u64,
+    },
+
+    /// Publish policy of the `WebRtcPublishEndpoint` can't be changed in
+    /// place to or from `Disabled`, since this requires `MediaTrack`s to be
+    /// added or removed.
+    #[display(
+        fmt = "Publish policy of Endpoint [id = {}] can't be updated from or \
+               to `Disabled` in place",
+        _0
+    )]
+    #[from(ignore)]
+    UnsupportedPublishPolicyUpdate(Fid<ToEndpoint>),
+
+    /// `Member` has no established `RpcConnection` to send an event to.
+    #[display(fmt = "Member [id = {}] is not connected", _0)]
+    #[from(ignore)]
+    MemberNotConnected(Fid<ToMember>),
+
     /// [`TurnAuthService`] errored to perform an operation.
     ///
     /// [`TurnAuthService`]: crate::turn::service::TurnAuthService
@@ -148,6 +188,26 @@ pub struct Room {
 
     /// Current state of this [`Room`].
     state: State,
+
+    /// Rule of automatic removing of this [`Room`].
+    auto_remove: AutoRemove,
+
+    /// URL of the callback to fire when this [`Room`] is removed
+    /// automatically.
+    on_remove: Option<CallbackUrl>,
+
+    /// Labels and metadata of this [`Room`].
+    meta: ElementMeta,
+
+    /// Current revision of this [`Room`], which is incremented every time
+    /// this [`Room`] or any of its elements is changed via [Control API].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    revision: u64,
+
+    /// Versions of this [`Room`] and all its elements, being the revisions
+    /// they have been changed at last time.
+    versions: HashMap<StatefulFid, u64>,
 }
 
 impl Room {
@@ -167,8 +227,16 @@ impl Room {
         // 16 is the default actix address channel capacity.
         let (_, rx) = actix::dev::channel::channel(16);
 
-        let ctx = Context::with_receiver(rx);
-        let this = Self {
+        let mut ctx = Context::with_receiver(rx);
+        let register_room = {
+            let peers_traffic_watcher = Arc::clone(&peers_traffic_watcher);
+            let room_id = room_spec.id().clone();
+            let handler = Box::new(ctx.address().downgrade());
+            async move {
+                peers_traffic_watcher.register_room(room_id, handler).await
+            }
+        };
+        let mut this = Self {
             id: room_spec.id().clone(),
             peers: PeersService::new(
                 room_spec.id().clone(),
@@ -181,7 +249,22 @@ impl Room {
             members: ParticipantService::new(room_spec, context)?,
             state: State::Started,
             callbacks: context.callbacks.clone(),
+            auto_remove: room_spec.auto_remove,
+            on_remove: room_spec.on_remove.clone(),
+            meta: room_spec.meta.clone(),
+            revision: 0,
+            versions: HashMap::new(),
         };
+        this.commit_versions(&HashMap::new());
+        ctx.wait(register_room.into_actor(&this).map(|res, room, _| {
+            if let Err(e) = res {
+                error!(
+                    "Failed to register Room [id = {}] in PeerTrafficWatcher: \
+                     {}",
+                    room.id, e,
+                );
+            }
+        }));
 
         Ok(ctx.run(this))
     }
@@ -298,6 +381,7 @@ impl Room {
                 self.callbacks.do_send(
                     on_leave,
                     member.get_fid().into(),
+                    member.meta(),
                     OnLeaveEvent::new(OnLeaveReason::ServerShutdown),
                 );
             });
@@ -351,6 +435,34 @@ impl Room {
         }
     }
 
+    /// Removes all [`Peer`]s related to the disconnected [`Member`] with the
+    /// provided [`MemberId`], signalling its partners about it.
+    ///
+    /// Traffic of the removed [`Peer`]s is stopped before, so `OnStop`
+    /// callbacks are sent.
+    fn remove_disconnected_member_peers(
+        &mut self,
+        member_id: &MemberId,
+        ctx: &mut Context<Self>,
+    ) {
+        if let Ok(member) = self.members.get_member_by_id(member_id) {
+            self.peers_traffic_stopped(member.peer_ids());
+        }
+        let removed_peers =
+            self.peers.remove_peers_related_to_member(member_id);
+
+        for (peer_member_id, peers_ids) in removed_peers {
+            // Here we may have some problems. If two participants
+            // disconnect at one moment then sending event
+            // to another participant fail,
+            // because connection already closed but we don't know about it
+            // because message in event loop.
+            self.member_peers_removed(peers_ids, peer_member_id)
+                .map(|_, _, _| ())
+                .spawn(ctx);
+        }
+    }
+
     /// Sends [`Event::TracksApplied`] with latest [`Peer`] changes to specified
     /// [`Member`]. Starts renegotiation, marking provided [`Peer`] as
     /// [`NegotiationRole::Offerer`].
@@ -398,6 +510,11 @@ impl Actor for Room {
         });
         ctx.add_stream(self.peers.subscribe_to_metrics_events());
     }
+
+    fn stopped(&mut self, _: &mut Self::Context) {
+        debug!("Room [id = {}] stopped.", self.id);
+        self.peers.unregister_room();
+    }
 }
 
 impl Handler<ShutdownGracefully> for Room {
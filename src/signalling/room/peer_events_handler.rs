@@ -1,17 +1,24 @@
 //! [`PeerConnectionStateEventsHandler`] implementation for [`Room`].
 
+use std::{collections::HashMap, iter, rc::Rc};
+
 use actix::{Handler, Message, StreamHandler, WeakAddr};
 use chrono::{DateTime, Utc};
 use medea_client_api_proto::{
-    ConnectionQualityScore, Event, MemberId, NegotiationRole, PeerId,
+    ConnectionQualityScore, Event, MemberId, NegotiationRole, PeerId, TrackId,
     TrackUpdate,
 };
 
 use crate::{
-    api::control::callback::{MediaDirection, MediaType},
+    api::control::callback::{
+        MediaDirection, MediaType, OnStartEvent, OnStopEvent,
+    },
     log::prelude::*,
-    media::{peer::PeerUpdatesSubscriber, Peer, PeerStateMachine, Stable},
+    media::{
+        peer::PeerUpdatesSubscriber, MediaTrack, Peer, PeerStateMachine, Stable,
+    },
     signalling::{
+        elements::endpoints::{Endpoint, WeakEndpoint},
         peers::{
             PeerConnectionStateEventsHandler, PeersMetricsEvent,
             PeersMetricsEventHandler,
@@ -49,6 +56,144 @@ impl Room {
 
         self.members.send_event_to_member(member_id, peer_created)
     }
+
+    /// Returns [`Endpoint`]s of the [`Peer`] with provided [`PeerId`] which
+    /// have provided [`MediaDirection`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if requested [`PeerId`] doesn't
+    /// exist in [`PeerRepository`].
+    fn peer_endpoints(
+        &self,
+        peer_id: PeerId,
+        direction: MediaDirection,
+    ) -> Result<Vec<Endpoint>, RoomError> {
+        Ok(self
+            .peers
+            .map_peer_by_id(peer_id, PeerStateMachine::endpoints)?
+            .iter()
+            .filter_map(WeakEndpoint::upgrade)
+            .filter(|endpoint| endpoint.direction() == direction)
+            .collect())
+    }
+
+    /// Marks provided [`MediaType`] as started in the [`Endpoint`]s of the
+    /// [`Peer`] with provided [`PeerId`] which have provided
+    /// [`MediaDirection`].
+    ///
+    /// Sends `OnStart` Control API callback for every [`Endpoint`] in which
+    /// some [`MediaType`] has started flowing.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if requested [`PeerId`] doesn't
+    /// exist in [`PeerRepository`].
+    fn endpoints_traffic_started(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+        direction: MediaDirection,
+    ) -> Result<(), RoomError> {
+        for endpoint in self.peer_endpoints(peer_id, direction)? {
+            let started = endpoint.traffic_started(peer_id, media_type);
+            if let (Some(started), Some(on_start)) =
+                (started, endpoint.get_on_start())
+            {
+                self.callbacks.do_send(
+                    on_start,
+                    endpoint.fid().into(),
+                    endpoint.meta(),
+                    OnStartEvent::new(started, direction),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks provided [`MediaType`] as stopped in the [`Endpoint`]s of the
+    /// [`Peer`] with provided [`PeerId`] which have provided
+    /// [`MediaDirection`].
+    ///
+    /// Sends `OnStop` Control API callback for every [`Endpoint`] in which
+    /// some [`MediaType`] has stopped flowing.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if requested [`PeerId`] doesn't
+    /// exist in [`PeerRepository`].
+    fn endpoints_traffic_stopped(
+        &self,
+        peer_id: PeerId,
+        media_type: MediaType,
+        direction: MediaDirection,
+        at: DateTime<Utc>,
+    ) -> Result<(), RoomError> {
+        for endpoint in self.peer_endpoints(peer_id, direction)? {
+            let stopped = endpoint.traffic_stopped(peer_id, media_type);
+            if let (Some(stopped), Some(on_stop)) =
+                (stopped, endpoint.get_on_stop())
+            {
+                self.callbacks.do_send_at(
+                    on_stop,
+                    endpoint.fid().into(),
+                    endpoint.meta(),
+                    OnStopEvent::new(stopped, direction),
+                    at,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks all [`MediaType`]s as stopped in the [`Endpoint`]s of the
+    /// [`Peer`]s with provided [`PeerId`]s and of their partner [`Peer`]s.
+    ///
+    /// Sends `OnStop` Control API callback for every [`Endpoint`] in which
+    /// some [`MediaType`] was flowing.
+    ///
+    /// Should be called before removing these [`Peer`]s, since traffic of the
+    /// removed [`Peer`]s is not watched anymore.
+    pub(super) fn peers_traffic_stopped<I: IntoIterator<Item = PeerId>>(
+        &self,
+        peer_ids: I,
+    ) {
+        let at = Utc::now();
+        for peer_id in peer_ids {
+            let partner_peer_id = self
+                .peers
+                .map_peer_by_id(peer_id, PeerStateMachine::partner_peer_id)
+                .ok();
+            for peer_id in iter::once(peer_id).chain(partner_peer_id) {
+                for direction in
+                    &[MediaDirection::Publish, MediaDirection::Play]
+                {
+                    // `Peer` existence is checked above.
+                    let _ = self.endpoints_traffic_stopped(
+                        peer_id,
+                        MediaType::Both,
+                        *direction,
+                        at,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Returns [`MediaType`] of all the provided [`MediaTrack`]s.
+///
+/// Returns `None` if there are no [`MediaTrack`]s.
+fn tracks_media_type(
+    tracks: &HashMap<TrackId, Rc<MediaTrack>>,
+) -> Option<MediaType> {
+    tracks
+        .values()
+        .map(|track| MediaType::from(&track.media_type()))
+        .fold(None, |acc, media_type| match acc {
+            Some(acc) if acc != media_type => Some(MediaType::Both),
+            _ => Some(media_type),
+        })
 }
 
 impl PeerConnectionStateEventsHandler for WeakAddr<Room> {
@@ -82,25 +227,40 @@ impl StreamHandler<PeersMetricsEvent> for Room {
 impl PeersMetricsEventHandler for Room {
     type Output = Result<(), RoomError>;
 
-    /// Does nothing atm.
+    /// Marks provided [`MediaType`] as stopped in the [`Endpoint`]s of the
+    /// [`Peer`] and sends `OnStop` Control API callbacks if needed.
     fn on_no_traffic_flow(
         &mut self,
-        _: PeerId,
-        _: DateTime<Utc>,
-        _: MediaType,
-        _: MediaDirection,
+        peer_id: PeerId,
+        was_flowing_at: DateTime<Utc>,
+        media_type: MediaType,
+        direction: MediaDirection,
     ) -> Self::Output {
-        Ok(())
+        debug!(
+            "NoTrafficFlow of {} in {:?} direction for Peer [id = {}].",
+            media_type, direction, peer_id,
+        );
+        self.endpoints_traffic_stopped(
+            peer_id,
+            media_type,
+            direction,
+            was_flowing_at,
+        )
     }
 
-    /// Does nothing atm.
+    /// Marks provided [`MediaType`] as started in the [`Endpoint`]s of the
+    /// [`Peer`] and sends `OnStart` Control API callbacks if needed.
     fn on_traffic_flows(
         &mut self,
-        _: PeerId,
-        _: MediaType,
-        _: MediaDirection,
+        peer_id: PeerId,
+        media_type: MediaType,
+        direction: MediaDirection,
     ) -> Self::Output {
-        Ok(())
+        debug!(
+            "TrafficFlows of {} in {:?} direction for Peer [id = {}].",
+            media_type, direction, peer_id,
+        );
+        self.endpoints_traffic_started(peer_id, media_type, direction)
     }
 
     /// Sends received [`ConnectionQualityScore`] to member.
@@ -150,24 +310,74 @@ struct PeerStopped {
 impl Handler<PeerStarted> for Room {
     type Result = ();
 
+    /// Marks [`MediaType`]s of all the [`Peer`]'s [`MediaTrack`]s as started
+    /// in its [`Endpoint`]s and sends `OnStart` Control API callbacks if
+    /// needed.
     fn handle(
         &mut self,
-        _: PeerStarted,
+        msg: PeerStarted,
         _: &mut Self::Context,
     ) -> Self::Result {
-        // TODO: Implement PeerStarted logic.
+        let peer_id = msg.0;
+        let media_types = self.peers.map_peer_by_id(peer_id, |peer| {
+            [
+                (MediaDirection::Publish, tracks_media_type(peer.senders())),
+                (MediaDirection::Play, tracks_media_type(peer.receivers())),
+            ]
+        });
+        let result = media_types.and_then(|media_types| {
+            for (direction, media_type) in &media_types {
+                if let Some(media_type) = media_type {
+                    self.endpoints_traffic_started(
+                        peer_id,
+                        *media_type,
+                        *direction,
+                    )?;
+                }
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            warn!("Failed to handle PeerStarted [id = {}]: {}", peer_id, e);
+        }
     }
 }
 
 impl Handler<PeerStopped> for Room {
     type Result = ();
 
+    /// Marks all [`MediaType`]s as stopped in the [`Endpoint`]s of the
+    /// [`Peer`] and sends `OnStop` Control API callbacks if needed.
+    ///
+    /// [`Peer`] may be removed already, in which case its traffic has been
+    /// stopped on removal, so nothing is done.
     fn handle(
         &mut self,
-        _: PeerStopped,
+        msg: PeerStopped,
         _: &mut Self::Context,
     ) -> Self::Result {
-        // TODO: Implement PeerStopped logic.
+        let PeerStopped { peer_id, at } = msg;
+        for direction in &[MediaDirection::Publish, MediaDirection::Play] {
+            match self.endpoints_traffic_stopped(
+                peer_id,
+                MediaType::Both,
+                *direction,
+                at,
+            ) {
+                Ok(()) => {}
+                Err(RoomError::PeerNotFound(_)) => {
+                    debug!("PeerStopped of removed Peer [id = {}]", peer_id);
+                    break;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to handle PeerStopped [id = {}]: {}",
+                        peer_id, e,
+                    );
+                    break;
+                }
+            }
+        }
     }
 }
 
@@ -291,3 +501,199 @@ impl Handler<NegotiationNeeded> for Room {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom as _;
+
+    use actix::{Actor as _, Context};
+    use medea_client_api_proto::RoomId;
+
+    use crate::{
+        api::control::{
+            callback::url::CallbackUrl,
+            credentials::Credentials,
+            endpoints::webrtc_publish_endpoint::{
+                AudioSettings, P2pMode, VideoSettings,
+            },
+            pipeline::Pipeline,
+            refs::{SrcUri, StatefulFid},
+            AutoRemove, ElementMeta, MemberSpec, RoomSpec,
+        },
+        conf::{self, Conf},
+        media::peer::tests::dummy_negotiation_sub_mock,
+        signalling::{
+            elements::endpoints::webrtc::{
+                WebRtcPlayEndpoint, WebRtcPublishEndpoint,
+            },
+            participants::ParticipantService,
+            peers::{build_peers_traffic_watcher, PeersService},
+            room::State,
+        },
+        AppContext,
+    };
+
+    use super::*;
+
+    fn empty_room() -> Room {
+        let room_spec = RoomSpec {
+            id: RoomId::from("test"),
+            pipeline: Pipeline::new(HashMap::new()),
+            auto_remove: AutoRemove::default(),
+            on_remove: None,
+            meta: ElementMeta::default(),
+        };
+        let context = AppContext::new(
+            Conf::default(),
+            crate::turn::new_turn_auth_service_mock(),
+        );
+
+        Room {
+            id: room_spec.id().clone(),
+            peers: PeersService::new(
+                room_spec.id().clone(),
+                context.turn_service.clone(),
+                build_peers_traffic_watcher(&conf::Media::default()),
+                &context.config.media,
+                dummy_negotiation_sub_mock(),
+            ),
+            members: ParticipantService::new(&room_spec, &context).unwrap(),
+            state: State::Started,
+            callbacks: context.callbacks.clone(),
+            auto_remove: AutoRemove::default(),
+            on_remove: None,
+            meta: ElementMeta::default(),
+            revision: 0,
+            versions: HashMap::new(),
+        }
+    }
+
+    /// Returns [`Room`] with `publisher` publishing to `receiver` and media
+    /// flowing between them, along with [`StatefulFid`]s of their
+    /// [`Endpoint`]s and [`PeerId`]s of their [`Peer`]s.
+    async fn room_with_flowing_media(
+    ) -> (Room, [StatefulFid; 2], (PeerId, PeerId)) {
+        let mut room = empty_room();
+        for id in &["publisher", "receiver"] {
+            let spec = MemberSpec::new(
+                Pipeline::new(HashMap::new()),
+                Credentials::Plain("w/e".into()),
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            room.members.create_member((*id).into(), &spec).unwrap();
+        }
+        let publisher = room.members.get_member(&"publisher".into()).unwrap();
+        let receiver = room.members.get_member(&"receiver".into()).unwrap();
+        let on_stop =
+            CallbackUrl::try_from(String::from("grpc://127.0.0.1:9099"))
+                .unwrap();
+
+        let publish = WebRtcPublishEndpoint::new(
+            "publish".to_string().into(),
+            P2pMode::Always,
+            publisher.downgrade(),
+            false,
+            AudioSettings::default(),
+            VideoSettings::default(),
+        );
+        publish.set_callback_urls(None, Some(on_stop.clone()));
+        let play = WebRtcPlayEndpoint::new(
+            "play-publisher".to_string().into(),
+            SrcUri::try_from("local://test/publisher/publish".to_string())
+                .unwrap(),
+            publish.downgrade(),
+            receiver.downgrade(),
+            false,
+        );
+        play.set_callback_urls(None, Some(on_stop));
+        publisher.insert_src(publish.clone());
+        receiver.insert_sink(play.clone());
+        let fids = [
+            Endpoint::from(publish.clone()).fid().into(),
+            Endpoint::from(play.clone()).fid().into(),
+        ];
+
+        let (src_peer_id, sink_peer_id) = room
+            .peers
+            .clone()
+            .connect_endpoints(publish, play)
+            .await
+            .unwrap()
+            .unwrap();
+        room.endpoints_traffic_started(
+            src_peer_id,
+            MediaType::Both,
+            MediaDirection::Publish,
+        )
+        .unwrap();
+        room.endpoints_traffic_started(
+            sink_peer_id,
+            MediaType::Both,
+            MediaDirection::Play,
+        )
+        .unwrap();
+
+        (room, fids, (src_peer_id, sink_peer_id))
+    }
+
+    /// Checks that `OnStop` callbacks are sent for all [`Endpoint`]s of the
+    /// [`Peer`]s removed because of [`Member`] disconnection.
+    #[actix_rt::test]
+    async fn sends_on_stop_when_disconnected_member_peers_removed() {
+        let (room, fids, _) = room_with_flowing_media().await;
+
+        Room::create(move |ctx| {
+            let mut room = room;
+            for fid in &fids {
+                assert_eq!(room.callbacks.last_seq(fid), None);
+            }
+
+            room.remove_disconnected_member_peers(&"publisher".into(), ctx);
+            for fid in &fids {
+                assert_eq!(room.callbacks.last_seq(fid), Some(1));
+            }
+            room
+        });
+    }
+
+    /// Checks that `OnStop` callbacks are sent once for the [`Endpoint`]s of
+    /// the [`Peer`]s which traffic has stopped before their removal, and
+    /// that [`PeerStopped`] of the removed [`Peer`]s is ignored.
+    #[actix_rt::test]
+    async fn sends_on_stop_once_when_traffic_stopped() {
+        let (room, fids, (src_peer_id, sink_peer_id)) =
+            room_with_flowing_media().await;
+
+        Room::create(move |ctx| {
+            let mut room = room;
+            let stop_peers = |room: &mut Room, ctx: &mut Context<Room>| {
+                for peer_id in &[src_peer_id, sink_peer_id] {
+                    Handler::handle(
+                        room,
+                        PeerStopped {
+                            peer_id: *peer_id,
+                            at: Utc::now(),
+                        },
+                        ctx,
+                    );
+                }
+            };
+
+            stop_peers(&mut room, ctx);
+            for fid in &fids {
+                assert_eq!(room.callbacks.last_seq(fid), Some(1));
+            }
+
+            room.remove_disconnected_member_peers(&"receiver".into(), ctx);
+            stop_peers(&mut room, ctx);
+            for fid in &fids {
+                assert_eq!(room.callbacks.last_seq(fid), Some(1));
+            }
+            room
+        });
+    }
+}
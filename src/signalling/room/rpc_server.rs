@@ -2,27 +2,26 @@
 
 use actix::{
     fut::{self, Either},
-    ActorFuture, Addr, ContextFutureSpawner as _, Handler, WrapFuture,
+    ActorFuture, Addr, Context, Handler, WrapFuture,
 };
 use derive_more::Display;
 use failure::Fail;
-use futures::future::{
-    self, FutureExt as _, LocalBoxFuture, TryFutureExt as _,
-};
-use medea_client_api_proto::{Command, Credential, MemberId, PeerId};
+use futures::future::{FutureExt as _, LocalBoxFuture, TryFutureExt as _};
+use medea_client_api_proto::{Command, MemberId, PeerId};
 
 use crate::{
     api::{
         client::rpc_connection::{
-            ClosedReason, CommandMessage, RpcConnection, RpcConnectionClosed,
-            RpcConnectionEstablished, RpcConnectionSettings,
+            ClosedReason, CommandMessage, RpcAuthorization, RpcConnection,
+            RpcConnectionClosed, RpcConnectionEstablished,
+            RpcConnectionSettings,
         },
         control::callback::{OnJoinEvent, OnLeaveEvent, OnLeaveReason},
         RpcServer, RpcServerError,
     },
     log::prelude::*,
     media::PeerStateMachine,
-    signalling::room::RoomError,
+    signalling::{participants::Resumption, room::RoomError},
 };
 
 use super::{ActFuture, Room};
@@ -82,6 +81,59 @@ impl Room {
 
         Ok(())
     }
+
+    /// Saves new [`RpcConnection`] of the authorized `Member` with provided
+    /// [`MemberId`] in [`ParticipantService`][1], and creates and
+    /// interconnects all available `Member`'s `Peer`s.
+    ///
+    /// Emits `on_join` callback if `Member` wasn't connected before.
+    ///
+    /// [1]: crate::signalling::participants::ParticipantService
+    fn establish_connection(
+        &mut self,
+        member_id: MemberId,
+        connection: Box<dyn RpcConnection>,
+        last_seq: Option<u32>,
+        ctx: &mut Context<Self>,
+    ) -> ActFuture<Result<RpcConnectionSettings, RoomError>> {
+        let is_reconnect = self.members.member_has_connection(&member_id);
+
+        Box::pin(
+            self.members
+                .connection_established(ctx, member_id, connection, last_seq)
+                .err_into()
+                .into_actor(self)
+                .then(move |res, this, ctx| match res {
+                    Ok((member, resumption)) => {
+                        if !is_reconnect {
+                            if let Some(callback_url) = member.get_on_join() {
+                                this.callbacks.do_send(
+                                    callback_url,
+                                    member.get_fid().into(),
+                                    member.meta(),
+                                    OnJoinEvent,
+                                );
+                            }
+                        }
+                        if resumption == Resumption::Overflowed {
+                            this.remove_disconnected_member_peers(
+                                &member.id(),
+                                ctx,
+                            );
+                        }
+                        Either::Left(this.init_member_connections(&member).map(
+                            move |res, _, _| {
+                                res.map(move |_| RpcConnectionSettings {
+                                    idle_timeout: member.get_idle_timeout(),
+                                    ping_interval: member.get_ping_interval(),
+                                })
+                            },
+                        ))
+                    }
+                    Err(err) => Either::Right(fut::err(err)),
+                }),
+        )
+    }
 }
 
 impl RpcServer for Addr<Room> {
@@ -97,14 +149,16 @@ impl RpcServer for Addr<Room> {
     fn connection_established(
         &self,
         member_id: MemberId,
-        credentials: Credential,
+        authorization: RpcAuthorization,
         connection: Box<dyn RpcConnection>,
+        last_seq: Option<u32>,
     ) -> LocalBoxFuture<'static, Result<RpcConnectionSettings, RpcServerError>>
     {
         self.send(RpcConnectionEstablished {
             member_id,
-            credentials,
+            authorization,
             connection,
+            last_seq,
         })
         .map(|r| {
             r.map_err(RpcServerError::RoomMailbox)
@@ -173,72 +227,86 @@ impl Handler<CommandMessage> for Room {
 impl Handler<RpcConnectionEstablished> for Room {
     type Result = ActFuture<Result<RpcConnectionSettings, RoomError>>;
 
+    /// Verifies credentials of the `Member` on the blocking thread pool,
+    /// unless it's authorized with an already validated signed token or with
+    /// the last credential verified successfully.
+    ///
+    /// Only one credential of the `Member` is verified at a time, so
+    /// `JoinRoom`s arriving while it's being verified are rejected.
+    ///
     /// Saves new [`RpcConnection`] in [`ParticipantService`][1], initiates
     /// media establishment between members.
     /// Creates and interconnects all available `Member`'s `Peer`s.
     ///
+    /// Emits `on_join` callback if `Member` wasn't connected before.
+    ///
+    /// If the reconnected client has missed [`Event`]s which cannot be
+    /// replayed anymore, then recreates all its `Peer`s, so the client is
+    /// resynchronized with a full state snapshot.
+    ///
     /// Returns [`RpcConnectionSettings`] of the connected `Member`.
     ///
+    /// [`Event`]: medea_client_api_proto::Event
     /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
     /// [1]: crate::signalling::participants::ParticipantService
     fn handle(
         &mut self,
         msg: RpcConnectionEstablished,
-        _: &mut Self::Context,
+        ctx: &mut Self::Context,
     ) -> Self::Result {
         let member_id = msg.member_id;
         let connection = msg.connection;
-        let credentials = msg.credentials;
+        let last_seq = msg.last_seq;
 
         info!("RpcConnectionEstablished for Member [id = {}].", member_id);
 
         let member = actix_try!(self
             .members
-            .get_member_by_id_and_credentials(&member_id, &credentials));
-
-        let is_reconnect = self.members.member_has_connection(&member_id);
-
-        let maybe_send_on_join = match (member.get_on_join(), is_reconnect) {
-            (Some(callback_url), false) => future::Either::Left({
-                let callback_service = self.callbacks.clone();
-                async move {
-                    callback_service
-                        .send(
-                            callback_url,
-                            member.get_fid().into(),
-                            OnJoinEvent,
-                        )
-                        .await
-                }
-                .err_into()
-            }),
-            _ => future::Either::Right(future::ok(())),
+            .get_member_by_id(&member_id)
+            .map_err(|_| RoomError::AuthorizationError));
+        let credentials = match msg.authorization {
+            RpcAuthorization::Credential(credentials)
+                if !member.is_verified_credential(&credentials) =>
+            {
+                credentials
+            }
+            RpcAuthorization::Credential(_) | RpcAuthorization::Token => {
+                return self.establish_connection(
+                    member_id, connection, last_seq, ctx,
+                );
+            }
         };
+        if !member.start_credential_verification() {
+            debug!(
+                "Rejecting JoinRoom of Member [id = {}], since its another \
+                 credential is being verified.",
+                member_id,
+            );
+            return Box::pin(fut::err(RoomError::AuthorizationError));
+        }
 
+        // Credentials are verified on the blocking thread pool, since their
+        // verification is intentionally slow.
+        let hash = member.credentials_hash();
         Box::pin(
-            maybe_send_on_join
+            hash.clone()
+                .verify_blocking(credentials.clone())
                 .into_actor(self)
-                .then(|res: Result<(), RoomError>, this, ctx| match res {
-                    Ok(_) => Either::Left(
-                        this.members
-                            .connection_established(ctx, member_id, connection)
-                            .err_into()
-                            .into_actor(this),
-                    ),
-                    Err(err) => Either::Right(fut::err(err)),
-                })
-                .then(|res, this, _| match res {
-                    Ok(member) => {
-                        Either::Left(this.init_member_connections(&member).map(
-                            move |res, _, _| {
-                                res.map(move |_| RpcConnectionSettings {
-                                    idle_timeout: member.get_idle_timeout(),
-                                    ping_interval: member.get_ping_interval(),
-                                })
-                            },
+                .then(move |is_valid, this, ctx| {
+                    member.finish_credential_verification();
+                    // Credentials may be changed while being verified.
+                    let is_actual = this
+                        .members
+                        .get_member_by_id(&member_id)
+                        .map_or(false, |m| m.credentials_hash() == hash);
+                    if is_valid && is_actual {
+                        member.remember_verified_credential(&credentials);
+                        Either::Left(this.establish_connection(
+                            member_id, connection, last_seq, ctx,
                         ))
+                    } else {
+                        Either::Right(fut::err(RoomError::AuthorizationError))
                     }
-                    Err(err) => Either::Right(fut::err(err)),
                 }),
         )
     }
@@ -278,6 +346,7 @@ impl Handler<RpcConnectionClosed> for Room {
                     self.callbacks.do_send(
                         on_leave_url,
                         member.get_fid().into(),
+                        member.meta(),
                         OnLeaveEvent::new(reason),
                     );
                 }
@@ -289,19 +358,7 @@ impl Handler<RpcConnectionClosed> for Room {
                 );
             }
 
-            let removed_peers =
-                self.peers.remove_peers_related_to_member(&msg.member_id);
-
-            for (peer_member_id, peers_ids) in removed_peers {
-                // Here we may have some problems. If two participants
-                // disconnect at one moment then sending event
-                // to another participant fail,
-                // because connection already closed but we don't know about it
-                // because message in event loop.
-                self.member_peers_removed(peers_ids, peer_member_id)
-                    .map(|_, _, _| ())
-                    .spawn(ctx);
-            }
+            self.remove_disconnected_member_peers(&msg.member_id, ctx);
         }
     }
 }
@@ -315,7 +372,10 @@ mod test {
     use super::*;
 
     use crate::{
-        api::control::{pipeline::Pipeline, MemberSpec, RoomSpec},
+        api::control::{
+            credentials::Credentials, pipeline::Pipeline, AutoRemove,
+            ElementMeta, MemberSpec, RoomSpec,
+        },
         conf::{self, Conf},
         media::peer::tests::dummy_negotiation_sub_mock,
         signalling::{
@@ -330,6 +390,9 @@ mod test {
         let room_spec = RoomSpec {
             id: RoomId::from("test"),
             pipeline: Pipeline::new(HashMap::new()),
+            auto_remove: AutoRemove::default(),
+            on_remove: None,
+            meta: ElementMeta::default(),
         };
         let context = AppContext::new(
             Conf::default(),
@@ -348,6 +411,11 @@ mod test {
             members: ParticipantService::new(&room_spec, &context).unwrap(),
             state: State::Started,
             callbacks: context.callbacks.clone(),
+            auto_remove: AutoRemove::default(),
+            on_remove: None,
+            meta: ElementMeta::default(),
+            revision: 0,
+            versions: HashMap::new(),
         }
     }
 
@@ -357,7 +425,7 @@ mod test {
 
         let member1 = MemberSpec::new(
             Pipeline::new(HashMap::new()),
-            "w/e".into(),
+            Credentials::Plain("w/e".into()),
             None,
             None,
             None,
@@ -395,7 +463,7 @@ mod test {
 
         let member1 = MemberSpec::new(
             Pipeline::new(HashMap::new()),
-            "w/e".into(),
+            Credentials::Plain("w/e".into()),
             None,
             None,
             None,
@@ -428,14 +496,16 @@ mod test {
     }
 
     mod callbacks {
-        use std::convert::TryFrom;
+        use std::{convert::TryFrom, time::Duration};
 
         use actix::Addr;
+        use futures::future;
         use medea_client_api_proto::{
             CloseDescription, CloseReason, Credential, MemberId, RoomId,
         };
         use mockall::predicate::eq;
         use serial_test::serial;
+        use tokio::time;
 
         use crate::api::{
             client::rpc_connection::MockRpcConnection,
@@ -469,16 +539,20 @@ mod test {
             let id = MemberId::from("member");
             let member = RoomElement::Member {
                 spec: Pipeline::new(HashMap::new()),
-                credentials: Credential::from("test"),
+                credentials: Credentials::Plain("test".into()),
                 on_leave,
                 on_join,
                 idle_timeout: None,
                 reconnect_timeout: None,
                 ping_interval: None,
+                meta: ElementMeta::default(),
             };
             RoomSpec {
                 id: RoomId::from("test"),
                 pipeline: Pipeline::new(hashmap! {id => member}),
+                auto_remove: AutoRemove::default(),
+                on_remove: None,
+                meta: ElementMeta::default(),
             }
         }
 
@@ -512,6 +586,7 @@ mod test {
                 callback_server
                     .expect_on_join()
                     .with(eq("test/member"))
+                    .times(1)
                     .return_once(|_| Ok(()));
                 let room = start_room(true, false).await;
                 let _callback_server =
@@ -520,11 +595,14 @@ mod test {
 
                 room.connection_established(
                     MemberId::from("member"),
-                    Credential::from("test"),
+                    Credential::from("test").into(),
                     Box::new(MockRpcConnection::new()),
+                    None,
                 )
                 .await
                 .unwrap();
+                // `on_join` callback is delivered asynchronously.
+                time::delay_for(Duration::from_millis(200)).await;
             }
 
             #[actix_rt::test]
@@ -534,6 +612,7 @@ mod test {
                 callback_server
                     .expect_on_join()
                     .with(eq("test/member"))
+                    .times(1)
                     .return_once(|_| Ok(()));
                 let room = start_room(true, false).await;
                 let _callback_server =
@@ -545,25 +624,27 @@ mod test {
                     .expect_close()
                     .with(
                         eq(RoomId::from("test")),
-                        eq(CloseDescription {
-                            reason: CloseReason::Reconnected,
-                        }),
+                        eq(CloseDescription::new(CloseReason::Reconnected)),
                     )
                     .return_once(|_, _| Box::pin(future::ready(())));
                 room.connection_established(
                     MemberId::from("member"),
-                    Credential::from("test"),
+                    Credential::from("test").into(),
                     Box::new(rpc_connection),
+                    None,
                 )
                 .await
                 .unwrap();
                 room.connection_established(
                     MemberId::from("member"),
-                    Credential::from("test"),
+                    Credential::from("test").into(),
                     Box::new(MockRpcConnection::new()),
+                    None,
                 )
                 .await
                 .unwrap();
+                // `on_join` callback is delivered asynchronously.
+                time::delay_for(Duration::from_millis(200)).await;
             }
 
             #[actix_rt::test]
@@ -577,8 +658,9 @@ mod test {
 
                 room.connection_established(
                     MemberId::from("member"),
-                    Credential::from("test"),
+                    Credential::from("test").into(),
                     Box::new(MockRpcConnection::new()),
+                    None,
                 )
                 .await
                 .unwrap();
@@ -586,12 +668,12 @@ mod test {
 
             #[actix_rt::test]
             #[serial]
-            async fn rpc_con_err_if_on_join_err() {
+            async fn rpc_con_established_if_on_join_err() {
                 let mut callback_server = MockGrpcCallbackServer::new();
                 callback_server
                     .expect_on_join()
                     .with(eq("test/member"))
-                    .return_once(|_| Err(()));
+                    .returning(|_| Err(()));
                 let room = start_room(true, false).await;
                 let _callback_server =
                     start_callback_server("0.0.0.0:9099", callback_server)
@@ -599,16 +681,19 @@ mod test {
 
                 room.connection_established(
                     MemberId::from("member"),
-                    Credential::from("test"),
+                    Credential::from("test").into(),
                     Box::new(MockRpcConnection::new()),
+                    None,
                 )
                 .await
-                .unwrap_err();
+                .unwrap();
+                // `on_join` callback is delivered asynchronously.
+                time::delay_for(Duration::from_millis(200)).await;
             }
 
             #[actix_rt::test]
             #[serial]
-            async fn rpc_con_err_if_io_err() {
+            async fn rpc_con_established_if_callback_server_unavailable() {
                 let mut callback_server = MockGrpcCallbackServer::new();
                 callback_server
                     .expect_on_join()
@@ -618,12 +703,134 @@ mod test {
 
                 room.connection_established(
                     MemberId::from("member"),
-                    Credential::from("test"),
+                    Credential::from("test").into(),
                     Box::new(MockRpcConnection::new()),
+                    None,
                 )
                 .await
-                .unwrap_err();
+                .unwrap();
             }
         }
     }
+
+    mod credentials {
+        use actix::Addr;
+        use futures::future;
+        use medea_client_api_proto::{
+            CloseDescription, CloseReason, Credential, MemberId, RoomId,
+        };
+        use mockall::predicate::eq;
+
+        use crate::api::{
+            client::rpc_connection::MockRpcConnection,
+            control::{credentials::CredentialHash, RoomElement},
+        };
+
+        use super::*;
+
+        /// Starts [`Room`] with a `member` having hashed `test` credential,
+        /// which hasn't been verified yet.
+        fn start_room() -> Addr<Room> {
+            let member = RoomElement::Member {
+                spec: Pipeline::new(HashMap::new()),
+                credentials: Credentials::Hash {
+                    hash: CredentialHash::new(&"test".into()),
+                },
+                on_leave: None,
+                on_join: None,
+                idle_timeout: None,
+                reconnect_timeout: None,
+                ping_interval: None,
+                meta: ElementMeta::default(),
+            };
+            let room_spec = RoomSpec {
+                id: RoomId::from("test"),
+                pipeline: Pipeline::new(
+                    hashmap! {MemberId::from("member") => member},
+                ),
+                auto_remove: AutoRemove::default(),
+                on_remove: None,
+                meta: ElementMeta::default(),
+            };
+            let app_ctx = AppContext::new(
+                Conf::default(),
+                crate::turn::new_turn_auth_service_mock(),
+            );
+            Room::start(
+                &room_spec,
+                &app_ctx,
+                build_peers_traffic_watcher(&app_ctx.config.media),
+            )
+            .unwrap()
+        }
+
+        /// Returns [`MockRpcConnection`] which expects to be closed because
+        /// of the `Member` reconnection.
+        fn reconnected_connection() -> Box<MockRpcConnection> {
+            let mut rpc_connection = MockRpcConnection::new();
+            rpc_connection
+                .expect_close()
+                .with(
+                    eq(RoomId::from("test")),
+                    eq(CloseDescription::new(CloseReason::Reconnected)),
+                )
+                .return_once(|_, _| Box::pin(future::ready(())));
+            Box::new(rpc_connection)
+        }
+
+        #[actix_rt::test]
+        async fn rejects_join_while_credential_is_verified() {
+            let room = start_room();
+
+            let (first, second) = future::join(
+                room.connection_established(
+                    MemberId::from("member"),
+                    Credential::from("test").into(),
+                    reconnected_connection(),
+                    None,
+                ),
+                room.connection_established(
+                    MemberId::from("member"),
+                    Credential::from("test").into(),
+                    Box::new(MockRpcConnection::new()),
+                    None,
+                ),
+            )
+            .await;
+            first.unwrap();
+            assert!(matches!(second, Err(RpcServerError::Authorization)));
+
+            room.connection_established(
+                MemberId::from("member"),
+                Credential::from("test").into(),
+                Box::new(MockRpcConnection::new()),
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        #[actix_rt::test]
+        async fn verifies_other_credential_after_verified_one() {
+            let room = start_room();
+
+            room.connection_established(
+                MemberId::from("member"),
+                Credential::from("test").into(),
+                Box::new(MockRpcConnection::new()),
+                None,
+            )
+            .await
+            .unwrap();
+            let res = room
+                .connection_established(
+                    MemberId::from("member"),
+                    Credential::from("qwerty").into(),
+                    Box::new(MockRpcConnection::new()),
+                    None,
+                )
+                .await;
+            assert!(matches!(res, Err(RpcServerError::Authorization)));
+        }
+    }
 }
@@ -47,6 +47,20 @@ impl RoomRepository {
         self.rooms.lock().unwrap().insert(id, room);
     }
 
+    /// Returns all [`Room`]s of this [`RoomRepository`] ordered by their
+    /// [`RoomId`]s.
+    pub fn sorted(&self) -> Vec<(RoomId, Addr<Room>)> {
+        let mut rooms: Vec<_> = self
+            .rooms
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, room)| (id.clone(), room.clone()))
+            .collect();
+        rooms.sort_unstable_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+        rooms
+    }
+
     /// Checks existence of [`Room`] in [`RoomRepository`] by provided
     /// [`RoomId`].
     pub fn contains_room_with_id(&self, id: &RoomId) -> bool {
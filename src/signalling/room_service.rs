@@ -1,14 +1,27 @@
 //! Service which provides CRUD actions for [`Room`].
 
-use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom as _,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
 
 use actix::{
-    Actor, Addr, Context, Handler, MailboxError, Message, ResponseFuture,
+    fut::{self, Either},
+    Actor, ActorFuture as _, Addr, AsyncContext as _, Context,
+    ContextFutureSpawner as _, Handler, MailboxError, Message, ResponseFuture,
+    SpawnHandle, StreamHandler, WrapFuture as _,
 };
+use actix_web::web;
 use derive_more::Display;
 use failure::Fail;
-use futures::future::{
-    self, FutureExt as _, LocalBoxFuture, TryFutureExt as _,
+use futures::{
+    channel::oneshot,
+    future::{self, FutureExt as _, LocalBoxFuture, TryFutureExt as _},
 };
 use medea_client_api_proto::{Credential, MemberId, RoomId};
 use medea_control_api_proto::grpc::api as proto;
@@ -16,21 +29,31 @@ use redis::RedisError;
 
 use crate::{
     api::control::{
-        endpoints::EndpointSpec,
-        load_static_specs_from_dir,
-        refs::{Fid, StatefulFid, ToMember, ToRoom},
-        EndpointId, LoadStaticControlSpecsError, MemberSpec, RoomSpec,
-        TryFromElementError,
+        callback::{url::CallbackUrl, OnRemoveEvent, OnRemoveReason},
+        credentials::Credentials,
+        element_events::{ElementEvent, ElementEventKind},
+        endpoints::{
+            webrtc_publish_endpoint::WebRtcPublishEndpointPatch, EndpointSpec,
+        },
+        parse_yaml_spec, read_static_specs_dir,
+        refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
+        ApplyPolicy, AutoRemove, ElementMeta, EndpointId,
+        LoadStaticControlSpecsError, MemberPatch, MemberSpec, RoomSpec,
+        RootElement, TryFromElementError,
     },
     log::prelude::*,
     shutdown::{self, GracefulShutdown},
     signalling::{
         peers::{build_peers_traffic_watcher, PeerTrafficWatcher},
         room::{
-            Close, CreateEndpoint, CreateMember, Delete, RoomError,
-            SerializeProto,
+            ApplyMember, ApplyRoomSpec, CheckVersions, Close, CreateEndpoint,
+            CreateMember, Delete, Kick, ProvisionMember as RoomProvisionMember,
+            RoomError, RoomSummary, SeedRevision,
+            SendCustomEvent as RoomSendCustomEvent, SerializeProto, Summarize,
+            UpdateEndpoint, UpdateMember,
         },
         room_repo::RoomRepository,
+        snapshot::{self, Snapshot, SnapshotStore, SnapshotStoreError},
         Room,
     },
     turn::coturn_metrics::CoturnMetricsService,
@@ -84,6 +107,10 @@ pub enum RoomServiceError {
         _1
     )]
     NotSameRoomIds(RoomId, RoomId),
+
+    /// Error of [`SnapshotStore`].
+    #[display(fmt = "Snapshot store error: {}", _0)]
+    SnapshotStore(SnapshotStoreError),
 }
 
 impl From<RoomError> for RoomServiceError {
@@ -98,6 +125,12 @@ impl From<LoadStaticControlSpecsError> for RoomServiceError {
     }
 }
 
+/// Type of [`ActorFuture`]s returned by [`RoomService`]'s handlers.
+///
+/// [`ActorFuture`]: actix::ActorFuture
+type ActFuture<O> =
+    Pin<Box<dyn actix::ActorFuture<Actor = RoomService, Output = O>>>;
+
 /// Service for controlling [`Room`]s.
 pub struct RoomService {
     /// Repository that stores [`Room`]s addresses.
@@ -119,6 +152,46 @@ pub struct RoomService {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     static_specs_dir: String,
 
+    /// IDs of [`Room`]s created from static [Control API] specs.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    static_rooms: HashSet<RoomId>,
+
+    /// Files of static [Control API] specs dir, as they were seen on the last
+    /// (re)load.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    static_spec_files: HashMap<PathBuf, StaticSpecFile>,
+
+    /// Contents of static [Control API] spec files which failed to be
+    /// parsed, so they're not parsed again until changed.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    invalid_static_specs: HashMap<PathBuf, String>,
+
+    /// [`SnapshotStore`] which [`Snapshot`]s of dynamically created [`Room`]s
+    /// are saved into.
+    ///
+    /// [`None`] if snapshots are disabled.
+    snapshot_store: Option<Arc<dyn SnapshotStore>>,
+
+    /// Timer of saving a [`Snapshot`], which is running since the first
+    /// change of [`Room`]s not saved yet.
+    snapshot_timer: Option<SpawnHandle>,
+
+    /// Indicator whether a [`Snapshot`] is being saved at the moment.
+    is_saving_snapshot: bool,
+
+    /// Indicator whether static [Control API] specs are being reloaded at the
+    /// moment.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    is_reloading_static_specs: bool,
+
+    /// Timers of removing [`Room`]s with [`AutoRemove::EmptyFor`] rule, which
+    /// are running while these [`Room`]s have no connected `Member`s.
+    empty_room_timers: HashMap<RoomId, SpawnHandle>,
+
     /// Public URL of server. Address for exposed [Client API].
     ///
     /// [Client API]: https://tinyurl.com/yx9thsnr
@@ -153,6 +226,14 @@ impl RoomService {
             )?
             .start(),
             static_specs_dir: app.config.control.static_specs_dir.clone(),
+            static_rooms: HashSet::new(),
+            static_spec_files: HashMap::new(),
+            invalid_static_specs: HashMap::new(),
+            snapshot_store: snapshot::new_store(&app.config.control.snapshot),
+            snapshot_timer: None,
+            is_saving_snapshot: false,
+            is_reloading_static_specs: false,
+            empty_room_timers: HashMap::new(),
             public_url: app.config.server.client.http.public_url.clone(),
             peer_traffic_watcher,
             room_repo,
@@ -163,11 +244,19 @@ impl RoomService {
 
     /// Closes [`Room`] with provided [`RoomId`].
     ///
-    /// This is also deletes this [`Room`] from [`RoomRepository`].
+    /// This is also deletes this [`Room`] from [`RoomRepository`] right away,
+    /// so it's not accessible anymore while being closed.
     fn close_room(
-        &self,
+        &mut self,
         id: RoomId,
+        ctx: &mut Context<Self>,
     ) -> LocalBoxFuture<'static, Result<(), MailboxError>> {
+        if let Some(timer) = self.empty_room_timers.remove(&id) {
+            ctx.cancel_future(timer);
+        }
+        if !self.static_rooms.remove(&id) {
+            self.schedule_snapshot(ctx);
+        }
         self.room_repo
             .get(&id)
             .map_or(future::ok(()).boxed_local(), |room| {
@@ -177,104 +266,221 @@ impl RoomService {
                     shutdown::Priority(2),
                 );
 
-                let room_repo = self.room_repo.clone();
+                self.room_repo.remove(&id);
+                let element_events = self.app.element_events.clone();
+                let callbacks = self.app.callbacks.clone();
                 room.send(Close)
-                    .inspect_ok(move |_| room_repo.remove(&id))
+                    .inspect_ok(move |_| {
+                        let fid = Fid::<ToRoom>::new(id);
+                        callbacks.remove_queues(&fid.clone().into());
+                        element_events.publish(fid, ElementEventKind::Deleted);
+                    })
                     .boxed_local()
             })
     }
 
-    /// Returns [Control API] sid based on provided arguments and
-    /// `MEDEA_SERVER__CLIENT__HTTP__PUBLIC_URL` config value.
-    fn get_sid(
-        &self,
-        room_id: &RoomId,
-        member_id: &MemberId,
-        credentials: &Credential,
-    ) -> String {
-        format!(
-            "{}/{}/{}/{}",
-            self.public_url, room_id, member_id, credentials
-        )
-    }
-}
-
-impl Actor for RoomService {
-    type Context = Context<Self>;
-}
-
-/// Signal for load all static specs and start [`Room`]s.
-#[derive(Message)]
-#[rtype(result = "Result<(), RoomServiceError>")]
-pub struct StartStaticRooms;
-
-impl Handler<StartStaticRooms> for RoomService {
-    type Result = Result<(), RoomServiceError>;
-
-    fn handle(
+    /// Closes [`Room`]s with provided [`RoomId`]s, if their elements have the
+    /// provided expected versions.
+    ///
+    /// [`RoomService`] doesn't process other messages until the versions are
+    /// checked and the [`Room`]s are deleted from [`RoomRepository`], so the
+    /// [`Room`]s can't be changed in between.
+    fn close_rooms_of_versions(
         &mut self,
-        _: StartStaticRooms,
-        _: &mut Self::Context,
-    ) -> Self::Result {
-        let room_specs = load_static_specs_from_dir(&self.static_specs_dir)?;
+        ids: Vec<RoomId>,
+        expected_versions: HashMap<StatefulFid, u64>,
+        ctx: &mut Context<Self>,
+    ) -> LocalBoxFuture<'static, Result<(), RoomServiceError>> {
+        let room = if let Some(room) = self.room_repo.get(&ids[0]) {
+            room
+        } else {
+            let (fid, expected) = expected_versions.into_iter().next().unwrap();
+            return future::err(
+                RoomError::VersionMismatch {
+                    fid,
+                    expected,
+                    actual: 0,
+                }
+                .into(),
+            )
+            .boxed_local();
+        };
 
-        for spec in room_specs {
-            if self.room_repo.contains_room_with_id(spec.id()) {
-                return Err(RoomServiceError::RoomAlreadyExists(
-                    Fid::<ToRoom>::new(spec.id),
-                ));
-            }
+        let (tx, rx) = oneshot::channel();
+        ctx.wait(
+            room.send(CheckVersions(expected_versions))
+                .into_actor(self)
+                .map(move |checked, this: &mut Self, ctx| {
+                    let closed = match checked {
+                        Ok(Ok(())) => {
+                            let futs: Vec<_> = ids
+                                .into_iter()
+                                .map(|id| this.close_room(id, ctx))
+                                .collect();
+                            future::try_join_all(futs)
+                                .map_ok(|_| ())
+                                .map_err(RoomServiceError::RoomMailboxErr)
+                                .boxed_local()
+                        }
+                        Ok(Err(e)) => future::err(e.into()).boxed_local(),
+                        Err(e) => {
+                            future::err(RoomServiceError::RoomMailboxErr(e))
+                                .boxed_local()
+                        }
+                    };
+                    let _ = tx.send(closed);
+                }),
+        );
+        async move {
+            rx.await
+                .map_err(|_| {
+                    RoomServiceError::RoomMailboxErr(MailboxError::Closed)
+                })?
+                .await
+        }
+        .boxed_local()
+    }
 
-            let room_id = spec.id().clone();
+    /// Checks [`AutoRemove`] rule of [`Room`] with provided [`RoomId`] on
+    /// provided [`AutoRemoveCheck`], and removes this [`Room`] if its rule is
+    /// satisfied.
+    ///
+    /// Starts [`AutoRemove::EmptyFor`] timer if [`Room`] has no connected
+    /// `Member`s.
+    fn check_auto_remove(
+        &mut self,
+        id: RoomId,
+        check: AutoRemoveCheck,
+        ctx: &mut Context<Self>,
+    ) {
+        let room = if let Some(room) = self.room_repo.get(&id) {
+            room
+        } else {
+            return;
+        };
+        room.send(Summarize)
+            .into_actor(self)
+            .map(move |summary, this: &mut Self, ctx| {
+                let summary = if let Ok(summary) = summary {
+                    summary
+                } else {
+                    return;
+                };
+                if !summary.is_operating || summary.connected_members > 0 {
+                    this.cancel_empty_room_timer(&id, ctx);
+                    return;
+                }
+                match (summary.auto_remove, check) {
+                    (AutoRemove::Never, _) => {
+                        this.cancel_empty_room_timer(&id, ctx);
+                    }
+                    (AutoRemove::AllLeft, AutoRemoveCheck::MemberLeft) => {
+                        this.auto_remove_room(
+                            id,
+                            OnRemoveReason::AllLeft,
+                            summary.on_remove,
+                            summary.meta,
+                            ctx,
+                        );
+                    }
+                    (AutoRemove::AllLeft, _) => {}
+                    (AutoRemove::EmptyFor(_), AutoRemoveCheck::TimedOut) => {
+                        this.auto_remove_room(
+                            id,
+                            OnRemoveReason::EmptyTimeout,
+                            summary.on_remove,
+                            summary.meta,
+                            ctx,
+                        );
+                    }
+                    (AutoRemove::EmptyFor(timeout), check) => {
+                        if check == AutoRemoveCheck::Updated {
+                            this.cancel_empty_room_timer(&id, ctx);
+                        }
+                        if !this.empty_room_timers.contains_key(&id) {
+                            let room_id = id.clone();
+                            let timer =
+                                ctx.run_later(timeout, move |this, ctx| {
+                                    this.empty_room_timers.remove(&room_id);
+                                    this.check_auto_remove(
+                                        room_id,
+                                        AutoRemoveCheck::TimedOut,
+                                        ctx,
+                                    );
+                                });
+                            this.empty_room_timers.insert(id, timer);
+                        }
+                    }
+                }
+            })
+            .spawn(ctx);
+    }
 
-            let room = Room::start(
-                &spec,
-                &self.app,
-                self.peer_traffic_watcher.clone(),
-            )?;
+    /// Cancels [`AutoRemove::EmptyFor`] timer of [`Room`] with provided
+    /// [`RoomId`], if any.
+    fn cancel_empty_room_timer(
+        &mut self,
+        id: &RoomId,
+        ctx: &mut Context<Self>,
+    ) {
+        if let Some(timer) = self.empty_room_timers.remove(id) {
+            ctx.cancel_future(timer);
+        }
+    }
 
-            shutdown::subscribe(
-                &self.graceful_shutdown,
-                room.clone().recipient(),
-                shutdown::Priority(2),
+    /// Removes [`Room`] with provided [`RoomId`] according to its
+    /// [`AutoRemove`] rule, firing its `on_remove` callback, if any.
+    fn auto_remove_room(
+        &mut self,
+        id: RoomId,
+        reason: OnRemoveReason,
+        on_remove: Option<CallbackUrl>,
+        meta: ElementMeta,
+        ctx: &mut Context<Self>,
+    ) {
+        info!(
+            "Removing Room [id = {}] according to its auto-removing rule: {:?}",
+            id, reason,
+        );
+        if let Some(url) = on_remove {
+            self.app.callbacks.do_send(
+                url,
+                Fid::<ToRoom>::new(id.clone()).into(),
+                meta,
+                OnRemoveEvent::new(reason),
             );
-
-            self.room_repo.add(room_id, room);
         }
-        Ok(())
+        self.close_room(id, ctx)
+            .map(|_| ())
+            .into_actor(self)
+            .spawn(ctx);
     }
-}
-
-/// Type alias for success [`CreateResponse`]'s sids.
-pub type Sids = HashMap<String, String>;
 
-/// Signal for creating new [`Room`].
-#[derive(Message)]
-#[rtype(result = "Result<Sids, RoomServiceError>")]
-pub struct CreateRoom {
-    /// [Control API] spec for [`Room`].
+    /// Starts new [`Room`] with provided [`RoomSpec`].
     ///
-    /// [Control API]: https://tinyurl.com/yxsqplq7
-    pub spec: RoomSpec,
-}
-
-impl Handler<CreateRoom> for RoomService {
-    type Result = Result<Sids, RoomServiceError>;
-
-    fn handle(
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError::RoomAlreadyExists`] if [`Room`] with
+    /// such [`RoomId`] already exists in [`RoomRepository`].
+    ///
+    /// Errors with [`RoomServiceError::TryFromElement`] if provided
+    /// [`RoomSpec`] contains invalid [`MemberSpec`]s.
+    ///
+    /// Errors with [`RoomServiceError::RoomError`] if [`Room`] failed to
+    /// start.
+    fn create_room(
         &mut self,
-        msg: CreateRoom,
-        _: &mut Self::Context,
-    ) -> Self::Result {
-        let room_spec = msg.spec;
+        room_spec: RoomSpec,
+    ) -> Result<Sids, RoomServiceError> {
         let sid = match room_spec.members() {
             Ok(members) => members
                 .iter()
                 .map(|(member_id, member)| {
-                    let uri = self.get_sid(
+                    let uri = get_sid(
+                        &self.public_url,
                         room_spec.id(),
                         &member_id,
-                        member.credentials(),
+                        member.credentials().plain(),
                     );
                     (member_id.clone().to_string(), uri)
                 })
@@ -288,8 +494,371 @@ impl Handler<CreateRoom> for RoomService {
             ));
         }
 
+        self.start_room(&room_spec)?;
+        self.static_rooms.remove(&room_spec.id);
+
+        Ok(sid)
+    }
+
+    /// Schedules saving of a [`Snapshot`] after the configured delay, unless
+    /// it's already scheduled or snapshots are disabled.
+    fn schedule_snapshot(&mut self, ctx: &mut Context<Self>) {
+        if self.snapshot_store.is_none() || self.snapshot_timer.is_some() {
+            return;
+        }
+        let delay = self.app.config.control.snapshot.save_delay;
+        self.snapshot_timer = Some(ctx.run_later(delay, |this, ctx| {
+            this.snapshot_timer = None;
+            if this.is_saving_snapshot {
+                this.schedule_snapshot(ctx);
+            } else {
+                this.save_snapshot(ctx);
+            }
+        }));
+    }
+
+    /// Takes a [`Snapshot`] of all the [`Room`]s not started from static
+    /// specs, and saves it into the [`SnapshotStore`].
+    fn save_snapshot(&mut self, ctx: &mut Context<Self>) {
+        let store = if let Some(store) = self.snapshot_store.clone() {
+            store
+        } else {
+            return;
+        };
+        let rooms: Vec<_> = self
+            .room_repo
+            .sorted()
+            .into_iter()
+            .filter(|(id, _)| !self.static_rooms.contains(id))
+            .map(|(id, room)| {
+                room.send(SerializeProto(vec![Fid::<ToRoom>::new(id).into()]))
+            })
+            .collect();
+
+        self.is_saving_snapshot = true;
+        async move {
+            let mut snapshot = Snapshot::default();
+            for serialized in future::join_all(rooms).await {
+                // Room may be closed while snapshot is being taken.
+                let room = serialized.ok().and_then(Result::ok).and_then(
+                    |serialized| {
+                        serialized.into_iter().find_map(|(_, el)| match el.el {
+                            Some(proto::element::El::Room(room)) => Some(room),
+                            _ => None,
+                        })
+                    },
+                );
+                if let Some(room) = room {
+                    let id = room.id.clone();
+                    let revision = room.version;
+                    match RoomSpec::try_from(room) {
+                        Ok(spec) => {
+                            snapshot.revisions.insert(id.into(), revision);
+                            snapshot.rooms.push(spec.into());
+                        }
+                        Err(e) => error!(
+                            "Room [id = {}] is not included into snapshot: {}",
+                            id, e,
+                        ),
+                    }
+                }
+            }
+            store.save(&snapshot).await
+        }
+        .into_actor(self)
+        .map(|res, this, _| {
+            this.is_saving_snapshot = false;
+            if let Err(e) = res {
+                error!("Failed to save snapshot of Rooms: {}", e);
+            }
+        })
+        .spawn(ctx);
+    }
+
+    /// Reconciles [`Room`]s started from static [Control API] specs with the
+    /// current contents of static specs dir.
+    ///
+    /// Spec files are read and credentials of changed specs are hashed on the
+    /// blocking thread pool. [`Room`]s of new spec files are started, changed
+    /// specs are applied to their [`Room`]s with [`ApplyPolicy::Apply`], and
+    /// [`Room`]s of removed spec files are closed. Invalid specs are logged
+    /// and don't affect running [`Room`]s, and aren't parsed again until
+    /// changed. Contents of a valid spec file are remembered only once it's
+    /// applied successfully, so failed ones are retried on the next reload.
+    ///
+    /// Returned [`ActorFuture`] resolves once all the changes are applied.
+    ///
+    /// [`ActorFuture`]: actix::ActorFuture
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn reload_static_specs(&mut self) -> ActFuture<()> {
+        if self.is_reloading_static_specs {
+            return Box::pin(fut::ready(()));
+        }
+        self.is_reloading_static_specs = true;
+
+        let dir = self.static_specs_dir.clone();
+        let applied: HashMap<_, _> = self
+            .static_spec_files
+            .iter()
+            .map(|(path, file)| (path.clone(), file.content.clone()))
+            .collect();
+        let invalid = self.invalid_static_specs.clone();
+        Box::pin(
+            async move {
+                let files = match web::block(move || read_static_specs_dir(dir))
+                    .await
+                {
+                    Ok(files) => files,
+                    Err(e) => {
+                        warn!(
+                            "Static Control API specs are not reloaded: {}",
+                            e
+                        );
+                        return None;
+                    }
+                };
+                let mut specs = Vec::with_capacity(files.len());
+                for (path, content) in files {
+                    let is_known = applied.get(&path) == Some(&content)
+                        || invalid.get(&path) == Some(&content);
+                    let spec = if is_known {
+                        None
+                    } else {
+                        Some(match parse_yaml_spec(&content) {
+                            Ok(spec) => Ok(spec.hash_credentials().await),
+                            Err(e) => Err(e),
+                        })
+                    };
+                    specs.push(ReadSpecFile {
+                        path,
+                        content,
+                        spec,
+                    });
+                }
+                Some(specs)
+            }
+            .into_actor(self)
+            .then(|specs, this, ctx| match specs {
+                Some(specs) => {
+                    Either::Left(this.apply_static_specs(specs, ctx))
+                }
+                None => Either::Right(fut::ready(())),
+            })
+            .map(|_, this, _| {
+                this.is_reloading_static_specs = false;
+            }),
+        )
+    }
+
+    /// Applies the provided static [Control API] spec files read by
+    /// [`RoomService::reload_static_specs()`], closing [`Room`]s of the files
+    /// which are not present anymore.
+    ///
+    /// Files having [`None`] instead of a [`RoomSpec`] are considered not
+    /// changed.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn apply_static_specs(
+        &mut self,
+        specs: Vec<ReadSpecFile>,
+        ctx: &mut Context<Self>,
+    ) -> ActFuture<()> {
+        let mut applied = Vec::new();
+        let mut present = HashSet::new();
+        for ReadSpecFile {
+            path,
+            content,
+            spec,
+        } in specs
+        {
+            present.insert(path.clone());
+            let spec = match spec {
+                Some(Ok(spec)) => {
+                    self.invalid_static_specs.remove(&path);
+                    spec
+                }
+                Some(Err(e)) => {
+                    warn!(
+                        "Static Control API spec {} is invalid: {}",
+                        path.display(),
+                        e,
+                    );
+                    self.invalid_static_specs.insert(path, content);
+                    continue;
+                }
+                None => continue,
+            };
+            match self.apply_static_spec(&path, spec, ctx) {
+                Ok((room_id, res)) => {
+                    applied.push(
+                        res.map(move |res| match res {
+                            Ok(_) => Some((
+                                path,
+                                StaticSpecFile { content, room_id },
+                            )),
+                            Err(e) => {
+                                error!(
+                                    "Failed to apply static spec to Room [id \
+                                     = {}]: {}",
+                                    room_id, e,
+                                );
+                                None
+                            }
+                        })
+                        .boxed_local(),
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "Static Control API spec {} is not applied: {}",
+                        path.display(),
+                        e,
+                    );
+                }
+            }
+        }
+
+        self.invalid_static_specs
+            .retain(|path, _| present.contains(path));
+        let removed: Vec<_> = self
+            .static_spec_files
+            .keys()
+            .filter(|path| !present.contains(*path))
+            .cloned()
+            .collect();
+        let mut closed = Vec::new();
+        for path in removed {
+            let file = self.static_spec_files.remove(&path).unwrap();
+            info!(
+                "Static Control API spec {} is removed, removing Room [id = \
+                 {}]",
+                path.display(),
+                file.room_id,
+            );
+            closed.push(self.close_static_room(file.room_id, ctx));
+        }
+
+        Box::pin(
+            future::join(future::join_all(applied), future::join_all(closed))
+                .into_actor(self)
+                .then(|(applied, _), this, ctx| {
+                    let mut closed = Vec::new();
+                    for (path, file) in applied.into_iter().flatten() {
+                        let room_id = file.room_id.clone();
+                        let prev = this.static_spec_files.insert(path, file);
+                        if let Some(prev) =
+                            prev.filter(|prev| prev.room_id != room_id)
+                        {
+                            closed.push(
+                                this.close_static_room(prev.room_id, ctx),
+                            );
+                        }
+                    }
+                    future::join_all(closed).map(|_| ()).into_actor(this)
+                }),
+        )
+    }
+
+    /// Applies static [Control API] spec from the provided file, starting a
+    /// new [`Room`] or applying it to an already running one.
+    ///
+    /// Returns [`RoomId`] of the [`Room`] and [`Future`] resolving once the
+    /// spec is applied.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError::RoomAlreadyExists`] if [`Room`] with
+    /// such [`RoomId`] is not a static one or is started from another spec
+    /// file.
+    ///
+    /// Errors with [`RoomServiceError::RoomError`] if [`Room`] failed to
+    /// start.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn apply_static_spec(
+        &mut self,
+        path: &Path,
+        spec: RoomSpec,
+        ctx: &mut Context<Self>,
+    ) -> Result<
+        (
+            RoomId,
+            LocalBoxFuture<'static, Result<(), RoomServiceError>>,
+        ),
+        RoomServiceError,
+    > {
+        let room_id = spec.id().clone();
+        let is_owned_by_another_file = self
+            .static_spec_files
+            .iter()
+            .any(|(p, file)| p.as_path() != path && file.room_id == room_id);
+
+        let applied = match self.room_repo.get(&room_id) {
+            Some(_)
+                if is_owned_by_another_file
+                    || !self.static_rooms.contains(&room_id) =>
+            {
+                return Err(RoomServiceError::RoomAlreadyExists(
+                    Fid::<ToRoom>::new(room_id),
+                ));
+            }
+            Some(room) => {
+                info!(
+                    "Static Control API spec {} is changed, applying it to \
+                     Room [id = {}]",
+                    path.display(),
+                    room_id,
+                );
+                room.send(ApplyRoomSpec(spec, ApplyPolicy::Apply, None))
+                    .map(|res| {
+                        res.map_err(RoomServiceError::RoomMailboxErr).and_then(
+                            |res| {
+                                res.map(|_| ()).map_err(RoomServiceError::from)
+                            },
+                        )
+                    })
+                    .boxed_local()
+            }
+            None => {
+                info!(
+                    "Static Control API spec {} is added, starting Room [id = \
+                     {}]",
+                    path.display(),
+                    room_id,
+                );
+                self.start_room(&spec)?;
+                self.static_rooms.insert(room_id.clone());
+                future::ok(()).boxed_local()
+            }
+        };
+        self.check_auto_remove(room_id.clone(), AutoRemoveCheck::Updated, ctx);
+        Ok((room_id, applied))
+    }
+
+    /// Closes [`Room`] with provided [`RoomId`], if it's still a static one.
+    fn close_static_room(
+        &mut self,
+        id: RoomId,
+        ctx: &mut Context<Self>,
+    ) -> LocalBoxFuture<'static, ()> {
+        if self.static_rooms.contains(&id) {
+            self.close_room(id, ctx).map(|_| ()).boxed_local()
+        } else {
+            future::ready(()).boxed_local()
+        }
+    }
+
+    /// Starts new [`Room`] with provided [`RoomSpec`], subscribes it to
+    /// [`GracefulShutdown`] and publishes [`ElementEventKind::Created`] events
+    /// of this [`Room`] and all its elements.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError::RoomError`] if [`Room`] failed to
+    /// start.
+    fn start_room(&mut self, room_spec: &RoomSpec) -> Result<(), RoomError> {
         let room_addr = Room::start(
-            &room_spec,
+            room_spec,
             &self.app,
             self.peer_traffic_watcher.clone(),
         )?;
@@ -301,629 +870,2635 @@ impl Handler<CreateRoom> for RoomService {
         );
 
         debug!("New Room [id = {}] started.", room_spec.id);
-        self.room_repo.add(room_spec.id, room_addr);
+        self.room_repo.add(room_spec.id.clone(), room_addr);
 
-        Ok(sid)
+        let events = &self.app.element_events;
+        events.publish(
+            Fid::<ToRoom>::new(room_spec.id.clone()),
+            ElementEventKind::Created,
+        );
+        for (member_id, member) in room_spec.members().unwrap_or_default() {
+            events.publish(
+                Fid::<ToMember>::new(room_spec.id.clone(), member_id.clone()),
+                ElementEventKind::Created,
+            );
+            let endpoint_ids = member
+                .publish_endpoints()
+                .map(|(id, _)| EndpointId::from(id))
+                .chain(
+                    member.play_endpoints().map(|(id, _)| EndpointId::from(id)),
+                );
+            for endpoint_id in endpoint_ids {
+                events.publish(
+                    Fid::<ToEndpoint>::new(
+                        room_spec.id.clone(),
+                        member_id.clone(),
+                        endpoint_id,
+                    ),
+                    ElementEventKind::Created,
+                );
+            }
+        }
+
+        Ok(())
     }
 }
 
-/// Signal for create new [`Member`] in [`Room`].
+/// File of static [Control API] specs dir.
 ///
-/// [`Member`]: crate::signalling::elements::member::Member
-#[derive(Message)]
-#[rtype(result = "Result<Sids, RoomServiceError>")]
-pub struct CreateMemberInRoom {
-    pub id: MemberId,
-    pub parent_fid: Fid<ToRoom>,
-    pub spec: MemberSpec,
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug)]
+struct StaticSpecFile {
+    /// Contents of this [`StaticSpecFile`].
+    content: String,
+
+    /// ID of [`Room`] started from this [`StaticSpecFile`].
+    room_id: RoomId,
 }
 
-impl Handler<CreateMemberInRoom> for RoomService {
-    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
+/// File of static [Control API] specs dir read on reload.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug)]
+struct ReadSpecFile {
+    /// Path of this [`ReadSpecFile`].
+    path: PathBuf,
 
-    fn handle(
-        &mut self,
-        msg: CreateMemberInRoom,
-        _: &mut Self::Context,
-    ) -> Self::Result {
-        let room_id = msg.parent_fid.take_room_id();
-        let id = msg.id;
-        let spec = msg.spec;
-        let sid = self.get_sid(&room_id, &id, spec.credentials());
+    /// Contents of this [`ReadSpecFile`].
+    content: String,
 
-        self.room_repo.get(&room_id).map_or_else(
-            || {
-                future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
-                    room_id,
-                )))
-                .boxed_local()
-            },
-            |room| {
-                async move {
-                    let id_str = id.to_string();
-                    room.send(CreateMember(id, spec))
-                        .await
-                        .map_err(RoomServiceError::RoomMailboxErr)??;
-                    Ok(hashmap! {id_str => sid})
-                }
-                .boxed_local()
-            },
-        )
-    }
+    /// [`RoomSpec`] parsed from this [`ReadSpecFile`] with hashed
+    /// credentials.
+    ///
+    /// [`None`] if its contents haven't changed since the last reload.
+    spec: Option<Result<RoomSpec, LoadStaticControlSpecsError>>,
 }
 
-/// Signal for create new [`Endpoint`] in [`Room`].
-///
-/// [`Endpoint`]: crate::signalling::elements::endpoints::Endpoint
-#[derive(Message)]
-#[rtype(result = "Result<Sids, RoomServiceError>")]
-pub struct CreateEndpointInRoom {
-    pub id: EndpointId,
-    pub parent_fid: Fid<ToMember>,
-    pub spec: EndpointSpec,
+/// Occasion of checking [`AutoRemove`] rule of [`Room`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AutoRemoveCheck {
+    /// [`Room`] has been started or its [`AutoRemove`] rule has been updated.
+    Updated,
+
+    /// `Member` has left [`Room`].
+    MemberLeft,
+
+    /// [`AutoRemove::EmptyFor`] timer of [`Room`] has fired.
+    TimedOut,
 }
 
-impl Handler<CreateEndpointInRoom> for RoomService {
-    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
+impl Actor for RoomService {
+    type Context = Context<Self>;
 
-    fn handle(
-        &mut self,
-        msg: CreateEndpointInRoom,
-        _: &mut Self::Context,
-    ) -> Self::Result {
-        let (room_id, member_id) = msg.parent_fid.take_all();
-        let endpoint_id = msg.id;
-        let spec = msg.spec;
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.add_stream(self.app.element_events.subscribe());
+    }
+}
 
-        self.room_repo.get(&room_id).map_or_else(
-            || {
-                future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+impl StreamHandler<ElementEvent> for RoomService {
+    /// Tracks connections of `Member`s to enforce [`AutoRemove`] rules of
+    /// [`Room`]s.
+    fn handle(&mut self, event: ElementEvent, ctx: &mut Self::Context) {
+        let room_id = event.fid.room_id().clone();
+        match event.kind {
+            ElementEventKind::MemberConnected => {
+                self.cancel_empty_room_timer(&room_id, ctx);
+            }
+            ElementEventKind::MemberDisconnected => {
+                self.check_auto_remove(
                     room_id,
-                )))
-                .boxed_local()
-            },
-            |room| {
-                async move {
-                    room.send(CreateEndpoint {
-                        member_id,
-                        endpoint_id,
-                        spec,
-                    })
-                    .await
-                    .map_err(RoomServiceError::RoomMailboxErr)??;
-                    Ok(HashMap::new())
-                }
-                .boxed_local()
-            },
-        )
+                    AutoRemoveCheck::MemberLeft,
+                    ctx,
+                );
+            }
+            ElementEventKind::Created | ElementEventKind::Deleted => {}
+        }
     }
+
+    /// Keeps [`RoomService`] running, since [`ElementEvent`]s stop being
+    /// published only on server shutdown.
+    fn finished(&mut self, _: &mut Self::Context) {}
 }
 
-/// State which indicates that [`DeleteElements`] message was validated and can
-/// be send to [`RoomService`].
-pub struct Validated;
+/// Signal for load all static specs and start [`Room`]s.
+///
+/// Also starts periodical reloading of static specs, if it's enabled.
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct StartStaticRooms;
 
-/// State which indicates that [`DeleteElements`] message is unvalidated and
-/// should be validated with `validate()` function of [`DeleteElements`] in
-/// [`Unvalidated`] state before sending to [`RoomService`].
-pub struct Unvalidated;
+impl Handler<StartStaticRooms> for RoomService {
+    type Result = Result<(), RoomServiceError>;
 
-// Clippy lint show use_self errors for DeleteElements with generic state. This
-// is fix for it. This allow not works on function.
-impl DeleteElements<Unvalidated> {
-    /// Creates new [`DeleteElements`] in [`Unvalidated`] state.
-    pub fn new() -> Self {
-        Self {
-            fids: Vec::new(),
-            _validation_state: PhantomData,
+    fn handle(
+        &mut self,
+        _: StartStaticRooms,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let reload_interval =
+            self.app.config.control.static_specs_reload_interval;
+        if reload_interval > Duration::from_secs(0) {
+            ctx.run_interval(reload_interval, |this, ctx| {
+                ctx.spawn(this.reload_static_specs());
+            });
         }
-    }
 
-    /// Adds [`StatefulFid`] to request.
-    pub fn add_fid(&mut self, fid: StatefulFid) {
-        self.fids.push(fid)
-    }
+        for (path, content) in read_static_specs_dir(&self.static_specs_dir)? {
+            let spec = parse_yaml_spec(&content)?;
+            if self.room_repo.contains_room_with_id(spec.id()) {
+                return Err(RoomServiceError::RoomAlreadyExists(
+                    Fid::<ToRoom>::new(spec.id),
+                ));
+            }
 
-    /// Validates request. It must have at least one fid, all fids must share
-    /// same [`RoomId`].
-    ///
-    /// # Errors
-    ///
-    /// Errors with [`RoomServiceError::EmptyUrisList`] if [`DeleteElements`]
-    /// consists of an empty [`Vec`] of [`StatefulFid`]s.
-    pub fn validate(
-        self,
-    ) -> Result<DeleteElements<Validated>, RoomServiceError> {
-        if self.fids.is_empty() {
-            return Err(RoomServiceError::EmptyUrisList);
+            self.start_room(&spec)?;
+            self.static_rooms.insert(spec.id.clone());
+            self.static_spec_files.insert(
+                path,
+                StaticSpecFile {
+                    content,
+                    room_id: spec.id.clone(),
+                },
+            );
+            self.check_auto_remove(spec.id, AutoRemoveCheck::Updated, ctx);
         }
+        Ok(())
+    }
+}
 
-        let first_room_id = self.fids[0].room_id();
+/// Signal for reconciling [`Room`]s started from static specs with the
+/// current contents of static specs dir.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct ReloadStaticRooms;
 
-        for id in &self.fids {
-            if first_room_id != id.room_id() {
-                return Err(RoomServiceError::NotSameRoomIds(
-                    first_room_id.clone(),
-                    id.room_id().clone(),
-                ));
-            }
-        }
+impl Handler<ReloadStaticRooms> for RoomService {
+    type Result = ActFuture<()>;
 
-        Ok(DeleteElements {
-            fids: self.fids,
-            _validation_state: PhantomData,
-        })
+    fn handle(
+        &mut self,
+        _: ReloadStaticRooms,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        self.reload_static_specs()
     }
 }
 
-/// Signal for delete [Control API] elements.
-///
-/// This message can be in two states: [`Validated`] and [`Unvalidated`].
-///
-/// For ability to send this message to [`RoomService`] [`DeleteElements`]
-/// should be in [`Validated`] state. You can go to [`Validated`] state
-/// from [`Unvalidated`] with [`DeleteElements::validate`] function
-/// which will validate all [`StatefulFid`]s.
-///
-/// Validation doesn't guarantee that message can't return [`RoomServiceError`].
-/// This is just validation for errors which we can catch before sending
-/// message.
+/// Signal for restoring [`Room`]s from the last saved [`Snapshot`].
 ///
-/// [Control API]: https://tinyurl.com/yxsqplq7
-#[derive(Message, Default)]
+/// [`Room`]s which already exist (started from static specs, for example) or
+/// fail to start are logged and skipped.
+#[derive(Message)]
 #[rtype(result = "Result<(), RoomServiceError>")]
-pub struct DeleteElements<T> {
-    fids: Vec<StatefulFid>,
-    _validation_state: PhantomData<T>,
-}
+pub struct RestoreRooms;
 
-impl Handler<DeleteElements<Validated>> for RoomService {
-    type Result = ResponseFuture<Result<(), RoomServiceError>>;
+impl Handler<RestoreRooms> for RoomService {
+    type Result = ActFuture<Result<(), RoomServiceError>>;
 
-    // TODO: delete 'clippy::unnecessary_filter_map` when drain_filter TODO will
-    //       be resolved.
-    #[allow(clippy::if_not_else, clippy::unnecessary_filter_map)]
     fn handle(
         &mut self,
-        msg: DeleteElements<Validated>,
+        _: RestoreRooms,
         _: &mut Self::Context,
     ) -> Self::Result {
-        let mut deletes_from_room: Vec<StatefulFid> = Vec::new();
-
-        // TODO: use Vec::drain_filter when it will be in stable
-        let room_messages_futs: Vec<ResponseFuture<Result<(), MailboxError>>> =
-            msg.fids
-                .into_iter()
-                .filter_map(|fid| {
-                    if let StatefulFid::Room(room_id) = fid {
-                        Some(self.close_room(room_id.take_room_id()))
-                    } else {
-                        deletes_from_room.push(fid);
-                        None
+        let store = if let Some(store) = self.snapshot_store.clone() {
+            store
+        } else {
+            return Box::pin(actix::fut::ready(Ok(())));
+        };
+        Box::pin(async move { store.load().await }.into_actor(self).map(
+            |loaded, this: &mut Self, ctx| {
+                let snapshot =
+                    match loaded.map_err(RoomServiceError::SnapshotStore)? {
+                        Some(snapshot) => snapshot,
+                        None => return Ok(()),
+                    };
+                for room in snapshot.rooms {
+                    let spec = match RoomSpec::try_from(&room) {
+                        Ok(spec) => spec,
+                        Err(e) => {
+                            let RootElement::Room { id, .. } = &room;
+                            error!(
+                                "Room [id = {}] is not restored from \
+                                 snapshot: {}",
+                                id, e,
+                            );
+                            continue;
+                        }
+                    };
+                    let room_id = spec.id().clone();
+                    if this.room_repo.contains_room_with_id(&room_id) {
+                        warn!(
+                            "Room [id = {}] is not restored from snapshot, \
+                             since it already exists",
+                            room_id,
+                        );
+                        continue;
                     }
-                })
-                .collect();
+                    if let Err(e) = this.create_room(spec) {
+                        error!(
+                            "Room [id = {}] is not restored from snapshot: {}",
+                            room_id, e,
+                        );
+                        continue;
+                    }
+                    if let Some(revision) = snapshot.revisions.get(&room_id) {
+                        if let Some(room) = this.room_repo.get(&room_id) {
+                            room.do_send(SeedRevision(*revision));
+                        }
+                    }
+                    this.check_auto_remove(
+                        room_id.clone(),
+                        AutoRemoveCheck::Updated,
+                        ctx,
+                    );
+                    info!("Room [id = {}] is restored from snapshot", room_id);
+                }
+                Ok(())
+            },
+        ))
+    }
+}
 
-        if !room_messages_futs.is_empty() {
-            future::try_join_all(room_messages_futs)
-                .map_ok(|_| ())
-                .map_err(RoomServiceError::RoomMailboxErr)
-                .boxed_local()
-        } else if !deletes_from_room.is_empty() {
-            let room_id = deletes_from_room[0].room_id().clone();
+/// Type alias for success [`CreateResponse`]'s sids.
+pub type Sids = HashMap<String, String>;
 
-            self.room_repo.get(&room_id).map_or_else(
-                || future::ok(()).boxed_local(),
-                |room| {
-                    room.send(Delete(deletes_from_room))
-                        .map_ok(|_| ())
-                        .map_err(RoomServiceError::RoomMailboxErr)
-                        .err_into()
-                        .boxed_local()
-                },
-            )
-        } else {
-            future::err(RoomServiceError::EmptyUrisList).boxed_local()
-        }
-    }
+/// Returns [Control API] sid based on provided arguments and
+/// `MEDEA_SERVER__CLIENT__HTTP__PUBLIC_URL` config value.
+///
+/// If plain `credentials` are unknown (only their hash is provided), then the
+/// returned sid doesn't contain them.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+fn get_sid(
+    public_url: &str,
+    room_id: &RoomId,
+    member_id: &MemberId,
+    credentials: Option<&Credential>,
+) -> String {
+    credentials.map_or_else(
+        || format!("{}/{}/{}", public_url, room_id, member_id),
+        |credentials| {
+            format!("{}/{}/{}/{}", public_url, room_id, member_id, credentials)
+        },
+    )
 }
 
-/// Serialized to protobuf `Element`s which will be returned from [`Get`] on
-/// success result.
-type SerializedElements = HashMap<StatefulFid, proto::Element>;
+/// Converts credentials of the applied `Member`s into [`Sids`].
+fn applied_sids(
+    public_url: &str,
+    room_id: &RoomId,
+    credentials: HashMap<MemberId, Option<Credential>>,
+) -> Sids {
+    credentials
+        .into_iter()
+        .map(|(member_id, credentials)| {
+            let sid =
+                get_sid(public_url, room_id, &member_id, credentials.as_ref());
+            (member_id.to_string(), sid)
+        })
+        .collect()
+}
 
-/// Message which returns serialized to protobuf objects by provided
-/// [`Fid`].
+/// Signal for creating new [`Room`].
 #[derive(Message)]
-#[rtype(result = "Result<SerializedElements, RoomServiceError>")]
-pub struct Get(pub Vec<StatefulFid>);
-
-impl Handler<Get> for RoomService {
-    type Result = ResponseFuture<Result<SerializedElements, RoomServiceError>>;
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct CreateRoom {
+    /// [Control API] spec for [`Room`].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub spec: RoomSpec,
+}
 
-    fn handle(&mut self, msg: Get, _: &mut Self::Context) -> Self::Result {
-        let mut rooms_elements = HashMap::new();
-        for fid in msg.0 {
-            let room_id = fid.room_id();
+impl Handler<CreateRoom> for RoomService {
+    type Result = Result<Sids, RoomServiceError>;
 
-            if let Some(room) = self.room_repo.get(room_id) {
-                rooms_elements
-                    .entry(room)
-                    .or_insert_with(Vec::new)
-                    .push(fid);
-            } else {
-                return future::err(RoomServiceError::RoomNotFound(fid.into()))
-                    .boxed_local();
-            }
-        }
+    fn handle(
+        &mut self,
+        msg: CreateRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.spec.id.clone();
+        let sids = self.create_room(msg.spec)?;
+        self.check_auto_remove(room_id, AutoRemoveCheck::Updated, ctx);
+        self.schedule_snapshot(ctx);
+        Ok(sids)
+    }
+}
 
-        let mut futs = Vec::new();
-        for (room, elements) in rooms_elements {
-            futs.push(room.send(SerializeProto(elements)));
-        }
+/// Signal for applying [`RoomSpec`] to [`Room`].
+///
+/// Creates new [`Room`] if it doesn't exist yet.
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct ApplyRoom {
+    /// [Control API] spec for [`Room`].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub spec: RoomSpec,
 
-        async {
-            let results = future::try_join_all(futs)
-                .await
-                .map_err(RoomServiceError::RoomMailboxErr)?;
+    /// Policy of applying the [`RoomSpec`] to an existing [`Room`].
+    pub policy: ApplyPolicy,
 
-            let mut all = HashMap::new();
-            for res in results {
-                match res {
-                    Ok(r) => all.extend(r),
-                    Err(e) => return Err(RoomServiceError::from(e)),
-                }
-            }
-            Ok(all)
-        }
-        .boxed_local()
-    }
+    /// Version which the [`Room`] is expected to have for the [`RoomSpec`]
+    /// to be applied.
+    ///
+    /// Not checked if [`None`]. Not existing [`Room`] never has the expected
+    /// version, so it's not created if this version is specified.
+    pub expected_version: Option<u64>,
 }
 
-#[cfg(test)]
-mod delete_elements_validation_specs {
-    use std::convert::TryFrom as _;
-
-    use super::*;
+impl Handler<ApplyRoom> for RoomService {
+    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
 
-    #[test]
-    fn empty_fids_list() {
-        let elements = DeleteElements::new();
-        match elements.validate() {
-            Ok(_) => panic!(
-                "Validation should fail with EmptyUrisList but returned Ok."
-            ),
-            Err(e) => match e {
-                RoomServiceError::EmptyUrisList => (),
-                _ => panic!(
-                    "Validation should fail with EmptyList error but errored \
-                     with {:?}.",
-                    e
-                ),
-            },
-        }
+    fn handle(
+        &mut self,
+        msg: ApplyRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.spec.id().clone();
+        let result = if let Some(room) = self.room_repo.get(&room_id) {
+            let public_url = self.public_url.clone();
+            // Spec is sent right away, so `AutoRemove` rule is checked only
+            // after it's applied.
+            let applied = room.send(ApplyRoomSpec(
+                msg.spec,
+                msg.policy,
+                msg.expected_version,
+            ));
+            let room_id = room_id.clone();
+            async move {
+                let credentials = applied
+                    .await
+                    .map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(applied_sids(&public_url, &room_id, credentials))
+            }
+            .boxed_local()
+        } else if let Some(expected) = msg.expected_version {
+            return future::err(
+                RoomError::VersionMismatch {
+                    fid: Fid::<ToRoom>::new(room_id).into(),
+                    expected,
+                    actual: 0,
+                }
+                .into(),
+            )
+            .boxed_local();
+        } else {
+            future::ready(self.create_room(msg.spec)).boxed_local()
+        };
+        self.check_auto_remove(room_id, AutoRemoveCheck::Updated, ctx);
+        self.schedule_snapshot(ctx);
+        result
     }
+}
 
-    #[test]
-    fn error_if_not_same_room_ids() {
-        let mut elements = DeleteElements::new();
-        ["room_id/member", "another_room_id/member"]
-            .iter()
-            .map(|fid| StatefulFid::try_from((*fid).to_string()).unwrap())
-            .for_each(|fid| elements.add_fid(fid));
+/// Signal for create new [`Member`] in [`Room`].
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct CreateMemberInRoom {
+    pub id: MemberId,
+    pub parent_fid: Fid<ToRoom>,
+    pub spec: MemberSpec,
 
-        match elements.validate() {
-            Ok(_) => panic!(
-                "Validation should fail with NotSameRoomIds but returned Ok."
-            ),
-            Err(e) => match e {
-                RoomServiceError::NotSameRoomIds(first, another) => {
-                    assert_eq!(&first.to_string(), "room_id");
-                    assert_eq!(&another.to_string(), "another_room_id");
+    /// Version which the [`Room`] is expected to have for the [`Member`] to
+    /// be created.
+    ///
+    /// Not checked if [`None`].
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub expected_version: Option<u64>,
+}
+
+impl Handler<CreateMemberInRoom> for RoomService {
+    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: CreateMemberInRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.parent_fid.take_room_id();
+        let id = msg.id;
+        let spec = msg.spec;
+        let expected_version = msg.expected_version;
+        let sid = get_sid(
+            &self.public_url,
+            &room_id,
+            &id,
+            spec.credentials().plain(),
+        );
+
+        self.room_repo.get(&room_id).map_or_else(
+            || {
+                future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                    room_id,
+                )))
+                .boxed_local()
+            },
+            |room| {
+                let id_str = id.to_string();
+                // Member is created right away, so snapshot includes it.
+                let created =
+                    room.send(CreateMember(id, spec, expected_version));
+                self.schedule_snapshot(ctx);
+                async move {
+                    created
+                        .await
+                        .map_err(RoomServiceError::RoomMailboxErr)??;
+                    Ok(hashmap! {id_str => sid})
                 }
-                _ => panic!(
-                    "Validation should fail with NotSameRoomIds error but \
-                     errored with {:?}.",
-                    e
-                ),
+                .boxed_local()
             },
-        }
+        )
     }
+}
 
-    #[test]
-    fn success_if_all_ok() {
-        let mut elements = DeleteElements::new();
-        [
-            "room_id/member_id",
-            "room_id/another_member_id",
-            "room_id/member_id/endpoint_id",
-        ]
-        .iter()
-        .map(|fid| StatefulFid::try_from((*fid).to_string()).unwrap())
-        .for_each(|fid| elements.add_fid(fid));
+/// Signal for applying [`MemberSpec`] to [`Member`] in [`Room`].
+///
+/// Creates new [`Member`] if it doesn't exist yet.
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct ApplyMemberInRoom {
+    pub id: MemberId,
+    pub parent_fid: Fid<ToRoom>,
+    pub spec: MemberSpec,
+    pub policy: ApplyPolicy,
 
-        assert!(elements.validate().is_ok());
-    }
+    /// Version which the [`Member`] is expected to have for the
+    /// [`MemberSpec`] to be applied.
+    ///
+    /// Not checked if [`None`]. Not existing [`Member`] never has the
+    /// expected version, so it's not created if this version is specified.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub expected_version: Option<u64>,
 }
 
-#[cfg(test)]
-mod room_service_specs {
-    use std::convert::TryFrom as _;
+impl Handler<ApplyMemberInRoom> for RoomService {
+    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
 
-    use crate::{
-        api::control::{
-            endpoints::webrtc_publish_endpoint::P2pMode,
-            refs::{Fid, ToEndpoint},
-            RootElement,
-        },
-        conf::{self, Conf},
-    };
+    fn handle(
+        &mut self,
+        msg: ApplyMemberInRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let ApplyMemberInRoom {
+            id,
+            parent_fid,
+            spec,
+            policy,
+            expected_version,
+        } = msg;
+        let room_id = parent_fid.take_room_id();
+
+        if let Some(room) = self.room_repo.get(&room_id) {
+            let public_url = self.public_url.clone();
+            // Spec is applied right away, so snapshot includes it.
+            let applied =
+                room.send(ApplyMember(id, spec, policy, expected_version));
+            self.schedule_snapshot(ctx);
+            async move {
+                let credentials = applied
+                    .await
+                    .map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(applied_sids(&public_url, &room_id, credentials))
+            }
+            .boxed_local()
+        } else {
+            future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                room_id,
+            )))
+            .boxed_local()
+        }
+    }
+}
 
-    use super::*;
+/// Signal for updating properties of [`Member`] in [`Room`] in place.
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct UpdateMemberInRoom {
+    pub fid: Fid<ToMember>,
+    pub patch: MemberPatch,
 
-    /// Returns [`RoomSpec`] parsed from
-    /// `../../tests/specs/pub-sub-video-call.yml` file.
+    /// Version which the [`Member`] is expected to have for the
+    /// [`MemberPatch`] to be applied.
     ///
-    /// Note that YAML spec is loads on compile time with [`include_str`]
-    /// macro.
-    fn room_spec() -> RoomSpec {
-        const ROOM_SPEC: &str =
-            include_str!("../../tests/specs/pub-sub-video-call.yml");
+    /// Not checked if [`None`].
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub expected_version: Option<u64>,
+}
 
-        let parsed: RootElement = serde_yaml::from_str(ROOM_SPEC).unwrap();
-        RoomSpec::try_from(&parsed).unwrap()
-    }
+impl Handler<UpdateMemberInRoom> for RoomService {
+    type Result = ResponseFuture<Result<(), RoomServiceError>>;
 
-    /// Returns [`AppContext`] with default [`Conf`] and mocked
-    /// [`TurnAuthService`].
-    fn app_ctx() -> AppContext {
-        let turn_service = crate::turn::new_turn_auth_service_mock();
-        AppContext::new(Conf::default(), turn_service)
+    fn handle(
+        &mut self,
+        msg: UpdateMemberInRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (room_id, member_id) = msg.fid.take_all();
+
+        if let Some(room) = self.room_repo.get(&room_id) {
+            // Patch is applied right away, so snapshot includes it.
+            let updated = room.send(UpdateMember(
+                member_id,
+                msg.patch,
+                msg.expected_version,
+            ));
+            self.schedule_snapshot(ctx);
+            async move {
+                updated.await.map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(())
+            }
+            .boxed_local()
+        } else {
+            future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                room_id,
+            )))
+            .boxed_local()
+        }
     }
+}
 
-    /// Returns [`Addr`] to [`RoomService`].
-    fn room_service(room_repo: RoomRepository) -> Addr<RoomService> {
-        let conf = Conf::default();
-        let shutdown_timeout = conf.shutdown.timeout;
+/// Signal for updating properties of `WebRtcPublishEndpoint` in [`Room`] in
+/// place.
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct UpdateEndpointInRoom {
+    pub fid: Fid<ToEndpoint>,
+    pub patch: WebRtcPublishEndpointPatch,
 
-        let app = app_ctx();
-        let graceful_shutdown = GracefulShutdown::new(shutdown_timeout).start();
+    /// Version which the `Endpoint` is expected to have for the
+    /// [`WebRtcPublishEndpointPatch`] to be applied.
+    ///
+    /// Not checked if [`None`].
+    pub expected_version: Option<u64>,
+}
 
-        RoomService::new(room_repo, app, graceful_shutdown)
-            .unwrap()
-            .start()
+impl Handler<UpdateEndpointInRoom> for RoomService {
+    type Result = ResponseFuture<Result<(), RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: UpdateEndpointInRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (room_id, member_id, endpoint_id) = msg.fid.take_all();
+
+        if let Some(room) = self.room_repo.get(&room_id) {
+            // Patch is applied right away, so snapshot includes it.
+            let updated = room.send(UpdateEndpoint {
+                member_id,
+                endpoint_id: endpoint_id.into(),
+                patch: msg.patch,
+                expected_version: msg.expected_version,
+            });
+            self.schedule_snapshot(ctx);
+            async move {
+                updated.await.map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(())
+            }
+            .boxed_local()
+        } else {
+            future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                room_id,
+            )))
+            .boxed_local()
+        }
     }
+}
 
-    /// Returns [`Future`] used for testing of all create methods of
-    /// [`RoomService`].
+/// Signal for kicking [`Member`] out of [`Room`], optionally rotating its
+/// credentials.
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct KickMember {
+    pub fid: Fid<ToMember>,
+
+    /// Human-readable description of why the [`Member`] is kicked.
     ///
-    /// This macro automatically stops [`actix::System`] when test completed.
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub description: Option<String>,
+
+    /// New [`Credentials`] of the kicked [`Member`].
     ///
-    /// `$room_service` - [`Addr`] to [`RoomService`],
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub credentials: Option<Credentials>,
+
+    /// Version which the [`Member`] is expected to have for being kicked.
     ///
-    /// `$create_msg` - [`actix::Message`] which will create `Element`,
+    /// Not checked if [`None`].
     ///
-    /// `$element_fid` - [`StatefulFid`] to `Element` which you try to
-    /// create,
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub expected_version: Option<u64>,
+}
+
+impl Handler<KickMember> for RoomService {
+    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: KickMember,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (room_id, member_id) = msg.fid.take_all();
+
+        if let Some(room) = self.room_repo.get(&room_id) {
+            let sid = get_sid(
+                &self.public_url,
+                &room_id,
+                &member_id,
+                msg.credentials.as_ref().and_then(Credentials::plain),
+            );
+            let member_id_str = member_id.to_string();
+            // Credentials are rotated right away, so snapshot includes them.
+            let kicked = room.send(Kick {
+                member_id,
+                description: msg.description,
+                credentials: msg.credentials,
+                expected_version: msg.expected_version,
+            });
+            self.schedule_snapshot(ctx);
+            async move {
+                kicked.await.map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(hashmap! {member_id_str => sid})
+            }
+            .boxed_local()
+        } else {
+            future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                room_id,
+            )))
+            .boxed_local()
+        }
+    }
+}
+
+/// Signal for sending custom event with an opaque JSON payload to [`Member`]
+/// of [`Room`], or to all the connected [`Member`]s of [`Room`].
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct SendCustomEvent {
+    /// [`Fid`] of the [`Room`] to send the event in.
+    pub room_fid: Fid<ToRoom>,
+
+    /// [`MemberId`] of the [`Member`] receiving the event.
     ///
-    /// `$test` - closure in which will be provided created
-    /// [`Element`].
-    macro_rules! test_for_create {
-        (
-            $room_service:expr,
-            $create_msg:expr,
-            $element_fid:expr,
-            $test:expr
-        ) => {
+    /// The event is sent to all the connected [`Member`]s of the [`Room`] if
+    /// [`None`].
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub member_id: Option<MemberId>,
+
+    /// Payload of the event, passed to clients as is.
+    pub payload: serde_json::Value,
+}
+
+impl Handler<SendCustomEvent> for RoomService {
+    type Result = ResponseFuture<Result<(), RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: SendCustomEvent,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.room_fid.take_room_id();
+
+        if let Some(room) = self.room_repo.get(&room_id) {
+            let sent =
+                room.send(RoomSendCustomEvent(msg.member_id, msg.payload));
             async move {
-                let get_msg = Get(vec![$element_fid.clone()]);
-                $room_service.send($create_msg).await.unwrap().unwrap();
-                let mut resp =
-                    $room_service.send(get_msg).await.unwrap().unwrap();
-                resp.remove(&$element_fid).unwrap();
-                actix::System::current().stop();
+                sent.await.map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(())
             }
-        };
+            .boxed_local()
+        } else {
+            future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                room_id,
+            )))
+            .boxed_local()
+        }
     }
+}
 
-    #[actix_rt::test]
-    async fn create_room() {
+/// Signal for provisioning [`Member`] authenticated with a signed token in
+/// [`Room`].
+///
+/// Creates new [`Member`] if it doesn't exist yet, otherwise does nothing.
+///
+/// [`Member`]: crate::signalling::elements::member::Member
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct ProvisionMember {
+    pub fid: Fid<ToMember>,
+    pub spec: MemberSpec,
+}
+
+impl Handler<ProvisionMember> for RoomService {
+    type Result = ResponseFuture<Result<(), RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: ProvisionMember,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (room_id, member_id) = msg.fid.take_all();
+
+        if let Some(room) = self.room_repo.get(&room_id) {
+            // Member is provisioned right away, so snapshot includes it.
+            let provisioned =
+                room.send(RoomProvisionMember(member_id, msg.spec));
+            self.schedule_snapshot(ctx);
+            async move {
+                provisioned
+                    .await
+                    .map_err(RoomServiceError::RoomMailboxErr)??;
+                Ok(())
+            }
+            .boxed_local()
+        } else {
+            future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                room_id,
+            )))
+            .boxed_local()
+        }
+    }
+}
+
+/// Signal for create new [`Endpoint`] in [`Room`].
+///
+/// [`Endpoint`]: crate::signalling::elements::endpoints::Endpoint
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct CreateEndpointInRoom {
+    pub id: EndpointId,
+    pub parent_fid: Fid<ToMember>,
+    pub spec: EndpointSpec,
+
+    /// Version which the [`Member`] is expected to have for the [`Endpoint`]
+    /// to be created.
+    ///
+    /// Not checked if [`None`].
+    ///
+    /// [`Endpoint`]: crate::signalling::elements::endpoints::Endpoint
+    /// [`Member`]: crate::signalling::elements::member::Member
+    pub expected_version: Option<u64>,
+}
+
+impl Handler<CreateEndpointInRoom> for RoomService {
+    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: CreateEndpointInRoom,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (room_id, member_id) = msg.parent_fid.take_all();
+        let endpoint_id = msg.id;
+        let spec = msg.spec;
+        let expected_version = msg.expected_version;
+
+        self.room_repo.get(&room_id).map_or_else(
+            || {
+                future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                    room_id,
+                )))
+                .boxed_local()
+            },
+            |room| {
+                // Endpoint is created right away, so snapshot includes it.
+                let created = room.send(CreateEndpoint {
+                    member_id,
+                    endpoint_id,
+                    spec,
+                    expected_version,
+                });
+                self.schedule_snapshot(ctx);
+                async move {
+                    created
+                        .await
+                        .map_err(RoomServiceError::RoomMailboxErr)??;
+                    Ok(HashMap::new())
+                }
+                .boxed_local()
+            },
+        )
+    }
+}
+
+/// State which indicates that [`DeleteElements`] message was validated and can
+/// be send to [`RoomService`].
+pub struct Validated;
+
+/// State which indicates that [`DeleteElements`] message is unvalidated and
+/// should be validated with `validate()` function of [`DeleteElements`] in
+/// [`Unvalidated`] state before sending to [`RoomService`].
+pub struct Unvalidated;
+
+// Clippy lint show use_self errors for DeleteElements with generic state. This
+// is fix for it. This allow not works on function.
+impl DeleteElements<Unvalidated> {
+    /// Creates new [`DeleteElements`] in [`Unvalidated`] state.
+    pub fn new() -> Self {
+        Self {
+            fids: Vec::new(),
+            expected_versions: HashMap::new(),
+            _validation_state: PhantomData,
+        }
+    }
+
+    /// Adds [`StatefulFid`] to request.
+    pub fn add_fid(&mut self, fid: StatefulFid) {
+        self.fids.push(fid)
+    }
+
+    /// Sets version which the element with the provided [`StatefulFid`] is
+    /// expected to have for the elements to be deleted.
+    pub fn expect_version(&mut self, fid: StatefulFid, version: u64) {
+        self.expected_versions.insert(fid, version);
+    }
+
+    /// Validates request. It must have at least one fid, all fids must share
+    /// same [`RoomId`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError::EmptyUrisList`] if [`DeleteElements`]
+    /// consists of an empty [`Vec`] of [`StatefulFid`]s.
+    pub fn validate(
+        self,
+    ) -> Result<DeleteElements<Validated>, RoomServiceError> {
+        if self.fids.is_empty() {
+            return Err(RoomServiceError::EmptyUrisList);
+        }
+
+        let first_room_id = self.fids[0].room_id();
+
+        for id in &self.fids {
+            if first_room_id != id.room_id() {
+                return Err(RoomServiceError::NotSameRoomIds(
+                    first_room_id.clone(),
+                    id.room_id().clone(),
+                ));
+            }
+        }
+
+        Ok(DeleteElements {
+            fids: self.fids,
+            expected_versions: self.expected_versions,
+            _validation_state: PhantomData,
+        })
+    }
+}
+
+/// Signal for delete [Control API] elements.
+///
+/// This message can be in two states: [`Validated`] and [`Unvalidated`].
+///
+/// For ability to send this message to [`RoomService`] [`DeleteElements`]
+/// should be in [`Validated`] state. You can go to [`Validated`] state
+/// from [`Unvalidated`] with [`DeleteElements::validate`] function
+/// which will validate all [`StatefulFid`]s.
+///
+/// Validation doesn't guarantee that message can't return [`RoomServiceError`].
+/// This is just validation for errors which we can catch before sending
+/// message.
+///
+/// Elements are deleted only if all the elements with expected versions have
+/// exactly these versions.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Message, Default)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct DeleteElements<T> {
+    fids: Vec<StatefulFid>,
+    expected_versions: HashMap<StatefulFid, u64>,
+    _validation_state: PhantomData<T>,
+}
+
+impl Handler<DeleteElements<Validated>> for RoomService {
+    type Result = ResponseFuture<Result<(), RoomServiceError>>;
+
+    // TODO: delete 'clippy::unnecessary_filter_map` when drain_filter TODO will
+    //       be resolved.
+    #[allow(clippy::if_not_else, clippy::unnecessary_filter_map)]
+    fn handle(
+        &mut self,
+        msg: DeleteElements<Validated>,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let DeleteElements {
+            fids,
+            expected_versions,
+            ..
+        } = msg;
+        let mut deletes_from_room: Vec<StatefulFid> = Vec::new();
+
+        // TODO: use Vec::drain_filter when it will be in stable
+        let closed_rooms: Vec<RoomId> = fids
+            .into_iter()
+            .filter_map(|fid| {
+                if let StatefulFid::Room(room_id) = fid {
+                    Some(room_id.take_room_id())
+                } else {
+                    deletes_from_room.push(fid);
+                    None
+                }
+            })
+            .collect();
+
+        if !closed_rooms.is_empty() {
+            if !expected_versions.is_empty() {
+                return self.close_rooms_of_versions(
+                    closed_rooms,
+                    expected_versions,
+                    ctx,
+                );
+            }
+            let room_messages_futs: Vec<_> = closed_rooms
+                .into_iter()
+                .map(|id| self.close_room(id, ctx))
+                .collect();
+            future::try_join_all(room_messages_futs)
+                .map_ok(|_| ())
+                .map_err(RoomServiceError::RoomMailboxErr)
+                .boxed_local()
+        } else if !deletes_from_room.is_empty() {
+            let room_id = deletes_from_room[0].room_id().clone();
+
+            self.schedule_snapshot(ctx);
+            self.room_repo.get(&room_id).map_or_else(
+                || future::ok(()).boxed_local(),
+                |room| {
+                    let deleted =
+                        room.send(Delete(deletes_from_room, expected_versions));
+                    async move {
+                        deleted
+                            .await
+                            .map_err(RoomServiceError::RoomMailboxErr)??;
+                        Ok(())
+                    }
+                    .boxed_local()
+                },
+            )
+        } else {
+            future::err(RoomServiceError::EmptyUrisList).boxed_local()
+        }
+    }
+}
+
+/// Serialized to protobuf `Element`s which will be returned from [`Get`] on
+/// success result.
+type SerializedElements = HashMap<StatefulFid, proto::Element>;
+
+/// Message which returns serialized to protobuf objects by provided
+/// [`Fid`].
+#[derive(Message)]
+#[rtype(result = "Result<SerializedElements, RoomServiceError>")]
+pub struct Get(pub Vec<StatefulFid>);
+
+impl Handler<Get> for RoomService {
+    type Result = ResponseFuture<Result<SerializedElements, RoomServiceError>>;
+
+    fn handle(&mut self, msg: Get, _: &mut Self::Context) -> Self::Result {
+        let mut rooms_elements = HashMap::new();
+        for fid in msg.0 {
+            let room_id = fid.room_id();
+
+            if let Some(room) = self.room_repo.get(room_id) {
+                rooms_elements
+                    .entry(room)
+                    .or_insert_with(Vec::new)
+                    .push(fid);
+            } else {
+                return future::err(RoomServiceError::RoomNotFound(fid.into()))
+                    .boxed_local();
+            }
+        }
+
+        let mut futs = Vec::new();
+        for (room, elements) in rooms_elements {
+            futs.push(room.send(SerializeProto(elements)));
+        }
+
+        async {
+            let results = future::try_join_all(futs)
+                .await
+                .map_err(RoomServiceError::RoomMailboxErr)?;
+
+            let mut all = HashMap::new();
+            for res in results {
+                match res {
+                    Ok(r) => all.extend(r),
+                    Err(e) => return Err(RoomServiceError::from(e)),
+                }
+            }
+            Ok(all)
+        }
+        .boxed_local()
+    }
+}
+
+/// Default maximum number of [`Room`]s returned by a single [`List`].
+pub const DEFAULT_LIST_LIMIT: usize = 100;
+
+/// Maximum number of [`Room`]s which can be returned by a single [`List`].
+pub const MAX_LIST_LIMIT: usize = 1000;
+
+/// Origin of [`Room`] to filter [`Room`]s by.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoomOrigin {
+    /// [`Room`] created from static [Control API] spec.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    Static,
+
+    /// [`Room`] created via [Control API].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    Dynamic,
+}
+
+/// Filter of [`Room`]s listed by [`List`].
+///
+/// [`None`] conditions are not checked.
+#[derive(Clone, Debug, Default)]
+pub struct ListFilter {
+    /// Minimum number of `Member`s in [`Room`].
+    pub min_members: Option<usize>,
+
+    /// Maximum number of `Member`s in [`Room`].
+    pub max_members: Option<usize>,
+
+    /// Minimum number of `Member`s connected to [`Room`].
+    pub min_connected_members: Option<usize>,
+
+    /// Maximum number of `Member`s connected to [`Room`].
+    pub max_connected_members: Option<usize>,
+
+    /// Origin of [`Room`].
+    pub origin: Option<RoomOrigin>,
+
+    /// Labels which [`Room`] must have with exactly the same values.
+    ///
+    /// Empty labels match any [`Room`].
+    pub labels: HashMap<String, String>,
+}
+
+impl ListFilter {
+    /// Indicates whether [`Room`] of provided origin satisfies origin
+    /// condition of this [`ListFilter`].
+    fn matches_origin(&self, is_static: bool) -> bool {
+        match self.origin {
+            None => true,
+            Some(RoomOrigin::Static) => is_static,
+            Some(RoomOrigin::Dynamic) => !is_static,
+        }
+    }
+
+    /// Indicates whether [`Room`] with provided [`RoomSummary`] satisfies
+    /// `Member`s and labels conditions of this [`ListFilter`].
+    fn matches_summary(&self, summary: &RoomSummary) -> bool {
+        let in_range =
+            |value: usize, min: Option<usize>, max: Option<usize>| {
+                min.map_or(true, |min| value >= min)
+                    && max.map_or(true, |max| value <= max)
+            };
+        in_range(summary.members, self.min_members, self.max_members)
+            && in_range(
+                summary.connected_members,
+                self.min_connected_members,
+                self.max_connected_members,
+            )
+            && summary.meta.matches(&self.labels)
+    }
+}
+
+/// [`Room`] listed by [`List`].
+#[derive(Debug)]
+pub struct ListedRoom {
+    /// ID of this [`Room`].
+    pub id: RoomId,
+
+    /// [`RoomSummary`] of this [`Room`].
+    pub summary: RoomSummary,
+
+    /// Indicator whether this [`Room`] was created from static [Control API]
+    /// spec.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub is_static: bool,
+
+    /// Serialized to protobuf spec of this [`Room`].
+    ///
+    /// Returned only if [`List::deep`] is `true`.
+    pub element: Option<proto::Element>,
+}
+
+/// Page of [`Room`]s returned by [`List`].
+#[derive(Debug)]
+pub struct RoomsPage {
+    /// Listed [`Room`]s ordered by their [`RoomId`]s.
+    pub rooms: Vec<ListedRoom>,
+
+    /// Cursor to request the next page with.
+    ///
+    /// [`None`] if there are no more [`Room`]s to list.
+    pub next_cursor: Option<RoomId>,
+}
+
+/// Message which lists existing [`Room`]s page by page, in order of their
+/// [`RoomId`]s.
+#[derive(Message, Debug, Default)]
+#[rtype(result = "Result<RoomsPage, RoomServiceError>")]
+pub struct List {
+    /// [`RoomId`] after which listing should be continued.
+    ///
+    /// [`None`] means listing from the first [`Room`].
+    pub cursor: Option<RoomId>,
+
+    /// Maximum number of [`Room`]s to list.
+    ///
+    /// `0` means [`DEFAULT_LIST_LIMIT`]. Values greater than
+    /// [`MAX_LIST_LIMIT`] are capped.
+    pub limit: usize,
+
+    /// Indicator whether specs of the listed [`Room`]s should be serialized.
+    pub deep: bool,
+
+    /// Filter of the listed [`Room`]s.
+    pub filter: ListFilter,
+}
+
+impl Handler<List> for RoomService {
+    type Result = ResponseFuture<Result<RoomsPage, RoomServiceError>>;
+
+    fn handle(&mut self, msg: List, _: &mut Self::Context) -> Self::Result {
+        let limit = match msg.limit {
+            0 => DEFAULT_LIST_LIMIT,
+            limit => limit.min(MAX_LIST_LIMIT),
+        };
+        let List {
+            cursor,
+            deep,
+            filter,
+            ..
+        } = msg;
+        let rooms: Vec<_> = self
+            .room_repo
+            .sorted()
+            .into_iter()
+            .filter(|(id, _)| {
+                cursor.as_ref().map_or(true, |cursor| id.0 > cursor.0)
+            })
+            .map(|(id, room)| {
+                let is_static = self.static_rooms.contains(&id);
+                (id, room, is_static)
+            })
+            .filter(|(_, _, is_static)| filter.matches_origin(*is_static))
+            .collect();
+
+        async move {
+            let mut page = RoomsPage {
+                rooms: Vec::new(),
+                next_cursor: None,
+            };
+            for (id, room, is_static) in rooms {
+                // Room may be closed concurrently, so it's just skipped.
+                let summary = match room.send(Summarize).await {
+                    Ok(summary) => summary,
+                    Err(_) => continue,
+                };
+                if !filter.matches_summary(&summary) {
+                    continue;
+                }
+                if page.rooms.len() == limit {
+                    page.next_cursor =
+                        page.rooms.last().map(|room| room.id.clone());
+                    break;
+                }
+                let element = if deep {
+                    let fid = StatefulFid::from(Fid::<ToRoom>::new(id.clone()));
+                    match room.send(SerializeProto(vec![fid])).await {
+                        Ok(Ok(serialized)) => serialized.into_iter().next(),
+                        Ok(Err(e)) => {
+                            // Single broken Room shouldn't fail the whole
+                            // page, so it's just skipped.
+                            warn!(
+                                "Failed to serialize Room [id = {}]: {}",
+                                id, e,
+                            );
+                            continue;
+                        }
+                        Err(_) => continue,
+                    }
+                    .map(|(_, element)| element)
+                } else {
+                    None
+                };
+                page.rooms.push(ListedRoom {
+                    id,
+                    summary,
+                    is_static,
+                    element,
+                });
+            }
+            Ok(page)
+        }
+        .boxed_local()
+    }
+}
+
+#[cfg(test)]
+mod delete_elements_validation_specs {
+    use std::convert::TryFrom as _;
+
+    use super::*;
+
+    #[test]
+    fn empty_fids_list() {
+        let elements = DeleteElements::new();
+        match elements.validate() {
+            Ok(_) => panic!(
+                "Validation should fail with EmptyUrisList but returned Ok."
+            ),
+            Err(e) => match e {
+                RoomServiceError::EmptyUrisList => (),
+                _ => panic!(
+                    "Validation should fail with EmptyList error but errored \
+                     with {:?}.",
+                    e
+                ),
+            },
+        }
+    }
+
+    #[test]
+    fn error_if_not_same_room_ids() {
+        let mut elements = DeleteElements::new();
+        ["room_id/member", "another_room_id/member"]
+            .iter()
+            .map(|fid| StatefulFid::try_from((*fid).to_string()).unwrap())
+            .for_each(|fid| elements.add_fid(fid));
+
+        match elements.validate() {
+            Ok(_) => panic!(
+                "Validation should fail with NotSameRoomIds but returned Ok."
+            ),
+            Err(e) => match e {
+                RoomServiceError::NotSameRoomIds(first, another) => {
+                    assert_eq!(&first.to_string(), "room_id");
+                    assert_eq!(&another.to_string(), "another_room_id");
+                }
+                _ => panic!(
+                    "Validation should fail with NotSameRoomIds error but \
+                     errored with {:?}.",
+                    e
+                ),
+            },
+        }
+    }
+
+    #[test]
+    fn success_if_all_ok() {
+        let mut elements = DeleteElements::new();
+        [
+            "room_id/member_id",
+            "room_id/another_member_id",
+            "room_id/member_id/endpoint_id",
+        ]
+        .iter()
+        .map(|fid| StatefulFid::try_from((*fid).to_string()).unwrap())
+        .for_each(|fid| elements.add_fid(fid));
+
+        assert!(elements.validate().is_ok());
+    }
+}
+
+#[cfg(test)]
+mod room_service_specs {
+    use std::{convert::TryFrom as _, time::Duration};
+
+    use tokio::time::delay_for;
+
+    use crate::{
+        api::control::{
+            credentials::{CredentialHash, Credentials},
+            endpoints::{
+                webrtc_publish_endpoint::{
+                    AudioSettings, P2pMode, PublishPolicy, VideoSettings,
+                },
+                WebRtcPublishEndpoint,
+            },
+            pipeline::Pipeline,
+            refs::{Fid, ToEndpoint},
+            RootElement,
+        },
+        conf::{self, Conf},
+    };
+
+    use super::*;
+
+    /// Returns [`RoomSpec`] parsed from
+    /// `../../tests/specs/pub-sub-video-call.yml` file.
+    ///
+    /// Note that YAML spec is loads on compile time with [`include_str`]
+    /// macro.
+    fn room_spec() -> RoomSpec {
+        const ROOM_SPEC: &str =
+            include_str!("../../tests/specs/pub-sub-video-call.yml");
+
+        let parsed: RootElement = serde_yaml::from_str(ROOM_SPEC).unwrap();
+        RoomSpec::try_from(&parsed).unwrap()
+    }
+
+    /// Returns [`AppContext`] with default [`Conf`] and mocked
+    /// [`TurnAuthService`].
+    fn app_ctx() -> AppContext {
+        let turn_service = crate::turn::new_turn_auth_service_mock();
+        AppContext::new(Conf::default(), turn_service)
+    }
+
+    /// Returns [`Addr`] to [`RoomService`].
+    fn room_service(room_repo: RoomRepository) -> Addr<RoomService> {
+        let conf = Conf::default();
+        let shutdown_timeout = conf.shutdown.timeout;
+
+        let app = app_ctx();
+        let graceful_shutdown = GracefulShutdown::new(shutdown_timeout).start();
+
+        RoomService::new(room_repo, app, graceful_shutdown)
+            .unwrap()
+            .start()
+    }
+
+    /// Returns [`Future`] used for testing of all create methods of
+    /// [`RoomService`].
+    ///
+    /// This macro automatically stops [`actix::System`] when test completed.
+    ///
+    /// `$room_service` - [`Addr`] to [`RoomService`],
+    ///
+    /// `$create_msg` - [`actix::Message`] which will create `Element`,
+    ///
+    /// `$element_fid` - [`StatefulFid`] to `Element` which you try to
+    /// create,
+    ///
+    /// `$test` - closure in which will be provided created
+    /// [`Element`].
+    macro_rules! test_for_create {
+        (
+            $room_service:expr,
+            $create_msg:expr,
+            $element_fid:expr,
+            $test:expr
+        ) => {
+            async move {
+                let get_msg = Get(vec![$element_fid.clone()]);
+                $room_service.send($create_msg).await.unwrap().unwrap();
+                let mut resp =
+                    $room_service.send(get_msg).await.unwrap().unwrap();
+                resp.remove(&$element_fid).unwrap();
+                actix::System::current().stop();
+            }
+        };
+    }
+
+    #[actix_rt::test]
+    async fn create_room() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        let spec = room_spec();
+        let caller_fid =
+            StatefulFid::try_from("pub-sub-video-call/caller".to_string())
+                .unwrap();
+
+        test_for_create!(
+            room_service,
+            CreateRoom { spec },
+            caller_fid,
+            |member_el| {
+                assert_eq!(member_el.get_member().get_pipeline().len(), 1);
+            }
+        )
+        .await;
+    }
+
+    #[actix_rt::test]
+    async fn create_member() {
+        let spec = room_spec();
+        let member_spec = spec
+            .members()
+            .unwrap()
+            .get(&"caller".to_string().into())
+            .unwrap()
+            .clone();
+
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let room = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::new(hashmap!(
+            room_id.clone() => room,
+        )));
+
+        let member_parent_fid = Fid::<ToRoom>::new(room_id);
+        let member_id: MemberId = "test-member".to_string().into();
+        let member_full_id: StatefulFid = member_parent_fid
+            .clone()
+            .push_member_id(member_id.clone())
+            .into();
+
+        test_for_create!(
+            room_service,
+            CreateMemberInRoom {
+                id: member_id,
+                spec: member_spec,
+                parent_fid: member_parent_fid,
+                expected_version: None,
+            },
+            member_full_id,
+            |member_el| {
+                assert_eq!(member_el.get_member().get_pipeline().len(), 1);
+            }
+        )
+        .await;
+    }
+
+    #[actix_rt::test]
+    async fn create_endpoint() {
+        let spec = room_spec();
+
+        let mut endpoint_spec = spec
+            .members()
+            .unwrap()
+            .get(&"caller".to_string().into())
+            .unwrap()
+            .get_publish_endpoint_by_id("publish".to_string().into())
+            .unwrap()
+            .clone();
+        endpoint_spec.p2p = P2pMode::Never;
+        let endpoint_spec = endpoint_spec.into();
+
+        let room_id: RoomId = "pub-sub-video-call".into();
+        let room = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::new(hashmap!(
+            room_id.clone() => room,
+        )));
+
+        let endpoint_parent_fid =
+            Fid::<ToMember>::new(room_id, "caller".to_string().into());
+        let endpoint_id: EndpointId = "test-publish".to_string().into();
+        let endpoint_full_id: StatefulFid = endpoint_parent_fid
+            .clone()
+            .push_endpoint_id(endpoint_id.clone())
+            .into();
+
+        test_for_create!(
+            room_service,
+            CreateEndpointInRoom {
+                id: endpoint_id,
+                spec: endpoint_spec,
+                parent_fid: endpoint_parent_fid,
+                expected_version: None,
+            },
+            endpoint_full_id,
+            |endpoint_el| {
+                assert_eq!(
+                    endpoint_el.get_webrtc_pub().get_p2p(),
+                    P2pMode::Never.into()
+                );
+            }
+        )
+        .await;
+    }
+
+    /// Returns [`RoomSpec`] of `pub-sub-video-call` [`Room`] with the
+    /// `caller` [`Member`] only.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    fn caller_only_room_spec() -> RoomSpec {
+        const ROOM_SPEC: &str = r#"
+            kind: Room
+            id: pub-sub-video-call
+            spec:
+              pipeline:
+                caller:
+                  kind: Member
+                  credentials: test
+                  spec:
+                    pipeline:
+                      publish:
+                        kind: WebRtcPublishEndpoint
+                        spec:
+                          p2p: Always
+                          force_relay: true
+        "#;
+
+        let parsed: RootElement = serde_yaml::from_str(ROOM_SPEC).unwrap();
+        RoomSpec::try_from(&parsed).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn apply_creates_room() {
         let room_service = room_service(RoomRepository::new(HashMap::new()));
-        let spec = room_spec();
         let caller_fid =
             StatefulFid::try_from("pub-sub-video-call/caller".to_string())
                 .unwrap();
 
-        test_for_create!(
-            room_service,
-            CreateRoom { spec },
-            caller_fid,
-            |member_el| {
-                assert_eq!(member_el.get_member().get_pipeline().len(), 1);
+        test_for_create!(
+            room_service,
+            ApplyRoom {
+                spec: room_spec(),
+                policy: ApplyPolicy::Apply,
+                expected_version: None,
+            },
+            caller_fid,
+            |member_el| {
+                assert_eq!(member_el.get_member().get_pipeline().len(), 1);
+            }
+        )
+        .await;
+    }
+
+    /// Applies [`caller_only_room_spec`] with provided [`ApplyPolicy`] to
+    /// the running `pub-sub-video-call` [`Room`] and checks whether the
+    /// unspecified `responder` [`Member`] still exists.
+    ///
+    /// This function automatically stops [`actix::System`] when test completed.
+    ///
+    /// [`Member`]: crate::signalling::elements::member::Member
+    async fn test_for_apply(policy: ApplyPolicy, responder_should_exist: bool) {
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let room = Room::start(
+            &room_spec(),
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::new(hashmap!(
+            room_id.clone() => room,
+        )));
+
+        let sids = room_service
+            .send(ApplyRoom {
+                spec: caller_only_room_spec(),
+                policy,
+                expected_version: None,
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sids.len(), 1);
+        assert!(sids.contains_key("caller"));
+
+        let caller_fid = StatefulFid::from(Fid::<ToMember>::new(
+            room_id.clone(),
+            "caller".to_string().into(),
+        ));
+        let responder_fid = StatefulFid::from(Fid::<ToMember>::new(
+            room_id,
+            "responder".to_string().into(),
+        ));
+        assert!(room_service
+            .send(Get(vec![caller_fid]))
+            .await
+            .unwrap()
+            .is_ok());
+        assert_eq!(
+            room_service
+                .send(Get(vec![responder_fid]))
+                .await
+                .unwrap()
+                .is_ok(),
+            responder_should_exist,
+        );
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn apply_policy_removes_unspecified_members() {
+        test_for_apply(ApplyPolicy::Apply, false).await;
+    }
+
+    #[actix_rt::test]
+    async fn append_policy_keeps_unspecified_members() {
+        test_for_apply(ApplyPolicy::Append, true).await;
+    }
+
+    /// Returns [`Future`] used for testing of all delete/get methods of
+    /// [`RoomService`].
+    ///
+    /// This test is simply try to delete element with provided
+    /// [`StatefulFid`] and the try to get it. If result of getting
+    /// deleted element is error then test considers successful.
+    ///
+    /// This function automatically stops [`actix::System`] when test completed.
+    async fn test_for_delete_and_get(
+        room_service: Addr<RoomService>,
+        element_fid: StatefulFid,
+    ) {
+        let mut delete_msg = DeleteElements::new();
+        delete_msg.add_fid(element_fid.clone());
+        let delete_msg = delete_msg.validate().unwrap();
+
+        room_service.send(delete_msg).await.unwrap().unwrap();
+        let get_result =
+            room_service.send(Get(vec![element_fid])).await.unwrap();
+
+        assert!(get_result.is_err());
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn delete_and_get_room() {
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let room_full_id =
+            StatefulFid::from(Fid::<ToRoom>::new(room_id.clone()));
+
+        let room = Room::start(
+            &room_spec(),
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::new(hashmap!(
+            room_id => room,
+        )));
+
+        test_for_delete_and_get(room_service, room_full_id).await;
+    }
+
+    #[actix_rt::test]
+    async fn delete_and_get_member() {
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let member_fid = StatefulFid::from(Fid::<ToMember>::new(
+            room_id.clone(),
+            "caller".to_string().into(),
+        ));
+
+        let room = Room::start(
+            &room_spec(),
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::new(hashmap!(
+            room_id => room,
+        )));
+
+        test_for_delete_and_get(room_service, member_fid).await;
+    }
+
+    #[actix_rt::test]
+    async fn delete_and_get_endpoint() {
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let endpoint_fid = StatefulFid::from(Fid::<ToEndpoint>::new(
+            room_id.clone(),
+            "caller".to_string().into(),
+            "publish".to_string().into(),
+        ));
+
+        let room = Room::start(
+            &room_spec(),
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::new(hashmap!(
+            room_id => room,
+        )));
+
+        test_for_delete_and_get(room_service, endpoint_fid).await;
+    }
+
+    #[actix_rt::test]
+    async fn provisions_members() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let credentials_hash = |fid: &str| {
+            let fid = StatefulFid::try_from(fid.to_string()).unwrap();
+            let room_service = room_service.clone();
+            async move {
+                let elements =
+                    room_service.send(Get(vec![fid.clone()])).await.unwrap();
+                match elements.unwrap().remove(&fid).unwrap().el.unwrap() {
+                    proto::element::El::Member(proto::Member {
+                        credentials:
+                            Some(proto::member::Credentials::Hash(hash)),
+                        ..
+                    }) => CredentialHash::try_from(hash).unwrap(),
+                    _ => unreachable!(),
+                }
+            }
+        };
+        let provision = |member_id: &str, token: &str| ProvisionMember {
+            fid: Fid::<ToMember>::new(
+                "pub-sub-video-call".into(),
+                member_id.into(),
+            ),
+            spec: MemberSpec::new(
+                Pipeline::new(HashMap::new()),
+                Credentials::Plain(token.into()),
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+        };
+
+        room_service
+            .send(provision("token-member", "foo"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(credentials_hash("pub-sub-video-call/token-member")
+            .await
+            .verify(&"foo".into()));
+
+        room_service
+            .send(provision("caller", "bar"))
+            .await
+            .unwrap()
+            .unwrap();
+        let caller_hash = credentials_hash("pub-sub-video-call/caller").await;
+        assert!(caller_hash.verify(&"test".into()));
+        assert!(!caller_hash.verify(&"bar".into()));
+
+        let not_found = room_service
+            .send(ProvisionMember {
+                fid: Fid::<ToMember>::new("unknown".into(), "caller".into()),
+                ..provision("caller", "bar")
+            })
+            .await
+            .unwrap();
+        assert!(matches!(not_found, Err(RoomServiceError::RoomNotFound(_))));
+    }
+
+    #[actix_rt::test]
+    async fn lists_rooms_page_by_page() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        for id in &["room-3", "room-1", "room-2"] {
+            let mut spec = room_spec();
+            spec.id = (*id).to_string().into();
+            room_service
+                .send(CreateRoom { spec })
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        let first = room_service
+            .send(List {
+                limit: 2,
+                ..List::default()
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let ids: Vec<_> =
+            first.rooms.iter().map(|r| r.id.to_string()).collect();
+        assert_eq!(ids, vec!["room-1", "room-2"]);
+        assert_eq!(first.rooms[0].summary.members, 2);
+        assert_eq!(first.rooms[0].summary.connected_members, 0);
+        assert!(!first.rooms[0].is_static);
+        assert!(first.rooms[0].element.is_none());
+        assert_eq!(first.next_cursor.as_ref().unwrap().to_string(), "room-2");
+
+        let second = room_service
+            .send(List {
+                cursor: first.next_cursor,
+                limit: 2,
+                deep: true,
+                ..List::default()
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.rooms.len(), 1);
+        assert_eq!(second.rooms[0].id.to_string(), "room-3");
+        assert!(second.rooms[0].element.is_some());
+        assert!(second.next_cursor.is_none());
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn filters_listed_rooms() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let list = |filter| {
+            room_service.send(List {
+                filter,
+                ..List::default()
+            })
+        };
+        let listed =
+            |filter| async { list(filter).await.unwrap().unwrap().rooms.len() };
+
+        assert_eq!(listed(ListFilter::default()).await, 1);
+        assert_eq!(
+            listed(ListFilter {
+                min_members: Some(3),
+                ..ListFilter::default()
+            })
+            .await,
+            0
+        );
+        assert_eq!(
+            listed(ListFilter {
+                max_connected_members: Some(0),
+                ..ListFilter::default()
+            })
+            .await,
+            1
+        );
+        assert_eq!(
+            listed(ListFilter {
+                min_connected_members: Some(1),
+                ..ListFilter::default()
+            })
+            .await,
+            0
+        );
+        assert_eq!(
+            listed(ListFilter {
+                origin: Some(RoomOrigin::Static),
+                ..ListFilter::default()
+            })
+            .await,
+            0
+        );
+        assert_eq!(
+            listed(ListFilter {
+                origin: Some(RoomOrigin::Dynamic),
+                ..ListFilter::default()
+            })
+            .await,
+            1
+        );
+
+        actix::System::current().stop();
+    }
+
+    /// Returns [`Addr`] to [`RoomService`] with an empty [`RoomRepository`]
+    /// and the provided [`AppContext`], and the started [`Room`] with
+    /// provided [`AutoRemove`] rule.
+    async fn room_service_with_auto_remove(
+        app: AppContext,
+        auto_remove: AutoRemove,
+    ) -> Addr<RoomService> {
+        let graceful_shutdown =
+            GracefulShutdown::new(Conf::default().shutdown.timeout).start();
+        let room_service = RoomService::new(
+            RoomRepository::new(HashMap::new()),
+            app,
+            graceful_shutdown,
+        )
+        .unwrap()
+        .start();
+
+        let mut spec = room_spec();
+        spec.auto_remove = auto_remove;
+        room_service
+            .send(CreateRoom { spec })
+            .await
+            .unwrap()
+            .unwrap();
+        room_service
+    }
+
+    /// Indicates whether [`Room`] with `pub-sub-video-call` ID exists in
+    /// provided [`RoomService`].
+    async fn room_exists(room_service: &Addr<RoomService>) -> bool {
+        room_service
+            .send(Get(vec![StatefulFid::from(Fid::<ToRoom>::new(
+                "pub-sub-video-call".to_string().into(),
+            ))]))
+            .await
+            .unwrap()
+            .is_ok()
+    }
+
+    /// Publishes [`ElementEvent`] of provided kind for `caller` `Member` of
+    /// `pub-sub-video-call` [`Room`].
+    fn publish_caller_event(app: &AppContext, kind: ElementEventKind) {
+        app.element_events.publish(
+            Fid::<ToMember>::new(
+                "pub-sub-video-call".to_string().into(),
+                "caller".to_string().into(),
+            ),
+            kind,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn removes_room_once_all_members_left() {
+        let app = app_ctx();
+        let room_service =
+            room_service_with_auto_remove(app.clone(), AutoRemove::AllLeft)
+                .await;
+
+        delay_for(Duration::from_millis(50)).await;
+        assert!(room_exists(&room_service).await);
+
+        publish_caller_event(&app, ElementEventKind::MemberDisconnected);
+        delay_for(Duration::from_millis(50)).await;
+        assert!(!room_exists(&room_service).await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn removes_room_empty_for_timeout() {
+        let room_service = room_service_with_auto_remove(
+            app_ctx(),
+            AutoRemove::EmptyFor(Duration::from_millis(50)),
+        )
+        .await;
+
+        assert!(room_exists(&room_service).await);
+        delay_for(Duration::from_millis(150)).await;
+        assert!(!room_exists(&room_service).await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn member_connection_cancels_empty_room_timer() {
+        let app = app_ctx();
+        let room_service = room_service_with_auto_remove(
+            app.clone(),
+            AutoRemove::EmptyFor(Duration::from_millis(100)),
+        )
+        .await;
+
+        delay_for(Duration::from_millis(20)).await;
+        publish_caller_event(&app, ElementEventKind::MemberConnected);
+        delay_for(Duration::from_millis(200)).await;
+        assert!(room_exists(&room_service).await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn keeps_room_without_auto_remove_rule() {
+        let app = app_ctx();
+        let room_service =
+            room_service_with_auto_remove(app.clone(), AutoRemove::Never).await;
+
+        publish_caller_event(&app, ElementEventKind::MemberDisconnected);
+        delay_for(Duration::from_millis(50)).await;
+        assert!(room_exists(&room_service).await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn publishes_element_events() {
+        use futures::StreamExt as _;
+
+        let app = app_ctx();
+        let mut events = app.element_events.watch(Some(
+            Fid::<ToRoom>::new("pub-sub-video-call".to_string().into()).into(),
+        ));
+        let graceful_shutdown =
+            GracefulShutdown::new(Conf::default().shutdown.timeout).start();
+        let room_service = RoomService::new(
+            RoomRepository::new(HashMap::new()),
+            app,
+            graceful_shutdown,
+        )
+        .unwrap()
+        .start();
+
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        let mut delete_msg = DeleteElements::new();
+        delete_msg.add_fid(
+            StatefulFid::try_from("pub-sub-video-call".to_string()).unwrap(),
+        );
+        room_service
+            .send(delete_msg.validate().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut published = Vec::new();
+        while let Some(event) = events.next().await {
+            let event = (event.fid.to_string(), event.kind);
+            let is_room_deleted = event
+                == (
+                    "pub-sub-video-call".to_string(),
+                    ElementEventKind::Deleted,
+                );
+            published.push(event);
+            if is_room_deleted {
+                break;
+            }
+        }
+
+        assert_eq!(
+            published[0],
+            ("pub-sub-video-call".to_string(), ElementEventKind::Created)
+        );
+        for fid in &[
+            "pub-sub-video-call/caller",
+            "pub-sub-video-call/caller/publish",
+            "pub-sub-video-call/responder",
+            "pub-sub-video-call/responder/play",
+        ] {
+            for kind in &[ElementEventKind::Created, ElementEventKind::Deleted]
+            {
+                assert!(published.contains(&((*fid).to_string(), *kind)));
             }
-        )
-        .await;
+        }
     }
 
-    #[actix_rt::test]
-    async fn create_member() {
-        let spec = room_spec();
-        let member_spec = spec
-            .members()
+    /// Returns static spec of [`Room`] with provided [`RoomId`] and `Member`s
+    /// without endpoints.
+    fn static_spec(room_id: &str, member_ids: &[&str]) -> String {
+        let mut spec =
+            format!("kind: Room\nid: {}\nspec:\n  pipeline:\n", room_id);
+        for member_id in member_ids {
+            spec.push_str(&format!(
+                "    {}:\n      kind: Member\n      credentials: test\n      \
+                 spec:\n        pipeline: {{}}\n",
+                member_id,
+            ));
+        }
+        spec
+    }
+
+    /// Returns IDs, `Member`s counts and static indicators of all [`Room`]s of
+    /// provided [`RoomService`].
+    async fn listed_rooms(
+        room_service: &Addr<RoomService>,
+    ) -> Vec<(String, usize, bool)> {
+        room_service
+            .send(List::default())
+            .await
             .unwrap()
-            .get(&"caller".to_string().into())
             .unwrap()
-            .clone();
+            .rooms
+            .into_iter()
+            .map(|r| (r.id.to_string(), r.summary.members, r.is_static))
+            .collect()
+    }
 
-        let room_id: RoomId = "pub-sub-video-call".to_string().into();
-        let room = Room::start(
-            &spec,
-            &app_ctx(),
-            build_peers_traffic_watcher(&conf::Media::default()),
+    #[actix_rt::test]
+    async fn reloads_static_specs() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |file: &str, content: &str| {
+            std::fs::write(dir.path().join(file), content).unwrap();
+        };
+        write("room.yml", &static_spec("static-room", &["alice", "bob"]));
+
+        let mut conf = Conf::default();
+        conf.control.static_specs_dir =
+            dir.path().to_string_lossy().into_owned();
+        conf.control.static_specs_reload_interval = Duration::from_secs(0);
+        let graceful_shutdown =
+            GracefulShutdown::new(conf.shutdown.timeout).start();
+        let app =
+            AppContext::new(conf, crate::turn::new_turn_auth_service_mock());
+        let room_service = RoomService::new(
+            RoomRepository::new(HashMap::new()),
+            app,
+            graceful_shutdown,
         )
-        .unwrap();
-        let room_service = room_service(RoomRepository::new(hashmap!(
-            room_id.clone() => room,
-        )));
+        .unwrap()
+        .start();
 
-        let member_parent_fid = Fid::<ToRoom>::new(room_id);
-        let member_id: MemberId = "test-member".to_string().into();
-        let member_full_id: StatefulFid = member_parent_fid
-            .clone()
-            .push_member_id(member_id.clone())
-            .into();
+        room_service.send(StartStaticRooms).await.unwrap().unwrap();
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            listed_rooms(&room_service).await,
+            vec![
+                ("pub-sub-video-call".to_string(), 2, false),
+                ("static-room".to_string(), 2, true),
+            ],
+        );
 
-        test_for_create!(
-            room_service,
-            CreateMemberInRoom {
-                id: member_id,
-                spec: member_spec,
-                parent_fid: member_parent_fid,
-            },
-            member_full_id,
-            |member_el| {
-                assert_eq!(member_el.get_member().get_pipeline().len(), 1);
-            }
-        )
-        .await;
+        write("room.yml", &static_spec("static-room", &["alice", "carol"]));
+        write("new.yml", &static_spec("new-room", &["dave"]));
+        write("invalid.yml", "kind: Room\nid: invalid-room\n");
+        write("dynamic.yml", &static_spec("pub-sub-video-call", &["eve"]));
+        room_service.send(ReloadStaticRooms).await.unwrap();
+        assert_eq!(
+            listed_rooms(&room_service).await,
+            vec![
+                ("new-room".to_string(), 1, true),
+                ("pub-sub-video-call".to_string(), 2, false),
+                ("static-room".to_string(), 2, true),
+            ],
+        );
+        let fid =
+            StatefulFid::try_from("static-room/carol".to_string()).unwrap();
+        room_service.send(Get(vec![fid])).await.unwrap().unwrap();
+
+        write("room.yml", "kind: Room\nid: static-room\nspec: {}\n");
+        std::fs::remove_file(dir.path().join("new.yml")).unwrap();
+        room_service.send(ReloadStaticRooms).await.unwrap();
+        assert_eq!(
+            listed_rooms(&room_service).await,
+            vec![
+                ("pub-sub-video-call".to_string(), 2, false),
+                ("static-room".to_string(), 2, true),
+            ],
+        );
+
+        // Not applied spec is retried once its Room ID is not taken anymore.
+        let mut delete_msg = DeleteElements::new();
+        delete_msg.add_fid(
+            StatefulFid::try_from("pub-sub-video-call".to_string()).unwrap(),
+        );
+        room_service
+            .send(delete_msg.validate().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        room_service.send(ReloadStaticRooms).await.unwrap();
+        assert_eq!(
+            listed_rooms(&room_service).await,
+            vec![
+                ("pub-sub-video-call".to_string(), 1, true),
+                ("static-room".to_string(), 2, true),
+            ],
+        );
+
+        actix::System::current().stop();
     }
 
     #[actix_rt::test]
-    async fn create_endpoint() {
-        let spec = room_spec();
-
-        let mut endpoint_spec = spec
-            .members()
+    async fn snapshots_and_restores_rooms() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut conf = Conf::default();
+        conf.control.snapshot.store = conf::control::SnapshotStoreKind::File;
+        conf.control.snapshot.file = dir
+            .path()
+            .join("snapshot.json")
+            .to_string_lossy()
+            .into_owned()
+            .into();
+        conf.control.snapshot.save_delay = Duration::from_millis(10);
+        let room_service = |conf: Conf| {
+            let graceful_shutdown =
+                GracefulShutdown::new(conf.shutdown.timeout).start();
+            let app = AppContext::new(
+                conf,
+                crate::turn::new_turn_auth_service_mock(),
+            );
+            RoomService::new(
+                RoomRepository::new(HashMap::new()),
+                app,
+                graceful_shutdown,
+            )
             .unwrap()
-            .get(&"caller".to_string().into())
+            .start()
+        };
+
+        let first = room_service(conf.clone());
+        first.send(RestoreRooms).await.unwrap().unwrap();
+        first
+            .send(CreateRoom { spec: room_spec() })
+            .await
             .unwrap()
-            .get_publish_endpoint_by_id("publish".to_string().into())
+            .unwrap();
+        first
+            .send(ProvisionMember {
+                fid: Fid::<ToMember>::new(
+                    "pub-sub-video-call".into(),
+                    "viewer".into(),
+                ),
+                spec: MemberSpec::new(
+                    Pipeline::new(HashMap::new()),
+                    Credentials::Plain("token".into()),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                ),
+            })
+            .await
             .unwrap()
-            .clone();
-        endpoint_spec.p2p = P2pMode::Never;
-        let endpoint_spec = endpoint_spec.into();
+            .unwrap();
+        let version =
+            element_version(&first, "pub-sub-video-call/caller").await;
+        delay_for(Duration::from_millis(200)).await;
+
+        let second = room_service(conf);
+        second.send(RestoreRooms).await.unwrap().unwrap();
+        assert!(
+            element_version(&second, "pub-sub-video-call/caller").await
+                > version
+        );
+        let page = second.send(List::default()).await.unwrap().unwrap();
+        assert_eq!(page.rooms.len(), 1);
+        assert_eq!(page.rooms[0].id.to_string(), "pub-sub-video-call");
+        assert_eq!(page.rooms[0].summary.members, 3);
 
-        let room_id: RoomId = "pub-sub-video-call".into();
-        let room = Room::start(
-            &spec,
-            &app_ctx(),
-            build_peers_traffic_watcher(&conf::Media::default()),
+        let fid = StatefulFid::try_from(
+            "pub-sub-video-call/responder/play".to_string(),
         )
         .unwrap();
-        let room_service = room_service(RoomRepository::new(hashmap!(
-            room_id.clone() => room,
-        )));
+        second.send(Get(vec![fid])).await.unwrap().unwrap();
+        let fid =
+            StatefulFid::try_from("pub-sub-video-call/viewer".to_string())
+                .unwrap();
+        let mut elements =
+            second.send(Get(vec![fid.clone()])).await.unwrap().unwrap();
+        match elements.remove(&fid).unwrap().el.unwrap() {
+            proto::element::El::Member(proto::Member {
+                credentials: Some(proto::member::Credentials::Hash(hash)),
+                ..
+            }) => {
+                assert!(CredentialHash::try_from(hash)
+                    .unwrap()
+                    .verify(&"token".into()));
+            }
+            _ => unreachable!(),
+        }
 
-        let endpoint_parent_fid =
-            Fid::<ToMember>::new(room_id, "caller".to_string().into());
-        let endpoint_id: EndpointId = "test-publish".to_string().into();
-        let endpoint_full_id: StatefulFid = endpoint_parent_fid
-            .clone()
-            .push_endpoint_id(endpoint_id.clone())
-            .into();
+        actix::System::current().stop();
+    }
 
-        test_for_create!(
-            room_service,
-            CreateEndpointInRoom {
-                id: endpoint_id,
-                spec: endpoint_spec,
-                parent_fid: endpoint_parent_fid,
-            },
-            endpoint_full_id,
-            |endpoint_el| {
-                assert_eq!(
-                    endpoint_el.get_webrtc_pub().get_p2p(),
-                    P2pMode::Never.into()
-                );
-            }
-        )
-        .await;
+    /// Returns version of the element with provided FID.
+    async fn element_version(
+        room_service: &Addr<RoomService>,
+        fid: &str,
+    ) -> u64 {
+        use proto::element::El;
+
+        let fid = StatefulFid::try_from(fid.to_string()).unwrap();
+        let mut elements = room_service
+            .send(Get(vec![fid.clone()]))
+            .await
+            .unwrap()
+            .unwrap();
+        match elements.remove(&fid).unwrap().el.unwrap() {
+            El::Room(room) => room.version,
+            El::Member(member) => member.version,
+            El::WebrtcPub(publish) => publish.version,
+            El::WebrtcPlay(play) => play.version,
+        }
     }
 
-    /// Returns [`Future`] used for testing of all delete/get methods of
-    /// [`RoomService`].
-    ///
-    /// This test is simply try to delete element with provided
-    /// [`StatefulFid`] and the try to get it. If result of getting
-    /// deleted element is error then test considers successful.
-    ///
-    /// This function automatically stops [`actix::System`] when test completed.
-    async fn test_for_delete_and_get(
-        room_service: Addr<RoomService>,
-        element_fid: StatefulFid,
-    ) {
-        let mut delete_msg = DeleteElements::new();
-        delete_msg.add_fid(element_fid.clone());
-        let delete_msg = delete_msg.validate().unwrap();
+    /// Returns [`CreateEndpointInRoom`] creating new publishing `Endpoint`
+    /// with provided ID in the `caller` `Member`.
+    fn create_publish_endpoint(
+        id: &str,
+        expected_version: Option<u64>,
+    ) -> CreateEndpointInRoom {
+        CreateEndpointInRoom {
+            id: id.to_string().into(),
+            parent_fid: Fid::<ToMember>::new(
+                "pub-sub-video-call".into(),
+                "caller".into(),
+            ),
+            spec: EndpointSpec::WebRtcPublish(WebRtcPublishEndpoint {
+                p2p: P2pMode::Always,
+                force_relay: false,
+                audio_settings: AudioSettings::default(),
+                video_settings: VideoSettings::default(),
+                on_start: None,
+                on_stop: None,
+                meta: ElementMeta::default(),
+            }),
+            expected_version,
+        }
+    }
 
-        room_service.send(delete_msg).await.unwrap().unwrap();
-        let get_result =
-            room_service.send(Get(vec![element_fid])).await.unwrap();
+    #[actix_rt::test]
+    async fn bumps_versions_of_changed_elements() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        for fid in &[
+            "pub-sub-video-call",
+            "pub-sub-video-call/caller",
+            "pub-sub-video-call/caller/publish",
+            "pub-sub-video-call/responder/play",
+        ] {
+            assert_eq!(element_version(&room_service, fid).await, 1);
+        }
 
-        assert!(get_result.is_err());
+        room_service
+            .send(create_publish_endpoint("publish-2", Some(1)))
+            .await
+            .unwrap()
+            .unwrap();
+        for (fid, version) in &[
+            ("pub-sub-video-call", 2),
+            ("pub-sub-video-call/caller", 2),
+            ("pub-sub-video-call/caller/publish-2", 2),
+            ("pub-sub-video-call/caller/publish", 1),
+            ("pub-sub-video-call/responder", 1),
+        ] {
+            assert_eq!(element_version(&room_service, fid).await, *version);
+        }
+
+        room_service
+            .send(ApplyRoom {
+                spec: room_spec(),
+                policy: ApplyPolicy::Append,
+                expected_version: Some(2),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            element_version(&room_service, "pub-sub-video-call").await,
+            2,
+        );
 
         actix::System::current().stop();
     }
 
     #[actix_rt::test]
-    async fn delete_and_get_room() {
-        let room_id: RoomId = "pub-sub-video-call".to_string().into();
-        let room_full_id =
-            StatefulFid::from(Fid::<ToRoom>::new(room_id.clone()));
+    async fn rejects_requests_with_mismatched_versions() {
+        fn is_mismatch<T>(res: Result<T, RoomServiceError>) -> bool {
+            matches!(
+                res,
+                Err(RoomServiceError::RoomError(
+                    RoomError::VersionMismatch { .. }
+                ))
+            )
+        }
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        room_service
+            .send(create_publish_endpoint("publish-2", None))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let created = room_service
+            .send(create_publish_endpoint("publish-3", Some(1)))
+            .await
+            .unwrap();
+        assert!(is_mismatch(created));
+        let applied = room_service
+            .send(ApplyRoom {
+                spec: caller_only_room_spec(),
+                policy: ApplyPolicy::Apply,
+                expected_version: Some(1),
+            })
+            .await
+            .unwrap();
+        assert!(is_mismatch(applied));
+
+        let delete = |fid: &str, version: u64| {
+            let fid = StatefulFid::try_from(fid.to_string()).unwrap();
+            let mut msg = DeleteElements::new();
+            msg.add_fid(fid.clone());
+            msg.expect_version(fid, version);
+            msg.validate().unwrap()
+        };
+        let deleted = room_service
+            .send(delete("pub-sub-video-call/responder", 2))
+            .await
+            .unwrap();
+        assert!(is_mismatch(deleted));
+        assert_eq!(
+            element_version(&room_service, "pub-sub-video-call/responder")
+                .await,
+            1,
+        );
+        let deleted = room_service
+            .send(delete("pub-sub-video-call", 1))
+            .await
+            .unwrap();
+        assert!(is_mismatch(deleted));
+        assert_eq!(
+            element_version(&room_service, "pub-sub-video-call").await,
+            2,
+        );
 
-        let room = Room::start(
-            &room_spec(),
-            &app_ctx(),
-            build_peers_traffic_watcher(&conf::Media::default()),
-        )
-        .unwrap();
-        let room_service = room_service(RoomRepository::new(hashmap!(
-            room_id => room,
-        )));
+        room_service
+            .send(delete("pub-sub-video-call", 2))
+            .await
+            .unwrap()
+            .unwrap();
+        let applied = room_service
+            .send(ApplyRoom {
+                spec: room_spec(),
+                policy: ApplyPolicy::Apply,
+                expected_version: Some(2),
+            })
+            .await
+            .unwrap();
+        assert!(is_mismatch(applied));
+        assert!(room_service
+            .send(Get(vec![
+                Fid::<ToRoom>::new("pub-sub-video-call".into()).into()
+            ]))
+            .await
+            .unwrap()
+            .is_err());
 
-        test_for_delete_and_get(room_service, room_full_id).await;
+        actix::System::current().stop();
     }
 
     #[actix_rt::test]
-    async fn delete_and_get_member() {
-        let room_id: RoomId = "pub-sub-video-call".to_string().into();
-        let member_fid = StatefulFid::from(Fid::<ToMember>::new(
-            room_id.clone(),
-            "caller".to_string().into(),
+    async fn updates_publish_endpoint_in_place() {
+        use proto::element::El;
+
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        let fid = Fid::<ToEndpoint>::new(
+            "pub-sub-video-call".into(),
+            "caller".into(),
+            "publish".to_string().into(),
+        );
+
+        room_service
+            .send(UpdateEndpointInRoom {
+                fid: fid.clone(),
+                patch: WebRtcPublishEndpointPatch {
+                    force_relay: Some(false),
+                    audio_settings: Some(AudioSettings {
+                        publish_policy: PublishPolicy::Required,
+                    }),
+                    video_settings: None,
+                },
+                expected_version: Some(1),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let stateful_fid = StatefulFid::from(fid.clone());
+        let mut elements = room_service
+            .send(Get(vec![stateful_fid.clone()]))
+            .await
+            .unwrap()
+            .unwrap();
+        match elements.remove(&stateful_fid).unwrap().el.unwrap() {
+            El::WebrtcPub(publish) => {
+                assert!(!publish.force_relay);
+                assert_eq!(publish.version, 2);
+            }
+            _ => unreachable!(),
+        }
+
+        let disabled = room_service
+            .send(UpdateEndpointInRoom {
+                fid,
+                patch: WebRtcPublishEndpointPatch {
+                    video_settings: Some(VideoSettings {
+                        publish_policy: PublishPolicy::Disabled,
+                    }),
+                    ..WebRtcPublishEndpointPatch::default()
+                },
+                expected_version: None,
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            disabled,
+            Err(RoomServiceError::RoomError(
+                RoomError::UnsupportedPublishPolicyUpdate(_)
+            ))
         ));
 
-        let room = Room::start(
-            &room_spec(),
-            &app_ctx(),
-            build_peers_traffic_watcher(&conf::Media::default()),
-        )
-        .unwrap();
-        let room_service = room_service(RoomRepository::new(hashmap!(
-            room_id => room,
-        )));
+        actix::System::current().stop();
+    }
 
-        test_for_delete_and_get(room_service, member_fid).await;
+    #[actix_rt::test]
+    async fn kicks_member_rotating_credentials() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        let fid =
+            Fid::<ToMember>::new("pub-sub-video-call".into(), "caller".into());
+
+        let sids = room_service
+            .send(KickMember {
+                fid: fid.clone(),
+                description: None,
+                credentials: None,
+                expected_version: Some(1),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sids.get("caller").unwrap(),
+            "ws://127.0.0.1:8080/ws/pub-sub-video-call/caller",
+        );
+        assert_eq!(
+            element_version(&room_service, "pub-sub-video-call/caller").await,
+            1,
+        );
+
+        let sids = room_service
+            .send(KickMember {
+                fid,
+                description: Some(String::from("Misbehaving")),
+                credentials: Some(Credentials::Plain("rotated".into())),
+                expected_version: Some(1),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sids.get("caller").unwrap(),
+            "ws://127.0.0.1:8080/ws/pub-sub-video-call/caller/rotated",
+        );
+        assert_eq!(
+            element_version(&room_service, "pub-sub-video-call/caller").await,
+            2,
+        );
+
+        actix::System::current().stop();
     }
 
     #[actix_rt::test]
-    async fn delete_and_get_endpoint() {
-        let room_id: RoomId = "pub-sub-video-call".to_string().into();
-        let endpoint_fid = StatefulFid::from(Fid::<ToEndpoint>::new(
-            room_id.clone(),
-            "caller".to_string().into(),
-            "publish".to_string().into(),
+    async fn sends_custom_events_only_to_connected_members() {
+        let room_service = room_service(RoomRepository::new(HashMap::new()));
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        let room_fid = Fid::<ToRoom>::new("pub-sub-video-call".into());
+
+        room_service
+            .send(SendCustomEvent {
+                room_fid: room_fid.clone(),
+                member_id: None,
+                payload: serde_json::json!({"text": "recording started"}),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let sent = room_service
+            .send(SendCustomEvent {
+                room_fid,
+                member_id: Some("caller".into()),
+                payload: serde_json::json!({"text": "recording started"}),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            sent,
+            Err(RoomServiceError::RoomError(RoomError::MemberNotConnected(
+                _
+            )))
         ));
 
-        let room = Room::start(
-            &room_spec(),
-            &app_ctx(),
-            build_peers_traffic_watcher(&conf::Media::default()),
-        )
-        .unwrap();
-        let room_service = room_service(RoomRepository::new(hashmap!(
-            room_id => room,
-        )));
-
-        test_for_delete_and_get(room_service, endpoint_fid).await;
+        actix::System::current().stop();
     }
 }
@@ -0,0 +1,244 @@
+//! Snapshots of [Control API] elements, which allow to restore [`Room`]s
+//! after server restart.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+//! [`Room`]: crate::signalling::Room
+
+use std::{collections::HashMap, fmt, fs, io, path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use derive_more::{Display, From};
+use medea_client_api_proto::RoomId;
+use redis::{AsyncCommands as _, ConnectionInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::control::RootElement,
+    conf::control::{Snapshot as SnapshotConf, SnapshotStoreKind},
+    utils::blocking_io,
+};
+
+/// Error of [`SnapshotStore`].
+#[derive(Debug, Display, From)]
+pub enum SnapshotStoreError {
+    /// I/O error of [`FileSnapshotStore`].
+    #[display(fmt = "I/O error: {}", _0)]
+    Io(io::Error),
+
+    /// [Redis] error of [`RedisSnapshotStore`].
+    ///
+    /// [Redis]: https://redis.io
+    #[display(fmt = "Redis error: {}", _0)]
+    Redis(redis::RedisError),
+
+    /// [`Snapshot`] failed to be (de)serialized.
+    #[display(fmt = "Failed to (de)serialize snapshot: {}", _0)]
+    Serde(serde_json::Error),
+}
+
+/// Snapshot of all dynamically created [Control API] elements.
+///
+/// [`Room`]s started from static specs are not included, since they're
+/// started from static specs anyway.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+/// [`Room`]: crate::signalling::Room
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Snapshot {
+    /// Specs of all the [`Room`]s with their `Member`s and `Endpoint`s.
+    ///
+    /// `Member`s credentials are included as hashes only.
+    ///
+    /// [`Room`]: crate::signalling::Room
+    pub rooms: Vec<RootElement>,
+
+    /// Revisions of the [`Room`]s at the moment this [`Snapshot`] was taken.
+    ///
+    /// Restored [`Room`]s continue from these revisions, so versions of their
+    /// elements never repeat the ones seen before the restart.
+    ///
+    /// [`Room`]: crate::signalling::Room
+    #[serde(default)]
+    pub revisions: HashMap<RoomId, u64>,
+}
+
+/// Store of the last taken [`Snapshot`].
+#[async_trait(?Send)]
+pub trait SnapshotStore: fmt::Debug {
+    /// Replaces the stored [`Snapshot`] with the provided one.
+    async fn save(&self, snapshot: &Snapshot)
+        -> Result<(), SnapshotStoreError>;
+
+    /// Returns the stored [`Snapshot`], if any.
+    async fn load(&self) -> Result<Option<Snapshot>, SnapshotStoreError>;
+}
+
+/// Returns [`SnapshotStore`] configured by provided [`SnapshotConf`].
+///
+/// [`None`] if snapshots are disabled.
+pub fn new_store(conf: &SnapshotConf) -> Option<Arc<dyn SnapshotStore>> {
+    match conf.store {
+        SnapshotStoreKind::None => None,
+        SnapshotStoreKind::File => {
+            Some(Arc::new(FileSnapshotStore::new(conf.file.as_ref())))
+        }
+        SnapshotStoreKind::Redis => Some(Arc::new(RedisSnapshotStore::new(
+            (&conf.redis).into(),
+            conf.redis_key.to_string(),
+        ))),
+    }
+}
+
+/// [`SnapshotStore`] which writes [`Snapshot`]s to a file as JSON.
+#[derive(Debug)]
+pub struct FileSnapshotStore {
+    /// Path to the file which [`Snapshot`]s are written to.
+    path: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// Returns [`FileSnapshotStore`] writing to the file at provided path.
+    #[inline]
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait(?Send)]
+impl SnapshotStore for FileSnapshotStore {
+    /// Writes provided [`Snapshot`] to a temporary file first, and then
+    /// renames it, so the previous [`Snapshot`] is never left half-written.
+    async fn save(
+        &self,
+        snapshot: &Snapshot,
+    ) -> Result<(), SnapshotStoreError> {
+        let bytes = serde_json::to_vec(snapshot)?;
+        let path = self.path.clone();
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        blocking_io(move || {
+            fs::write(&tmp, bytes)?;
+            fs::rename(&tmp, &path)
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Option<Snapshot>, SnapshotStoreError> {
+        let path = self.path.clone();
+        let bytes = blocking_io(move || match fs::read(&path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        })
+        .await?;
+        bytes
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(Into::into))
+            .transpose()
+    }
+}
+
+/// [`SnapshotStore`] which stores [`Snapshot`]s in a [Redis] key.
+///
+/// [Redis]: https://redis.io
+#[derive(Debug)]
+pub struct RedisSnapshotStore {
+    /// Info for connecting to [Redis] database.
+    ///
+    /// [Redis]: https://redis.io
+    info: ConnectionInfo,
+
+    /// [Redis] key which [`Snapshot`]s are stored in.
+    ///
+    /// [Redis]: https://redis.io
+    key: String,
+}
+
+impl RedisSnapshotStore {
+    /// Returns [`RedisSnapshotStore`] storing [`Snapshot`]s in provided `key`.
+    ///
+    /// Connection to [Redis] database is established lazily on each
+    /// operation, since [`Snapshot`]s are saved with a delay.
+    ///
+    /// [Redis]: https://redis.io
+    #[inline]
+    pub fn new(info: ConnectionInfo, key: String) -> Self {
+        Self { info, key }
+    }
+
+    /// Establishes new connection to [Redis] database.
+    ///
+    /// [Redis]: https://redis.io
+    async fn connect(
+        &self,
+    ) -> Result<redis::aio::Connection, SnapshotStoreError> {
+        Ok(redis::Client::open(self.info.clone())?
+            .get_async_connection()
+            .await?)
+    }
+}
+
+#[async_trait(?Send)]
+impl SnapshotStore for RedisSnapshotStore {
+    async fn save(
+        &self,
+        snapshot: &Snapshot,
+    ) -> Result<(), SnapshotStoreError> {
+        let snapshot = serde_json::to_string(snapshot)?;
+        self.connect()
+            .await?
+            .set::<_, _, ()>(&self.key, snapshot)
+            .await?;
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Option<Snapshot>, SnapshotStoreError> {
+        let snapshot: Option<String> =
+            self.connect().await?.get(&self.key).await?;
+        snapshot
+            .map(|s| serde_json::from_str(&s).map_err(Into::into))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::convert::TryFrom as _;
+
+    use crate::api::control::RoomSpec;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn file_store_saves_and_loads_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSnapshotStore::new(dir.path().join("snapshot.json"));
+        assert!(store.load().await.unwrap().is_none());
+
+        let room: RootElement = serde_yaml::from_str(include_str!(
+            "../../tests/specs/pub-sub-video-call.yml"
+        ))
+        .unwrap();
+        store
+            .save(&Snapshot {
+                rooms: vec![room.clone()],
+                revisions: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .save(&Snapshot {
+                rooms: vec![room],
+                revisions: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
+        let snapshot = store.load().await.unwrap().unwrap();
+        assert_eq!(snapshot.rooms.len(), 1);
+        let spec = RoomSpec::try_from(&snapshot.rooms[0]).unwrap();
+        assert_eq!(spec.id().to_string(), "pub-sub-video-call");
+        assert_eq!(spec.members().unwrap().len(), 2);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -2,8 +2,9 @@
 
 mod actix_try_join_all;
 
-use std::time::Instant;
+use std::{io, time::Instant};
 
+use actix_web::{error::BlockingError, web};
 use chrono::{DateTime, Utc};
 
 pub use self::actix_try_join_all::actix_try_join_all;
@@ -122,3 +123,18 @@ pub fn instant_into_utc(instant: Instant) -> DateTime<Utc> {
     chrono::Duration::from_std(instant.elapsed())
         .map_or_else(|_| Utc::now(), |dur| Utc::now() - dur)
 }
+
+/// Runs provided blocking I/O operation on the blocking thread pool, so it
+/// doesn't block the current thread.
+pub async fn blocking_io<F, T>(op: F) -> Result<T, io::Error>
+where
+    F: FnOnce() -> Result<T, io::Error> + Send + 'static,
+    T: Send + 'static,
+{
+    web::block(op).await.map_err(|e| match e {
+        BlockingError::Error(e) => e,
+        BlockingError::Canceled => {
+            io::Error::new(io::ErrorKind::Other, "Blocking thread pool is gone")
+        }
+    })
+}
@@ -6,7 +6,7 @@ use actix::{clock::delay_for, Addr, Context};
 use actix_http::ws::CloseCode;
 use function_name::named;
 use medea_client_api_proto::Event as RpcEvent;
-use medea_control_api_proto::grpc::callback as proto;
+use medea_control_api_proto::grpc::{api as control_proto, callback as proto};
 use proto::request::Event;
 
 use crate::{
@@ -168,3 +168,48 @@ async fn on_leave_on_connection_loss() {
         .count();
     assert_eq!(on_leaves_count, 1);
 }
+
+/// Checks that `on_leave` callback works when `Member` is kicked via Control
+/// API.
+///
+/// # Algorithm
+///
+/// 1. Start test callback server and connect [`TestMember`] to it.
+///
+/// 2. Kick `Member` via Control API.
+///
+/// 3. Wait `500ms`.
+///
+/// 4. Check that test callback server receives `on_leave` callback with
+/// [`proto::on_leave::Reason::KICKED`].
+#[actix_rt::test]
+#[named]
+async fn on_leave_on_kick() {
+    let (_client, callback_server) = callback_test(test_name!(), 9095).await;
+    let mut control_client = ControlClient::new().await;
+    control_client
+        .kick(control_proto::KickRequest {
+            fid: format!("{}/{}", test_name!(), test_name!()),
+            description: String::from("Misbehaving"),
+            credentials: None,
+            expected_version: 0,
+        })
+        .await
+        .unwrap();
+    delay_for(Duration::from_millis(500)).await;
+
+    let callbacks = callback_server.send(GetCallbacks).await.unwrap().unwrap();
+
+    let on_leaves_count = callbacks
+        .into_iter()
+        .filter_map(|req| {
+            if let Some(Event::OnLeave(on_leave)) = req.event {
+                Some(on_leave.reason)
+            } else {
+                None
+            }
+        })
+        .filter(|reason| reason == &(proto::on_leave::Reason::Kicked as i32))
+        .count();
+    assert_eq!(on_leaves_count, 1);
+}
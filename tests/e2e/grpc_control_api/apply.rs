@@ -0,0 +1,164 @@
+//! Tests for `Apply` method of gRPC [Control API].
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use function_name::named;
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::{
+    grpc_control_api::{take_member, take_room},
+    test_name,
+};
+
+use super::{
+    create_room_req, ControlClient, MemberBuilder, RoomBuilder,
+    WebRtcPublishEndpointBuilder,
+};
+
+/// Converts provided [`proto::CreateRequest`] into [`proto::ApplyRequest`]
+/// with provided [`proto::apply_request::Policy`].
+fn apply_req(
+    req: proto::CreateRequest,
+    policy: proto::apply_request::Policy,
+) -> proto::ApplyRequest {
+    let el = match req.el.unwrap() {
+        proto::create_request::El::Room(room) => {
+            proto::apply_request::El::Room(room)
+        }
+        proto::create_request::El::Member(member) => {
+            proto::apply_request::El::Member(member)
+        }
+        _ => unreachable!(),
+    };
+    proto::ApplyRequest {
+        parent_fid: req.parent_fid,
+        el: Some(el),
+        policy: policy as i32,
+        expected_version: 0,
+    }
+}
+
+/// Returns [`proto::CreateRequest`] for `Room` with a single `publisher`
+/// `Member`, which is the subset of [`create_room_req`].
+fn publisher_only_room_req(room_id: &str) -> proto::CreateRequest {
+    RoomBuilder::default()
+        .id(room_id.to_string())
+        .add_member(
+            MemberBuilder::default()
+                .id("publisher")
+                .add_endpoint(
+                    WebRtcPublishEndpointBuilder::default()
+                        .id("publish")
+                        .p2p_mode(proto::web_rtc_publish_endpoint::P2p::Always)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap()
+        .build_request(String::new())
+}
+
+#[actix_rt::test]
+#[named]
+async fn creates_room() {
+    let mut client = ControlClient::new().await;
+    let sids = client
+        .apply(apply_req(
+            create_room_req(test_name!()),
+            proto::apply_request::Policy::Apply,
+        ))
+        .await;
+    assert_eq!(sids.len(), 2);
+
+    let room = take_room(client.get(test_name!()).await);
+    assert_eq!(room.pipeline.len(), 2);
+}
+
+#[actix_rt::test]
+#[named]
+async fn is_idempotent() {
+    let mut client = ControlClient::new().await;
+    let req = || {
+        apply_req(
+            create_room_req(test_name!()),
+            proto::apply_request::Policy::Apply,
+        )
+    };
+    let first_sids = client.apply(req()).await;
+    let second_sids = client.apply(req()).await;
+    assert_eq!(first_sids, second_sids);
+
+    let room = take_room(client.get(test_name!()).await);
+    assert_eq!(room.pipeline.len(), 2);
+}
+
+#[actix_rt::test]
+#[named]
+async fn apply_policy_removes_unspecified_elements() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    let sids = client
+        .apply(apply_req(
+            publisher_only_room_req(test_name!()),
+            proto::apply_request::Policy::Apply,
+        ))
+        .await;
+    assert_eq!(sids.len(), 1);
+
+    let room = take_room(client.get(test_name!()).await);
+    assert_eq!(room.pipeline.len(), 1);
+    assert!(room.pipeline.contains_key("publisher"));
+}
+
+#[actix_rt::test]
+#[named]
+async fn append_policy_keeps_unspecified_elements() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    client
+        .apply(apply_req(
+            publisher_only_room_req(test_name!()),
+            proto::apply_request::Policy::Append,
+        ))
+        .await;
+
+    let room = take_room(client.get(test_name!()).await);
+    assert_eq!(room.pipeline.len(), 2);
+}
+
+#[actix_rt::test]
+#[named]
+async fn applies_member() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    let responder = MemberBuilder::default()
+        .id("responder")
+        .credentials("test")
+        .build()
+        .unwrap();
+    let sids = client
+        .apply(proto::ApplyRequest {
+            parent_fid: test_name!().to_string(),
+            el: Some(proto::apply_request::El::Member(responder.into())),
+            policy: proto::apply_request::Policy::Apply as i32,
+            expected_version: 0,
+        })
+        .await;
+    assert_eq!(
+        sids.get("responder").unwrap(),
+        &format!("ws://127.0.0.1:8080/ws/{}/responder/test", test_name!())
+    );
+
+    let responder = take_member(
+        client
+            .get(&format!("{}/{}", test_name!(), "responder"))
+            .await,
+    );
+    assert!(responder.pipeline.is_empty());
+}
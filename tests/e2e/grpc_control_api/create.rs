@@ -5,8 +5,12 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+use std::convert::TryFrom as _;
+
 use function_name::named;
-use medea::api::control::error_codes::ErrorCode;
+use medea::api::control::{
+    credentials::CredentialHash, error_codes::ErrorCode,
+};
 use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
@@ -21,6 +25,18 @@ use super::{
     WebRtcPlayEndpointBuilder, WebRtcPublishEndpointBuilder,
 };
 
+/// Returns [`CredentialHash`] of the provided [`proto::Member`].
+fn credentials_hash(member: &proto::Member) -> CredentialHash {
+    match member.credentials.clone().unwrap() {
+        proto::member::Credentials::Hash(hash) => {
+            CredentialHash::try_from(hash).unwrap()
+        }
+        proto::member::Credentials::Plain(_) => {
+            panic!("Plain credentials are returned")
+        }
+    }
+}
+
 mod room {
     use super::*;
 
@@ -30,7 +46,13 @@ mod room {
         let mut client = ControlClient::new().await;
         let sids = client.create(create_room_req(test_name!())).await;
         assert_eq!(sids.len(), 2);
-        sids.get(&"publisher".to_string()).unwrap();
+        let publisher_credentials = sids
+            .get(&"publisher".to_string())
+            .unwrap()
+            .rsplit('/')
+            .next()
+            .unwrap()
+            .to_owned();
         let responder_sid =
             sids.get(&"responder".to_string()).unwrap().as_str();
         assert_eq!(
@@ -45,7 +67,7 @@ mod room {
             proto::room::element::El::Member(member) => member,
             _ => panic!(),
         };
-        assert_eq!(responder.credentials.as_str(), "test");
+        assert!(credentials_hash(&responder).verify(&"test".into()));
         let mut responder_pipeline = responder.pipeline;
         assert_eq!(responder_pipeline.len(), 1);
         let responder_play = responder_pipeline.remove("play").unwrap();
@@ -63,8 +85,9 @@ mod room {
             proto::room::element::El::Member(member) => member,
             _ => panic!(),
         };
-        assert_ne!(publisher.credentials.as_str(), "test");
-        assert_ne!(publisher.credentials.as_str(), "");
+        assert!(credentials_hash(&publisher)
+            .verify(&publisher_credentials.as_str().into()));
+        assert!(!credentials_hash(&publisher).verify(&"test".into()));
         let publisher_pipeline = publisher.pipeline;
         assert_eq!(publisher_pipeline.len(), 1);
     }
@@ -146,7 +169,51 @@ mod member {
         let member = client.get(&format!("{}/test-member", test_name!())).await;
         let member = take_member(member);
         assert_eq!(member.pipeline.len(), 1);
-        assert_eq!(member.credentials.as_str(), "qwerty");
+        assert!(credentials_hash(&member).verify(&"qwerty".into()));
+    }
+
+    #[actix_rt::test]
+    #[named]
+    async fn member_with_hashed_credentials() {
+        let mut client = ControlClient::new().await;
+        client.create(create_room_req(test_name!())).await;
+
+        let hash = CredentialHash::new(&"qwerty".into()).to_string();
+        let add_member = MemberBuilder::default()
+            .id("test-member")
+            .credentials_hash(hash.clone())
+            .build()
+            .unwrap()
+            .build_request(test_name!());
+
+        let sids = client.create(add_member).await;
+        assert_eq!(
+            sids.get(&"test-member".to_string()).unwrap().as_str(),
+            format!("ws://127.0.0.1:8080/ws/{}/test-member", test_name!())
+        );
+
+        let member = client.get(&format!("{}/test-member", test_name!())).await;
+        assert_eq!(credentials_hash(&take_member(member)).to_string(), hash);
+    }
+
+    #[actix_rt::test]
+    #[named]
+    async fn cant_create_member_with_invalid_credentials_hash() {
+        let mut client = ControlClient::new().await;
+        client.create(create_room_req(test_name!())).await;
+
+        let add_member = MemberBuilder::default()
+            .id("test-member")
+            .credentials_hash("qwerty")
+            .build()
+            .unwrap()
+            .build_request(test_name!());
+
+        if let Err(err) = client.try_create(add_member).await {
+            assert_eq!(err.code, ErrorCode::InvalidCredentialsHash as u32)
+        } else {
+            panic!("should err")
+        }
     }
 
     #[actix_rt::test]
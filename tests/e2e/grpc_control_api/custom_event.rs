@@ -0,0 +1,84 @@
+//! Tests for `SendCustomEvent` method of gRPC [Control API].
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::time::Duration;
+
+use actix::{clock::delay_for, Context};
+use function_name::named;
+use futures::{channel::mpsc, StreamExt as _};
+use medea_client_api_proto::Event;
+use medea_control_api_proto::grpc::api as proto;
+use serde_json::json;
+use tokio::time::timeout;
+
+use crate::{signalling::TestMember, test_name};
+
+use super::{create_room_req, ControlClient};
+
+#[actix_rt::test]
+#[named]
+async fn delivers_custom_events_to_members() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    let (tx, mut rx) = mpsc::unbounded();
+    let on_event =
+        move |event: &Event, _: &mut Context<TestMember>, _: Vec<&Event>| {
+            if let Event::Custom { payload } = event {
+                tx.unbounded_send(payload.clone()).unwrap();
+            }
+        };
+    let _responder = TestMember::connect(
+        &format!("ws://127.0.0.1:8080/ws/{}/responder/test", test_name!()),
+        Some(Box::new(on_event)),
+        None,
+        TestMember::DEFAULT_DEADLINE,
+        false,
+    )
+    .await;
+    delay_for(Duration::from_millis(500)).await;
+
+    for fid in &[
+        format!("{}/responder", test_name!()),
+        test_name!().to_string(),
+    ] {
+        client
+            .send_custom_event(proto::CustomEventRequest {
+                fid: fid.clone(),
+                payload: json!({ "fid": fid }).to_string(),
+            })
+            .await
+            .unwrap();
+        let payload = timeout(Duration::from_secs(5), rx.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(payload, json!({ "fid": fid }));
+    }
+}
+
+#[actix_rt::test]
+#[named]
+async fn rejects_undeliverable_custom_events() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    let err = client
+        .send_custom_event(proto::CustomEventRequest {
+            fid: format!("{}/responder", test_name!()),
+            payload: String::from("{\"text\": \"recording started\"}"),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, 1030);
+
+    let err = client
+        .send_custom_event(proto::CustomEventRequest {
+            fid: test_name!().to_string(),
+            payload: String::from("{\"text\": "),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, 1031);
+}
@@ -0,0 +1,106 @@
+//! Tests for labels and metadata of gRPC [Control API] elements.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use function_name::named;
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::test_name;
+
+use super::{
+    labels_map, take_member, take_room, ControlClient, MemberBuilder,
+    RoomBuilder,
+};
+
+/// Creates `Room` with the provided ID, labels and a single `Member` having
+/// labels and metadata.
+async fn create_labeled_room(
+    client: &mut ControlClient,
+    id: &str,
+    labels: &[(&str, &str)],
+) {
+    let request = RoomBuilder::default()
+        .id(id)
+        .labels(labels_map(labels))
+        .metadata(r#"{"topic":"standup"}"#)
+        .add_member(
+            MemberBuilder::default()
+                .id("member")
+                .labels(labels_map(&[("role", "host")]))
+                .metadata(r#"{"name":"Alice"}"#)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap()
+        .build_request(String::new());
+    client.create(request).await;
+}
+
+#[actix_rt::test]
+#[named]
+async fn returns_labels_and_metadata() {
+    let mut client = ControlClient::new().await;
+    create_labeled_room(&mut client, test_name!(), &[("tenant", "acme")]).await;
+
+    let room = take_room(client.get(test_name!()).await);
+    assert_eq!(room.labels, labels_map(&[("tenant", "acme")]));
+    assert_eq!(room.metadata, r#"{"topic":"standup"}"#);
+
+    let member =
+        take_member(client.get(&format!("{}/member", test_name!())).await);
+    assert_eq!(member.labels, labels_map(&[("role", "host")]));
+    assert_eq!(member.metadata, r#"{"name":"Alice"}"#);
+
+    client.delete(&[test_name!()]).await.unwrap();
+}
+
+#[actix_rt::test]
+#[named]
+async fn selects_rooms_by_labels() {
+    let name = test_name!().to_string();
+    let mut client = ControlClient::new().await;
+    for (suffix, tenant) in &[("a", "acme"), ("b", "acme"), ("c", "other")] {
+        create_labeled_room(
+            &mut client,
+            &format!("{}-{}", name, suffix),
+            &[("test", &name), ("tenant", tenant)],
+        )
+        .await;
+    }
+    let selector = [("test", name.as_str()), ("tenant", "acme")];
+
+    let mut selected: Vec<_> = client
+        .get_by_labels(&selector)
+        .await
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    selected.sort();
+    assert_eq!(selected, vec![format!("{}-a", name), format!("{}-b", name)]);
+
+    let listed: Vec<_> = client
+        .list(proto::list_request::Filter {
+            labels: labels_map(&selector),
+            ..proto::list_request::Filter::default()
+        })
+        .await
+        .into_iter()
+        .map(|room| room.id)
+        .collect();
+    assert_eq!(listed, vec![format!("{}-a", name), format!("{}-b", name)]);
+
+    client.delete_by_labels(&selector).await;
+    let remaining: Vec<_> = client
+        .get_by_labels(&[("test", &name)])
+        .await
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(remaining, vec![format!("{}-c", name)]);
+
+    // Selector matching nothing is not an error.
+    client.delete_by_labels(&selector).await;
+
+    client.delete(&[&format!("{}-c", name)]).await.unwrap();
+}
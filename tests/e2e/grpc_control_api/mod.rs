@@ -3,10 +3,14 @@
 //! [Medea]: https://github.com/instrumentisto/medea
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+mod apply;
 mod create;
+mod custom_event;
 mod delete;
+mod labels;
 mod rpc_settings;
 mod signaling;
+mod update;
 
 use std::{collections::HashMap, time::Duration};
 
@@ -63,7 +67,11 @@ impl ControlClient {
     /// - if connection with server failed
     pub async fn get(&mut self, uri: &str) -> proto::Element {
         let room = vec![uri.to_string()];
-        let get_room_request = proto::IdRequest { fid: room };
+        let get_room_request = proto::IdRequest {
+            fid: room,
+            expected_versions: HashMap::new(),
+            label_selector: HashMap::new(),
+        };
 
         let mut resp = self.0.get(get_room_request).await.unwrap().into_inner();
         if let Some(err) = resp.error {
@@ -82,7 +90,11 @@ impl ControlClient {
         uri: &str,
     ) -> Result<proto::Element, proto::Error> {
         let room = vec![uri.to_string()];
-        let get_room_request = proto::IdRequest { fid: room };
+        let get_room_request = proto::IdRequest {
+            fid: room,
+            expected_versions: HashMap::new(),
+            label_selector: HashMap::new(),
+        };
 
         let mut resp = self.0.get(get_room_request).await.unwrap().into_inner();
         if let Some(e) = resp.error {
@@ -127,6 +139,75 @@ impl ControlClient {
         }
     }
 
+    /// Applies `proto::Element` and returns it sids.
+    ///
+    /// # Panics
+    ///
+    /// - if [`CreateResponse`] has error.
+    /// - if connection with server failed.
+    pub async fn apply(
+        &mut self,
+        req: proto::ApplyRequest,
+    ) -> HashMap<String, String> {
+        let resp = self.0.apply(req).await.unwrap().into_inner();
+        if let Some(e) = resp.error {
+            panic!("{:?}", e);
+        }
+
+        resp.sid
+    }
+
+    /// Updates `proto::Element` in place.
+    ///
+    /// # Panics
+    ///
+    /// - if connection with server failed.
+    pub async fn update(
+        &mut self,
+        req: proto::UpdateRequest,
+    ) -> Result<(), proto::Error> {
+        let resp = self.0.update(req).await.unwrap().into_inner();
+        if let Some(e) = resp.error {
+            Err(e)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Kicks `Member` out of its `Room` and returns its sids.
+    ///
+    /// # Panics
+    ///
+    /// - if connection with server failed.
+    pub async fn kick(
+        &mut self,
+        req: proto::KickRequest,
+    ) -> Result<HashMap<String, String>, proto::Error> {
+        let resp = self.0.kick(req).await.unwrap().into_inner();
+        if let Some(e) = resp.error {
+            Err(e)
+        } else {
+            Ok(resp.sid)
+        }
+    }
+
+    /// Sends custom event to `Room` or `Member`.
+    ///
+    /// # Panics
+    ///
+    /// - if connection with server failed.
+    pub async fn send_custom_event(
+        &mut self,
+        req: proto::CustomEventRequest,
+    ) -> Result<(), proto::Error> {
+        let resp = self.0.send_custom_event(req).await.unwrap().into_inner();
+        if let Some(e) = resp.error {
+            Err(e)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Deletes `proto::Element`s by local URIs.
     ///
     /// # Panics
@@ -135,7 +216,11 @@ impl ControlClient {
     /// - if connection with server failed.
     pub async fn delete(&mut self, ids: &[&str]) -> Result<(), proto::Error> {
         let delete_ids = ids.iter().map(|id| (*id).to_string()).collect();
-        let delete_req = proto::IdRequest { fid: delete_ids };
+        let delete_req = proto::IdRequest {
+            fid: delete_ids,
+            expected_versions: HashMap::new(),
+            label_selector: HashMap::new(),
+        };
 
         let resp = self.0.delete(delete_req).await.unwrap().into_inner();
         if let Some(e) = resp.error {
@@ -144,6 +229,80 @@ impl ControlClient {
             Ok(())
         }
     }
+
+    /// Gets all the `Room`s having the provided labels.
+    ///
+    /// # Panics
+    ///
+    /// - if [`GetResponse`] has error
+    /// - if connection with server failed.
+    pub async fn get_by_labels(
+        &mut self,
+        labels: &[(&str, &str)],
+    ) -> HashMap<String, proto::Element> {
+        let get_req = proto::IdRequest {
+            fid: Vec::new(),
+            expected_versions: HashMap::new(),
+            label_selector: labels_map(labels),
+        };
+
+        let resp = self.0.get(get_req).await.unwrap().into_inner();
+        if let Some(err) = resp.error {
+            panic!("{:?}", err);
+        }
+        resp.elements
+    }
+
+    /// Deletes all the `Room`s having the provided labels.
+    ///
+    /// # Panics
+    ///
+    /// - if [`Response`] has error
+    /// - if connection with server failed.
+    pub async fn delete_by_labels(&mut self, labels: &[(&str, &str)]) {
+        let delete_req = proto::IdRequest {
+            fid: Vec::new(),
+            expected_versions: HashMap::new(),
+            label_selector: labels_map(labels),
+        };
+
+        let resp = self.0.delete(delete_req).await.unwrap().into_inner();
+        if let Some(err) = resp.error {
+            panic!("{:?}", err);
+        }
+    }
+
+    /// Lists the first page of `Room`s matching the provided filter.
+    ///
+    /// # Panics
+    ///
+    /// - if [`ListResponse`] has error
+    /// - if connection with server failed.
+    pub async fn list(
+        &mut self,
+        filter: proto::list_request::Filter,
+    ) -> Vec<proto::list_response::RoomInfo> {
+        let list_req = proto::ListRequest {
+            cursor: String::new(),
+            limit: 0,
+            deep: false,
+            filter: Some(filter),
+        };
+
+        let resp = self.0.list(list_req).await.unwrap().into_inner();
+        if let Some(err) = resp.error {
+            panic!("{:?}", err);
+        }
+        resp.rooms
+    }
+}
+
+/// Converts the provided label pairs into a labels map.
+pub fn labels_map(labels: &[(&str, &str)]) -> HashMap<String, String> {
+    labels
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+        .collect()
 }
 
 #[derive(Builder)]
@@ -153,6 +312,18 @@ pub struct Room {
 
     #[builder(default = "HashMap::new()")]
     members: HashMap<String, Member>,
+
+    #[builder(default = "None")]
+    auto_remove: Option<proto::room::AutoRemove>,
+
+    #[builder(default = "String::new()")]
+    on_remove: String,
+
+    #[builder(default = "HashMap::new()")]
+    labels: HashMap<String, String>,
+
+    #[builder(default = "String::new()")]
+    metadata: String,
 }
 
 impl Room {
@@ -174,11 +345,17 @@ impl Room {
         let grpc_room = proto::Room {
             id: self.id,
             pipeline: members,
+            auto_remove: self.auto_remove,
+            on_remove: self.on_remove,
+            version: 0,
+            labels: self.labels,
+            metadata: self.metadata,
         };
 
         proto::CreateRequest {
             parent_fid: uri.into(),
             el: Some(proto::create_request::El::Room(grpc_room)),
+            expected_version: 0,
         }
     }
 }
@@ -202,6 +379,9 @@ pub struct Member {
     #[builder(default = "None")]
     #[builder(setter(strip_option))]
     credentials: Option<String>,
+    #[builder(default = "None")]
+    #[builder(setter(strip_option))]
+    credentials_hash: Option<String>,
     #[builder(default = "HashMap::new()")]
     endpoints: HashMap<String, Endpoint>,
     #[builder(default = "None")]
@@ -216,10 +396,18 @@ pub struct Member {
     idle_timeout: Option<Duration>,
     #[builder(default = "None")]
     reconnect_timeout: Option<Duration>,
+    #[builder(default = "HashMap::new()")]
+    labels: HashMap<String, String>,
+    #[builder(default = "String::new()")]
+    metadata: String,
 }
 
 impl Into<proto::Member> for Member {
     fn into(self) -> proto::Member {
+        let credentials = match (self.credentials_hash, self.credentials) {
+            (Some(hash), _) => Some(proto::member::Credentials::Hash(hash)),
+            (None, plain) => plain.map(proto::member::Credentials::Plain),
+        };
         let pipeline = self
             .endpoints
             .into_iter()
@@ -231,10 +419,13 @@ impl Into<proto::Member> for Member {
             pipeline,
             on_leave: self.on_leave.unwrap_or_default(),
             on_join: self.on_join.unwrap_or_default(),
-            credentials: self.credentials.unwrap_or_default(),
+            credentials,
             ping_interval: self.ping_interval.map(Into::into),
             idle_timeout: self.idle_timeout.map(Into::into),
             reconnect_timeout: self.reconnect_timeout.map(Into::into),
+            version: 0,
+            labels: self.labels,
+            metadata: self.metadata,
         }
     }
 }
@@ -244,6 +435,7 @@ impl Member {
         proto::CreateRequest {
             parent_fid: url.into(),
             el: Some(proto::create_request::El::Member(self.into())),
+            expected_version: 0,
         }
     }
 }
@@ -306,6 +498,7 @@ impl WebRtcPlayEndpoint {
         proto::CreateRequest {
             el: Some(proto::create_request::El::WebrtcPlay(self.into())),
             parent_fid: url.into(),
+            expected_version: 0,
         }
     }
 }
@@ -318,6 +511,9 @@ impl Into<proto::WebRtcPlayEndpoint> for WebRtcPlayEndpoint {
             on_stop: String::new(),
             id: self.id,
             force_relay: false,
+            version: 0,
+            labels: HashMap::new(),
+            metadata: String::new(),
         }
     }
 }
@@ -343,6 +539,7 @@ impl WebRtcPublishEndpoint {
         proto::CreateRequest {
             el: Some(proto::create_request::El::WebrtcPub(self.into())),
             parent_fid: url.into(),
+            expected_version: 0,
         }
     }
 }
@@ -364,6 +561,9 @@ impl Into<proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
             video_settings: Some(VideoSettings {
                 publish_policy: PublishPolicy::Optional as i32,
             }),
+            version: 0,
+            labels: HashMap::new(),
+            metadata: String::new(),
         }
     }
 }
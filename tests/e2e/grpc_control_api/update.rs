@@ -0,0 +1,129 @@
+//! Tests for `Update` method of gRPC [Control API].
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::time::Duration;
+
+use function_name::named;
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::{
+    grpc_control_api::{take_member, take_webrtc_pub},
+    test_name,
+};
+
+use super::{create_room_req, ControlClient};
+
+#[actix_rt::test]
+#[named]
+async fn updates_member() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+    let fid = format!("{}/responder", test_name!());
+    let version = take_member(client.get(&fid).await).version;
+
+    client
+        .update(proto::UpdateRequest {
+            fid: fid.clone(),
+            patch: Some(proto::update_request::Patch::Member(
+                proto::MemberPatch {
+                    idle_timeout: Some(Duration::from_secs(5).into()),
+                    reconnect_timeout: None,
+                    ping_interval: Some(Duration::from_secs(2).into()),
+                },
+            )),
+            expected_version: version,
+        })
+        .await
+        .unwrap();
+
+    let member = take_member(client.get(&fid).await);
+    assert_eq!(member.idle_timeout, Some(Duration::from_secs(5).into()));
+    assert_eq!(member.ping_interval, Some(Duration::from_secs(2).into()));
+    assert!(member.version > version);
+    assert_eq!(member.pipeline.len(), 1);
+}
+
+#[actix_rt::test]
+#[named]
+async fn updates_publish_endpoint() {
+    use proto::web_rtc_publish_endpoint::{AudioSettings, PublishPolicy};
+
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+    let fid = format!("{}/publisher/publish", test_name!());
+
+    client
+        .update(proto::UpdateRequest {
+            fid: fid.clone(),
+            patch: Some(proto::update_request::Patch::WebrtcPub(
+                proto::WebRtcPublishEndpointPatch {
+                    force_relay: Some(true),
+                    audio_settings: Some(AudioSettings {
+                        publish_policy: PublishPolicy::Required as i32,
+                    }),
+                    video_settings: None,
+                },
+            )),
+            expected_version: 0,
+        })
+        .await
+        .unwrap();
+
+    let publish = take_webrtc_pub(client.get(&fid).await);
+    assert!(publish.force_relay);
+    assert_eq!(
+        publish.audio_settings.unwrap().publish_policy,
+        PublishPolicy::Required as i32,
+    );
+    assert_eq!(
+        publish.video_settings.unwrap().publish_policy,
+        PublishPolicy::Optional as i32,
+    );
+}
+
+#[actix_rt::test]
+#[named]
+async fn cannot_disable_publish_policy() {
+    use proto::web_rtc_publish_endpoint::{PublishPolicy, VideoSettings};
+
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    let err = client
+        .update(proto::UpdateRequest {
+            fid: format!("{}/publisher/publish", test_name!()),
+            patch: Some(proto::update_request::Patch::WebrtcPub(
+                proto::WebRtcPublishEndpointPatch {
+                    force_relay: None,
+                    audio_settings: None,
+                    video_settings: Some(VideoSettings {
+                        publish_policy: PublishPolicy::Disabled as i32,
+                    }),
+                },
+            )),
+            expected_version: 0,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, 1029);
+}
+
+#[actix_rt::test]
+#[named]
+async fn patch_must_match_element() {
+    let mut client = ControlClient::new().await;
+    client.create(create_room_req(test_name!())).await;
+
+    let err = client
+        .update(proto::UpdateRequest {
+            fid: format!("{}/publisher", test_name!()),
+            patch: Some(proto::update_request::Patch::WebrtcPub(
+                proto::WebRtcPublishEndpointPatch::default(),
+            )),
+            expected_version: 0,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, 1002);
+}
@@ -0,0 +1,152 @@
+//! Tests for HTTP REST [Control API].
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use actix_web::http::StatusCode;
+use awc::Client;
+use function_name::named;
+use serde_json::Value;
+
+use crate::test_name;
+
+/// Address of HTTP REST [Control API] server.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+const CONTROL_API_ADDR: &str = "http://127.0.0.1:6566";
+
+/// Returns YAML spec of `Room` with `publisher` and `responder` `Member`s.
+fn room_spec(room_id: &str) -> String {
+    format!(
+        r#"
+kind: Room
+spec:
+  pipeline:
+    publisher:
+      kind: Member
+      credentials: test
+      spec:
+        pipeline:
+          publish:
+            kind: WebRtcPublishEndpoint
+            spec:
+              p2p: Always
+    responder:
+      kind: Member
+      credentials: test
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://{}/publisher/publish"
+"#,
+        room_id
+    )
+}
+
+#[actix_rt::test]
+#[named]
+async fn create_get_and_delete_room() {
+    let client = Client::default();
+    let url = format!("{}/{}", CONTROL_API_ADDR, test_name!());
+
+    let mut resp = client
+        .post(&url)
+        .content_type("application/yaml")
+        .send_body(room_spec(test_name!()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value =
+        serde_yaml::from_slice(&resp.body().await.unwrap()).unwrap();
+    assert_eq!(
+        body["sid"]["responder"],
+        format!("ws://127.0.0.1:8080/ws/{}/responder/test", test_name!())
+    );
+
+    let mut resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = resp.json().await.unwrap();
+    let room = &body["elements"][test_name!()];
+    assert_eq!(room["kind"], "Room");
+    assert_eq!(
+        room["spec"]["pipeline"]["responder"]["spec"]["pipeline"]["play"]
+            ["spec"]["src"],
+        format!("local://{}/publisher/publish", test_name!())
+    );
+
+    let resp = client.delete(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let mut resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"]["code"], 1003);
+    assert_eq!(body["error"]["status"], 404);
+}
+
+#[actix_rt::test]
+#[named]
+async fn apply_member() {
+    let client = Client::default();
+    let room_url = format!("{}/{}", CONTROL_API_ADDR, test_name!());
+    client
+        .post(&room_url)
+        .content_type("application/yaml")
+        .send_body(room_spec(test_name!()))
+        .await
+        .unwrap();
+
+    let member_url = format!("{}/responder", room_url);
+    let mut resp = client
+        .put(&member_url)
+        .content_type("application/json")
+        .send_body(
+            r#"{"kind":"Member","credentials":"test","spec":{"pipeline":{}}}"#,
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(
+        body["sid"]["responder"],
+        format!("ws://127.0.0.1:8080/ws/{}/responder/test", test_name!())
+    );
+
+    let mut resp = client.get(&member_url).send().await.unwrap();
+    let body: Value = resp.json().await.unwrap();
+    let responder = &body["elements"][format!("{}/responder", test_name!())];
+    assert_eq!(responder["kind"], "Member");
+    assert!(responder["spec"]["pipeline"]
+        .as_object()
+        .unwrap()
+        .is_empty());
+}
+
+#[actix_rt::test]
+#[named]
+async fn malformed_body() {
+    let mut resp = Client::default()
+        .post(format!("{}/{}", CONTROL_API_ADDR, test_name!()))
+        .content_type("application/json")
+        .send_body("{ not a json")
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"]["code"], 1024);
+}
+
+#[actix_rt::test]
+#[named]
+async fn unsupported_content_type() {
+    let mut resp = Client::default()
+        .post(format!("{}/{}", CONTROL_API_ADDR, test_name!()))
+        .content_type("application/msgpack")
+        .send_body(room_spec(test_name!()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"]["code"], 1024);
+}
@@ -2,6 +2,7 @@
 
 mod callbacks;
 mod grpc_control_api;
+mod http_control_api;
 pub mod signalling;
 
 /// Polls `$name` [`Stream`] until finds provided `$pattern`.
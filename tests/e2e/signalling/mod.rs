@@ -26,9 +26,9 @@ use awc::{
 };
 use futures::{executor, stream::SplitSink, SinkExt as _, StreamExt as _};
 use medea_client_api_proto::{
-    ClientMsg, Command, Credential, Event, IceCandidate, MemberId,
-    NegotiationRole, PeerId, RoomId, RpcSettings, ServerMsg, Track, TrackId,
-    TrackUpdate,
+    Capability, ClientMsg, Command, Credential, Event, Handshake, IceCandidate,
+    MemberId, NegotiationRole, PeerId, RoomId, RpcSettings, ServerMsg, Track,
+    TrackId, TrackUpdate,
 };
 use url::Url;
 
@@ -121,11 +121,13 @@ impl TestMember {
     }
 
     /// Authorizes this [`TestMember`] on `Media Server` with a provided
-    /// connection info.
+    /// connection info, declaring all the known [`Capability`]s.
     fn authorize(&mut self, member_id: MemberId, credential: Credential) {
         self.send_command(Command::JoinRoom {
             member_id,
             credential,
+            last_seq: None,
+            handshake: Some(Handshake::new(Capability::ALL.iter().copied())),
         });
     }
 
@@ -295,7 +297,7 @@ impl StreamHandler<Result<Frame, WsProtocolError>> for TestMember {
 
             match server_msg {
                 ServerMsg::Ping(id) => self.send_pong(id),
-                ServerMsg::Event { room_id, event } => {
+                ServerMsg::Event { room_id, event, .. } => {
                     assert_eq!(self.room_id, room_id);
                     if matches!(
                         event,
@@ -428,6 +430,7 @@ impl StreamHandler<Result<Frame, WsProtocolError>> for TestMember {
                             } => assert!(self.known_peers.contains(peer_id)),
                             Event::PeersRemoved { .. }
                             | Event::ConnectionQualityUpdated { .. }
+                            | Event::Custom { .. }
                             | Event::RoomJoined { .. }
                             | Event::RoomLeft { .. } => (),
                         }